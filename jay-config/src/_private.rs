@@ -10,7 +10,7 @@ use {
     std::marker::PhantomData,
 };
 
-pub const VERSION: u32 = 1;
+pub const VERSION: u32 = 2;
 
 #[repr(C)]
 pub struct ConfigEntry {
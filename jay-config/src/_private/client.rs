@@ -9,10 +9,11 @@ use {
             },
             logging, Config, ConfigEntry, ConfigEntryGen, PollableId, WireMode, VERSION,
         },
-        exec::Command,
+        client::ClientCapabilities,
+        exec::{Command, Output},
         input::{
-            acceleration::AccelProfile, capability::Capability, FocusFollowsMouseMode, InputDevice,
-            Seat, SwitchEvent,
+            acceleration::AccelProfile, capability::Capability, DeviceMatch, FocusFollowsMouseMode,
+            InputDevice, PointerContext, PointerInput, Seat, SwitchEvent,
         },
         keyboard::{
             mods::{Modifiers, RELEASE},
@@ -25,10 +26,12 @@ use {
         timer::Timer,
         video::{
             connector_type::{ConnectorType, CON_UNKNOWN},
-            Connector, DrmDevice, Format, GfxApi, Mode, TearingMode, Transform, VrrMode,
+            BackgroundMode, Connector, ContentType, DrmDevice, Format, GfxApi, Mode, TearingMode,
+            Transform, VrrMode,
         },
         xwayland::XScalingMode,
-        AppMod, Axis, Direction, ModifiedKeySym, PciId, Workspace,
+        AnimationCurve, AppMod, Axis, Container, Direction, ModifiedKeySym, PciId, TabDirection,
+        Workspace,
     },
     bincode::Options,
     futures_util::task::ArcWake,
@@ -72,6 +75,15 @@ fn ignore_panic(name: &str, f: impl FnOnce()) {
     }
 }
 
+pub fn configure(f: impl FnOnce()) {
+    if catch_unwind(AssertUnwindSafe(f)).is_err() {
+        log::error!("A panic occurred in the configuration function");
+        if let Some(client) = try_get!() {
+            client.send(&ClientMessage::ConfigureFailed);
+        }
+    }
+}
+
 struct KeyHandler {
     registered_mask: Modifiers,
     cb_mask: Modifiers,
@@ -85,13 +97,23 @@ pub(crate) struct Client {
     srv_unref: unsafe extern "C" fn(data: *const u8),
     srv_handler: unsafe extern "C" fn(data: *const u8, msg: *const u8, size: usize),
     key_handlers: RefCell<HashMap<(Seat, AppMod, ModifiedKeySym), KeyHandler>>,
+    pointer_handlers:
+        RefCell<HashMap<(Seat, Modifiers, PointerInput, PointerContext), Callback<Seat>>>,
+    pointer_motion_handlers: RefCell<HashMap<Seat, Callback<(i32, i32)>>>,
+    pointer_release_handlers: RefCell<HashMap<Seat, Callback>>,
     timer_handlers: RefCell<HashMap<Timer, Callback>>,
+    output_handlers: RefCell<HashMap<u64, Box<dyn FnOnce(Output)>>>,
+    next_output_id: Cell<u64>,
     response: RefCell<Vec<Response>>,
+    awaiting_response: Cell<u32>,
+    next_request_id: Cell<u64>,
+    last_error: RefCell<Option<(u64, String)>>,
     on_new_seat: RefCell<Option<Callback<Seat>>>,
     on_new_input_device: RefCell<Option<Callback<InputDevice>>>,
     on_input_device_removed: RefCell<Option<Callback<InputDevice>>>,
     on_connector_connected: RefCell<Option<Callback<Connector>>>,
     on_connector_disconnected: RefCell<Option<Callback<Connector>>>,
+    on_content_type_changed: RefCell<Option<Callback<(Connector, ContentType)>>>,
     on_graphics_initialized: Cell<Option<Box<dyn FnOnce()>>>,
     on_devices_enumerated: Cell<Option<Box<dyn FnOnce()>>>,
     on_new_connector: RefCell<Option<Callback<Connector>>>,
@@ -110,6 +132,7 @@ pub(crate) struct Client {
 
     feat_mod_mask_global: Cell<bool>,
     feat_mod_mask_modal: Cell<bool>,
+    feat_request_ids: Cell<bool>,
 }
 
 struct Interest {
@@ -218,13 +241,22 @@ pub unsafe extern "C" fn init(
         srv_unref,
         srv_handler,
         key_handlers: Default::default(),
+        pointer_handlers: Default::default(),
+        pointer_motion_handlers: Default::default(),
+        pointer_release_handlers: Default::default(),
         timer_handlers: Default::default(),
+        output_handlers: Default::default(),
+        next_output_id: Cell::new(0),
         response: Default::default(),
+        awaiting_response: Cell::new(0),
+        next_request_id: Cell::new(0),
+        last_error: Default::default(),
         on_new_seat: Default::default(),
         on_new_input_device: Default::default(),
         on_input_device_removed: Default::default(),
         on_connector_connected: Default::default(),
         on_connector_disconnected: Default::default(),
+        on_content_type_changed: Default::default(),
         on_graphics_initialized: Default::default(),
         on_devices_enumerated: Default::default(),
         on_new_connector: Default::default(),
@@ -242,6 +274,7 @@ pub unsafe extern "C" fn init(
         pressed_keysym: Cell::new(None),
         feat_mod_mask_global: Cell::new(false),
         feat_mod_mask_modal: Cell::new(false),
+        feat_request_ids: Cell::new(false),
     });
     let init = unsafe { slice::from_raw_parts(init, size) };
     client.handle_init_msg(init);
@@ -280,6 +313,11 @@ impl Client {
     fn send(&self, msg: &ClientMessage) {
         let mut buf = self.bufs.borrow_mut().pop().unwrap_or_default();
         buf.clear();
+        if self.feat_request_ids.get() {
+            let id = self.next_request_id.get();
+            self.next_request_id.set(id + 1);
+            buf.extend_from_slice(&id.to_le_bytes());
+        }
         bincode_ops().serialize_into(&mut buf, msg).unwrap();
         unsafe {
             (self.srv_handler)(self.srv_data, buf.as_ptr(), buf.len());
@@ -311,7 +349,24 @@ impl Client {
             .drain()
             .map(|(a, b)| (a, b.into_raw_fd()))
             .collect();
-        if fds.is_empty() {
+        if command.kill_on_reload {
+            self.send(&ClientMessage::Run4 {
+                prog: &command.prog,
+                args: command.args.clone(),
+                env,
+                fds,
+                log_output: command.log_output,
+                kill_on_reload: true,
+            });
+        } else if command.log_output {
+            self.send(&ClientMessage::Run3 {
+                prog: &command.prog,
+                args: command.args.clone(),
+                env,
+                fds,
+                log_output: true,
+            });
+        } else if fds.is_empty() {
             self.send(&ClientMessage::Run {
                 prog: &command.prog,
                 args: command.args.clone(),
@@ -327,6 +382,32 @@ impl Client {
         }
     }
 
+    pub fn spawn_with_output<F: FnOnce(Output) + 'static>(&self, command: &Command, f: F) {
+        let env = command
+            .env
+            .iter()
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+            .collect();
+        let fds: Vec<_> = command
+            .fds
+            .borrow_mut()
+            .drain()
+            .map(|(a, b)| (a, b.into_raw_fd()))
+            .collect();
+        let id = self.next_output_id.get() + 1;
+        self.next_output_id.set(id);
+        self.output_handlers
+            .borrow_mut()
+            .insert(id, Box::new(f));
+        self.send(&ClientMessage::RunWithOutput {
+            prog: &command.prog,
+            args: command.args.clone(),
+            env,
+            fds,
+            id,
+        });
+    }
+
     pub fn grab(&self, kb: InputDevice, grab: bool) {
         self.send(&ClientMessage::GrabKb { kb, grab });
     }
@@ -359,12 +440,47 @@ impl Client {
         }
     }
 
+    pub fn set_shortcut_repeat(
+        &self,
+        seat: Seat,
+        mod_sym: ModifiedKeySym,
+        app_mod: AppMod,
+        repeat: bool,
+    ) {
+        self.send(&ClientMessage::SetShortcutRepeat {
+            seat,
+            mods: mod_sym.mods,
+            sym: mod_sym.sym,
+            app_mod,
+            repeat,
+        });
+    }
+
     pub fn set_app_mod(&self, seat: Seat, app_mod: AppMod) {
         self.send(&ClientMessage::SetAppMod { seat, app_mod });
     }
 
+    pub fn set_app_mod_escape(&self, seat: Seat, escape: Option<ModifiedKeySym>) {
+        self.send(&ClientMessage::SetAppModEscape { seat, escape });
+    }
+
+    pub fn set_shortcuts_inhibit_escape(&self, seat: Seat, escape: Option<ModifiedKeySym>) {
+        self.send(&ClientMessage::SetShortcutsInhibitEscape { seat, escape });
+    }
+
+    pub fn set_app_mod_forward_keys(&self, seat: Seat, app_mod: AppMod, forward: bool) {
+        self.send(&ClientMessage::SetAppModForwardKeys {
+            seat,
+            app_mod,
+            forward,
+        });
+    }
+
     fn with_response<F: FnOnce()>(&self, f: F) -> Response {
+        let awaiting = &self.awaiting_response;
+        awaiting.set(awaiting.get() + 1);
         f();
+        awaiting.set(awaiting.get() - 1);
         self.response
             .borrow_mut()
             .pop()
@@ -383,6 +499,12 @@ impl Client {
         mono
     }
 
+    pub fn stacked(&self, seat: Seat) -> bool {
+        let res = self.send_with_response(&ClientMessage::GetStacked { seat });
+        get_response!(res, false, GetStacked { stacked });
+        stacked
+    }
+
     pub fn get_timer(&self, name: &str) -> Timer {
         let res = self.send_with_response(&ClientMessage::GetTimer { name });
         get_response!(res, Timer(0), GetTimer { timer });
@@ -450,6 +572,12 @@ impl Client {
         capture
     }
 
+    pub fn get_workspace_name(&self, workspace: Workspace) -> String {
+        let res = self.send_with_response(&ClientMessage::GetWorkspaceName { workspace });
+        get_response!(res, String::new(), GetWorkspaceName { name });
+        name
+    }
+
     pub fn show_workspace(&self, seat: Seat, workspace: Workspace) {
         self.send(&ClientMessage::ShowWorkspace { seat, workspace });
     }
@@ -468,6 +596,10 @@ impl Client {
         self.send(&ClientMessage::DisablePointerConstraint { seat });
     }
 
+    pub fn adopt_orphaned_workspaces(&self, connector: Connector) {
+        self.send(&ClientMessage::AdoptOrphanedWorkspaces { connector });
+    }
+
     pub fn move_to_output(&self, workspace: WorkspaceSource, connector: Connector) {
         self.send(&ClientMessage::MoveToOutput {
             workspace,
@@ -556,6 +688,10 @@ impl Client {
         self.send(&ClientMessage::SetMono { seat, mono });
     }
 
+    pub fn set_stacked(&self, seat: Seat, stacked: bool) {
+        self.send(&ClientMessage::SetStacked { seat, stacked });
+    }
+
     pub fn set_env(&self, key: &str, val: &str) {
         self.send(&ClientMessage::SetEnv { key, val });
     }
@@ -590,6 +726,22 @@ impl Client {
         self.send(&ClientMessage::SetSplit { seat, axis });
     }
 
+    pub fn seat_container(&self, seat: Seat) -> Container {
+        let res = self.send_with_response(&ClientMessage::GetSeatContainer { seat });
+        get_response!(res, Container(0), GetSeatContainer { container });
+        container
+    }
+
+    pub fn container_split(&self, container: Container) -> Axis {
+        let res = self.send_with_response(&ClientMessage::GetContainerSplit { container });
+        get_response!(res, Axis::Horizontal, GetSplit { axis });
+        axis
+    }
+
+    pub fn set_container_split(&self, container: Container, axis: Axis) {
+        self.send(&ClientMessage::SetContainerSplit { container, axis });
+    }
+
     pub fn create_split(&self, seat: Seat, axis: Axis) {
         self.send(&ClientMessage::CreateSplit { seat, axis });
     }
@@ -598,6 +750,10 @@ impl Client {
         self.send(&ClientMessage::Close { seat });
     }
 
+    pub fn kill(&self, seat: Seat) {
+        self.send(&ClientMessage::Kill { seat });
+    }
+
     pub fn focus_parent(&self, seat: Seat) {
         self.send(&ClientMessage::FocusParent { seat });
     }
@@ -666,6 +822,14 @@ impl Client {
         self.send(&ClientMessage::ConnectorSetPosition { connector, x, y });
     }
 
+    pub fn connector_mirror(&self, dst: Connector, src: Connector) {
+        self.send(&ClientMessage::MirrorOutput { src, dst });
+    }
+
+    pub fn connector_unmirror(&self, dst: Connector) {
+        self.send(&ClientMessage::UnmirrorOutput { dst });
+    }
+
     pub fn connector_set_enabled(&self, connector: Connector, enabled: bool) {
         self.send(&ClientMessage::ConnectorSetEnabled { connector, enabled });
     }
@@ -677,6 +841,25 @@ impl Client {
         });
     }
 
+    pub fn connector_set_background(
+        &self,
+        connector: Connector,
+        path: &str,
+        mode: BackgroundMode,
+    ) -> Result<(), String> {
+        let res = self.send_with_response(&ClientMessage::ConnectorSetBackground {
+            connector,
+            path: path.to_string(),
+            mode,
+        });
+        get_response!(
+            res,
+            Err("Compositor did not send a response".to_string()),
+            ConnectorSetBackground { res }
+        );
+        res
+    }
+
     pub fn connector_get_name(&self, connector: Connector) -> String {
         let res = self.send_with_response(&ClientMessage::GetConnectorName { connector });
         get_response!(res, String::new(), GetConnectorName { name });
@@ -777,6 +960,98 @@ impl Client {
         self.send(&ClientMessage::SetUiDragThreshold { threshold });
     }
 
+    pub fn set_animations_enabled(&self, enabled: bool) {
+        self.send(&ClientMessage::SetAnimationsEnabled { enabled });
+    }
+
+    pub fn set_animation_duration(&self, duration: Duration) {
+        self.send(&ClientMessage::SetAnimationDuration { duration });
+    }
+
+    pub fn set_animation_curve(&self, curve: AnimationCurve) {
+        self.send(&ClientMessage::SetAnimationCurve { curve });
+    }
+
+    pub fn set_window_opacity(&self, seat: Seat, opacity: f64) {
+        self.send(&ClientMessage::SetWindowOpacity { seat, opacity });
+    }
+
+    pub fn set_dim_unfocused(&self, factor: f64) {
+        self.send(&ClientMessage::SetDimUnfocused { factor });
+    }
+
+    pub fn focus_tab(&self, seat: Seat, direction: TabDirection) {
+        self.send(&ClientMessage::FocusTab { seat, direction });
+    }
+
+    pub fn focus_previous(&self, seat: Seat) {
+        self.send(&ClientMessage::FocusPrevious { seat });
+    }
+
+    pub fn send_to_scratch(&self, seat: Seat) {
+        self.send(&ClientMessage::SendToScratch { seat });
+    }
+
+    pub fn fetch_from_scratch(&self, seat: Seat, app_id: &str) {
+        self.send(&ClientMessage::FetchFromScratch { seat, app_id });
+    }
+
+    pub fn bind_pointer<F: FnMut(Seat) + 'static>(
+        &self,
+        seat: Seat,
+        mods: Modifiers,
+        input: PointerInput,
+        context: PointerContext,
+        f: F,
+    ) {
+        let cb = cb(f);
+        let key = (seat, mods, input, context);
+        let prev = self.pointer_handlers.borrow_mut().insert(key, cb);
+        if prev.is_none() {
+            self.send(&ClientMessage::AddPointerBinding {
+                seat,
+                mods,
+                input,
+                context,
+            });
+        }
+    }
+
+    pub fn unbind_pointer(
+        &self,
+        seat: Seat,
+        mods: Modifiers,
+        input: PointerInput,
+        context: PointerContext,
+    ) {
+        let key = (seat, mods, input, context);
+        let prev = self.pointer_handlers.borrow_mut().remove(&key);
+        if prev.is_some() {
+            self.send(&ClientMessage::RemovePointerBinding {
+                seat,
+                mods,
+                input,
+                context,
+            });
+        }
+    }
+
+    pub fn on_pointer_binding_motion<F: FnMut(i32, i32) + 'static>(&self, seat: Seat, mut f: F) {
+        self.pointer_motion_handlers
+            .borrow_mut()
+            .insert(seat, cb(move |(x, y)| f(x, y)));
+    }
+
+    pub fn on_pointer_binding_release<F: FnMut() + 'static>(&self, seat: Seat, mut f: F) {
+        self.pointer_release_handlers
+            .borrow_mut()
+            .insert(seat, cb(move |_| f()));
+    }
+
+    pub fn start_pointer_move(&self, seat: Seat) {
+        self.send(&ClientMessage::StartPointerMove { seat });
+    }
+
     pub fn connector_connected(&self, connector: Connector) -> bool {
         let res = self.send_with_response(&ClientMessage::ConnectorConnected { connector });
         get_response!(res, false, ConnectorConnected { connected });
@@ -797,6 +1072,12 @@ impl Client {
         scale
     }
 
+    pub fn connector_get_transform(&self, connector: Connector) -> Transform {
+        let res = self.send_with_response(&ClientMessage::ConnectorGetTransform { connector });
+        get_response!(res, Transform::None, ConnectorGetTransform { transform });
+        transform
+    }
+
     pub fn connector_type(&self, connector: Connector) -> ConnectorType {
         let res = self.send_with_response(&ClientMessage::ConnectorType { connector });
         get_response!(res, CON_UNKNOWN, ConnectorType { ty });
@@ -841,10 +1122,24 @@ impl Client {
         self.send(&ClientMessage::SetXScalingMode { mode })
     }
 
+    pub fn grant_client_capabilities(&self, pid: i32, caps: ClientCapabilities) {
+        self.send(&ClientMessage::GrantClientCapabilities { pid, caps })
+    }
+
+    pub fn revoke_client_capabilities(&self, pid: i32, caps: ClientCapabilities) {
+        self.send(&ClientMessage::RevokeClientCapabilities { pid, caps })
+    }
+
     pub fn set_vrr_mode(&self, connector: Option<Connector>, mode: VrrMode) {
         self.send(&ClientMessage::SetVrrMode { connector, mode })
     }
 
+    pub fn connector_get_vrr_enabled(&self, connector: Connector) -> bool {
+        let res = self.send_with_response(&ClientMessage::ConnectorGetVrrEnabled { connector });
+        get_response!(res, false, ConnectorGetVrrEnabled { enabled });
+        enabled
+    }
+
     pub fn set_vrr_cursor_hz(&self, connector: Option<Connector>, hz: f64) {
         self.send(&ClientMessage::SetVrrCursorHz { connector, hz })
     }
@@ -883,6 +1178,10 @@ impl Client {
         *self.on_connector_disconnected.borrow_mut() = Some(cb(f));
     }
 
+    pub fn on_content_type_changed<F: FnMut(Connector, ContentType) + 'static>(&self, mut f: F) {
+        *self.on_content_type_changed.borrow_mut() = Some(cb(move |(c, t)| f(c, t)));
+    }
+
     pub fn on_graphics_initialized<F: FnOnce() + 'static>(&self, f: F) {
         self.on_graphics_initialized.set(Some(Box::new(f)));
     }
@@ -907,6 +1206,26 @@ impl Client {
         self.send(&ClientMessage::SetIdle { timeout })
     }
 
+    pub fn enter_idle(&self) {
+        self.send(&ClientMessage::EnterIdle)
+    }
+
+    pub fn set_client_send_timeout(&self, msec: u64) {
+        self.send(&ClientMessage::SetClientSendTimeoutMsec { msec })
+    }
+
+    pub fn set_client_ping_timeout(&self, msec: u64) {
+        self.send(&ClientMessage::SetClientPingTimeoutMsec { msec })
+    }
+
+    pub fn set_max_clients(&self, max: usize) {
+        self.send(&ClientMessage::SetMaxClients { max })
+    }
+
+    pub fn set_max_client_objects(&self, max: usize) {
+        self.send(&ClientMessage::SetMaxClientObjects { max })
+    }
+
     pub fn set_explicit_sync_enabled(&self, enabled: bool) {
         self.send(&ClientMessage::SetExplicitSyncEnabled { enabled })
     }
@@ -1012,6 +1331,22 @@ impl Client {
         self.send(&ClientMessage::SetFocusFollowsMouseMode { seat, mode })
     }
 
+    pub fn set_focus_follows_mouse_delay(&self, seat: Seat, msec: u64) {
+        self.send(&ClientMessage::SetFocusFollowsMouseDelay { seat, msec })
+    }
+
+    pub fn set_warp_pointer_to_focus(&self, seat: Seat, warp: bool) {
+        self.send(&ClientMessage::SetWarpPointerToFocus { seat, warp })
+    }
+
+    pub fn set_seat_devices(&self, seat: Seat, devices: Vec<DeviceMatch>) {
+        self.send(&ClientMessage::SetSeatDevices { seat, devices })
+    }
+
+    pub fn set_default_seat(&self, seat: Seat) {
+        self.send(&ClientMessage::SetDefaultSeat { seat })
+    }
+
     pub fn set_window_management_enabled(&self, seat: Seat, enabled: bool) {
         self.send(&ClientMessage::SetWindowManagementEnabled { seat, enabled })
     }
@@ -1174,6 +1509,22 @@ impl Client {
         })
     }
 
+    pub fn last_error(&self) -> Option<String> {
+        let last_error = self.last_error.borrow();
+        match &*last_error {
+            Some((id, msg)) if id + 1 == self.next_request_id.get() => Some(msg.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn set_protocol_tracing(&self, enabled: bool) {
+        self.send(&ClientMessage::SetProtocolTracing { enabled })
+    }
+
+    pub fn set_client_protocol_tracing(&self, pid: i32, enabled: bool) {
+        self.send(&ClientMessage::SetClientProtocolTracing { pid, enabled })
+    }
+
     pub fn get_socket_path(&self) -> Option<String> {
         let res = self.send_with_response(&ClientMessage::GetSocketPath);
         get_response!(res, None, GetSocketPath { path });
@@ -1389,6 +1740,12 @@ impl Client {
                 self.reload.set(false);
             }
             ServerMessage::Response { response } => {
+                if let Response::NotSupported = response {
+                    log::error!("The compositor does not support the request");
+                    if self.awaiting_response.get() == 0 {
+                        return;
+                    }
+                }
                 self.response.borrow_mut().push(response);
             }
             ServerMessage::InvokeShortcut {
@@ -1498,6 +1855,7 @@ impl Client {
                         ServerFeature::NONE => {}
                         ServerFeature::MOD_MASK => self.feat_mod_mask_global.set(true),
                         ServerFeature::MOD_MASK_MODAL => self.feat_mod_mask_modal.set(true),
+                        ServerFeature::REQUEST_IDS => self.feat_request_ids.set(true),
                         _ => {}
                     }
                 }
@@ -1517,6 +1875,56 @@ impl Client {
                     run_cb("switch event", &cb, event);
                 }
             }
+            ServerMessage::Error { request_id, msg } => {
+                *self.last_error.borrow_mut() = Some((request_id, msg));
+            }
+            ServerMessage::InvokePointerBinding {
+                seat,
+                mods,
+                input,
+                context,
+            } => {
+                let key = (seat, mods, input, context);
+                let handler = self.pointer_handlers.borrow_mut().get(&key).cloned();
+                if let Some(handler) = handler {
+                    run_cb("pointer binding", &handler, seat);
+                }
+            }
+            ServerMessage::RunCompleted { id, code, stdout } => {
+                let handler = self.output_handlers.borrow_mut().remove(&id);
+                if let Some(handler) = handler {
+                    ignore_panic("command output", || handler(Output { code, stdout }));
+                }
+            }
+            ServerMessage::PointerBindingMotion { seat, x, y } => {
+                let handler = self
+                    .pointer_motion_handlers
+                    .borrow()
+                    .get(&seat)
+                    .cloned();
+                if let Some(handler) = handler {
+                    run_cb("pointer binding motion", &handler, (x, y));
+                }
+            }
+            ServerMessage::PointerBindingReleased { seat } => {
+                let handler = self
+                    .pointer_release_handlers
+                    .borrow()
+                    .get(&seat)
+                    .cloned();
+                if let Some(handler) = handler {
+                    run_cb("pointer binding release", &handler, ());
+                }
+            }
+            ServerMessage::ContentTypeChanged {
+                connector,
+                content_type,
+            } => {
+                let handler = self.on_content_type_changed.borrow_mut().clone();
+                if let Some(handler) = handler {
+                    run_cb("content type changed", &handler, (connector, content_type));
+                }
+            }
         }
     }
 
@@ -1,18 +1,19 @@
 use {
     crate::{
+        client::ClientCapabilities,
         input::{
-            acceleration::AccelProfile, capability::Capability, FocusFollowsMouseMode, InputDevice,
-            Seat, SwitchEvent,
+            acceleration::AccelProfile, capability::Capability, DeviceMatch, FocusFollowsMouseMode,
+            InputDevice, PointerContext, PointerInput, Seat, SwitchEvent,
         },
         keyboard::{mods::Modifiers, syms::KeySym, AppMod, Keymap, ModifiedKeySym},
         logging::LogLevel,
         theme::{colors::Colorable, sized::Resizable, Color},
         timer::Timer,
         video::{
-            connector_type::ConnectorType, Connector, DrmDevice, Format, GfxApi, TearingMode,
-            Transform, VrrMode,
+            connector_type::ConnectorType, BackgroundMode, Connector, ContentType, DrmDevice,
+            Format, GfxApi, TearingMode, Transform, VrrMode,
         },
-        Axis, Direction, PciId, Workspace,
+        AnimationCurve, Axis, Container, Direction, PciId, TabDirection, Workspace,
         _private::{PollableId, WireMode},
         xwayland::XScalingMode,
     },
@@ -28,6 +29,8 @@ impl ServerFeature {
     pub const NONE: Self = Self(0);
     pub const MOD_MASK: Self = Self(1);
     pub const MOD_MASK_MODAL: Self = Self(2);
+    /// Every request is prefixed with its id as a little-endian u64.
+    pub const REQUEST_IDS: Self = Self(3);
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -95,6 +98,36 @@ pub enum ServerMessage {
         input_device: InputDevice,
         event: SwitchEvent,
     },
+    /// The request with the given id could not be handled.
+    ///
+    /// Only sent if the `REQUEST_IDS` feature has been announced.
+    Error {
+        request_id: u64,
+        msg: String,
+    },
+    InvokePointerBinding {
+        seat: Seat,
+        mods: Modifiers,
+        input: PointerInput,
+        context: PointerContext,
+    },
+    RunCompleted {
+        id: u64,
+        code: Option<i32>,
+        stdout: Vec<u8>,
+    },
+    PointerBindingMotion {
+        seat: Seat,
+        x: i32,
+        y: i32,
+    },
+    PointerBindingReleased {
+        seat: Seat,
+    },
+    ContentTypeChanged {
+        connector: Connector,
+        content_type: ContentType,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -539,6 +572,181 @@ pub enum ClientMessage<'a> {
         seat: Seat,
         app_mod: AppMod,
     },
+    Run3 {
+        prog: &'a str,
+        args: Vec<String>,
+        env: Vec<(String, String)>,
+        fds: Vec<(i32, i32)>,
+        log_output: bool,
+    },
+    GrantClientCapabilities {
+        pid: i32,
+        caps: ClientCapabilities,
+    },
+    RevokeClientCapabilities {
+        pid: i32,
+        caps: ClientCapabilities,
+    },
+    ConfigureFailed,
+    GetSeatContainer {
+        seat: Seat,
+    },
+    GetContainerSplit {
+        container: Container,
+    },
+    SetContainerSplit {
+        container: Container,
+        axis: Axis,
+    },
+    SetProtocolTracing {
+        enabled: bool,
+    },
+    SetClientProtocolTracing {
+        pid: i32,
+        enabled: bool,
+    },
+    SetFocusFollowsMouseDelay {
+        seat: Seat,
+        msec: u64,
+    },
+    Kill {
+        seat: Seat,
+    },
+    SetWarpPointerToFocus {
+        seat: Seat,
+        warp: bool,
+    },
+    SetSeatDevices {
+        seat: Seat,
+        devices: Vec<DeviceMatch>,
+    },
+    SetDefaultSeat {
+        seat: Seat,
+    },
+    ConnectorSetBackground {
+        connector: Connector,
+        path: String,
+        mode: BackgroundMode,
+    },
+    SetAnimationsEnabled {
+        enabled: bool,
+    },
+    SetAnimationDuration {
+        duration: Duration,
+    },
+    SetAnimationCurve {
+        curve: AnimationCurve,
+    },
+    SetWindowOpacity {
+        seat: Seat,
+        opacity: f64,
+    },
+    SetDimUnfocused {
+        factor: f64,
+    },
+    FocusTab {
+        seat: Seat,
+        direction: TabDirection,
+    },
+    AddPointerBinding {
+        seat: Seat,
+        mods: Modifiers,
+        input: PointerInput,
+        context: PointerContext,
+    },
+    RemovePointerBinding {
+        seat: Seat,
+        mods: Modifiers,
+        input: PointerInput,
+        context: PointerContext,
+    },
+    SendToScratch {
+        seat: Seat,
+    },
+    FetchFromScratch {
+        seat: Seat,
+        app_id: &'a str,
+    },
+    SetShortcutRepeat {
+        seat: Seat,
+        mods: Modifiers,
+        sym: KeySym,
+        app_mod: AppMod,
+        repeat: bool,
+    },
+    GetWorkspaceName {
+        workspace: Workspace,
+    },
+    RunWithOutput {
+        prog: &'a str,
+        args: Vec<String>,
+        env: Vec<(String, String)>,
+        fds: Vec<(i32, i32)>,
+        id: u64,
+    },
+    AdoptOrphanedWorkspaces {
+        connector: Connector,
+    },
+    FocusPrevious {
+        seat: Seat,
+    },
+    ConnectorGetTransform {
+        connector: Connector,
+    },
+    ConnectorGetVrrEnabled {
+        connector: Connector,
+    },
+    EnterIdle,
+    SetClientSendTimeoutMsec {
+        msec: u64,
+    },
+    MirrorOutput {
+        src: Connector,
+        dst: Connector,
+    },
+    UnmirrorOutput {
+        dst: Connector,
+    },
+    SetClientPingTimeoutMsec {
+        msec: u64,
+    },
+    SetAppModEscape {
+        seat: Seat,
+        escape: Option<ModifiedKeySym>,
+    },
+    SetAppModForwardKeys {
+        seat: Seat,
+        app_mod: AppMod,
+        forward: bool,
+    },
+    StartPointerMove {
+        seat: Seat,
+    },
+    Run4 {
+        prog: &'a str,
+        args: Vec<String>,
+        env: Vec<(String, String)>,
+        fds: Vec<(i32, i32)>,
+        log_output: bool,
+        kill_on_reload: bool,
+    },
+    GetStacked {
+        seat: Seat,
+    },
+    SetStacked {
+        seat: Seat,
+        stacked: bool,
+    },
+    SetShortcutsInhibitEscape {
+        seat: Seat,
+        escape: Option<ModifiedKeySym>,
+    },
+    SetMaxClients {
+        max: usize,
+    },
+    SetMaxClientObjects {
+        max: usize,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -687,6 +895,25 @@ pub enum Response {
     GetSocketPath {
         path: String,
     },
+    NotSupported,
+    GetSeatContainer {
+        container: Container,
+    },
+    ConnectorSetBackground {
+        res: Result<(), String>,
+    },
+    GetWorkspaceName {
+        name: String,
+    },
+    ConnectorGetTransform {
+        transform: Transform,
+    },
+    ConnectorGetVrrEnabled {
+        enabled: bool,
+    },
+    GetStacked {
+        stacked: bool,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
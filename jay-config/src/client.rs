@@ -0,0 +1,88 @@
+//! Tools for managing the privileges of wayland clients.
+
+use {
+    serde::{Deserialize, Serialize},
+    std::ops::{BitOr, BitOrAssign},
+};
+
+/// A set of privileged wayland protocols that a client can access.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq, Default)]
+pub struct ClientCapabilities(pub u32);
+
+impl ClientCapabilities {
+    /// No capabilities.
+    pub const NONE: Self = Self(0);
+    /// Access to the `ext-data-control-v1` and `zwlr-data-control-unstable-v1` protocols.
+    pub const DATA_CONTROL: Self = Self(1 << 0);
+    /// Access to the `zwp-virtual-keyboard-v1` protocol.
+    pub const VIRTUAL_KEYBOARD: Self = Self(1 << 1);
+    /// Access to the `ext-foreign-toplevel-list-v1` protocol.
+    pub const FOREIGN_TOPLEVEL_LIST: Self = Self(1 << 2);
+    /// Access to the `ext-idle-notify-v1` protocol.
+    pub const IDLE_NOTIFIER: Self = Self(1 << 3);
+    /// Access to the `ext-session-lock-v1` protocol.
+    pub const SESSION_LOCK: Self = Self(1 << 4);
+    /// Access to the Jay-specific protocols.
+    pub const JAY_COMPOSITOR: Self = Self(1 << 5);
+    /// Access to the `zwlr-layer-shell-v1` protocol.
+    pub const LAYER_SHELL: Self = Self(1 << 6);
+    /// Access to the screen capture protocols.
+    pub const SCREENCOPY: Self = Self(1 << 7);
+    /// Access to the `ext-transient-seat-v1` protocol.
+    pub const SEAT_MANAGER: Self = Self(1 << 8);
+    /// Access to the `wp-drm-lease-v1` protocol.
+    pub const DRM_LEASE: Self = Self(1 << 9);
+    /// Access to the `zwp-input-method-v2` protocol.
+    pub const INPUT_METHOD: Self = Self(1 << 10);
+    /// Access to the `zwlr-gamma-control-unstable-v1` protocol.
+    pub const GAMMA_CONTROL: Self = Self(1 << 11);
+}
+
+impl BitOr for ClientCapabilities {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for ClientCapabilities {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Grants capabilities to all clients of the process with the given pid.
+///
+/// The clients are notified about the protocols that have become available to them.
+/// Clients connected via a security context can never be granted capabilities beyond
+/// those of the security context.
+pub fn grant_client_capabilities(pid: i32, caps: ClientCapabilities) {
+    get!().grant_client_capabilities(pid, caps)
+}
+
+/// Revokes capabilities from all clients of the process with the given pid.
+///
+/// Objects that the clients have already created from the affected protocols are not
+/// destroyed but the clients can no longer create new ones.
+pub fn revoke_client_capabilities(pid: i32, caps: ClientCapabilities) {
+    get!().revoke_client_capabilities(pid, caps)
+}
+
+/// Enables or disables protocol tracing for all clients.
+///
+/// While enabled, every request and event is logged with the object, interface, and
+/// decoded arguments. Tracing for all clients can also be enabled by starting the
+/// compositor with `JAY_TRACE=1`.
+///
+/// The default is `false`.
+pub fn set_protocol_tracing(enabled: bool) {
+    get!().set_protocol_tracing(enabled)
+}
+
+/// Enables or disables protocol tracing for all clients of the process with the given pid.
+///
+/// See [`set_protocol_tracing`].
+pub fn set_client_protocol_tracing(pid: i32, enabled: bool) {
+    get!().set_client_protocol_tracing(pid, enabled)
+}
@@ -22,6 +22,8 @@ pub struct Command {
     pub(crate) args: Vec<String>,
     pub(crate) env: HashMap<String, String>,
     pub(crate) fds: RefCell<HashMap<i32, OwnedFd>>,
+    pub(crate) log_output: bool,
+    pub(crate) kill_on_reload: bool,
 }
 
 impl Command {
@@ -37,6 +39,8 @@ impl Command {
             args: vec![],
             env: Default::default(),
             fds: Default::default(),
+            log_output: false,
+            kill_on_reload: false,
         }
     }
 
@@ -82,6 +86,30 @@ impl Command {
         self.fd(2, fd)
     }
 
+    /// Writes the stdout and stderr of the process to the compositor log.
+    ///
+    /// Each line is prefixed with the name of the program. Lines written to stdout are
+    /// logged at the info level, lines written to stderr at the warn level.
+    ///
+    /// This has no effect on file descriptors that have been set explicitly via
+    /// [`Command::fd`], [`Command::stdout`], or [`Command::stderr`].
+    ///
+    /// The default is `false`.
+    pub fn log_output(&mut self) -> &mut Self {
+        self.log_output = true;
+        self
+    }
+
+    /// Terminates the program when the configuration is reloaded or the compositor exits.
+    ///
+    /// The program receives `SIGTERM` if it is still running at that point.
+    ///
+    /// The default is `false`.
+    pub fn kill_on_reload(&mut self) -> &mut Self {
+        self.kill_on_reload = true;
+        self
+    }
+
     /// Runs the application with access to privileged wayland protocols.
     ///
     /// The default is `false`.
@@ -103,4 +131,25 @@ impl Command {
     pub fn spawn(&self) {
         get!().spawn(self);
     }
+
+    /// Executes the command and invokes the callback once it has exited.
+    ///
+    /// The stdout of the process, up to 1 MiB, is passed to the callback. This overrides
+    /// [`Command::stdout`] and [`Command::log_output`].
+    ///
+    /// This consumes all attached file descriptors.
+    pub fn output<F: FnOnce(Output) + 'static>(&self, f: F) {
+        get!().spawn_with_output(self, f);
+    }
+}
+
+/// The result of a command executed via [`Command::output`].
+#[derive(Clone, Debug)]
+pub struct Output {
+    /// The exit code of the process.
+    ///
+    /// This is `None` if the process could not be spawned or was terminated by a signal.
+    pub code: Option<i32>,
+    /// The data the process wrote to stdout.
+    pub stdout: Vec<u8>,
 }
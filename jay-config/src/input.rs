@@ -7,7 +7,7 @@ use {
     crate::{
         input::{acceleration::AccelProfile, capability::Capability},
         keyboard::{mods::Modifiers, Keymap},
-        AppMod, Axis, Direction, ModifiedKeySym, Workspace,
+        AppMod, Axis, Container, Direction, ModifiedKeySym, TabDirection, Workspace,
         _private::{ipc::WorkspaceSource, DEFAULT_SEAT_NAME},
         video::Connector,
     },
@@ -274,16 +274,140 @@ impl Seat {
         get!().unbind(self, mod_sym.into(), app_mod)
     }
 
+    /// Creates a binding for a pointer button or a scroll direction.
+    ///
+    /// The closure is invoked when the input occurs while the pointer is over the
+    /// specified part of the desktop and exactly the modifiers `mods` are pressed.
+    /// CapsLock and NumLock are ignored.
+    ///
+    /// The event is not forwarded to any application. For buttons, the matching
+    /// release event is not forwarded either.
+    ///
+    /// Scroll bindings are only invoked for discrete scroll events, such as those
+    /// generated by mouse wheels.
+    pub fn bind_pointer<F: FnMut(Seat) + 'static>(
+        self,
+        mods: Modifiers,
+        input: PointerInput,
+        context: PointerContext,
+        f: F,
+    ) {
+        get!().bind_pointer(self, mods, input, context, f)
+    }
+
+    /// Removes a binding created with [`Seat::bind_pointer`].
+    pub fn unbind_pointer(self, mods: Modifiers, input: PointerInput, context: PointerContext) {
+        get!().unbind_pointer(self, mods, input, context)
+    }
+
+    /// Sets a callback that is invoked when the pointer moves while the button of a
+    /// pointer binding is held.
+    ///
+    /// The callback receives the position of the pointer in global coordinates.
+    pub fn on_pointer_binding_motion<F: FnMut(i32, i32) + 'static>(self, f: F) {
+        get!().on_pointer_binding_motion(self, f)
+    }
+
+    /// Sets a callback that is invoked when the button of a pointer binding is released.
+    pub fn on_pointer_binding_release<F: FnMut() + 'static>(self, f: F) {
+        get!().on_pointer_binding_release(self, f)
+    }
+
+    /// Moves the window under the pointer until the button of the current pointer
+    /// binding is released.
+    ///
+    /// This only has an effect when called from the callback of a button binding and
+    /// only floating windows are moved.
+    pub fn start_pointer_move(self) {
+        get!().start_pointer_move(self)
+    }
+
+    /// Sets whether a hotkey is invoked repeatedly while its key is held.
+    ///
+    /// The hotkey repeats according to the repeat rate of the seat. Repetition stops when
+    /// the key is released or another key is pressed. This should not be enabled for
+    /// hotkeys that toggle something, such as fullscreen.
+    ///
+    /// The default is `false`.
+    pub fn set_shortcut_repeat<T: Into<ModifiedKeySym>>(
+        self,
+        mod_sym: T,
+        app_mod: AppMod,
+        repeat: bool,
+    ) {
+        get!().set_shortcut_repeat(self, mod_sym.into(), app_mod, repeat)
+    }
+
     /// Moves the keyboard focus of the seat in the specified direction.
     pub fn set_app_mod(self, app_mod: AppMod) {
         get!().set_app_mod(self, app_mod)
     }
 
+    /// Sets a key that returns to the default mode from any other mode.
+    ///
+    /// The key is matched before the shortcuts of the current mode and is never sent to
+    /// clients. `None` removes the key.
+    pub fn set_app_mod_escape<T: Into<ModifiedKeySym>>(self, mod_sym: Option<T>) {
+        get!().set_app_mod_escape(self, mod_sym.map(|m| m.into()))
+    }
+
+    /// Sets a key that stops the focused window from inhibiting shortcuts.
+    ///
+    /// Windows can ask for all keys to be sent to them, including keys that would otherwise
+    /// trigger shortcuts. This key is matched even then and is never sent to the window.
+    /// Shortcuts work again until the window loses and regains the keyboard focus. `None`
+    /// removes the key.
+    pub fn set_shortcuts_inhibit_escape<T: Into<ModifiedKeySym>>(self, mod_sym: Option<T>) {
+        get!().set_shortcuts_inhibit_escape(self, mod_sym.map(|m| m.into()))
+    }
+
+    /// Sets whether keys that don't trigger a shortcut are sent to clients while the mod
+    /// is active.
+    ///
+    /// By default, keys are only sent to clients in the insert mod.
+    pub fn set_app_mod_forward_keys(self, app_mod: AppMod, forward: bool) {
+        get!().set_app_mod_forward_keys(self, app_mod, forward)
+    }
+
     /// Moves the keyboard focus of the seat in the specified direction.
     pub fn focus(self, direction: Direction) {
         get!().focus(self, direction)
     }
 
+    /// Focuses the next or previous window in the closest mono container.
+    ///
+    /// The closest mono container is the innermost container in mono mode that contains the
+    /// focused window. Focus wraps around at the first and last window.
+    pub fn focus_tab(self, direction: TabDirection) {
+        get!().focus_tab(self, direction)
+    }
+
+    /// Focuses the previously focused window.
+    ///
+    /// Each seat remembers the windows it has recently focused. This focuses the most recent
+    /// of those windows that still exists, switching to its workspace if necessary, and moves
+    /// the pointer to the center of the window. Calling this repeatedly toggles between the
+    /// two most recently focused windows.
+    pub fn focus_previous(self) {
+        get!().focus_previous(self)
+    }
+
+    /// Moves the focused window to the scratchpad.
+    ///
+    /// Windows in the scratchpad are not shown on any output until they are fetched with
+    /// [`Seat::fetch_from_scratch`].
+    pub fn send_to_scratch(self) {
+        get!().send_to_scratch(self)
+    }
+
+    /// Moves a window from the scratchpad to the current workspace and focuses it.
+    ///
+    /// The window is the one most recently sent to the scratchpad whose app id contains
+    /// `app_id`. An empty string matches all windows. The window becomes floating.
+    pub fn fetch_from_scratch(self, app_id: &str) {
+        get!().fetch_from_scratch(self, app_id)
+    }
+
     /// Moves the focused window in the specified direction.
     pub fn move_(self, direction: Direction) {
         get!().move_(self, direction)
@@ -322,6 +446,24 @@ impl Seat {
         self.set_mono(!self.mono());
     }
 
+    /// Returns whether the parent-container of the currently focused window stacks its titles.
+    pub fn stacked(self) -> bool {
+        get!(false).stacked(self)
+    }
+
+    /// Sets whether the parent-container of the currently focused window stacks its titles.
+    ///
+    /// In mono-mode, the titles of all windows are usually shown as tabs in a single row. If
+    /// this is enabled, they are instead stacked on top of each other.
+    pub fn set_stacked(self, stacked: bool) {
+        get!().set_stacked(self, stacked)
+    }
+
+    /// Toggles whether the parent-container of the currently focused window stacks its titles.
+    pub fn toggle_stacked(self) {
+        self.set_stacked(!self.stacked());
+    }
+
     /// Returns the split axis of the parent-container of the currently focused window.
     pub fn split(self) -> Axis {
         get!(Axis::Horizontal).split(self)
@@ -337,6 +479,14 @@ impl Seat {
         self.set_split(self.split().other());
     }
 
+    /// Returns the parent-container of the currently focused window.
+    ///
+    /// If the focused window is not part of a container, the returned container does not
+    /// exist.
+    pub fn container(self) -> Container {
+        get!(Container(0)).seat_container(self)
+    }
+
     /// Returns the input devices assigned to this seat.
     pub fn input_devices(self) -> Vec<InputDevice> {
         get!().get_input_devices(Some(self))
@@ -357,6 +507,13 @@ impl Seat {
         get!().close(self);
     }
 
+    /// Disconnects the client that owns the currently focused window.
+    ///
+    /// Unlike [`Seat::close`], this does not give the application a chance to clean up.
+    pub fn kill(self) {
+        get!().kill(self);
+    }
+
     /// Returns whether the currently focused window is floating.
     pub fn get_floating(self) -> bool {
         get!().get_floating(self)
@@ -409,6 +566,15 @@ impl Seat {
         get!().set_fullscreen(self, fullscreen)
     }
 
+    /// Sets the opacity of the currently focused window.
+    ///
+    /// The opacity is clamped to `[0, 1]`. Fullscreen windows are always opaque.
+    ///
+    /// The default is `1`.
+    pub fn set_window_opacity(self, opacity: f64) {
+        get!().set_window_opacity(self, opacity)
+    }
+
     /// Disables the currently active pointer constraint on this seat.
     pub fn disable_pointer_constraint(self) {
         get!().disable_pointer_constraint(self)
@@ -444,6 +610,41 @@ impl Seat {
         get!().set_focus_follows_mouse_mode(self, mode);
     }
 
+    /// Sets how long the pointer has to rest over a window before focus-follows-mouse
+    /// moves the keyboard focus to it.
+    ///
+    /// This avoids focus changes when the pointer quickly crosses windows. If the keyboard
+    /// focus changes in another way while the delay is running, for example because a
+    /// shortcut focused a different window, the window under the pointer is not focused.
+    ///
+    /// The default is 0 ms.
+    pub fn set_focus_follows_mouse_delay(self, delay: Duration) {
+        let msec = delay.as_millis().min(u64::MAX as u128);
+        get!().set_focus_follows_mouse_delay(self, msec as u64);
+    }
+
+    /// Sets whether the pointer is warped to the center of a window when the keyboard
+    /// focus is moved to it with [`Seat::focus`].
+    ///
+    /// The pointer is not moved if it is already inside the newly focused window.
+    ///
+    /// The default is `false`.
+    pub fn set_warp_pointer_to_focus(self, warp: bool) {
+        get!().set_warp_pointer_to_focus(self, warp);
+    }
+
+    /// Sets the input devices that are claimed by this seat.
+    ///
+    /// Existing devices that match are moved to this seat immediately. Devices that are
+    /// added later and match are attached to this seat before any of their events are
+    /// processed, instead of being attached to the default seat.
+    ///
+    /// This replaces the matches previously set for this seat. The config can still move
+    /// the devices to other seats with [`InputDevice::set_seat`].
+    pub fn set_devices(self, devices: &[DeviceMatch]) {
+        get!().set_seat_devices(self, devices.to_vec());
+    }
+
     /// Enables or disable window management mode.
     ///
     /// In window management mode, floating windows can be moved by pressing the left
@@ -480,6 +681,9 @@ impl Seat {
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
 pub enum FocusFollowsMouseMode {
     /// When the mouse moves and enters a toplevel, that toplevel gets the keyboard focus.
+    ///
+    /// When the mouse leaves a toplevel without entering another one, the keyboard focus
+    /// stays on the toplevel it left.
     True,
     /// The keyboard focus changes only when clicking on a window or the previously
     /// focused window becomes invisible.
@@ -506,6 +710,28 @@ pub fn get_seat(name: &str) -> Seat {
     get!(Seat(0)).get_seat(name)
 }
 
+/// Returns or creates a seat and makes it claim the matching input devices.
+///
+/// This is equivalent to calling [`get_seat`] followed by [`Seat::set_devices`].
+pub fn get_seat_with_devices(name: &str, devices: &[DeviceMatch]) -> Seat {
+    let seat = get_seat(name);
+    seat.set_devices(devices);
+    seat
+}
+
+/// Identifies input devices that are claimed by a seat.
+///
+/// See [`Seat::set_devices`].
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub enum DeviceMatch {
+    /// Matches devices with this name.
+    Name(String),
+    /// Matches devices with this syspath.
+    Syspath(String),
+    /// Matches devices with this devnode.
+    Devnode(String),
+}
+
 /// Returns or creates the default seat.
 ///
 /// This is equivalent to `get_seat("default")`.
@@ -513,6 +739,18 @@ pub fn get_default_seat() -> Seat {
     get_seat(DEFAULT_SEAT_NAME)
 }
 
+/// Sets the seat that newly added input devices are attached to.
+///
+/// Devices claimed by a seat via [`Seat::set_devices`] are still attached to that seat.
+/// If this function has not been called, new devices are attached to the seat called
+/// `default`, if it exists.
+///
+/// The configuration can still move devices to another seat with
+/// [`InputDevice::set_seat`] in the [`on_new_input_device`] callback.
+pub fn set_default_seat(seat: Seat) {
+    get!().set_default_seat(seat);
+}
+
 /// Sets a closure to run when a new seat has been created.
 pub fn on_new_seat<F: FnMut(Seat) + 'static>(f: F) {
     get!().on_new_seat(f)
@@ -559,7 +797,8 @@ pub fn set_double_click_distance(distance: i32) {
 /// will automatically be created.
 ///
 /// When a new input device is attached and a seat called `default` exists, the input
-/// device is initially attached to this seat.
+/// device is initially attached to this seat unless another seat has been set with
+/// [`set_default_seat`].
 pub fn disable_default_seat() {
     get!().disable_default_seat();
 }
@@ -587,6 +826,33 @@ pub enum SwitchEvent {
     ConvertedToTablet,
 }
 
+/// A pointer input that can be bound with [`Seat::bind_pointer`].
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum PointerInput {
+    /// A button, identified by its evdev code, e.g. `0x110` for the left button.
+    Button(u32),
+    /// Scrolling up.
+    ScrollUp,
+    /// Scrolling down.
+    ScrollDown,
+    /// Scrolling left.
+    ScrollLeft,
+    /// Scrolling right.
+    ScrollRight,
+}
+
+/// The part of the desktop that a pointer binding applies to.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum PointerContext {
+    /// The desktop itself, i.e. empty workspaces, bars, and areas not covered by a
+    /// window.
+    Root,
+    /// The title bars and borders of windows.
+    Title,
+    /// The contents of windows.
+    Window,
+}
+
 /// Enables or disables the unauthenticated libei socket.
 ///
 /// Even if the socket is disabled, application can still request access via the portal.
@@ -30,7 +30,8 @@
 //! the compositor. It should then use your configuration file.
 //!
 //! Note that you do not have to restart the compositor every time you want to reload your
-//! configuration afterwards. Instead, simply invoke the [`reload`] function via a shortcut.
+//! configuration afterwards. Instead, simply invoke the [`reload`] function via a shortcut or
+//! send `SIGUSR1` or `SIGHUP` to the compositor.
 
 #![allow(
     clippy::zero_prefixed_literal,
@@ -61,6 +62,7 @@ use {
 mod macros;
 #[doc(hidden)]
 pub mod _private;
+pub mod client;
 pub mod embedded;
 pub mod exec;
 pub mod input;
@@ -83,6 +85,13 @@ pub enum Direction {
     Right,
 }
 
+/// The direction in which to cycle through the windows of a mono container.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TabDirection {
+    Next,
+    Previous,
+}
+
 /// A planar axis.
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
 pub enum Axis {
@@ -100,6 +109,46 @@ impl Axis {
     }
 }
 
+/// The easing curve of animations.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+pub enum AnimationCurve {
+    /// The animation progresses at a constant rate.
+    Linear,
+    /// The animation starts fast and slows down towards the end.
+    #[default]
+    EaseOut,
+    /// The animation starts slow, speeds up, and slows down towards the end.
+    EaseInOut,
+}
+
+/// A container in the tiling tree.
+///
+/// Containers are identified by their node id. Node ids are never reused.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct Container(pub u64);
+
+impl Container {
+    /// Returns whether this container existed at the time it was retrieved.
+    pub fn exists(self) -> bool {
+        self.0 != 0
+    }
+
+    /// Returns the split axis of this container.
+    pub fn split(self) -> Axis {
+        get!(Axis::Horizontal).container_split(self)
+    }
+
+    /// Sets the split axis of this container.
+    pub fn set_split(self, axis: Axis) {
+        get!().set_container_split(self, axis)
+    }
+
+    /// Toggles the split axis of this container.
+    pub fn toggle_split(self) {
+        self.set_split(self.split().other());
+    }
+}
+
 /// Exits the compositor.
 pub fn quit(_seat: Seat) {
     get!().quit()
@@ -152,6 +201,13 @@ impl Workspace {
         self.0 != 0
     }
 
+    /// Returns the name of this workspace.
+    ///
+    /// Together with [`workspaces`], this can be used to find workspaces by name.
+    pub fn name(self) -> String {
+        get!(String::new()).get_workspace_name(self)
+    }
+
     /// Sets whether the workspaces is captured.
     ///
     /// The default is determined by `set_default_workspace_capture`.
@@ -216,6 +272,23 @@ pub fn on_devices_enumerated<F: FnOnce() + 'static>(f: F) {
     get!().on_devices_enumerated(f)
 }
 
+/// Returns the error produced by the most recent request sent to the compositor.
+///
+/// Returns `None` if the most recent request succeeded. This can be used to detect
+/// failures of functions that otherwise fail silently, for example
+///
+/// ```rust,ignore
+/// seat.set_keymap(keymap);
+/// if let Some(e) = jay_config::last_error() {
+///     log::warn!("Could not set the keymap: {}", e);
+/// }
+/// ```
+///
+/// Older compositors do not report errors. In that case this always returns `None`.
+pub fn last_error() -> Option<String> {
+    get!(None).last_error()
+}
+
 /// Returns the Jay config directory.
 pub fn config_dir() -> String {
     get!().config_dir()
@@ -233,6 +306,58 @@ pub fn set_idle(timeout: Option<Duration>) {
     get!().set_idle(timeout.unwrap_or_default())
 }
 
+/// Turns off all outputs immediately, as if the idle timeout had expired.
+///
+/// This also invokes the [`on_idle`] callback. Idle inhibitors are ignored. The outputs
+/// are turned back on by the next input event. Input during the first second is ignored
+/// so that releasing the shortcut that invoked this function does not immediately turn
+/// the outputs back on.
+pub fn enter_idle() {
+    get!().enter_idle()
+}
+
+/// Sets how long a client may take to read the events sent to it.
+///
+/// Events for a client are buffered by the compositor until the client reads them from
+/// its socket. If the client does not make progress reading them within this time, or
+/// if the buffer grows too large, the client is disconnected.
+///
+/// The default is 5 seconds.
+pub fn set_client_send_timeout(timeout: Duration) {
+    let msec = timeout.as_millis().min(u64::MAX as u128);
+    get!().set_client_send_timeout(msec as u64)
+}
+
+/// Sets how long a client may take to respond to a ping before its windows are marked as
+/// unresponsive.
+///
+/// Clients are pinged when one of their windows is focused or closed. Closing a window
+/// of an unresponsive client a second time disconnects the client.
+///
+/// The default is 5 seconds.
+pub fn set_client_ping_timeout(timeout: Duration) {
+    let msec = timeout.as_millis().min(u64::MAX as u128);
+    get!().set_client_ping_timeout(msec as u64)
+}
+
+/// Sets the maximum number of clients that can be connected at the same time.
+///
+/// New connections are rejected while this many clients are connected.
+///
+/// The default is `1024`.
+pub fn set_max_clients(max: usize) {
+    get!().set_max_clients(max)
+}
+
+/// Sets the maximum number of objects that a single client can have at the same time.
+///
+/// Clients that try to create more objects are disconnected.
+///
+/// The default is `1048576`.
+pub fn set_max_client_objects(max: usize) {
+    get!().set_max_client_objects(max)
+}
+
 /// Enables or disables explicit sync.
 ///
 /// Calling this after the compositor has started has no effect.
@@ -255,3 +380,38 @@ pub fn set_ui_drag_enabled(enabled: bool) {
 pub fn set_ui_drag_threshold(threshold: i32) {
     get!().set_ui_drag_threshold(threshold);
 }
+
+/// Enables or disables animations.
+///
+/// If enabled, windows fade in when they are opened, fade out when they are closed, and
+/// workspaces slide in when switching between them. Animations are never shown on
+/// outputs that currently use variable refresh rate.
+///
+/// The default is `false`.
+pub fn set_animations_enabled(enabled: bool) {
+    get!().set_animations_enabled(enabled);
+}
+
+/// Sets the duration of animations.
+///
+/// The default is 150 ms.
+pub fn set_animation_duration(duration: Duration) {
+    get!().set_animation_duration(duration);
+}
+
+/// Sets the easing curve of animations.
+///
+/// The default is [`AnimationCurve::EaseOut`].
+pub fn set_animation_curve(curve: AnimationCurve) {
+    get!().set_animation_curve(curve);
+}
+
+/// Sets how much unfocused windows are dimmed.
+///
+/// The opacity of unfocused windows is multiplied by `1 - factor`. The factor is clamped to
+/// `[0, 1]`. Fullscreen windows are never dimmed.
+///
+/// The default is `0`.
+pub fn set_dim_unfocused(factor: f64) {
+    get!().set_dim_unfocused(factor);
+}
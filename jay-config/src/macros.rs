@@ -8,7 +8,7 @@ macro_rules! config {
             struct X;
             impl $crate::_private::Config for X {
                 extern "C" fn configure() {
-                    $f();
+                    $crate::_private::client::configure($f);
                 }
             }
             $crate::_private::ConfigEntryGen::<X>::ENTRY
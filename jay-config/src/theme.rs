@@ -224,7 +224,7 @@ pub mod colors {
         ///
         /// Default: `#333333`.
         const 06 => SEPARATOR_COLOR,
-        /// The color of the border between windows.
+        /// The color of the border between windows and around unfocused windows.
         ///
         /// Default: `#3f474a`.
         const 07 => BORDER_COLOR,
@@ -261,8 +261,17 @@ pub mod colors {
         ///
         /// Default: `#9d28c67f`.
         const 15 => HIGHLIGHT_COLOR,
+        /// The color of the border around a focused window.
+        ///
+        /// Default: `#3f474a`.
+        const 16 => FOCUSED_BORDER_COLOR,
     }
 
+    /// The color of the border around an unfocused window.
+    ///
+    /// This is the same element as [BORDER_COLOR].
+    pub const UNFOCUSED_BORDER_COLOR: Colorable = BORDER_COLOR;
+
     /// Sets the color of GUI element.
     pub fn set_color(element: Colorable, color: Color) {
         get!().set_color(element, color);
@@ -312,5 +321,13 @@ pub mod sized {
         ///
         /// Default: 4
         const 02 => BORDER_WIDTH,
+        /// The gap between adjacent tiled windows.
+        ///
+        /// Default: 0
+        const 03 => INNER_GAP,
+        /// The gap between tiled windows and the edges of the output.
+        ///
+        /// Default: 0
+        const 04 => OUTER_GAP,
     }
 }
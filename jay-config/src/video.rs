@@ -89,6 +89,8 @@ impl Connector {
     }
 
     /// Sets the scale to use for the currently connected monitor.
+    ///
+    /// The scale must be between 0.25 and 4.
     pub fn set_scale(self, scale: f64) {
         if !self.exists() {
             return;
@@ -201,6 +203,30 @@ impl Connector {
         get!().connector_set_position(self, x, y);
     }
 
+    /// Makes this connector display the contents of another connector.
+    ///
+    /// The contents are scaled to fit this connector and centered. The workspaces of this
+    /// connector are not shown while it is mirroring another connector.
+    ///
+    /// A connector that is mirrored cannot itself mirror another connector. Mirroring
+    /// stops automatically when the source is disconnected.
+    pub fn mirror(self, source: Connector) {
+        if !self.exists() {
+            log::warn!("mirror called on a connector that does not exist");
+            return;
+        }
+        get!().connector_mirror(self, source);
+    }
+
+    /// Stops mirroring another connector.
+    pub fn unmirror(self) {
+        if !self.exists() {
+            log::warn!("unmirror called on a connector that does not exist");
+            return;
+        }
+        get!().connector_unmirror(self);
+    }
+
     /// Enables or disables the connector.
     ///
     /// By default, all connectors are enabled.
@@ -221,6 +247,14 @@ impl Connector {
         get!().connector_set_transform(self, transform);
     }
 
+    /// Returns the transformation applied to the content of this connector.
+    pub fn transform(self) -> Transform {
+        if !self.exists() {
+            return Transform::None;
+        }
+        get!(Transform::None).connector_get_transform(self)
+    }
+
     pub fn name(self) -> String {
         if !self.exists() {
             return String::new();
@@ -254,6 +288,17 @@ impl Connector {
         get!().set_vrr_mode(Some(self), mode)
     }
 
+    /// Returns whether VRR is currently active on this connector.
+    ///
+    /// This depends on the VRR mode, on the content of the connector, and on whether the
+    /// connected monitor supports VRR.
+    pub fn vrr_enabled(self) -> bool {
+        if !self.exists() {
+            return false;
+        }
+        get!(false).connector_get_vrr_enabled(self)
+    }
+
     /// Sets the VRR cursor refresh rate.
     ///
     /// Limits the rate at which cursors are updated on screen when VRR is active.
@@ -272,6 +317,31 @@ impl Connector {
     pub fn set_format(self, format: Format) {
         get!().connector_set_format(self, format);
     }
+
+    /// Sets the background image of this connector.
+    ///
+    /// The image must be a PNG file. It is drawn beneath all windows and layer shell
+    /// surfaces.
+    ///
+    /// The image is loaded in the background. An error is returned if the connector
+    /// cannot display a background. If the image cannot be loaded, the previous
+    /// background is kept and the error is logged.
+    pub fn set_background(self, path: &str, mode: BackgroundMode) -> Result<(), String> {
+        if !self.exists() {
+            return Err("The connector does not exist".to_string());
+        }
+        get!(Err("Not connected to the compositor".to_string()))
+            .connector_set_background(self, path, mode)
+    }
+
+    /// Moves all workspaces whose output is currently disconnected to this connector.
+    ///
+    /// When an output is disconnected, its workspaces are moved to another output and
+    /// return automatically once the output is connected again. After calling this
+    /// function, the moved workspaces instead stay on this connector.
+    pub fn adopt_orphaned_workspaces(self) {
+        get!().adopt_orphaned_workspaces(self);
+    }
 }
 
 /// Returns all available DRM devices.
@@ -566,6 +636,22 @@ pub enum Transform {
     FlipRotate270,
 }
 
+/// Determines how a background image is fitted to a connector.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub enum BackgroundMode {
+    /// Scale the image to cover the whole output, cropping it if necessary.
+    #[default]
+    Fill,
+    /// Scale the image to fit into the output, leaving bars if necessary.
+    Fit,
+    /// Stretch the image to the size of the output.
+    Stretch,
+    /// Repeat the image at its original size.
+    Tile,
+    /// Center the image at its original size.
+    Center,
+}
+
 /// The VRR mode of a connector.
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
 pub struct VrrMode(pub u32);
@@ -628,6 +714,29 @@ pub fn set_tearing_mode(mode: TearingMode) {
     get!().set_tearing_mode(None, mode)
 }
 
+/// The content type of the fullscreen surface on a connector.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub struct ContentType(pub u32);
+
+impl ContentType {
+    /// No fullscreen surface or no content type has been set.
+    pub const NONE: Self = Self(0);
+    /// The content is a still image or a slideshow.
+    pub const PHOTO: Self = Self(1);
+    /// The content is a video or an animation.
+    pub const VIDEO: Self = Self(2);
+    /// The content is an interactive game.
+    pub const GAME: Self = Self(3);
+}
+
+/// Sets the callback to be called when the content type of a connector changes.
+///
+/// The content type is the type of the surface displayed fullscreen on the connector. It is
+/// [ContentType::NONE] if no fullscreen surface is displayed.
+pub fn on_content_type_changed<F: FnMut(Connector, ContentType) + 'static>(f: F) {
+    get!().on_content_type_changed(f)
+}
+
 /// A graphics format.
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Format(pub u32);
@@ -661,4 +770,6 @@ impl Format {
     pub const XBGR16161616: Self = Self(25);
     pub const ABGR16161616F: Self = Self(26);
     pub const XBGR16161616F: Self = Self(27);
+    pub const NV12: Self = Self(28);
+    pub const YUV420: Self = Self(29);
 }
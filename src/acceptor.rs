@@ -2,10 +2,11 @@ use {
     crate::{
         async_engine::SpawnedFuture,
         client::{ClientCaps, CAPS_DEFAULT},
+        io_uring::IoUringError,
         state::State,
         utils::{errorfmt::ErrorFmt, oserror::OsError, xrd::xrd},
     },
-    std::rc::Rc,
+    std::{fs::DirBuilder, io, os::unix::fs::DirBuilderExt, rc::Rc},
     thiserror::Error,
     uapi::{c, format_ustr, Errno, OwnedFd, Ustr, Ustring},
 };
@@ -14,8 +15,10 @@ use {
 pub enum AcceptorError {
     #[error("XDG_RUNTIME_DIR is not set")]
     XrdNotSet,
-    #[error("XDG_RUNTIME_DIR ({0:?}) is too long to form a unix socket address")]
-    XrdTooLong(String),
+    #[error("The socket directory ({0:?}) is too long to form a unix socket address")]
+    SocketDirTooLong(String),
+    #[error("Could not create the socket directory {0:?}")]
+    CreateSocketDir(String, #[source] io::Error),
     #[error("Could not create a wayland socket")]
     SocketFailed(#[source] OsError),
     #[error("Could not stat the existing socket")]
@@ -37,7 +40,7 @@ pub struct Acceptor {
 }
 
 struct AllocatedSocket {
-    // wayland-x
+    // wayland-x, relative to XDG_RUNTIME_DIR unless --socket-dir is absolute
     name: String,
     // /run/user/1000/wayland-x
     path: Ustring,
@@ -58,20 +61,54 @@ impl Drop for AllocatedSocket {
     }
 }
 
+struct SocketDir {
+    // /run/user/1000/dir
+    path: String,
+    // dir/, prepended to the socket name
+    prefix: String,
+}
+
+impl SocketDir {
+    fn new(xrd: String, dir: Option<&str>) -> Result<Self, AcceptorError> {
+        let Some(dir) = dir else {
+            return Ok(Self {
+                path: xrd,
+                prefix: String::new(),
+            });
+        };
+        let dir = dir.trim_end_matches('/');
+        let path = match dir.starts_with('/') {
+            true => dir.to_string(),
+            false => format!("{}/{}", xrd, dir),
+        };
+        let res = DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(&path);
+        if let Err(e) = res {
+            return Err(AcceptorError::CreateSocketDir(path, e));
+        }
+        Ok(Self {
+            path,
+            prefix: format!("{}/", dir),
+        })
+    }
+}
+
 fn bind_socket(
     insecure: &Rc<OwnedFd>,
     secure: &Rc<OwnedFd>,
-    xrd: &str,
+    dir: &SocketDir,
     id: u32,
 ) -> Result<AllocatedSocket, AcceptorError> {
     let mut addr: c::sockaddr_un = uapi::pod_zeroed();
     addr.sun_family = c::AF_UNIX as _;
-    let name = format!("wayland-{}", id);
-    let path = format_ustr!("{}/{}", xrd, name);
+    let name = format!("{}wayland-{}", dir.prefix, id);
+    let path = format_ustr!("{}/wayland-{}", dir.path, id);
     let jay_path = format_ustr!("{}.jay", path.display());
     let lock_path = format_ustr!("{}.lock", path.display());
     if jay_path.len() + 1 > addr.sun_path.len() {
-        return Err(AcceptorError::XrdTooLong(xrd.to_string()));
+        return Err(AcceptorError::SocketDirTooLong(dir.path.clone()));
     }
     let lock_fd = match uapi::open(&*lock_path, c::O_CREAT | c::O_CLOEXEC | c::O_RDWR, 0o644) {
         Ok(l) => l,
@@ -107,11 +144,12 @@ fn bind_socket(
     })
 }
 
-fn allocate_socket() -> Result<AllocatedSocket, AcceptorError> {
+fn allocate_socket(dir: Option<&str>) -> Result<AllocatedSocket, AcceptorError> {
     let xrd = match xrd() {
         Some(d) => d,
         _ => return Err(AcceptorError::XrdNotSet),
     };
+    let dir = SocketDir::new(xrd, dir)?;
     let mut fds = [None, None];
     for fd in &mut fds {
         let socket = match uapi::socket(c::AF_UNIX, c::SOCK_STREAM | c::SOCK_CLOEXEC, 0) {
@@ -123,7 +161,7 @@ fn allocate_socket() -> Result<AllocatedSocket, AcceptorError> {
     let unsecure = fds[0].take().unwrap();
     let secure = fds[1].take().unwrap();
     for i in 1..1000 {
-        match bind_socket(&unsecure, &secure, &xrd, i) {
+        match bind_socket(&unsecure, &secure, &dir, i) {
             Ok(s) => return Ok(s),
             Err(e) => {
                 log::warn!("Cannot use the wayland-{} socket: {}", i, ErrorFmt(e));
@@ -137,7 +175,7 @@ impl Acceptor {
     pub fn install(
         state: &Rc<State>,
     ) -> Result<(Rc<Acceptor>, Vec<SpawnedFuture<()>>), AcceptorError> {
-        let socket = allocate_socket()?;
+        let socket = allocate_socket(state.run_args.socket_dir.as_deref())?;
         log::info!("bound to socket {}", socket.path.display());
         for fd in [&socket.secure, &socket.insecure] {
             if let Err(e) = uapi::listen(fd.raw(), 4096) {
@@ -159,6 +197,9 @@ impl Acceptor {
         Ok((acc, futures))
     }
 
+    /// Returns the value of `WAYLAND_DISPLAY` that clients use to connect to this socket.
+    ///
+    /// This is relative to `XDG_RUNTIME_DIR` unless the socket directory is absolute.
     pub fn socket_name(&self) -> &str {
         &self.socket.name
     }
@@ -169,9 +210,38 @@ impl Acceptor {
     }
 }
 
+/// How long to wait before accepting again after running out of fds or memory.
+const ACCEPT_RETRY_MS: u64 = 100;
+
+/// Accepts the next connection on a listening socket.
+///
+/// Errors that only affect a single connection and errors caused by temporary resource
+/// exhaustion are logged and the accept is retried. Any other error is returned.
+pub async fn accept_client(state: &State, fd: &Rc<OwnedFd>) -> Result<Rc<OwnedFd>, IoUringError> {
+    loop {
+        let e = match state.ring.accept(fd, c::SOCK_CLOEXEC).await {
+            Ok(fd) => return Ok(fd),
+            Err(e) => e,
+        };
+        let IoUringError::OsError(OsError(errno)) = e else {
+            return Err(e);
+        };
+        match errno {
+            c::ECONNABORTED | c::EINTR => {}
+            c::EMFILE | c::ENFILE | c::ENOBUFS | c::ENOMEM => {
+                log::warn!("Could not accept a client: {}", ErrorFmt(e));
+                if state.wheel.timeout(ACCEPT_RETRY_MS).await.is_err() {
+                    return Err(e);
+                }
+            }
+            _ => return Err(e),
+        }
+    }
+}
+
 async fn accept(fd: Rc<OwnedFd>, state: Rc<State>, effective_caps: ClientCaps) {
     loop {
-        let fd = match state.ring.accept(&fd, c::SOCK_CLOEXEC).await {
+        let fd = match accept_client(&state, &fd).await {
             Ok(fd) => fd,
             Err(e) => {
                 log::error!("Could not accept a client: {}", ErrorFmt(e));
@@ -183,8 +253,7 @@ async fn accept(fd: Rc<OwnedFd>, state: Rc<State>, effective_caps: ClientCaps) {
             .clients
             .spawn(id, &state, fd, effective_caps, ClientCaps::all())
         {
-            log::error!("Could not spawn a client: {}", ErrorFmt(e));
-            break;
+            log::warn!("Rejected a client: {}", ErrorFmt(e));
         }
     }
     state.ring.stop();
@@ -0,0 +1,191 @@
+use {
+    crate::{
+        allocator::{AllocatorError, BufferObject, BufferUsage, BO_USE_RENDERING},
+        format::ARGB8888,
+        gfx_api::{
+            needs_render_usage, AcquireSync, BufferResv, GfxError, GfxTexture, ReleaseSync,
+            SampleRect,
+        },
+        rect::Rect,
+        state::State,
+        tree::{OutputNode, WorkspaceNode},
+    },
+    jay_config::AnimationCurve,
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+    thiserror::Error,
+};
+
+const DEFAULT_DURATION_NSEC: u64 = 150_000_000;
+
+pub struct Animations {
+    pub enabled: Cell<bool>,
+    pub duration_nsec: Cell<u64>,
+    pub curve: Cell<AnimationCurve>,
+    pub closing: RefCell<Vec<ClosingToplevel>>,
+}
+
+impl Default for Animations {
+    fn default() -> Self {
+        Self {
+            enabled: Cell::new(false),
+            duration_nsec: Cell::new(DEFAULT_DURATION_NSEC),
+            curve: Default::default(),
+            closing: Default::default(),
+        }
+    }
+}
+
+impl Animations {
+    pub fn clear(&self) {
+        self.closing.take();
+    }
+}
+
+/// A running animation.
+///
+/// Animations never change the geometry of nodes. They are only applied while rendering so
+/// that input is always delivered according to the final geometry.
+#[derive(Copy, Clone)]
+pub struct Animation {
+    start: u64,
+    duration: u64,
+    curve: AnimationCurve,
+}
+
+impl Animation {
+    /// Starts a new animation on the output.
+    ///
+    /// Returns `None` if animations are disabled or if the output is using VRR.
+    pub fn start(state: &State, output: &OutputNode) -> Option<Self> {
+        let animations = &state.animations;
+        if !animations.enabled.get() || output.schedule.vrr_enabled() {
+            return None;
+        }
+        let duration = animations.duration_nsec.get();
+        if duration == 0 {
+            return None;
+        }
+        Some(Self {
+            start: state.now_nsec(),
+            duration,
+            curve: animations.curve.get(),
+        })
+    }
+
+    /// Returns the eased progress in `[0, 1)` or `None` if the animation has finished.
+    pub fn progress(&self, now: u64) -> Option<f64> {
+        let elapsed = now.saturating_sub(self.start);
+        if elapsed >= self.duration {
+            return None;
+        }
+        let t = elapsed as f64 / self.duration as f64;
+        let progress = match self.curve {
+            AnimationCurve::Linear => t,
+            AnimationCurve::EaseOut => 1.0 - (1.0 - t).powi(3),
+            AnimationCurve::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (2.0 - 2.0 * t).powi(3) / 2.0
+                }
+            }
+        };
+        Some(progress)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error("There is no render context")]
+    NoRenderContext,
+    #[error("Render context supports no modifiers for ARGB8888 rendering")]
+    Modifiers,
+    #[error(transparent)]
+    AllocatorError(#[from] AllocatorError),
+    #[error(transparent)]
+    RenderError(#[from] GfxError),
+}
+
+/// A copy of a texture in a buffer owned by the compositor.
+pub struct Snapshot {
+    _bo: Rc<dyn BufferObject>,
+    pub texture: Rc<dyn GfxTexture>,
+}
+
+impl Snapshot {
+    /// Copies `texture` into a new buffer.
+    ///
+    /// Clients may reuse their buffers once they have been released. Animations that
+    /// outlive the surface must therefore sample from a copy.
+    pub fn new(
+        state: &State,
+        texture: &Rc<dyn GfxTexture>,
+        resv: &Rc<dyn BufferResv>,
+        release_sync: ReleaseSync,
+    ) -> Result<Self, SnapshotError> {
+        let Some(ctx) = state.render_ctx.get() else {
+            return Err(SnapshotError::NoRenderContext);
+        };
+        let formats = ctx.formats();
+        let Some(format) = formats.get(&ARGB8888.drm) else {
+            return Err(SnapshotError::Modifiers);
+        };
+        let modifiers: Vec<_> = format
+            .write_modifiers
+            .iter()
+            .filter(|(m, _)| format.read_modifiers.contains(*m))
+            .collect();
+        if modifiers.is_empty() {
+            return Err(SnapshotError::Modifiers);
+        }
+        let mut usage = BO_USE_RENDERING;
+        if !needs_render_usage(modifiers.iter().map(|(_, m)| *m)) {
+            usage = BufferUsage::none();
+        }
+        let modifiers: Vec<_> = modifiers.iter().map(|(m, _)| **m).collect();
+        let (width, height) = texture.size();
+        let bo = ctx.allocator().create_bo(
+            &state.dma_buf_ids,
+            width,
+            height,
+            ARGB8888,
+            &modifiers,
+            usage,
+        )?;
+        let img = ctx.clone().dmabuf_img(bo.dmabuf())?;
+        let fb = img.clone().to_framebuffer()?;
+        fb.copy_texture(
+            AcquireSync::Unnecessary,
+            ReleaseSync::Implicit,
+            texture,
+            Some(resv),
+            AcquireSync::Unnecessary,
+            release_sync,
+            0,
+            0,
+        )?;
+        Ok(Self {
+            _bo: bo,
+            texture: img.to_texture()?,
+        })
+    }
+}
+
+/// The last frame of a toplevel that is fading out.
+pub struct ClosingToplevel {
+    pub animation: Animation,
+    pub snapshot: Snapshot,
+    pub tpoints: SampleRect,
+    pub rect: Rect,
+    /// The area the animation is clipped to.
+    pub bounds: Rect,
+}
+
+pub struct WorkspaceSlide {
+    pub animation: Animation,
+    pub old: Option<Rc<WorkspaceNode>>,
+    pub forward: bool,
+}
@@ -15,6 +15,7 @@ use {
                 },
                 wl_pointer::{CONTINUOUS, FINGER, HORIZONTAL_SCROLL, VERTICAL_SCROLL, WHEEL},
             },
+            wp_content_type_v1::ContentType,
         },
         libinput::consts::DeviceCapability,
         video::drm::{ConnectorType, DrmConnector, DrmError, DrmVersion},
@@ -38,6 +39,13 @@ pub trait Backend {
     fn clear(&self) {
         // nothing
     }
+    /// Restores the display state that was present before the compositor started.
+    ///
+    /// This is called once when the compositor quits. Outputs are not presented to
+    /// afterwards.
+    fn restore_display_state(&self) {
+        // nothing
+    }
     #[cfg_attr(not(feature = "it"), expect(dead_code))]
     fn into_any(self: Rc<Self>) -> Rc<dyn Any>;
 
@@ -126,9 +134,19 @@ pub trait Connector {
     fn set_tearing_enabled(&self, enabled: bool) {
         let _ = enabled;
     }
+    fn set_content_type(&self, content_type: Option<ContentType>) {
+        let _ = content_type;
+    }
     fn set_fb_format(&self, format: &'static Format) {
         let _ = format;
     }
+    fn gamma_lut_size(&self) -> Option<u32> {
+        None
+    }
+    fn set_gamma_lut(&self, lut: Option<&[[u16; 3]]>) -> bool {
+        let _ = lut;
+        false
+    }
 }
 
 #[derive(Debug)]
@@ -441,7 +459,6 @@ pub enum InputEvent {
 }
 
 pub enum DrmEvent {
-    #[expect(dead_code)]
     Removed,
     GfxApiChanged,
 }
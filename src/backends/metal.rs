@@ -1,6 +1,7 @@
 mod input;
 mod monitor;
 mod present;
+mod session;
 mod video;
 
 use {
@@ -10,11 +11,16 @@ use {
             Backend, InputDevice, InputDeviceAccelProfile, InputDeviceCapability,
             InputDeviceGroupId, InputDeviceId, InputEvent, KeyState, TransformMatrix,
         },
-        backends::metal::video::{
-            MetalDrmDeviceData, MetalLeaseData, MetalRenderContext, PendingDrmDevice,
-            PersistentDisplayData,
+        backends::metal::{
+            session::{
+                libseat_requested, LibSeatSession, LogindSession, LogindSessionError, SeatEvent,
+                SeatProvider,
+            },
+            video::{
+                MetalDrmDeviceData, MetalLeaseData, MetalRenderContext, PendingDrmDevice,
+                PersistentDisplayData,
+            },
         },
-        dbus::{DbusError, SignalHandler},
         drm_feedback::DrmFeedback,
         gfx_api::{GfxError, SyncFile},
         ifs::{
@@ -32,7 +38,7 @@ use {
             device::{LibInputDevice, RegisteredDevice},
             LibInput, LibInputAdapter, LibInputError,
         },
-        logind::{LogindError, Session},
+        libseat::LibSeatError,
         state::State,
         udev::{Udev, UdevError, UdevMonitor},
         utils::{
@@ -42,6 +48,7 @@ use {
             hash_map_ext::HashMapExt,
             numcell::NumCell,
             oserror::OsError,
+            queue::AsyncQueue,
             smallmap::SmallMap,
             syncqueue::SyncQueue,
         },
@@ -66,12 +73,10 @@ use {
 
 #[derive(Debug, Error)]
 pub enum MetalError {
-    #[error("Could not connect to the dbus system socket")]
-    DbusSystemSocket(#[source] DbusError),
-    #[error("Could not retrieve the logind session")]
-    LogindSession(#[source] LogindError),
-    #[error("Could not take control of the logind session")]
-    TakeControl(#[source] LogindError),
+    #[error(transparent)]
+    LogindSession(#[from] LogindSessionError),
+    #[error("Could not open the seat via libseat")]
+    LibSeat(#[source] LibSeatError),
     #[error("Could not enumerate devices")]
     Enumerate(#[source] Box<Self>),
     #[error(transparent)]
@@ -114,10 +119,6 @@ pub enum MetalError {
     CreateEncoder(#[source] DrmError),
     #[error(transparent)]
     DrmError(#[from] DrmError),
-    #[error("Could not create device-paused signal handler")]
-    DevicePauseSignalHandler(#[source] DbusError),
-    #[error("Could not create device-resumed signal handler")]
-    DeviceResumeSignalHandler(#[source] DbusError),
     #[error("Device render context does not support required format {0}")]
     MissingDevFormat(&'static str),
     #[error("Render context does not support required format {0}")]
@@ -146,9 +147,8 @@ pub struct MetalBackend {
     libinput: Rc<LibInput>,
     libinput_fd: Rc<OwnedFd>,
     device_holder: Rc<DeviceHolder>,
-    session: Session,
-    pause_handler: Cell<Option<SignalHandler>>,
-    resume_handler: Cell<Option<SignalHandler>>,
+    session: Rc<dyn SeatProvider>,
+    seat_events: Rc<AsyncQueue<SeatEvent>>,
     ctx: CloneCell<Option<Rc<MetalRenderContext>>>,
     signaled_sync_file: CloneCell<Option<SyncFile>>,
     default_feedback: CloneCell<Option<Rc<DrmFeedback>>>,
@@ -171,6 +171,10 @@ impl MetalBackend {
             "handle libinput events",
             self.clone().handle_libinput_events(),
         );
+        let _seat_events = self
+            .state
+            .eng
+            .spawn("handle seat events", self.clone().handle_seat_events());
         if let Err(e) = self.enumerate_devices() {
             return Err(MetalError::Enumerate(Box::new(e)));
         }
@@ -187,9 +191,26 @@ impl Backend for MetalBackend {
         })
     }
 
+    fn restore_display_state(&self) {
+        for dev in self.device_holder.drm_devices.lock().values() {
+            if dev.dev.paused.get() {
+                continue;
+            }
+            for connector in dev.connectors.lock().values() {
+                connector.can_present.set(false);
+            }
+            if let Err(e) = dev.dev.saved_state.restore(&dev.dev.master) {
+                log::error!(
+                    "Could not restore the display state of {}: {}",
+                    dev.dev.devnode.to_bytes().as_bstr(),
+                    ErrorFmt(e),
+                );
+            }
+        }
+    }
+
     fn clear(&self) {
-        self.pause_handler.take();
-        self.resume_handler.take();
+        self.seat_events.clear();
         self.ctx.take();
         self.device_holder.devices.clear();
         for dev in self.device_holder.input_devices.take() {
@@ -238,24 +259,21 @@ impl Backend for MetalBackend {
     }
 
     fn switch_to(&self, vtnr: u32) {
-        self.session.switch_to(vtnr, move |res| {
-            if let Err(e) = res {
-                log::error!("Could not switch to VT {}: {}", vtnr, ErrorFmt(e));
-            }
-        })
+        self.session.switch_session(vtnr);
     }
 
     fn set_idle(&self, idle: bool) {
         let devices = self.device_holder.drm_devices.lock();
         for device in devices.values() {
             let mut change = device.dev.master.change();
+            let mut changed = vec![];
             for connector in device.connectors.lock().values() {
                 if let Some(crtc) = connector.crtc.get() {
                     if idle == crtc.active.value.get() {
-                        crtc.active.value.set(!idle);
                         change.change_object(crtc.id, |c| {
                             c.change(crtc.active.id, (!idle) as _);
                         });
+                        changed.push(crtc);
                     }
                 }
             }
@@ -263,6 +281,10 @@ impl Backend for MetalBackend {
                 log::error!("Could not set monitors idle/not idle: {}", ErrorFmt(e));
                 return;
             }
+            // Only update the cached state once the kernel has accepted the change.
+            for crtc in changed {
+                crtc.active.value.set(!idle);
+            }
         }
         if idle {
             self.state.set_backend_idle(true);
@@ -291,18 +313,34 @@ fn dup_fd(fd: c::c_int) -> Result<Rc<OwnedFd>, MetalError> {
     }
 }
 
-pub async fn create(state: &Rc<State>) -> Result<Rc<MetalBackend>, MetalError> {
-    let socket = match state.dbus.system().await {
-        Ok(s) => s,
-        Err(e) => return Err(MetalError::DbusSystemSocket(e)),
-    };
-    let session = match Session::get(&socket).await {
-        Ok(s) => s,
-        Err(e) => return Err(MetalError::LogindSession(e)),
+async fn create_session(
+    state: &Rc<State>,
+    events: &Rc<AsyncQueue<SeatEvent>>,
+) -> Result<Rc<dyn SeatProvider>, MetalError> {
+    if libseat_requested() {
+        return match LibSeatSession::new(state, events) {
+            Ok(s) => Ok(s),
+            Err(e) => Err(MetalError::LibSeat(e)),
+        };
+    }
+    let e = match LogindSession::new(state, events).await {
+        Ok(s) => return Ok(Rc::new(s)),
+        Err(e) => e,
     };
-    if let Err(e) = session.take_control().await {
-        return Err(MetalError::TakeControl(e));
+    if !LibSeatSession::is_available() {
+        return Err(e.into());
     }
+    log::warn!("Could not use logind: {}", ErrorFmt(&e));
+    log::info!("Falling back to libseat");
+    match LibSeatSession::new(state, events) {
+        Ok(s) => Ok(s),
+        Err(e) => Err(MetalError::LibSeat(e)),
+    }
+}
+
+pub async fn create(state: &Rc<State>) -> Result<Rc<MetalBackend>, MetalError> {
+    let seat_events = Rc::new(AsyncQueue::new());
+    let session = create_session(state, &seat_events).await?;
     let device_holder = Rc::new(DeviceHolder {
         devices: Default::default(),
         input_devices: Default::default(),
@@ -327,31 +365,12 @@ pub async fn create(state: &Rc<State>) -> Result<Rc<MetalBackend>, MetalError> {
         libinput_fd,
         device_holder,
         session,
-        pause_handler: Default::default(),
-        resume_handler: Default::default(),
+        seat_events,
         ctx: Default::default(),
         signaled_sync_file: Default::default(),
         default_feedback: Default::default(),
         persistent_display_data: Default::default(),
     });
-    metal.pause_handler.set(Some({
-        let mtl = metal.clone();
-        let sh = metal.session.on_pause(move |p| mtl.handle_device_pause(p));
-        match sh {
-            Ok(sh) => sh,
-            Err(e) => return Err(MetalError::DevicePauseSignalHandler(e)),
-        }
-    }));
-    metal.resume_handler.set(Some({
-        let mtl = metal.clone();
-        let sh = metal
-            .session
-            .on_resume(move |p| mtl.handle_device_resume(p));
-        match sh {
-            Ok(sh) => sh,
-            Err(e) => return Err(MetalError::DeviceResumeSignalHandler(e)),
-        }
-    }));
     Ok(metal)
 }
 
@@ -377,6 +396,7 @@ struct MetalInputDevice {
     // state
     pressed_keys: SmallMap<u32, (), 5>,
     pressed_buttons: SmallMap<u32, (), 2>,
+    pressed_touches: SmallMap<i32, (), 10>,
 
     // config
     desired: InputDeviceProperties,
@@ -508,6 +528,13 @@ impl MetalInputDevice {
                 state: KeyState::Released,
             });
         }
+        let touches = self.pressed_touches.take();
+        for &(id, _) in &touches {
+            self.event(InputEvent::TouchCancel { time_usec, id });
+        }
+        if !touches.is_empty() {
+            self.event(InputEvent::TouchFrame { time_usec });
+        }
     }
 
     fn set_accel_profile_(&self, profile: AccelProfile) {
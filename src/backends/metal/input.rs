@@ -547,6 +547,7 @@ impl MetalBackend {
 
     fn handle_touch_down(self: &Rc<Self>, event: LibInputEvent) {
         let (event, dev) = unpack!(self, event, touch_event);
+        dev.pressed_touches.insert(event.seat_slot(), ());
         dev.event(InputEvent::TouchDown {
             time_usec: event.time_usec(),
             id: event.seat_slot(),
@@ -557,6 +558,7 @@ impl MetalBackend {
 
     fn handle_touch_up(self: &Rc<Self>, event: LibInputEvent) {
         let (event, dev) = unpack!(self, event, touch_event);
+        dev.pressed_touches.remove(&event.seat_slot());
         dev.event(InputEvent::TouchUp {
             time_usec: event.time_usec(),
             id: event.seat_slot(),
@@ -575,6 +577,7 @@ impl MetalBackend {
 
     fn handle_touch_cancel(self: &Rc<Self>, event: LibInputEvent) {
         let (event, dev) = unpack!(self, event, touch_event);
+        dev.pressed_touches.remove(&event.seat_slot());
         dev.event(InputEvent::TouchCancel {
             time_usec: event.time_usec(),
             id: event.seat_slot(),
@@ -2,10 +2,10 @@ use {
     crate::{
         backend::{BackendEvent, ConnectorEvent},
         backends::metal::{
+            session::{SeatError, SeatEvent, TakenDevice},
             video::{FrontState, MetalDrmDeviceData, PendingDrmDevice},
             MetalBackend, MetalDevice, MetalError, MetalInputDevice,
         },
-        dbus::{DbusError, TRUE},
         udev::UdevDevice,
         utils::{
             bitflags::BitflagsExt,
@@ -14,12 +14,9 @@ use {
             nonblock::{set_block, set_nonblock},
         },
         video::drm::DrmMaster,
-        wire_dbus::org::freedesktop::login1::session::{
-            PauseDevice, ResumeDevice, TakeDeviceReply,
-        },
     },
     bstr::ByteSlice,
-    std::{cell::Cell, rc::Rc},
+    std::{cell::Cell, ffi::CStr, rc::Rc},
     uapi::{c, OwnedFd},
 };
 
@@ -61,6 +58,7 @@ impl MetalBackend {
                 match action.to_bytes() {
                     b"add" => self.handle_device_add(dev),
                     b"change" => self.handle_device_change(dev),
+                    b"remove" => self.handle_device_remove(dev),
                     _ => None,
                 };
             }
@@ -68,31 +66,77 @@ impl MetalBackend {
         log::error!("Monitor task exited. Future hotplug events will be ignored.");
     }
 
-    pub fn handle_device_pause(self: &Rc<Self>, pause: PauseDevice) {
-        if pause.ty == "pause" {
-            self.session.device_paused(pause.major, pause.minor);
+    pub async fn handle_seat_events(self: Rc<Self>) {
+        loop {
+            match self.seat_events.pop().await {
+                SeatEvent::DevicePaused(dev) => self.handle_device_paused(dev),
+                SeatEvent::DeviceRemoved(dev) => self.handle_device_removed(dev),
+                SeatEvent::DeviceResumed(dev, fd) => self.handle_device_resume(dev, fd),
+                SeatEvent::SeatDisabled => self.handle_seat_disabled(),
+                SeatEvent::SeatEnabled => self.handle_seat_enabled(),
+            }
         }
-        let dev = uapi::makedev(pause.major as _, pause.minor as _);
-        if pause.ty == "gone" {
-            self.handle_device_removed(dev);
-        } else {
-            self.handle_device_paused(dev);
+    }
+
+    fn all_devices(&self) -> Vec<MetalDevice> {
+        let devices = self.device_holder.devices.lock();
+        devices.values().cloned().collect()
+    }
+
+    fn handle_seat_disabled(self: &Rc<Self>) {
+        for dev in self.all_devices() {
+            match dev {
+                MetalDevice::Input(id) => self.handle_input_device_paused(&id),
+                MetalDevice::Drm(dd) => self.handle_drm_device_paused(&dd),
+            }
         }
+        self.session.disable_seat();
     }
 
-    pub fn handle_device_resume(self: &Rc<Self>, resume: ResumeDevice) {
-        let dev = uapi::makedev(resume.major as _, resume.minor as _);
+    fn handle_seat_enabled(self: &Rc<Self>) {
+        for dev in self.all_devices() {
+            match dev {
+                MetalDevice::Input(id) => {
+                    if id.inputdev.is_some() || !id.fully_initialized.get() {
+                        continue;
+                    }
+                    // Input devices are revoked when the seat is disabled and must be
+                    // opened again.
+                    self.session.release_device(id.devnum);
+                    let slf = self.clone();
+                    let devnode = id.devnode.clone();
+                    self.session.take_device(
+                        id.devnum,
+                        &devnode,
+                        Box::new(move |res| match res {
+                            Ok(res) => slf.handle_input_device_resume(&id, res.fd),
+                            Err(e) => {
+                                log::error!("Could not reopen input device: {}", ErrorFmt(e));
+                            }
+                        }),
+                    );
+                }
+                MetalDevice::Drm(dd) => {
+                    if dd.dev.paused.get() {
+                        self.handle_drm_device_resume(&dd);
+                    }
+                }
+            }
+        }
+    }
+
+    fn handle_device_resume(self: &Rc<Self>, dev: c::dev_t, fd: Rc<OwnedFd>) {
         let dev = match self.device_holder.devices.get(&dev) {
             Some(d) => d,
             _ => return,
         };
         match dev {
-            MetalDevice::Input(id) => self.handle_input_device_resume(&id, resume.fd),
-            MetalDevice::Drm(dd) => self.handle_drm_device_resume(&dd, resume.fd),
+            MetalDevice::Input(id) => self.handle_input_device_resume(&id, fd),
+            MetalDevice::Drm(dd) => self.handle_drm_device_resume(&dd),
         }
     }
 
-    fn handle_drm_device_resume(self: &Rc<Self>, dev: &Rc<MetalDrmDeviceData>, _fd: Rc<OwnedFd>) {
+    fn handle_drm_device_resume(self: &Rc<Self>, dev: &Rc<MetalDrmDeviceData>) {
         log::info!("Device resumed: {}", dev.dev.devnode.to_bytes().as_bstr());
         dev.dev.paused.set(false);
         self.break_leases(dev);
@@ -119,6 +163,15 @@ impl MetalBackend {
         self.reinit_input_device(dev, &fd);
     }
 
+    fn handle_device_remove(self: &Rc<Self>, dev: UdevDevice) -> Option<()> {
+        let devnum = dev.devnum();
+        if self.device_holder.devices.contains(&devnum) {
+            self.session.release_device(devnum);
+            self.handle_device_removed(devnum);
+        }
+        None
+    }
+
     fn handle_device_removed(self: &Rc<Self>, dev: c::dev_t) {
         let dev = match self.device_holder.devices.remove(&dev) {
             Some(d) => d,
@@ -132,6 +185,7 @@ impl MetalBackend {
 
     fn handle_drm_device_removed(self: &Rc<Self>, dev: &Rc<MetalDrmDeviceData>) {
         log::info!("Device removed: {}", dev.dev.devnode.to_bytes().as_bstr());
+        self.remove_drm_device(dev);
     }
 
     fn handle_input_device_removed(self: &Rc<Self>, dev: &Rc<MetalInputDevice>) {
@@ -227,7 +281,7 @@ impl MetalBackend {
         };
         self.device_holder.pending_drm_devices.set(devnum, dev);
         let slf = self.clone();
-        self.get_device(devnum, move |res| {
+        self.get_device(devnum, devnode, move |res| {
             let dev = match slf.device_holder.pending_drm_devices.remove(&devnum) {
                 Some(d) if d.id == id => d,
                 _ => return,
@@ -252,7 +306,11 @@ impl MetalBackend {
                     return;
                 }
             };
-            let dev = match slf.create_drm_device(dev, &master) {
+            let inactive = res.inactive;
+            if inactive {
+                log::info!("Session is inactive. Deferring initialization until it resumes.");
+            }
+            let dev = match slf.create_drm_device(dev, &master, inactive) {
                 Ok(d) => d,
                 Err(e) => {
                     log::error!("Could not initialize drm device: {}", ErrorFmt(e));
@@ -331,6 +389,7 @@ impl MetalBackend {
             name: Default::default(),
             pressed_keys: Default::default(),
             pressed_buttons: Default::default(),
+            pressed_touches: Default::default(),
             desired: Default::default(),
             transform_matrix: Default::default(),
             effective: Default::default(),
@@ -341,7 +400,7 @@ impl MetalBackend {
         self.device_holder
             .devices
             .set(devnum, MetalDevice::Input(dev));
-        self.get_device(devnum, move |res| {
+        self.get_device(devnum, devnode, move |res| {
             let id = &slf.device_holder.devices;
             let mut slots = slf.device_holder.input_devices.borrow_mut();
             let dev = 'dev: {
@@ -361,7 +420,7 @@ impl MetalBackend {
                     return;
                 }
             };
-            if res.inactive == TRUE {
+            if res.inactive {
                 return;
             }
             slf.reinit_input_device(&dev, &res.fd);
@@ -391,22 +450,26 @@ impl MetalBackend {
         dev.apply_config();
     }
 
-    fn get_device<F>(self: &Rc<Self>, dev: c::dev_t, f: F)
+    fn get_device<F>(self: &Rc<Self>, dev: c::dev_t, devnode: &CStr, f: F)
     where
-        F: FnOnce(Result<&TakeDeviceReply, DbusError>) + 'static,
+        F: FnOnce(Result<TakenDevice, SeatError>) + 'static,
     {
         self.device_holder.num_pending_devices.fetch_add(1);
         let slf = self.clone();
-        self.session.get_device(dev, move |res| {
-            let rem = slf.device_holder.num_pending_devices.fetch_sub(1);
-            f(res);
-            if rem == 1 {
-                slf.state
-                    .backend_events
-                    .push(BackendEvent::DevicesEnumerated);
-                // Set to 1 to ensure this branch is never taken again.
-                slf.device_holder.num_pending_devices.set(1);
-            }
-        })
+        self.session.take_device(
+            dev,
+            devnode,
+            Box::new(move |res| {
+                let rem = slf.device_holder.num_pending_devices.fetch_sub(1);
+                f(res);
+                if rem == 1 {
+                    slf.state
+                        .backend_events
+                        .push(BackendEvent::DevicesEnumerated);
+                    // Set to 1 to ensure this branch is never taken again.
+                    slf.device_holder.num_pending_devices.set(1);
+                }
+            }),
+        )
     }
 }
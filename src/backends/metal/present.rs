@@ -8,8 +8,8 @@ use {
             MetalError,
         },
         gfx_api::{
-            create_render_pass, AcquireSync, BufferResv, GfxApiOpt, GfxRenderPass, GfxTexture,
-            ReleaseSync, SyncFile,
+            create_output_render_pass, AcquireSync, BufferResv, GfxApiOpt, GfxRenderPass,
+            GfxTexture, ReleaseSync, SyncFile,
         },
         theme::Color,
         time::Time,
@@ -261,6 +261,10 @@ impl MetalConnector {
             apply_change!(plane.crtc_y);
             apply_change!(plane.crtc_w);
             apply_change!(plane.crtc_h);
+            if let Some(gamma_lut) = self.pending_gamma_lut.take() {
+                self.gamma_lut_blob.set(gamma_lut.blob);
+                self.gamma_lut_blob_size.set(gamma_lut.size);
+            }
             if let Some(fb) = present_fb {
                 self.presentation_is_zero_copy
                     .set(fb.direct_scanout_data.is_some());
@@ -276,6 +280,9 @@ impl MetalConnector {
             self.can_present.set(false);
             if let Some(latched) = latched {
                 self.has_damage.fetch_sub(latched.damage);
+                let mut ops = latched.pass.ops;
+                ops.clear();
+                self.render_ops.set(ops);
             }
             self.cursor_changed.set(false);
             Ok(())
@@ -365,6 +372,20 @@ impl MetalConnector {
                 }
             }
         }
+        if let Some(prop) = crtc.gamma_lut {
+            let mut pending = self.pending_gamma_lut.borrow_mut();
+            let size = crtc.gamma_lut_size;
+            if pending.as_ref().is_some_and(|p| p.size != size) {
+                *pending = None;
+            }
+            if let Some(pending) = &*pending {
+                try_async_flip = false;
+                let blob = pending.blob.as_ref().map(|b| b.id().0 as _);
+                changes.change_object(crtc.id, |c| {
+                    c.change(prop, blob.unwrap_or(0));
+                });
+            }
+        }
         if let Some(cursor) = cursor {
             try_async_flip = false;
             match cursor {
@@ -495,16 +516,12 @@ impl MetalConnector {
         node.global.connector.damaged.set(false);
         let render_hw_cursor = !self.cursor_enabled.get();
         let mode = node.global.mode.get();
-        let pass = create_render_pass(
+        let pass = create_output_render_pass(
+            self.render_ops.take(),
             (mode.width, mode.height),
-            &**node,
+            node,
             &self.state,
-            Some(node.global.pos.get()),
-            node.global.persistent.scale.get(),
-            true,
             render_hw_cursor,
-            node.has_fullscreen(),
-            node.global.persistent.transform.get(),
             Some(&self.state.damage_visualizer),
         );
         Some(Latched { pass, damage })
@@ -639,36 +656,45 @@ impl MetalConnector {
         }
         let format = 'format: {
             if let Some(f) = plane.formats.get(&dmabuf.format.drm) {
-                break 'format f;
+                break 'format Some(f);
             }
             // Try opaque format if possible.
             if let Some(opaque) = dmabuf.format.opaque {
                 if let Some(f) = plane.formats.get(&opaque.drm) {
-                    break 'format f;
+                    break 'format Some(f);
                 }
             }
-            return None;
+            None
         };
-        if !format.modifiers.contains(&dmabuf.modifier) {
-            return None;
-        }
-        let data = match self.dev.master.add_fb(dmabuf, Some(format.format)) {
-            Ok(fb) => Some(DirectScanoutData {
-                tex: ct.tex.clone(),
-                acquire_sync: ct.acquire_sync.clone(),
-                release_sync: ct.release_sync,
-                resv: ct.buffer_resv.clone(),
-                fb: Rc::new(fb),
-                dma_buf_id: dmabuf.id,
-                position,
-            }),
-            Err(e) => {
+        let format = format.filter(|f| f.modifiers.contains(&dmabuf.modifier));
+        let data = match format {
+            // Remember unsupported buffers so that the check is not repeated every frame.
+            None => {
                 log::debug!(
-                    "Could not import dmabuf for direct scanout: {}",
-                    ErrorFmt(e)
+                    "Primary plane does not support format {} with modifier {:#x}",
+                    dmabuf.format.name,
+                    dmabuf.modifier,
                 );
                 None
             }
+            Some(format) => match self.dev.master.add_fb(dmabuf, Some(format.format)) {
+                Ok(fb) => Some(DirectScanoutData {
+                    tex: ct.tex.clone(),
+                    acquire_sync: ct.acquire_sync.clone(),
+                    release_sync: ct.release_sync,
+                    resv: ct.buffer_resv.clone(),
+                    fb: Rc::new(fb),
+                    dma_buf_id: dmabuf.id,
+                    position,
+                }),
+                Err(e) => {
+                    log::debug!(
+                        "Could not import dmabuf for direct scanout: {}",
+                        ErrorFmt(e)
+                    );
+                    None
+                }
+            },
         };
         cache.insert(
             dmabuf.id,
@@ -0,0 +1,308 @@
+use {
+    crate::{
+        async_engine::SpawnedFuture,
+        dbus::{DbusError, SignalHandler, TRUE},
+        libseat::{LibSeat, LibSeatError, LibSeatEvent},
+        logind::{LogindError, Session},
+        state::State,
+        utils::{
+            bitflags::BitflagsExt, copyhashmap::CopyHashMap, errorfmt::ErrorFmt, queue::AsyncQueue,
+        },
+    },
+    std::{
+        cell::{Cell, RefCell},
+        ffi::{CStr, CString},
+        rc::{Rc, Weak},
+    },
+    thiserror::Error,
+    uapi::{c, OwnedFd},
+};
+
+#[derive(Debug, Error)]
+pub enum SeatError {
+    #[error(transparent)]
+    Dbus(#[from] DbusError),
+    #[error(transparent)]
+    LibSeat(#[from] LibSeatError),
+}
+
+pub struct TakenDevice {
+    pub fd: Rc<OwnedFd>,
+    pub inactive: bool,
+}
+
+pub type TakeDeviceCallback = Box<dyn FnOnce(Result<TakenDevice, SeatError>)>;
+
+/// Events emitted by a seat provider. They are handled by the metal backend.
+pub enum SeatEvent {
+    DevicePaused(c::dev_t),
+    DeviceRemoved(c::dev_t),
+    DeviceResumed(c::dev_t, Rc<OwnedFd>),
+    /// The whole seat is being disabled. The backend must pause all devices and then
+    /// call `SeatProvider::disable_seat`.
+    SeatDisabled,
+    /// The whole seat has been enabled again. Devices that were paused must be
+    /// resumed.
+    SeatEnabled,
+}
+
+/// Grants access to the devices of the seat.
+pub trait SeatProvider {
+    fn take_device(&self, dev: c::dev_t, devnode: &CStr, f: TakeDeviceCallback);
+    fn release_device(&self, dev: c::dev_t);
+    fn switch_session(&self, vtnr: u32);
+    fn disable_seat(&self);
+}
+
+/// Returns whether libseat should be used instead of logind.
+///
+/// libseat is used if `LIBSEAT_BACKEND` is set. Otherwise logind is preferred and
+/// libseat is only used as a fallback.
+pub fn libseat_requested() -> bool {
+    std::env::var_os("LIBSEAT_BACKEND").is_some()
+}
+
+pub struct LogindSession {
+    session: Rc<Session>,
+    _pause_handler: SignalHandler,
+    _resume_handler: SignalHandler,
+}
+
+#[derive(Debug, Error)]
+pub enum LogindSessionError {
+    #[error("Could not connect to the dbus system socket")]
+    DbusSystemSocket(#[source] DbusError),
+    #[error("Could not retrieve the logind session")]
+    LogindSession(#[source] LogindError),
+    #[error("Could not take control of the logind session")]
+    TakeControl(#[source] LogindError),
+    #[error("Could not create device-paused signal handler")]
+    DevicePauseSignalHandler(#[source] DbusError),
+    #[error("Could not create device-resumed signal handler")]
+    DeviceResumeSignalHandler(#[source] DbusError),
+}
+
+impl LogindSession {
+    pub async fn new(
+        state: &Rc<State>,
+        events: &Rc<AsyncQueue<SeatEvent>>,
+    ) -> Result<Self, LogindSessionError> {
+        let socket = match state.dbus.system().await {
+            Ok(s) => s,
+            Err(e) => return Err(LogindSessionError::DbusSystemSocket(e)),
+        };
+        let session = match Session::get(&socket).await {
+            Ok(s) => Rc::new(s),
+            Err(e) => return Err(LogindSessionError::LogindSession(e)),
+        };
+        if let Err(e) = session.take_control().await {
+            return Err(LogindSessionError::TakeControl(e));
+        }
+        let pause_handler = {
+            let events = events.clone();
+            let s = session.clone();
+            let sh = session.on_pause(move |p| {
+                if p.ty == "pause" {
+                    s.device_paused(p.major, p.minor);
+                }
+                let dev = uapi::makedev(p.major as _, p.minor as _);
+                if p.ty == "gone" {
+                    events.push(SeatEvent::DeviceRemoved(dev));
+                } else {
+                    events.push(SeatEvent::DevicePaused(dev));
+                }
+            });
+            match sh {
+                Ok(sh) => sh,
+                Err(e) => return Err(LogindSessionError::DevicePauseSignalHandler(e)),
+            }
+        };
+        let resume_handler = {
+            let events = events.clone();
+            let sh = session.on_resume(move |r| {
+                let dev = uapi::makedev(r.major as _, r.minor as _);
+                events.push(SeatEvent::DeviceResumed(dev, r.fd));
+            });
+            match sh {
+                Ok(sh) => sh,
+                Err(e) => return Err(LogindSessionError::DeviceResumeSignalHandler(e)),
+            }
+        };
+        Ok(Self {
+            session,
+            _pause_handler: pause_handler,
+            _resume_handler: resume_handler,
+        })
+    }
+}
+
+impl SeatProvider for LogindSession {
+    fn take_device(&self, dev: c::dev_t, _devnode: &CStr, f: TakeDeviceCallback) {
+        self.session.get_device(dev, move |res| {
+            let res = res.map(|r| TakenDevice {
+                fd: r.fd.clone(),
+                inactive: r.inactive == TRUE,
+            });
+            f(res.map_err(SeatError::Dbus))
+        });
+    }
+
+    fn release_device(&self, dev: c::dev_t) {
+        self.session.release_device(dev);
+    }
+
+    fn switch_session(&self, vtnr: u32) {
+        self.session.switch_to(vtnr, move |res| {
+            if let Err(e) = res {
+                log::error!("Could not switch to VT {}: {}", vtnr, ErrorFmt(e));
+            }
+        })
+    }
+
+    fn disable_seat(&self) {
+        // logind pauses devices one at a time and each pause has already been
+        // acknowledged in the PauseDevice handler.
+    }
+}
+
+struct PendingDevice {
+    dev: c::dev_t,
+    devnode: CString,
+    f: TakeDeviceCallback,
+}
+
+pub struct LibSeatSession {
+    seat: LibSeat,
+    active: Cell<bool>,
+    devices: CopyHashMap<c::dev_t, c::c_int>,
+    pending: RefCell<Vec<PendingDevice>>,
+    events: Rc<AsyncQueue<SeatEvent>>,
+    dispatcher: Cell<Option<SpawnedFuture<()>>>,
+}
+
+impl LibSeatSession {
+    pub fn is_available() -> bool {
+        LibSeat::is_available()
+    }
+
+    pub fn new(
+        state: &Rc<State>,
+        events: &Rc<AsyncQueue<SeatEvent>>,
+    ) -> Result<Rc<Self>, LibSeatError> {
+        let seat = LibSeat::open()?;
+        let fd = seat.fd()?;
+        log::info!("Opened seat {} via libseat", seat.name());
+        let slf = Rc::new(Self {
+            seat,
+            active: Cell::new(false),
+            devices: Default::default(),
+            pending: Default::default(),
+            events: events.clone(),
+            dispatcher: Default::default(),
+        });
+        slf.dispatch()?;
+        let dispatcher = state.eng.spawn(
+            "libseat dispatcher",
+            dispatch_libseat(state.clone(), Rc::downgrade(&slf), fd),
+        );
+        slf.dispatcher.set(Some(dispatcher));
+        Ok(slf)
+    }
+
+    fn dispatch(&self) -> Result<(), LibSeatError> {
+        self.seat.dispatch()?;
+        while let Some(event) = self.seat.event() {
+            match event {
+                LibSeatEvent::Enable => {
+                    log::info!("Seat enabled");
+                    self.active.set(true);
+                    for pending in self.pending.take() {
+                        self.open_device(pending.dev, &pending.devnode, pending.f);
+                    }
+                    self.events.push(SeatEvent::SeatEnabled);
+                }
+                LibSeatEvent::Disable => {
+                    log::info!("Seat disabled");
+                    self.active.set(false);
+                    self.events.push(SeatEvent::SeatDisabled);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn open_device(&self, dev: c::dev_t, devnode: &CStr, f: TakeDeviceCallback) {
+        let res = match self.seat.open_device(devnode) {
+            Ok((id, fd)) => {
+                self.devices.set(dev, id);
+                Ok(TakenDevice {
+                    fd,
+                    inactive: false,
+                })
+            }
+            Err(e) => Err(SeatError::LibSeat(e)),
+        };
+        f(res);
+    }
+}
+
+async fn dispatch_libseat(state: Rc<State>, seat: Weak<LibSeatSession>, fd: Rc<OwnedFd>) {
+    loop {
+        match state.ring.readable(&fd).await {
+            Err(e) => {
+                log::error!(
+                    "Cannot wait for the libseat fd to become readable: {}",
+                    ErrorFmt(e)
+                );
+                break;
+            }
+            Ok(n) if n.intersects(c::POLLERR | c::POLLHUP) => {
+                log::error!("libseat fd is in an error state");
+                break;
+            }
+            _ => {}
+        }
+        let Some(seat) = seat.upgrade() else {
+            return;
+        };
+        if let Err(e) = seat.dispatch() {
+            log::error!("Could not dispatch libseat events: {}", ErrorFmt(e));
+            break;
+        }
+    }
+    log::error!("libseat dispatcher exited. Session changes will be ignored.");
+}
+
+impl SeatProvider for LibSeatSession {
+    fn take_device(&self, dev: c::dev_t, devnode: &CStr, f: TakeDeviceCallback) {
+        if self.active.get() {
+            self.open_device(dev, devnode, f);
+        } else {
+            self.pending.borrow_mut().push(PendingDevice {
+                dev,
+                devnode: devnode.to_owned(),
+                f,
+            });
+        }
+    }
+
+    fn release_device(&self, dev: c::dev_t) {
+        if let Some(id) = self.devices.remove(&dev) {
+            if let Err(e) = self.seat.close_device(id) {
+                log::warn!("Could not close device: {}", ErrorFmt(e));
+            }
+        }
+    }
+
+    fn switch_session(&self, vtnr: u32) {
+        if let Err(e) = self.seat.switch_session(vtnr as _) {
+            log::error!("Could not switch to VT {}: {}", vtnr, ErrorFmt(e));
+        }
+    }
+
+    fn disable_seat(&self) {
+        if let Err(e) = self.seat.disable() {
+            log::error!("Could not disable the seat: {}", ErrorFmt(e));
+        }
+    }
+}
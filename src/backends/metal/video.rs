@@ -1,9 +1,12 @@
+#[cfg(test)]
+mod tests;
+
 use {
     crate::{
         allocator::BufferObject,
         async_engine::{Phase, SpawnedFuture},
         backend::{
-            BackendDrmDevice, BackendDrmLease, BackendDrmLessee, BackendEvent, Connector,
+            self, BackendDrmDevice, BackendDrmLease, BackendDrmLessee, BackendEvent, Connector,
             ConnectorEvent, ConnectorId, ConnectorKernelId, DrmDeviceId, HardwareCursor,
             HardwareCursorUpdate, Mode, MonitorInfo,
         },
@@ -18,11 +21,12 @@ use {
         edid::{CtaDataBlock, Descriptor, EdidExtension},
         format::{Format, ARGB8888, XRGB8888},
         gfx_api::{
-            needs_render_usage, AcquireSync, GfxContext, GfxFramebuffer, GfxTexture, ReleaseSync,
-            SyncFile,
+            needs_render_usage, AcquireSync, GfxApiOpt, GfxContext, GfxFramebuffer, GfxTexture,
+            ReleaseSync, SyncFile,
         },
         ifs::{
             wl_output::OutputId,
+            wp_content_type_v1::ContentType,
             wp_presentation_feedback::{KIND_HW_COMPLETION, KIND_VSYNC, KIND_ZERO_COPY},
         },
         state::State,
@@ -36,9 +40,9 @@ use {
         video::{
             dmabuf::DmaBufId,
             drm::{
-                drm_mode_modeinfo, Change, ConnectorStatus, ConnectorType, DrmBlob, DrmConnector,
-                DrmCrtc, DrmEncoder, DrmError, DrmEvent, DrmFramebuffer, DrmLease, DrmMaster,
-                DrmModeInfo, DrmObject, DrmPlane, DrmProperty, DrmPropertyDefinition,
+                drm_color_lut, drm_mode_modeinfo, Change, ConnectorStatus, ConnectorType, DrmBlob,
+                DrmConnector, DrmCrtc, DrmEncoder, DrmError, DrmEvent, DrmFramebuffer, DrmLease,
+                DrmMaster, DrmModeInfo, DrmObject, DrmPlane, DrmProperty, DrmPropertyDefinition,
                 DrmPropertyType, DrmVersion, PropBlob, DRM_CLIENT_CAP_ATOMIC,
                 DRM_MODE_ATOMIC_ALLOW_MODESET,
             },
@@ -56,6 +60,7 @@ use {
         any::Any,
         cell::{Cell, RefCell},
         collections::hash_map::Entry,
+        error::Error,
         ffi::CString,
         fmt::{Debug, Formatter},
         mem,
@@ -100,7 +105,7 @@ pub struct MetalDrmDevice {
     pub gbm: Rc<GbmDevice>,
     pub handle_events: HandleEvents,
     pub ctx: CloneCell<Rc<MetalRenderContext>>,
-    pub on_change: OnChange<crate::backend::DrmEvent>,
+    pub on_change: OnChange<backend::DrmEvent>,
     pub direct_scanout_enabled: Cell<Option<bool>>,
     pub is_nvidia: bool,
     pub is_amd: bool,
@@ -109,6 +114,7 @@ pub struct MetalDrmDevice {
     pub leases_to_break: CopyHashMap<MetalLeaseId, MetalLeaseData>,
     pub paused: Cell<bool>,
     pub min_post_commit_margin: Cell<u64>,
+    pub saved_state: SavedDisplayState,
 }
 
 impl Debug for MetalDrmDevice {
@@ -131,7 +137,7 @@ impl BackendDrmDevice for MetalDrmDevice {
         self.id
     }
 
-    fn event(&self) -> Option<crate::backend::DrmEvent> {
+    fn event(&self) -> Option<backend::DrmEvent> {
         self.on_change.events.pop()
     }
 
@@ -332,6 +338,7 @@ pub struct ConnectorDisplayData {
     pub non_desktop_effective: bool,
     pub vrr_capable: bool,
     pub _vrr_refresh_max_nsec: u64,
+    pub content_type: Option<ContentTypeProperty>,
 
     pub connector_id: ConnectorKernelId,
     pub output_id: Rc<OutputId>,
@@ -348,6 +355,26 @@ impl ConnectorDisplayData {
     }
 }
 
+#[derive(Debug)]
+pub struct ContentTypeProperty {
+    pub prop: MutableProperty<u64>,
+    pub no_data: u64,
+    pub photo: u64,
+    pub cinema: u64,
+    pub game: u64,
+}
+
+impl ContentTypeProperty {
+    fn value(&self, content_type: Option<ContentType>) -> u64 {
+        match content_type {
+            None => self.no_data,
+            Some(ContentType::Photo) => self.photo,
+            Some(ContentType::Video) => self.cinema,
+            Some(ContentType::Game) => self.game,
+        }
+    }
+}
+
 linear_ids!(MetalLeaseIds, MetalLeaseId, u64);
 
 pub struct MetalLeaseData {
@@ -423,6 +450,12 @@ pub enum FrontState {
     Unavailable,
 }
 
+/// A gamma LUT that is applied with the next atomic commit of the connector.
+pub struct PendingGammaLut {
+    pub blob: Option<Rc<PropBlob>>,
+    pub size: u32,
+}
+
 pub struct MetalConnector {
     pub id: DrmConnector,
     pub master: Rc<DrmMaster>,
@@ -474,10 +507,15 @@ pub struct MetalConnector {
     pub active_framebuffer: RefCell<Option<PresentFb>>,
     pub next_framebuffer: OpaqueCell<Option<PresentFb>>,
     pub direct_scanout_active: Cell<bool>,
+    pub render_ops: Cell<Vec<GfxApiOpt>>,
 
     pub tearing_requested: Cell<bool>,
     pub try_switch_format: Cell<bool>,
 
+    pub gamma_lut_blob: CloneCell<Option<Rc<PropBlob>>>,
+    pub gamma_lut_blob_size: Cell<u32>,
+    pub pending_gamma_lut: RefCell<Option<PendingGammaLut>>,
+
     pub version: NumCell<u64>,
     pub sequence: Cell<u64>,
     pub expected_sequence: Cell<Option<u64>>,
@@ -588,7 +626,12 @@ impl MetalConnector {
         }
         let mut formats = vec![];
         if let Some(plane) = self.primary_plane.get() {
-            formats = plane.formats.values().map(|f| f.format).collect();
+            formats = plane
+                .formats
+                .values()
+                .map(|f| f.format)
+                .filter(|f| !f.yuv)
+                .collect();
         }
         let formats = Rc::new(formats);
         self.send_event(ConnectorEvent::FormatsChanged(
@@ -906,6 +949,72 @@ impl Connector for MetalConnector {
         self.send_vrr_enabled();
     }
 
+    fn set_content_type(&self, content_type: Option<ContentType>) {
+        if self.frontend_state.get() != (FrontState::Connected { non_desktop: false }) {
+            return;
+        }
+        let dd = self.display.borrow();
+        let Some(ct) = &dd.content_type else {
+            return;
+        };
+        let value = ct.value(content_type);
+        if ct.prop.value.get() == value {
+            return;
+        }
+        let mut change = self.master.change();
+        change.change_object(self.id, |c| {
+            c.change(ct.prop.id, value);
+        });
+        if let Err(e) = change.commit(0, 0) {
+            log::error!("Could not change content type: {}", ErrorFmt(e));
+            return;
+        }
+        ct.prop.value.set(value);
+    }
+
+    fn gamma_lut_size(&self) -> Option<u32> {
+        let crtc = self.crtc.get()?;
+        crtc.gamma_lut?;
+        Some(crtc.gamma_lut_size).filter(|s| *s > 0)
+    }
+
+    fn set_gamma_lut(&self, lut: Option<&[[u16; 3]]>) -> bool {
+        let Some(crtc) = self.crtc.get() else {
+            return false;
+        };
+        if crtc.gamma_lut.is_none() {
+            return false;
+        }
+        let mut blob = None;
+        if let Some(lut) = lut {
+            if lut.len() != crtc.gamma_lut_size as usize {
+                return false;
+            }
+            let lut: Vec<_> = lut
+                .iter()
+                .map(|&[red, green, blue]| drm_color_lut {
+                    red,
+                    green,
+                    blue,
+                    reserved: 0,
+                })
+                .collect();
+            match self.master.create_blob(&lut[..]) {
+                Ok(b) => blob = Some(Rc::new(b)),
+                Err(e) => {
+                    log::error!("Could not create gamma blob: {}", ErrorFmt(e));
+                    return false;
+                }
+            }
+        }
+        *self.pending_gamma_lut.borrow_mut() = Some(PendingGammaLut {
+            blob,
+            size: crtc.gamma_lut_size,
+        });
+        self.damage();
+        true
+    }
+
     fn set_tearing_enabled(&self, enabled: bool) {
         if !self.dev.supports_async_commit {
             return;
@@ -953,6 +1062,8 @@ pub struct MetalCrtc {
     pub mode_id: MutableProperty<DrmBlob>,
     pub out_fence_ptr: DrmProperty,
     pub vrr_enabled: MutableProperty<bool>,
+    pub gamma_lut: Option<DrmProperty>,
+    pub gamma_lut_size: u32,
 
     pub mode_blob: CloneCell<Option<Rc<PropBlob>>>,
     pub have_queued_sequence: Cell<bool>,
@@ -1086,9 +1197,13 @@ fn create_connector(
         active_framebuffer: Default::default(),
         next_framebuffer: Default::default(),
         direct_scanout_active: Cell::new(false),
+        render_ops: Default::default(),
         next_vblank_nsec: Cell::new(0),
         tearing_requested: Cell::new(false),
         try_switch_format: Cell::new(false),
+        gamma_lut_blob: Default::default(),
+        gamma_lut_blob_size: Cell::new(0),
+        pending_gamma_lut: Default::default(),
         version: Default::default(),
         sequence: Default::default(),
         expected_sequence: Default::default(),
@@ -1261,6 +1376,7 @@ fn create_connector_display_data(
         Ok(c) => c.value.get() == 1,
         Err(_) => false,
     };
+    let content_type = content_type_property(&props);
     let mode = mode_opt.clone();
     drop(mode_opt);
     Ok(ConnectorDisplayData {
@@ -1274,6 +1390,7 @@ fn create_connector_display_data(
         non_desktop_effective: non_desktop_override.unwrap_or(non_desktop),
         vrr_capable,
         _vrr_refresh_max_nsec: vrr_refresh_max_nsec,
+        content_type,
         connection,
         mm_width: info.mm_width,
         mm_height: info.mm_height,
@@ -1326,6 +1443,11 @@ fn create_crtc(
         mode_id: props.get("MODE_ID")?.map(|v| DrmBlob(v as u32)),
         out_fence_ptr: props.get("OUT_FENCE_PTR")?.id,
         vrr_enabled: props.get("VRR_ENABLED")?.map(|v| v == 1),
+        gamma_lut: props.get("GAMMA_LUT").ok().map(|p| p.id),
+        gamma_lut_size: props
+            .get("GAMMA_LUT_SIZE")
+            .map(|p| p.value.get() as u32)
+            .unwrap_or(0),
         mode_blob: Default::default(),
         have_queued_sequence: Cell::new(false),
         needs_vblank_emulation: Cell::new(false),
@@ -1411,6 +1533,135 @@ fn create_plane(plane: DrmPlane, master: &Rc<DrmMaster>) -> Result<MetalPlane, D
     })
 }
 
+fn content_type_property(props: &CollectedProperties) -> Option<ContentTypeProperty> {
+    let (def, _) = props.props.get(b"content type".as_bstr())?;
+    let DrmPropertyType::Enum { values, .. } = &def.ty else {
+        return None;
+    };
+    let get = |name: &str| values.iter().find(|v| v.name == name).map(|v| v.value);
+    Some(ContentTypeProperty {
+        prop: props.get("content type").ok()?,
+        no_data: get("No Data")?,
+        photo: get("Photo")?,
+        cinema: get("Cinema")?,
+        game: get("Game")?,
+    })
+}
+
+/// The display state that was present before the compositor took over a device.
+#[derive(Default)]
+pub struct SavedDisplayState {
+    connectors: Vec<(DrmConnector, Vec<SavedProperty>)>,
+    crtcs: Vec<(DrmCrtc, Vec<SavedProperty>)>,
+    planes: Vec<(DrmPlane, Vec<SavedProperty>)>,
+}
+
+enum SavedProperty {
+    Value(DrmProperty, u64),
+    /// The contents of a blob. Blobs created by the previous master are destroyed when
+    /// it releases the device, so they have to be re-created.
+    Blob(DrmProperty, Option<Vec<u8>>),
+}
+
+const SAVED_CONNECTOR_PROPERTIES: &[&str] = &["CRTC_ID"];
+const SAVED_CRTC_PROPERTIES: &[&str] = &["ACTIVE"];
+const SAVED_CRTC_BLOBS: &[&str] = &["MODE_ID", "GAMMA_LUT"];
+const SAVED_PLANE_PROPERTIES: &[&str] = &[
+    "FB_ID", "CRTC_ID", "SRC_X", "SRC_Y", "SRC_W", "SRC_H", "CRTC_X", "CRTC_Y", "CRTC_W", "CRTC_H",
+];
+
+impl SavedDisplayState {
+    fn save(
+        master: &Rc<DrmMaster>,
+        connectors: &[DrmConnector],
+        crtcs: &AHashMap<DrmCrtc, Rc<MetalCrtc>>,
+        planes: &AHashMap<DrmPlane, Rc<MetalPlane>>,
+    ) -> Result<Self, DrmError> {
+        let mut res = Self::default();
+        for &connector in connectors {
+            let props = save_properties(master, connector, SAVED_CONNECTOR_PROPERTIES, &[])?;
+            res.connectors.push((connector, props));
+        }
+        for &crtc in crtcs.keys() {
+            let props = save_properties(master, crtc, SAVED_CRTC_PROPERTIES, SAVED_CRTC_BLOBS)?;
+            res.crtcs.push((crtc, props));
+        }
+        for &plane in planes.keys() {
+            let props = save_properties(master, plane, SAVED_PLANE_PROPERTIES, &[])?;
+            res.planes.push((plane, props));
+        }
+        Ok(res)
+    }
+
+    /// Restores the saved state with a blocking modeset.
+    pub fn restore(&self, master: &Rc<DrmMaster>) -> Result<(), DrmError> {
+        let mut blobs = vec![];
+        let mut change = master.change();
+        for (connector, props) in &self.connectors {
+            restore_properties(master, &mut change, &mut blobs, *connector, props)?;
+        }
+        for (crtc, props) in &self.crtcs {
+            restore_properties(master, &mut change, &mut blobs, *crtc, props)?;
+        }
+        for (plane, props) in &self.planes {
+            restore_properties(master, &mut change, &mut blobs, *plane, props)?;
+        }
+        change.commit(DRM_MODE_ATOMIC_ALLOW_MODESET, 0)
+    }
+}
+
+fn save_properties<T: DrmObject>(
+    master: &Rc<DrmMaster>,
+    t: T,
+    values: &[&str],
+    blobs: &[&str],
+) -> Result<Vec<SavedProperty>, DrmError> {
+    let props = collect_properties(master, t)?;
+    let mut res = vec![];
+    for name in values {
+        if let Some((def, value)) = props.props.get(name.as_bytes().as_bstr()) {
+            res.push(SavedProperty::Value(def.id, *value));
+        }
+    }
+    for name in blobs {
+        if let Some((def, value)) = props.props.get(name.as_bytes().as_bstr()) {
+            let data = match *value {
+                0 => None,
+                v => Some(master.getblob_vec::<u8>(DrmBlob(v as _))?),
+            };
+            res.push(SavedProperty::Blob(def.id, data));
+        }
+    }
+    Ok(res)
+}
+
+fn restore_properties<T: DrmObject>(
+    master: &Rc<DrmMaster>,
+    change: &mut Change,
+    blobs: &mut Vec<PropBlob>,
+    t: T,
+    props: &[SavedProperty],
+) -> Result<(), DrmError> {
+    let mut values = vec![];
+    for prop in props {
+        match prop {
+            SavedProperty::Value(id, value) => values.push((*id, *value)),
+            SavedProperty::Blob(id, None) => values.push((*id, 0)),
+            SavedProperty::Blob(id, Some(data)) => {
+                let blob = master.create_blob(&data[..])?;
+                values.push((*id, blob.id().0 as u64));
+                blobs.push(blob);
+            }
+        }
+    }
+    change.change_object(t, |c| {
+        for (id, value) in values {
+            c.change(id, value);
+        }
+    });
+    Ok(())
+}
+
 fn collect_properties<T: DrmObject>(
     master: &Rc<DrmMaster>,
     t: T,
@@ -1697,6 +1948,7 @@ impl MetalBackend {
         self: &Rc<Self>,
         pending: PendingDrmDevice,
         master: &Rc<DrmMaster>,
+        inactive: bool,
     ) -> Result<Rc<MetalDrmDeviceData>, MetalError> {
         if let Err(e) = master.set_client_cap(DRM_CLIENT_CAP_ATOMIC, 2) {
             return Err(MetalError::AtomicModesetting(e));
@@ -1756,6 +2008,15 @@ impl MetalBackend {
             gbm: gbm.clone(),
         });
 
+        let saved_state =
+            match SavedDisplayState::save(master, &resources.connectors, &crtcs, &planes) {
+                Ok(s) => s,
+                Err(e) => {
+                    log::warn!("Could not save the display state: {}", ErrorFmt(e));
+                    Default::default()
+                }
+            };
+
         let mut is_nvidia = false;
         let mut is_amd = false;
         match gbm.drm.version() {
@@ -1802,8 +2063,9 @@ impl MetalBackend {
             lease_ids: Default::default(),
             leases: Default::default(),
             leases_to_break: Default::default(),
-            paused: Cell::new(false),
+            paused: Cell::new(inactive),
             min_post_commit_margin: Cell::new(DEFAULT_POST_COMMIT_MARGIN),
+            saved_state,
         });
 
         let (connectors, futures) = get_connectors(self, &dev, &resources.connectors)?;
@@ -1815,7 +2077,15 @@ impl MetalBackend {
             unprocessed_change: Cell::new(false),
         });
 
-        self.init_drm_device(&slf, &mut Preserve::default())?;
+        if inactive {
+            // We are not the DRM master while the session is inactive. The device will be
+            // initialized once logind resumes it.
+            for connector in slf.connectors.lock().values() {
+                connector.can_present.set(false);
+            }
+        } else {
+            self.init_drm_device(&slf, &mut Preserve::default())?;
+        }
 
         self.state
             .backend_events
@@ -1851,6 +2121,9 @@ impl MetalBackend {
             dd.crtc_id
                 .value
                 .set(DrmCrtc(get(&props, dd.crtc_id.id)? as _));
+            if let Some(ct) = &dd.content_type {
+                ct.prop.value.set(get(&props, ct.prop.id)?);
+            }
         }
         for c in dev.dev.crtcs.values() {
             let props = collect_untyped_properties(master, c.id)?;
@@ -2125,10 +2398,58 @@ impl MetalBackend {
                 c.change(crtc.mode_id.id, 0);
                 c.change(crtc.out_fence_ptr, 0);
                 c.change(crtc.vrr_enabled.id, 0);
+                if let Some(gamma_lut) = crtc.gamma_lut {
+                    c.change(gamma_lut, 0);
+                }
             })
         }
     }
 
+    /// Tests a modeset and disables VRR if the hardware only accepts it without VRR.
+    ///
+    /// VRR is optional, so it should not prevent outputs from being lit up. Returns the
+    /// CRTCs whose cached VRR state must be cleared once the modeset has been committed.
+    fn disable_vrr_if_rejected(
+        &self,
+        dev: &MetalDrmDeviceData,
+        changes: &mut Change,
+        flags: u32,
+        preserve: &Preserve,
+    ) -> Vec<Rc<MetalCrtc>> {
+        let vrr_crtcs: Vec<_> = dev
+            .dev
+            .crtcs
+            .values()
+            .filter(|c| !preserve.crtcs.contains(&c.id))
+            .filter(|c| c.vrr_enabled.value.get())
+            .cloned()
+            .collect();
+        if vrr_crtcs.is_empty() {
+            return vec![];
+        }
+        let set_vrr = |changes: &mut Change, enabled: bool| {
+            for crtc in &vrr_crtcs {
+                changes.change_object(crtc.id, |c| {
+                    c.change(crtc.vrr_enabled.id, enabled as u64);
+                });
+            }
+        };
+        let fallback = vrr_fallback(|vrr| {
+            if !vrr {
+                set_vrr(changes, false);
+            }
+            changes.test(flags)
+        });
+        match fallback {
+            VrrFallback::Keep => vec![],
+            VrrFallback::Disable => vrr_crtcs,
+            VrrFallback::Rejected => {
+                set_vrr(changes, true);
+                vec![]
+            }
+        }
+    }
+
     fn validate_preserve(&self, dev: &Rc<MetalDrmDeviceData>, preserve: &mut Preserve) {
         let mut remove_connectors = vec![];
         macro_rules! fail {
@@ -2259,7 +2580,7 @@ impl MetalBackend {
             }
         };
         dev.on_change
-            .send_event(crate::backend::DrmEvent::GfxApiChanged);
+            .send_event(backend::DrmEvent::GfxApiChanged);
         dev.ctx.set(Rc::new(MetalRenderContext {
             dev_id: dev.id,
             gfx,
@@ -2292,6 +2613,56 @@ impl MetalBackend {
             .retain(|_, lease| !lease.try_revoke());
     }
 
+    /// Tears down a DRM device that has been removed from the system.
+    ///
+    /// The frontend removes the outputs of the device and moves their workspaces to
+    /// the remaining outputs.
+    pub fn remove_drm_device(&self, dev: &Rc<MetalDrmDeviceData>) {
+        let was_render_device = dev.dev.is_render_device();
+        self.device_holder.drm_devices.remove(&dev.dev.devnum);
+        dev.dev.handle_events.handle_events.take();
+        dev.futures.clear();
+        for crtc in dev.dev.crtcs.values() {
+            crtc.connector.take();
+        }
+        for lease in dev.dev.leases.lock().drain_values() {
+            lease.try_revoke();
+        }
+        dev.dev.leases_to_break.clear();
+        for connector in dev.connectors.lock().drain_values() {
+            match connector.frontend_state.get() {
+                FrontState::Removed | FrontState::Disconnected => {}
+                FrontState::Connected { .. } | FrontState::Unavailable => {
+                    connector.send_event(ConnectorEvent::Disconnected);
+                }
+            }
+            connector.send_event(ConnectorEvent::Removed);
+            connector.primary_plane.take();
+            connector.cursor_plane.take();
+            connector.crtc.take();
+            connector.active_framebuffer.take();
+            connector.next_framebuffer.take();
+        }
+        dev.dev
+            .on_change
+            .send_event(backend::DrmEvent::Removed);
+        if was_render_device {
+            self.ctx.take();
+            self.default_feedback.take();
+            let next = self
+                .device_holder
+                .drm_devices
+                .lock()
+                .values()
+                .next()
+                .cloned();
+            match next {
+                Some(next) => self.make_render_device(&next.dev, true),
+                None => self.state.set_render_ctx(None),
+            }
+        }
+    }
+
     fn init_drm_device(
         &self,
         dev: &Rc<MetalDrmDeviceData>,
@@ -2309,11 +2680,19 @@ impl MetalBackend {
             log::warn!("Cannot use existing connector configuration. Trying to perform modeset.");
             flags = DRM_MODE_ATOMIC_ALLOW_MODESET;
             self.reset_connectors_and_crtcs(dev, &mut changes, preserve);
-            for connector in dev.connectors.lock().values() {
-                if !preserve.connectors.contains(&connector.id) {
-                    if let Err(e) = self.assign_connector_crtc(connector, &mut changes) {
-                        log::error!("Could not assign a crtc: {}", ErrorFmt(e));
-                    }
+            let mut connectors: Vec<_> = dev
+                .connectors
+                .lock()
+                .values()
+                .filter(|c| !preserve.connectors.contains(&c.id))
+                .cloned()
+                .collect();
+            // Connectors that can be driven by fewer CRTCs go first. Otherwise a connector
+            // that could use any CRTC might take the only CRTC usable by another one.
+            connectors.sort_by_key(|c| c.display.borrow().crtcs.len());
+            for connector in &connectors {
+                if let Err(e) = self.assign_connector_crtc(connector, &mut changes) {
+                    log::error!("Could not assign a crtc: {}", ErrorFmt(e));
                 }
             }
         }
@@ -2328,9 +2707,16 @@ impl MetalBackend {
                 }
             }
         }
+        let vrr_disabled = match flags & DRM_MODE_ATOMIC_ALLOW_MODESET != 0 {
+            true => self.disable_vrr_if_rejected(dev, &mut changes, flags, preserve),
+            false => vec![],
+        };
         if let Err(e) = changes.commit(flags, 0) {
             return Err(MetalError::Modeset(e));
         }
+        for crtc in vrr_disabled {
+            crtc.vrr_enabled.value.set(false);
+        }
         for connector in dev.connectors.lock().values() {
             if preserve.connectors.contains(&connector.id) {
                 continue;
@@ -2623,10 +3009,17 @@ impl MetalBackend {
         changes.change_object(connector.id, |c| {
             c.change(dd.crtc_id.id, crtc.id.0 as _);
         });
+        let gamma_lut = connector
+            .gamma_lut_blob
+            .get()
+            .filter(|_| connector.gamma_lut_blob_size.get() == crtc.gamma_lut_size);
         changes.change_object(crtc.id, |c| {
             c.change(crtc.active.id, 1);
             c.change(crtc.mode_id.id, mode_blob.id().0 as _);
             c.change(crtc.vrr_enabled.id, dd.should_enable_vrr() as _);
+            if let Some(prop) = crtc.gamma_lut {
+                c.change(prop, gamma_lut.map(|b| b.id().0 as _).unwrap_or(0));
+            }
         });
         connector.crtc.set(Some(crtc.clone()));
         connector.version.fetch_add(1);
@@ -2773,6 +3166,8 @@ impl MetalBackend {
         }
         connector.next_buffer.set(1);
         connector.primary_plane.set(Some(primary_plane.clone()));
+        // Cached direct scanout decisions depend on the formats of the primary plane.
+        connector.scanout_buffers.borrow_mut().clear();
         if let Some(cp) = &cursor_plane {
             cp.assigned.set(true);
         }
@@ -2876,3 +3271,33 @@ fn should_ignore(connector: &MetalConnector, dd: &ConnectorDisplayData) -> bool
         || dd.connection != ConnectorStatus::Connected
         || dd.non_desktop_effective
 }
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum VrrFallback {
+    /// The modeset is accepted with VRR.
+    Keep,
+    /// The modeset is only accepted without VRR.
+    Disable,
+    /// The modeset is rejected with and without VRR.
+    Rejected,
+}
+
+/// Tests a modeset first with VRR and then without VRR.
+///
+/// `test` is called with whether VRR should be enabled.
+fn vrr_fallback<E: Error>(mut test: impl FnMut(bool) -> Result<(), E>) -> VrrFallback {
+    let Err(e) = test(true) else {
+        return VrrFallback::Keep;
+    };
+    log::warn!(
+        "Modeset was rejected, retrying without VRR: {}",
+        ErrorFmt(e)
+    );
+    match test(false) {
+        Ok(()) => VrrFallback::Disable,
+        Err(e) => {
+            log::warn!("Modeset was also rejected without VRR: {}", ErrorFmt(e));
+            VrrFallback::Rejected
+        }
+    }
+}
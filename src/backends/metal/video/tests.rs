@@ -0,0 +1,34 @@
+use {
+    crate::backends::metal::video::{vrr_fallback, VrrFallback},
+    std::io,
+};
+
+fn run(accept_vrr: bool, accept_no_vrr: bool) -> (VrrFallback, Vec<bool>) {
+    let mut tests = vec![];
+    let res = vrr_fallback(|vrr| {
+        tests.push(vrr);
+        match (vrr && accept_vrr) || (!vrr && accept_no_vrr) {
+            true => Ok(()),
+            false => Err(io::Error::from(io::ErrorKind::InvalidInput)),
+        }
+    });
+    (res, tests)
+}
+
+#[test]
+fn keep() {
+    assert_eq!(run(true, true), (VrrFallback::Keep, vec![true]));
+}
+
+#[test]
+fn disable() {
+    assert_eq!(run(false, true), (VrrFallback::Disable, vec![true, false]));
+}
+
+#[test]
+fn rejected() {
+    assert_eq!(
+        run(false, false),
+        (VrrFallback::Rejected, vec![true, false])
+    );
+}
@@ -1,5 +1,6 @@
 mod color;
 mod damage_tracking;
+mod debug;
 mod duration;
 mod generate;
 mod idle;
@@ -17,8 +18,8 @@ mod xwayland;
 use {
     crate::{
         cli::{
-            damage_tracking::DamageTrackingArgs, input::InputArgs, randr::RandrArgs,
-            xwayland::XwaylandArgs,
+            damage_tracking::DamageTrackingArgs, debug::DebugArgs, input::InputArgs,
+            randr::RandrArgs, xwayland::XwaylandArgs,
         },
         compositor::start_compositor,
         format::{ref_formats, Format},
@@ -76,6 +77,9 @@ pub enum Cmd {
     /// Modify damage tracking settings. (Only for debugging.)
     #[clap(hide = true)]
     DamageTracking(DamageTrackingArgs),
+    /// Inspect the internal state of the compositor. (Only for debugging.)
+    #[clap(hide = true)]
+    Debug(DebugArgs),
     /// Inspect/modify xwayland settings.
     Xwayland(XwaylandArgs),
     #[cfg(feature = "it")]
@@ -167,6 +171,12 @@ pub struct RunArgs {
     /// which they will be tried. Multiple backends can be supplied as a comma-separated list.
     #[clap(value_enum, use_value_delimiter = true, long)]
     pub backends: Vec<CliBackend>,
+    /// The directory in which to create the wayland socket.
+    ///
+    /// By default, the socket is created directly in XDG_RUNTIME_DIR. Relative paths are
+    /// interpreted relative to XDG_RUNTIME_DIR. The directory is created if it does not exist.
+    #[clap(long)]
+    pub socket_dir: Option<String>,
 }
 
 #[derive(Args, Debug)]
@@ -265,6 +275,7 @@ pub fn main() {
         Cmd::Randr(a) => randr::main(cli.global, a),
         Cmd::Input(a) => input::main(cli.global, a),
         Cmd::DamageTracking(a) => damage_tracking::main(cli.global, a),
+        Cmd::Debug(a) => debug::main(cli.global, a),
         Cmd::Xwayland(a) => xwayland::main(cli.global, a),
         #[cfg(feature = "it")]
         Cmd::RunTests => crate::it::run_tests(),
@@ -0,0 +1,54 @@
+use {
+    crate::{
+        cli::GlobalArgs,
+        tools::tool_client::{with_tool_client, Handle, ToolClient},
+        wire::{jay_compositor, jay_debug},
+    },
+    clap::{Args, Subcommand},
+    std::rc::Rc,
+};
+
+#[derive(Args, Debug)]
+pub struct DebugArgs {
+    #[clap(subcommand)]
+    pub command: DebugCmd,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DebugCmd {
+    /// Print the number of live objects of each client.
+    Objects,
+    /// Print the node tree.
+    Tree,
+}
+
+pub fn main(global: GlobalArgs, debug_args: DebugArgs) {
+    with_tool_client(global.log_level.into(), |tc| async move {
+        let debug = Rc::new(DebugTool { tc: tc.clone() });
+        debug.run(debug_args).await;
+    });
+}
+
+struct DebugTool {
+    tc: Rc<ToolClient>,
+}
+
+impl DebugTool {
+    async fn run(&self, args: DebugArgs) {
+        let tc = &self.tc;
+        let comp = tc.jay_compositor().await;
+        let debug = tc.id();
+        tc.send(jay_compositor::GetDebug {
+            self_id: comp,
+            id: debug,
+        });
+        jay_debug::Line::handle(tc, debug, (), |_, msg| {
+            println!("{}", msg.line);
+        });
+        match args.command {
+            DebugCmd::Objects => tc.send(jay_debug::DumpObjects { self_id: debug }),
+            DebugCmd::Tree => tc.send(jay_debug::DumpTree { self_id: debug }),
+        }
+        tc.round_trip().await;
+    }
+}
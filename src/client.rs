@@ -5,6 +5,7 @@ use {
         ifs::{
             wl_display::WlDisplay,
             wl_registry::WlRegistry,
+            wl_seat::SeatId,
             wl_surface::{commit_timeline::CommitTimelines, WlSurface},
         },
         leaks::Tracker,
@@ -35,8 +36,8 @@ use {
     uapi::{c, OwnedFd},
 };
 pub use {
-    error::{ClientError, ParserError},
-    objects::MIN_SERVER_ID,
+    error::{ClientError, InputSerialError, ParserError},
+    objects::{DEFAULT_MAX_CLIENT_OBJECTS, MIN_SERVER_ID},
 };
 
 mod error;
@@ -56,6 +57,7 @@ bitflags! {
         CAP_SEAT_MANAGER             = 1 << 8,
         CAP_DRM_LEASE                = 1 << 9,
         CAP_INPUT_METHOD             = 1 << 10,
+        CAP_GAMMA_CONTROL            = 1 << 11,
 }
 
 pub const CAPS_DEFAULT: ClientCaps = ClientCaps(CAP_LAYER_SHELL.0 | CAP_DRM_LEASE.0);
@@ -80,6 +82,9 @@ impl Display for ClientId {
     }
 }
 
+/// The default maximum number of clients that can be connected at the same time.
+pub const DEFAULT_MAX_CLIENTS: usize = 1024;
+
 pub struct Clients {
     next_client_id: NumCell<u64>,
     pub clients: RefCell<AHashMap<ClientId, ClientHolder>>,
@@ -121,8 +126,12 @@ impl Clients {
         effective_caps: ClientCaps,
         bounding_caps: ClientCaps,
     ) -> Result<(), ClientError> {
+        let max_clients = global.max_clients.get();
+        if self.clients.borrow().len() >= max_clients {
+            return Err(ClientError::TooManyClients(max_clients));
+        }
         let Some((uid, pid)) = get_socket_creds(&socket) else {
-            return Ok(());
+            return Err(ClientError::NoPeerCredentials);
         };
         self.spawn2(
             id,
@@ -159,11 +168,12 @@ impl Clients {
             shutdown: Default::default(),
             tracker: Default::default(),
             is_xwayland,
-            effective_caps,
+            effective_caps: Cell::new(effective_caps),
             bounding_caps,
             last_enter_serial: Default::default(),
             pid_info: get_pid_info(uid, pid),
             serials: Default::default(),
+            input_serials: Default::default(),
             symmetric_delete: Cell::new(false),
             last_xwayland_serial: Cell::new(0),
             surfaces_by_xwayland_serial: Default::default(),
@@ -176,12 +186,15 @@ impl Clients {
             )),
             wire_scale: Default::default(),
             focus_stealing_serial: Default::default(),
+            trace: Cell::new(false),
         });
         track!(data, data);
         let display = Rc::new(WlDisplay::new(&data));
         track!(data, display);
         data.objects.display.set(Some(display.clone()));
-        data.objects.add_client_object(display).expect("");
+        data.objects
+            .add_client_object(display, usize::MAX)
+            .expect("");
         let client = ClientHolder {
             _handler: global.eng.spawn("client", tasks::client(data.clone())),
             data: data.clone(),
@@ -221,7 +234,11 @@ impl Clients {
     {
         let clients = self.clients.borrow();
         for client in clients.values() {
-            if client.data.effective_caps.contains(required_caps)
+            if client
+                .data
+                .effective_caps
+                .get()
+                .contains(required_caps)
                 && (!xwayland_only || client.data.is_xwayland)
             {
                 f(&client.data);
@@ -245,6 +262,7 @@ pub struct ClientHolder {
 impl Drop for ClientHolder {
     fn drop(&mut self) {
         self.data.objects.destroy();
+        self.data.state.client_removed(&self.data);
         self.data.flush_request.clear();
         self.data.shutdown.clear();
         self.data.surfaces_by_xwayland_serial.clear();
@@ -276,11 +294,12 @@ pub struct Client {
     shutdown: AsyncEvent,
     pub tracker: Tracker<Client>,
     pub is_xwayland: bool,
-    pub effective_caps: ClientCaps,
+    pub effective_caps: Cell<ClientCaps>,
     pub bounding_caps: ClientCaps,
     pub last_enter_serial: Cell<Option<u64>>,
     pub pid_info: PidInfo,
     pub serials: RefCell<VecDeque<SerialRange>>,
+    pub input_serials: RefCell<VecDeque<InputSerial>>,
     pub symmetric_delete: Cell<bool>,
     pub last_xwayland_serial: Cell<u64>,
     pub surfaces_by_xwayland_serial: CopyHashMap<u64, Rc<WlSurface>>,
@@ -288,6 +307,7 @@ pub struct Client {
     pub commit_timelines: Rc<CommitTimelines>,
     pub wire_scale: Cell<Option<i32>>,
     pub focus_stealing_serial: Cell<Option<u64>>,
+    pub trace: Cell<bool>,
 }
 
 pub const NUM_CACHED_SERIAL_RANGES: usize = 64;
@@ -297,6 +317,55 @@ pub struct SerialRange {
     pub hi: u64,
 }
 
+pub const NUM_CACHED_INPUT_SERIALS: usize = 32;
+
+/// Input serials older than this are no longer accepted.
+pub const INPUT_SERIAL_MAX_AGE_NSEC: u64 = 30_000_000_000;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum InputSerialKind {
+    KeyPress,
+    ButtonPress,
+    TouchDown,
+    TabletToolDown,
+    KeyboardEnter,
+}
+
+pub const SELECTION_SERIAL_KINDS: &[InputSerialKind] = &[
+    InputSerialKind::KeyPress,
+    InputSerialKind::ButtonPress,
+    InputSerialKind::TouchDown,
+    InputSerialKind::TabletToolDown,
+    InputSerialKind::KeyboardEnter,
+];
+
+pub const ACTIVATION_SERIAL_KINDS: &[InputSerialKind] = &[
+    InputSerialKind::KeyPress,
+    InputSerialKind::ButtonPress,
+    InputSerialKind::TouchDown,
+    InputSerialKind::TabletToolDown,
+];
+
+pub const GRAB_SERIAL_KINDS: &[InputSerialKind] = &[
+    InputSerialKind::ButtonPress,
+    InputSerialKind::TouchDown,
+    InputSerialKind::TabletToolDown,
+];
+
+/// The serials that become invalid when a surface loses the keyboard focus.
+pub const KEYBOARD_SERIAL_KINDS: &[InputSerialKind] =
+    &[InputSerialKind::KeyPress, InputSerialKind::KeyboardEnter];
+
+/// The serials that become invalid when the pointer leaves a surface.
+pub const POINTER_SERIAL_KINDS: &[InputSerialKind] = &[InputSerialKind::ButtonPress];
+
+pub struct InputSerial {
+    pub serial: u64,
+    pub seat: SeatId,
+    pub kind: InputSerialKind,
+    pub time_nsec: u64,
+}
+
 impl Client {
     pub fn invalid_request(&self, obj: &dyn Object, request: u32) {
         log::error!(
@@ -347,6 +416,76 @@ impl Client {
         self.state.next_serial(Some(self))
     }
 
+    pub fn add_input_serial(&self, seat: SeatId, kind: InputSerialKind, serial: u64) {
+        let mut serials = self.input_serials.borrow_mut();
+        if serials.len() >= NUM_CACHED_INPUT_SERIALS {
+            serials.pop_front();
+        }
+        serials.push_back(InputSerial {
+            serial,
+            seat,
+            kind,
+            time_nsec: self.state.now_nsec(),
+        });
+    }
+
+    /// Forgets the input serials of the given kinds that were sent for the seat.
+    pub fn remove_input_serials(&self, seat: SeatId, kinds: &[InputSerialKind]) {
+        self.input_serials
+            .borrow_mut()
+            .retain(|s| s.seat != seat || !kinds.contains(&s.kind));
+    }
+
+    /// Maps a serial sent by the client and checks that it belongs to one of the recent
+    /// input events of the given kinds that were sent for the seat.
+    pub fn validate_input_serial(
+        &self,
+        request: &str,
+        serial: u32,
+        seat: SeatId,
+        kinds: &[InputSerialKind],
+    ) -> Option<u64> {
+        match self.validate_input_serial_(serial, seat, kinds) {
+            Ok(s) => Some(s),
+            Err(e) => {
+                log::debug!(
+                    "Client {} sent {} with serial {}: {}",
+                    self.id,
+                    request,
+                    serial,
+                    ErrorFmt(e),
+                );
+                None
+            }
+        }
+    }
+
+    fn validate_input_serial_(
+        &self,
+        serial: u32,
+        seat: SeatId,
+        kinds: &[InputSerialKind],
+    ) -> Result<u64, InputSerialError> {
+        let Some(serial) = self.map_serial(serial) else {
+            return Err(InputSerialError::Unknown);
+        };
+        let mut err = InputSerialError::NotAnInputEvent;
+        let now = self.state.now_nsec();
+        let serials = self.input_serials.borrow();
+        for input in serials.iter().rev().filter(|s| s.serial == serial) {
+            if now.saturating_sub(input.time_nsec) > INPUT_SERIAL_MAX_AGE_NSEC {
+                err = InputSerialError::Expired;
+            } else if input.seat != seat {
+                err = InputSerialError::WrongSeat(input.seat);
+            } else if !kinds.contains(&input.kind) {
+                err = InputSerialError::WrongKind(input.kind);
+            } else {
+                return Ok(serial);
+            }
+        }
+        Err(err)
+    }
+
     pub fn pending_serial(&self) -> PendingSerial<'_> {
         PendingSerial::new(self)
     }
@@ -362,6 +501,10 @@ impl Client {
         }
     }
 
+    pub fn queued_bytes(&self) -> usize {
+        self.swapchain.borrow().queued_bytes()
+    }
+
     pub fn parse<'a, R: RequestParser<'a>>(
         &self,
         obj: &impl Object,
@@ -369,16 +512,29 @@ impl Client {
     ) -> Result<R, MsgParserError> {
         let res = R::parse(&mut parser)?;
         parser.eof()?;
-        log::trace!(
-            "Client {} -> {}@{}.{:?}",
-            self.id,
-            obj.interface().name(),
-            obj.id(),
-            res
-        );
+        if let Some(level) = self.trace_level() {
+            log::log!(
+                level,
+                "Client {} -> {}@{}.{:?}",
+                self.id,
+                obj.interface().name(),
+                obj.id(),
+                res
+            );
+        }
         Ok(res)
     }
 
+    fn trace_level(&self) -> Option<log::Level> {
+        if self.trace.get() || self.state.trace_protocol.get() {
+            Some(log::Level::Info)
+        } else if log::log_enabled!(log::Level::Trace) {
+            Some(log::Level::Trace)
+        } else {
+            None
+        }
+    }
+
     pub fn error(&self, message: impl Error) {
         let msg = ErrorFmt(message).to_string();
         log::error!("Client {}: A fatal error occurred: {}", self.id.0, msg,);
@@ -406,8 +562,8 @@ impl Client {
     }
 
     pub fn event<T: EventFormatter>(self: &Rc<Self>, event: T) {
-        if log::log_enabled!(log::Level::Trace) {
-            self.log_event(&event);
+        if let Some(level) = self.trace_level() {
+            self.log_event(level, &event);
         }
         let mut fds = vec![];
         let mut swapchain = self.swapchain.borrow_mut();
@@ -445,8 +601,23 @@ impl Client {
         self.objects.registries()
     }
 
-    pub fn log_event<T: EventFormatter>(&self, event: &T) {
-        log::trace!(
+    pub fn set_effective_caps(&self, caps: ClientCaps) {
+        let caps = caps & self.bounding_caps;
+        let old = self.effective_caps.replace(caps);
+        if old != caps {
+            log::info!(
+                "Capabilities of client {} changed from {:?} to {:?}",
+                self.id,
+                old,
+                caps,
+            );
+            self.state.globals.update_client_caps(self, old, caps);
+        }
+    }
+
+    pub fn log_event<T: EventFormatter>(&self, level: log::Level, event: &T) {
+        log::log!(
+            level,
             "Client {} <= {}@{}.{:?}",
             self.id,
             event.interface().name(),
@@ -465,7 +636,8 @@ impl Client {
 
     fn add_obj<T: WaylandObject>(&self, obj: &Rc<T>, client: bool) -> Result<(), ClientError> {
         if client {
-            self.objects.add_client_object(obj.clone())?;
+            let max = self.state.max_client_objects.get();
+            self.objects.add_client_object(obj.clone(), max)?;
         } else {
             self.objects.add_server_object(obj.clone());
         }
@@ -1,6 +1,7 @@
 use {
     crate::{
-        client::ClientId,
+        client::{ClientId, InputSerialKind},
+        ifs::wl_seat::SeatId,
         object::{Interface, ObjectId},
         utils::buffd::{BufFdError, MsgParserError},
         wire::WlDisplayId,
@@ -33,6 +34,12 @@ pub enum ClientError {
     UnknownId,
     #[error("The id is already in use")]
     IdAlreadyInUse,
+    #[error("The client has created more than {0} objects")]
+    TooManyObjects(usize),
+    #[error("{0} clients are already connected")]
+    TooManyClients(usize),
+    #[error("Could not determine the peer credentials of the client")]
+    NoPeerCredentials,
     #[error("The client object id is out of bounds")]
     ClientIdOutOfBounds,
     #[error("Object {0} is not a display")]
@@ -67,3 +74,17 @@ pub struct LookupError {
     pub interface: Interface,
     pub id: ObjectId,
 }
+
+#[derive(Debug, Error)]
+pub enum InputSerialError {
+    #[error("The serial was never sent to the client")]
+    Unknown,
+    #[error("The serial does not belong to a recent input event")]
+    NotAnInputEvent,
+    #[error("The serial belongs to seat {0}")]
+    WrongSeat(SeatId),
+    #[error("The serial belongs to a {0:?} event")]
+    WrongKind(InputSerialKind),
+    #[error("The serial belongs to an input event that is too old")]
+    Expired,
+}
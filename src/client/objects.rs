@@ -47,6 +47,7 @@ use {
             ZwpTabletToolV2Id,
         },
     },
+    ahash::AHashMap,
     std::{cell::RefCell, rc::Rc},
 };
 
@@ -86,6 +87,8 @@ pub struct Objects {
 }
 
 pub const MIN_SERVER_ID: u32 = 0xff000000;
+/// The default maximum number of objects that a single client can have at the same time.
+pub const DEFAULT_MAX_CLIENT_OBJECTS: usize = 1 << 20;
 const SEG_SIZE: usize = usize::BITS as usize;
 
 impl Objects {
@@ -193,7 +196,11 @@ impl Objects {
         self.registry.set(id, obj.clone());
     }
 
-    pub fn add_client_object(&self, obj: Rc<dyn Object>) -> Result<(), ClientError> {
+    pub fn add_client_object(
+        &self,
+        obj: Rc<dyn Object>,
+        max_objects: usize,
+    ) -> Result<(), ClientError> {
         let id = obj.id();
         let res = (|| {
             if id.raw() == 0 || id.raw() >= MIN_SERVER_ID {
@@ -202,6 +209,9 @@ impl Objects {
             if self.registry.contains(&id) {
                 return Err(ClientError::IdAlreadyInUse);
             }
+            if self.registry.len() >= max_objects {
+                return Err(ClientError::TooManyObjects(max_objects));
+            }
             self.registry.set(id, obj.clone());
             Ok(())
         })();
@@ -238,6 +248,16 @@ impl Objects {
         self.registries.lock()
     }
 
+    pub fn count_by_interface(&self) -> Vec<(&'static str, usize)> {
+        let mut counts = AHashMap::<&'static str, usize>::new();
+        for obj in self.registry.lock().values() {
+            *counts.entry(obj.interface().name()).or_default() += 1;
+        }
+        let mut counts: Vec<_> = counts.into_iter().collect();
+        counts.sort_unstable();
+        counts
+    }
+
     fn id_offset(&self) -> u32 {
         let mut ids = self.ids.borrow_mut();
         for (pos, seg) in ids.iter_mut().enumerate() {
@@ -4,7 +4,7 @@ use {
         client::{Client, ClientError},
         object::ObjectId,
         utils::{
-            buffd::{BufFdIn, BufFdOut, MsgParser},
+            buffd::{BufFdError, BufFdIn, BufFdOut, MsgParser},
             errorfmt::ErrorFmt,
             vec_ext::VecExt,
         },
@@ -118,7 +118,8 @@ async fn send(data: Rc<Client>) {
                 swapchain.commit();
                 mem::swap(&mut swapchain.pending, &mut buffers);
             }
-            let timeout = data.state.now() + Duration::from_millis(5000);
+            let msec = data.state.client_send_timeout_msec.get();
+            let timeout = data.state.now() + Duration::from_millis(msec);
             while let Some(mut cur) = buffers.pop_front() {
                 out.flush(&mut cur, timeout).await?;
                 data.swapchain.borrow_mut().free.push(cur);
@@ -129,6 +130,12 @@ async fn send(data: Rc<Client>) {
     if let Err(e) = res {
         if e.peer_closed() {
             log::info!("Client {} terminated the connection", data.id.0);
+        } else if matches!(e, ClientError::Io(BufFdError::Timeout)) {
+            log::error!(
+                "Client {} did not read its events within {} ms",
+                data.id.0,
+                data.state.client_send_timeout_msec.get(),
+            );
         } else {
             log::error!(
                 "An error occurred while sending data to client {}: {}",
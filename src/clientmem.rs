@@ -116,10 +116,17 @@ impl ClientMem {
     pub fn sigbus_impossible(&self) -> bool {
         self.sigbus_impossible
     }
+
+    /// Returns whether an access to this memory has caused a SIGBUS.
+    ///
+    /// After a SIGBUS, the mapping is replaced by anonymous memory and all further
+    /// accesses fail.
+    pub fn sigbus_occurred(&self) -> bool {
+        self.failed.get()
+    }
 }
 
 impl ClientMemOffset {
-    #[expect(dead_code)]
     pub fn pool(&self) -> &ClientMem {
         &self.mem
     }
@@ -10,7 +10,7 @@ use {
             metal, x,
         },
         cli::{CliBackend, GlobalArgs, RunArgs},
-        client::{ClientId, Clients},
+        client::{ClientId, Clients, DEFAULT_MAX_CLIENTS, DEFAULT_MAX_CLIENT_OBJECTS},
         clientmem::{self, ClientMemError},
         config::ConfigProxy,
         cpu_worker::{CpuWorker, CpuWorkerError},
@@ -32,7 +32,7 @@ use {
         scale::Scale,
         sighand::{self, SighandError},
         state::{ConnectorData, IdleState, ScreenlockState, State, XWaylandState},
-        tasks::{self, handle_const_40hz_latch, idle},
+        tasks::{self, handle_const_1hz_vblank, handle_const_40hz_latch, idle},
         tracy::enable_profiler,
         tree::{
             container_layout, container_render_positions, container_render_titles, float_layout,
@@ -41,9 +41,9 @@ use {
         },
         user_session::import_environment,
         utils::{
-            clonecell::CloneCell, errorfmt::ErrorFmt, fdcloser::FdCloser, numcell::NumCell,
-            oserror::OsError, queue::AsyncQueue, refcounted::RefCounted, run_toplevel::RunToplevel,
-            tri::Try,
+            asyncevent::AsyncEvent, clonecell::CloneCell, errorfmt::ErrorFmt, fdcloser::FdCloser,
+            numcell::NumCell, oserror::OsError, queue::AsyncQueue, refcounted::RefCounted,
+            run_toplevel::RunToplevel, tri::Try,
         },
         version::VERSION,
         video::drm::wait_for_sync_obj::WaitForSyncObj,
@@ -142,7 +142,9 @@ fn start_compositor2(
     let xkb_keymap = xkb_ctx.keymap_from_str(include_str!("keymap.xkb")).unwrap();
     let engine = AsyncEngine::new();
     let ring = IoUring::new(&engine, 32)?;
-    let _signal_future = sighand::install(&engine, &ring)?;
+    let config_reload = Rc::new(AsyncEvent::default());
+    let quit_requested = Rc::new(AsyncEvent::default());
+    let _signal_future = sighand::install(&engine, &ring, &config_reload, &quit_requested)?;
     let wheel = Wheel::new(&engine, &ring)?;
     let (_run_toplevel_future, run_toplevel) = RunToplevel::install(&engine);
     let node_ids = NodeIds::default();
@@ -168,6 +170,7 @@ fn start_compositor2(
         root: Rc::new(DisplayNode::new(node_ids.next())),
         workspaces: Default::default(),
         dummy_output: Default::default(),
+        scratch_workspace: Default::default(),
         node_ids,
         backend_events: AsyncQueue::new(),
         seat_ids: Default::default(),
@@ -176,6 +179,7 @@ fn start_compositor2(
         none_surface_ext: Rc::new(NoneSurfaceExt),
         tree_changed_sent: Cell::new(false),
         config: Default::default(),
+        config_reload,
         input_device_ids: Default::default(),
         input_device_handlers: Default::default(),
         theme: Default::default(),
@@ -201,8 +205,10 @@ fn start_compositor2(
             change: Default::default(),
             timeout: Cell::new(Duration::from_secs(10 * 60)),
             timeout_changed: Default::default(),
+            force: Default::default(),
             inhibitors: Default::default(),
             inhibitors_changed: Default::default(),
+            inhibited_notifications: Default::default(),
             backend_idle: Cell::new(true),
         },
         run_args,
@@ -246,9 +252,15 @@ fn start_compositor2(
         double_click_interval_usec: Cell::new(400 * 1000),
         double_click_distance: Cell::new(5),
         create_default_seat: Cell::new(true),
+        default_seat: Default::default(),
         subsurface_ids: Default::default(),
         wait_for_sync_obj: Rc::new(WaitForSyncObj::new(&ring, &engine)),
         explicit_sync_enabled: Cell::new(true),
+        client_send_timeout_msec: Cell::new(5000),
+        client_ping_timeout_msec: Cell::new(5000),
+        max_clients: Cell::new(DEFAULT_MAX_CLIENTS),
+        max_client_objects: Cell::new(DEFAULT_MAX_CLIENT_OBJECTS),
+        render_scratch: Default::default(),
         keyboard_state_ids: Default::default(),
         security_context_acceptors: Default::default(),
         cursor_user_group_ids: Default::default(),
@@ -271,10 +283,19 @@ fn start_compositor2(
         cpu_worker,
         ui_drag_enabled: Cell::new(true),
         ui_drag_threshold_squared: Cell::new(10),
+        animations: Default::default(),
+        dim_unfocused: Cell::new(0.0),
+        upower: Default::default(),
         toplevels: Default::default(),
         const_40hz_latch: Default::default(),
+        const_1hz_vblank: Default::default(),
+        trace_protocol: Cell::new(env::var("JAY_TRACE").ok().as_deref() == Some("1")),
         tray_item_ids: Default::default(),
         data_control_device_ids: Default::default(),
+        imported_environment: Default::default(),
+        session_bus_reconnect_handler: Default::default(),
+        quit_requested,
+        quitting: Default::default(),
     });
     state.tracker.register(ClientId::from_raw(0));
     create_dummy_output(&state);
@@ -296,6 +317,7 @@ fn start_compositor2(
             portal.spawn(engine.clone(), ring.clone(), logger.clone()),
         ));
     }
+    let _quit = engine.spawn("quit", tasks::handle_quit_requests(state.clone()));
     let _compositor = engine.spawn("compositor", start_compositor3(state.clone(), test_future));
     ring.run()?;
     state.clear();
@@ -325,8 +347,7 @@ async fn start_compositor3(state: Rc<State>, test_future: Option<TestFuture>) {
         }
     }
 
-    let config = load_config(&state, is_test);
-    config.configure(false);
+    let config = load_config(&state, is_test).configure_or_default(&state, false);
     state.config.set(Some(Rc::new(config)));
 
     if state.create_default_seat.get() && state.globals.seats.is_empty() {
@@ -374,6 +395,7 @@ fn start_global_event_handlers(
             tasks::handle_backend_events(state.clone()),
         ),
         eng.spawn("slow client", tasks::handle_slow_clients(state.clone())),
+        eng.spawn("config reload", tasks::handle_config_reload(state.clone())),
         eng.spawn(
             "handware cursor tick",
             tasks::handle_hardware_cursor_tick(state.clone()),
@@ -443,6 +465,7 @@ fn start_global_event_handlers(
             Phase::Present,
             handle_const_40hz_latch(state.clone()),
         ),
+        eng.spawn("const 1hz vblank", handle_const_1hz_vblank(state.clone())),
     ]
 }
 
@@ -520,6 +543,8 @@ fn create_dummy_output(state: &Rc<State>) {
         vrr_mode: Cell::new(VrrMode::NEVER),
         vrr_cursor_hz: Default::default(),
         tearing_mode: Cell::new(&TearingMode::Never),
+        wallpaper: Default::default(),
+        pending_wallpaper: Default::default(),
     });
     let connector = Rc::new(DummyOutput {
         id: state.connector_ids.next(),
@@ -590,6 +615,11 @@ fn create_dummy_output(state: &Rc<State>) {
         before_latch_event: Default::default(),
         tray_start_rel: Default::default(),
         tray_items: Default::default(),
+        gamma_control: Default::default(),
+        workspace_slide: Default::default(),
+        mirror: Default::default(),
+        mirrors: Default::default(),
+        content_type: Default::default(),
     });
     let dummy_workspace = Rc::new(WorkspaceNode {
         id: state.node_ids.next(),
@@ -616,6 +646,29 @@ fn create_dummy_output(state: &Rc<State>) {
     *dummy_workspace.output_link.borrow_mut() =
         Some(dummy_output.workspaces.add_last(dummy_workspace.clone()));
     dummy_output.show_workspace(&dummy_workspace);
+    let scratch_workspace = Rc::new(WorkspaceNode {
+        id: state.node_ids.next(),
+        state: state.clone(),
+        is_dummy: true,
+        output: CloneCell::new(dummy_output.clone()),
+        position: Default::default(),
+        container: Default::default(),
+        stacked: Default::default(),
+        seat_state: Default::default(),
+        name: "scratch".to_string(),
+        output_link: Default::default(),
+        visible: Default::default(),
+        fullscreen: Default::default(),
+        visible_on_desired_output: Default::default(),
+        desired_output: CloneCell::new(dummy_output.global.output_id.clone()),
+        jay_workspaces: Default::default(),
+        may_capture: Cell::new(false),
+        has_capture: Cell::new(false),
+        title_texture: Default::default(),
+        attention_requests: Default::default(),
+        render_highlight: Default::default(),
+    });
+    state.scratch_workspace.set(Some(scratch_workspace));
     state.dummy_output.set(Some(dummy_output));
 }
 
@@ -4,7 +4,7 @@ use {
     crate::{
         backend::{ConnectorId, DrmDeviceId, InputDeviceId},
         config::handler::ConfigProxyHandler,
-        ifs::wl_seat::SeatId,
+        ifs::{wl_seat::SeatId, wp_content_type_v1::ContentType},
         state::State,
         utils::{
             clonecell::CloneCell, numcell::NumCell, ptr_ext::PtrExt, unlink_on_drop::UnlinkOnDrop,
@@ -18,9 +18,9 @@ use {
             ipc::{InitMessage, ServerFeature, ServerMessage, V1InitMessage},
             ConfigEntry, VERSION,
         },
-        input::{InputDevice, Seat, SwitchEvent},
+        input::{InputDevice, PointerContext, PointerInput, Seat, SwitchEvent},
         keyboard::{mods::Modifiers, syms::KeySym, AppMod},
-        video::{Connector, DrmDevice},
+        video::{self, Connector, DrmDevice},
     },
     libloading::Library,
     std::{cell::Cell, io, mem, ptr, rc::Rc},
@@ -30,6 +30,8 @@ use {
 #[cfg(feature = "it")]
 use crate::it::test_config::TEST_CONFIG_ENTRY;
 
+const REQUEST_IDS_SINCE: u32 = 2;
+
 #[derive(Debug, Error)]
 pub enum ConfigError {
     #[error("Could not load the config library")]
@@ -84,6 +86,35 @@ impl ConfigProxy {
         self.send(&msg);
     }
 
+    pub fn invoke_pointer_binding(
+        &self,
+        seat: SeatId,
+        mods: Modifiers,
+        input: PointerInput,
+        context: PointerContext,
+    ) {
+        self.send(&ServerMessage::InvokePointerBinding {
+            seat: Seat(seat.raw() as _),
+            mods,
+            input,
+            context,
+        });
+    }
+
+    pub fn pointer_binding_motion(&self, seat: SeatId, x: i32, y: i32) {
+        self.send(&ServerMessage::PointerBindingMotion {
+            seat: Seat(seat.raw() as _),
+            x,
+            y,
+        });
+    }
+
+    pub fn pointer_binding_released(&self, seat: SeatId) {
+        self.send(&ServerMessage::PointerBindingReleased {
+            seat: Seat(seat.raw() as _),
+        });
+    }
+
     pub fn new_drm_dev(&self, dev: DrmDeviceId) {
         self.send(&ServerMessage::NewDrmDev {
             device: DrmDevice(dev.raw() as _),
@@ -120,6 +151,19 @@ impl ConfigProxy {
         });
     }
 
+    pub fn content_type_changed(&self, connector: ConnectorId, content_type: Option<ContentType>) {
+        let content_type = match content_type {
+            None => video::ContentType::NONE,
+            Some(ContentType::Photo) => video::ContentType::PHOTO,
+            Some(ContentType::Video) => video::ContentType::VIDEO,
+            Some(ContentType::Game) => video::ContentType::GAME,
+        };
+        self.send(&ServerMessage::ContentTypeChanged {
+            connector: Connector(connector.raw() as _),
+            content_type,
+        });
+    }
+
     pub fn new_input_device(&self, dev: InputDeviceId) {
         self.send(&ServerMessage::NewInputDevice {
             device: InputDevice(dev.raw() as _),
@@ -171,7 +215,7 @@ unsafe extern "C" fn default_client_init(
     size: usize,
 ) -> *const u8 {
     extern "C" fn configure() {
-        jay_toml_config::configure();
+        jay_config::_private::client::configure(jay_toml_config::configure);
     }
     unsafe {
         jay_config::_private::client::init(srv_data, srv_unref, srv_handler, msg, size, configure)
@@ -190,12 +234,14 @@ impl ConfigProxy {
             path,
             client_data: Cell::new(ptr::null()),
             dropped: Cell::new(false),
+            configure_failed: Cell::new(false),
             _lib: lib,
-            _version: version,
+            version,
             unref: entry.unref,
             handle_msg: entry.handle_msg,
             state: state.clone(),
             next_id: NumCell::new(1),
+            request_ids: Cell::new(false),
             keymaps: Default::default(),
             bufs: Default::default(),
             workspace_ids: NumCell::new(1),
@@ -206,6 +252,9 @@ impl ConfigProxy {
             timers_by_id: Default::default(),
             pollable_id: Default::default(),
             pollables: Default::default(),
+            running_commands: Default::default(),
+            pending_spawns: Default::default(),
+            kill_on_reload: Default::default(),
         });
         let init_msg = bincode_ops()
             .serialize(&InitMessage::V1(V1InitMessage {}))
@@ -226,12 +275,44 @@ impl ConfigProxy {
     }
 
     pub fn configure(&self, reload: bool) {
-        self.send(&ServerMessage::Features {
-            features: vec![ServerFeature::MOD_MASK],
-        });
+        let mut features = vec![ServerFeature::MOD_MASK];
+        let handler = self.handler.get();
+        let request_ids = handler
+            .as_ref()
+            .is_some_and(|h| h.version >= REQUEST_IDS_SINCE);
+        if request_ids {
+            features.push(ServerFeature::REQUEST_IDS);
+        }
+        self.send(&ServerMessage::Features { features });
+        if let Some(handler) = handler {
+            handler.request_ids.set(request_ids);
+        }
         self.send(&ServerMessage::Configure { reload });
     }
 
+    fn configure_failed(&self) -> bool {
+        match self.handler.get() {
+            Some(handler) => handler.configure_failed.get(),
+            None => false,
+        }
+    }
+
+    /// Runs the configuration function and falls back to the default config if it panics.
+    pub fn configure_or_default(self, state: &Rc<State>, reload: bool) -> Self {
+        self.configure(reload);
+        if !self.configure_failed() {
+            return self;
+        }
+        log::error!("Could not configure the compositor. Using the default config");
+        self.destroy();
+        for seat in state.globals.seats.lock().values() {
+            seat.clear_shortcuts();
+        }
+        let config = Self::default(state);
+        config.configure(reload);
+        config
+    }
+
     pub fn default(state: &Rc<State>) -> Self {
         let entry = ConfigEntry {
             version: VERSION,
@@ -318,6 +399,7 @@ unsafe extern "C" fn handle_msg(data: *const u8, msg: *const u8, size: usize) {
     }
 }
 
+#[derive(Clone)]
 pub struct InvokedShortcut {
     pub unmasked_mods: Modifiers,
     pub effective_mods: Modifiers,
@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod tests;
+
 use {
     crate::{
         async_engine::SpawnedFuture,
@@ -5,74 +8,90 @@ use {
             self, ConnectorId, DrmDeviceId, InputDeviceAccelProfile, InputDeviceCapability,
             InputDeviceId,
         },
+        client::ClientCaps,
         compositor::MAX_EXTENTS,
-        config::ConfigProxy,
         format::config_formats,
         ifs::wl_seat::{SeatId, WlSeatGlobal},
-        io_uring::TaskResultExt,
+        io_uring::{IoUring, TaskResultExt},
         output_schedule::map_cursor_hz,
-        scale::Scale,
+        scale::{Scale, MAX_OUTPUT_SCALE, MIN_OUTPUT_SCALE},
         state::{ConnectorData, DeviceHandlerData, DrmDevData, OutputData, State},
         theme::{Color, ThemeSized},
         tree::{
-            move_ws_to_output, ContainerNode, ContainerSplit, FloatNode, Node, NodeVisitorBase,
-            OutputNode, TearingMode, VrrMode, WsMoveConfig,
+            move_ws_to_output, ContainerNode, ContainerSplit, FloatNode, Node, NodeId,
+            NodeVisitorBase, OutputNode, TearingMode, VrrMode, WsMoveConfig,
         },
         utils::{
             asyncevent::AsyncEvent,
+            clone3::pidfd_send_signal,
             copyhashmap::CopyHashMap,
             debug_fn::debug_fn,
             errorfmt::ErrorFmt,
+            hash_map_ext::HashMapExt,
+            nonblock::set_nonblock,
             numcell::NumCell,
             oserror::OsError,
             stack::Stack,
             timer::{TimerError, TimerFd},
         },
+        wallpaper::Wallpaper,
         xkbcommon::{XkbCommonError, XkbKeymap},
     },
     bincode::Options,
+    futures_util::future::{self, Either},
     jay_config::{
         _private::{
             bincode_ops,
             ipc::{ClientMessage, Response, ServerMessage, WorkspaceSource},
             PollableId, WireMode,
         },
+        client::ClientCapabilities,
         input::{
             acceleration::{AccelProfile, ACCEL_PROFILE_ADAPTIVE, ACCEL_PROFILE_FLAT},
             capability::{
                 Capability, CAP_GESTURE, CAP_KEYBOARD, CAP_POINTER, CAP_SWITCH, CAP_TABLET_PAD,
                 CAP_TABLET_TOOL, CAP_TOUCH,
             },
-            FocusFollowsMouseMode, InputDevice, Seat,
+            DeviceMatch, FocusFollowsMouseMode, InputDevice, PointerContext, PointerInput, Seat,
         },
         keyboard::{mods::Modifiers, syms::KeySym, AppMod, Keymap, ModifiedKeySym},
         logging::LogLevel,
         theme::{colors::Colorable, sized::Resizable},
         timer::Timer as JayTimer,
         video::{
-            Connector, DrmDevice, Format as ConfigFormat, GfxApi, TearingMode as ConfigTearingMode,
-            Transform, VrrMode as ConfigVrrMode,
+            BackgroundMode, Connector, DrmDevice, Format as ConfigFormat, GfxApi,
+            TearingMode as ConfigTearingMode, Transform, VrrMode as ConfigVrrMode,
         },
         xwayland::XScalingMode,
-        Axis, Direction, Workspace,
+        AnimationCurve, Axis, Container, Direction, TabDirection, Workspace,
     },
     libloading::Library,
     log::Level,
-    std::{cell::Cell, ops::Deref, rc::Rc, sync::Arc, time::Duration},
+    std::{
+        cell::{Cell, RefCell},
+        future::Future,
+        ops::Deref,
+        pin::pin,
+        rc::Rc,
+        sync::Arc,
+        time::Duration,
+    },
     thiserror::Error,
-    uapi::{c, fcntl_dupfd_cloexec, OwnedFd},
+    uapi::{c, fcntl_dupfd_cloexec, Errno, OwnedFd},
 };
 
 pub(super) struct ConfigProxyHandler {
     pub path: Option<String>,
     pub client_data: Cell<*const u8>,
     pub dropped: Cell<bool>,
+    pub configure_failed: Cell<bool>,
     pub _lib: Option<Library>,
-    pub _version: u32,
+    pub version: u32,
     pub unref: unsafe extern "C" fn(data: *const u8),
     pub handle_msg: unsafe extern "C" fn(data: *const u8, msg: *const u8, size: usize),
     pub state: Rc<State>,
     pub next_id: NumCell<u64>,
+    pub request_ids: Cell<bool>,
     pub keymaps: CopyHashMap<Keymap, Rc<XkbKeymap>>,
     pub bufs: Stack<Vec<u8>>,
 
@@ -86,8 +105,15 @@ pub(super) struct ConfigProxyHandler {
 
     pub pollable_id: NumCell<u64>,
     pub pollables: CopyHashMap<PollableId, Rc<Pollable>>,
+
+    pub running_commands: CopyHashMap<u64, SpawnedFuture<()>>,
+
+    pub pending_spawns: CopyHashMap<u64, SpawnedFuture<()>>,
+    pub kill_on_reload: CopyHashMap<u64, Rc<OwnedFd>>,
 }
 
+const MAX_COMMAND_OUTPUT: usize = 1 << 20;
+
 pub struct Pollable {
     write_trigger: Rc<AsyncEvent>,
     _write_future: SpawnedFuture<()>,
@@ -111,6 +137,16 @@ impl ConfigProxyHandler {
 
         self.pollables.clear();
 
+        self.running_commands.clear();
+
+        self.pending_spawns.clear();
+        for pidfd in self.kill_on_reload.lock().drain_values() {
+            match pidfd_send_signal(&pidfd, c::SIGTERM) {
+                Ok(()) | Err(OsError(c::ESRCH)) => {}
+                Err(e) => log::error!("Could not terminate a spawned program: {}", ErrorFmt(e)),
+            }
+        }
+
         if let Some(path) = &self.path {
             if let Err(e) = uapi::unlink(path.as_str()) {
                 log::error!("Could not unlink {}: {}", path, ErrorFmt(OsError(e.0)));
@@ -165,15 +201,10 @@ impl ConfigProxyHandler {
     }
 
     fn handle_get_seat(&self, name: &str) {
-        for seat in self.state.globals.seats.lock().values() {
-            if seat.seat_name() == name {
-                self.respond(Response::GetSeat {
-                    seat: Seat(seat.id().raw() as _),
-                });
-                return;
-            }
-        }
-        let seat = self.state.create_seat(name);
+        let seat = match self.state.globals.seat_by_name(name) {
+            Some(seat) => seat,
+            _ => self.state.create_seat(name),
+        };
         self.respond(Response::GetSeat {
             seat: Seat(seat.id().raw() as _),
         });
@@ -266,22 +297,7 @@ impl ConfigProxyHandler {
     }
 
     fn handle_reload(&self) {
-        log::info!("Reloading config");
-        let config = match ConfigProxy::from_config_dir(&self.state) {
-            Ok(c) => c,
-            Err(e) => {
-                log::error!("Cannot reload config: {}", ErrorFmt(e));
-                return;
-            }
-        };
-        if let Some(config) = self.state.config.take() {
-            config.destroy();
-            for seat in self.state.globals.seats.lock().values() {
-                seat.clear_shortcuts();
-            }
-        }
-        config.configure(true);
-        self.state.config.set(Some(Rc::new(config)));
+        self.state.reload_config();
     }
 
     fn handle_get_fullscreen(&self, seat: Seat) -> Result<(), CphError> {
@@ -344,6 +360,34 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_set_focus_follows_mouse_delay(&self, seat: Seat, msec: u64) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_focus_follows_mouse_delay(msec);
+        Ok(())
+    }
+
+    fn handle_set_warp_pointer_to_focus(&self, seat: Seat, warp: bool) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_warp_pointer_to_focus(warp);
+        Ok(())
+    }
+
+    fn handle_set_seat_devices(
+        &self,
+        seat: Seat,
+        devices: Vec<DeviceMatch>,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_device_matches(devices);
+        Ok(())
+    }
+
+    fn handle_set_default_seat(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        self.state.default_seat.set(Some(seat));
+        Ok(())
+    }
+
     fn handle_set_window_management_enabled(
         &self,
         seat: Seat,
@@ -485,12 +529,30 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_kill(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.kill();
+        Ok(())
+    }
+
     fn handle_focus(&self, seat: Seat, direction: Direction) -> Result<(), CphError> {
         let seat = self.get_seat(seat)?;
         seat.move_focus(direction.into());
         Ok(())
     }
 
+    fn handle_focus_tab(&self, seat: Seat, direction: TabDirection) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.focus_tab(direction == TabDirection::Next);
+        Ok(())
+    }
+
+    fn handle_focus_previous(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.focus_previous();
+        Ok(())
+    }
+
     fn handle_move(&self, seat: Seat, direction: Direction) -> Result<(), CphError> {
         let seat = self.get_seat(seat)?;
         seat.move_focused(direction.into());
@@ -590,6 +652,25 @@ impl ConfigProxyHandler {
         Err(CphError::SeatDoesNotExist(seat))
     }
 
+    fn get_container(&self, container: Container) -> Result<Rc<ContainerNode>, CphError> {
+        struct V(NodeId, Option<Rc<ContainerNode>>);
+        impl NodeVisitorBase for V {
+            fn visit_container(&mut self, node: &Rc<ContainerNode>) {
+                if node.node_id() == self.0 {
+                    self.1 = Some(node.clone());
+                } else {
+                    node.node_visit_children(self);
+                }
+            }
+        }
+        let Ok(id) = u32::try_from(container.0) else {
+            return Err(CphError::NotAContainer(container));
+        };
+        let mut v = V(NodeId(id), None);
+        self.state.root.clone().node_visit(&mut v);
+        v.1.ok_or(CphError::NotAContainer(container))
+    }
+
     fn get_kb(&self, kb: InputDevice) -> Result<Rc<dyn backend::InputDevice>, CphError> {
         let kbs = self.state.input_device_handlers.borrow_mut();
         match kbs.get(&(InputDeviceId::from_raw(kb.0 as _))) {
@@ -736,6 +817,14 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_get_workspace_name(&self, workspace: Workspace) -> Result<(), CphError> {
+        let name = self.get_workspace(workspace)?;
+        self.respond(Response::GetWorkspaceName {
+            name: name.to_string(),
+        });
+        Ok(())
+    }
+
     fn handle_set_workspace_capture(
         &self,
         workspace: Workspace,
@@ -770,6 +859,41 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_set_shortcuts_inhibit_escape(
+        &self,
+        seat: Seat,
+        escape: Option<ModifiedKeySym>,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_shortcuts_inhibit_escape(escape);
+        Ok(())
+    }
+
+    fn handle_set_app_mod_escape(
+        &self,
+        seat: Seat,
+        escape: Option<ModifiedKeySym>,
+    ) -> Result<(), CphError> {
+        self.get_seat(seat)?.set_app_mod_escape(escape);
+        Ok(())
+    }
+
+    fn handle_set_app_mod_forward_keys(
+        &self,
+        seat: Seat,
+        app_mod: AppMod,
+        forward: bool,
+    ) -> Result<(), CphError> {
+        self.get_seat(seat)?
+            .set_app_mod_forward_keys(app_mod, forward);
+        Ok(())
+    }
+
+    fn handle_start_pointer_move(&self, seat: Seat) -> Result<(), CphError> {
+        self.get_seat(seat)?.start_pointer_move();
+        Ok(())
+    }
+
     fn handle_set_x_scaling_mode(&self, mode: XScalingMode) -> Result<(), CphError> {
         let use_wire_scale = match mode {
             XScalingMode::DEFAULT => false,
@@ -791,6 +915,32 @@ impl ConfigProxyHandler {
         self.state.ui_drag_threshold_squared.set(squared);
     }
 
+    fn handle_set_animations_enabled(&self, enabled: bool) {
+        self.state.animations.enabled.set(enabled);
+    }
+
+    fn handle_set_animation_duration(&self, duration: Duration) {
+        let nsec = duration.as_nanos().try_into().unwrap_or(u64::MAX);
+        self.state.animations.duration_nsec.set(nsec);
+    }
+
+    fn handle_set_animation_curve(&self, curve: AnimationCurve) {
+        self.state.animations.curve.set(curve);
+    }
+
+    fn handle_set_window_opacity(&self, seat: Seat, opacity: f64) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_window_opacity(opacity as f32);
+        Ok(())
+    }
+
+    fn handle_set_dim_unfocused(&self, factor: f64) {
+        let factor = (factor as f32).clamp(0.0, 1.0);
+        if self.state.dim_unfocused.replace(factor) != factor {
+            self.state.damage(self.state.root.extents.get());
+        }
+    }
+
     fn handle_set_direct_scanout_enabled(
         &self,
         device: Option<DrmDevice>,
@@ -925,10 +1075,73 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_adopt_orphaned_workspaces(&self, connector: Connector) -> Result<(), CphError> {
+        let output = self.get_output_node(connector)?;
+        if output.is_dummy {
+            return Ok(());
+        }
+        let connected: Vec<_> = self
+            .state
+            .root
+            .outputs
+            .lock()
+            .values()
+            .map(|o| o.global.output_id.clone())
+            .collect();
+        let orphaned: Vec<_> = self
+            .state
+            .workspaces
+            .lock()
+            .values()
+            .filter(|ws| !ws.is_dummy)
+            .filter(|ws| !connected.contains(&ws.desired_output.get()))
+            .cloned()
+            .collect();
+        for ws in orphaned {
+            ws.desired_output.set(output.global.output_id.clone());
+            if ws.output.get().id == output.id {
+                continue;
+            }
+            let link = match &*ws.output_link.borrow() {
+                None => continue,
+                Some(l) => l.to_ref(),
+            };
+            let config = WsMoveConfig {
+                make_visible_always: false,
+                make_visible_if_empty: true,
+                source_is_destroyed: false,
+                before: None,
+            };
+            move_ws_to_output(&link, &output, config);
+        }
+        self.state.tree_changed();
+        Ok(())
+    }
+
     fn handle_set_idle(&self, timeout: Duration) {
         self.state.idle.set_timeout(timeout);
     }
 
+    fn handle_enter_idle(&self) {
+        self.state.idle.enter_idle();
+    }
+
+    fn handle_set_client_send_timeout(&self, msec: u64) {
+        self.state.client_send_timeout_msec.set(msec);
+    }
+
+    fn handle_set_client_ping_timeout(&self, msec: u64) {
+        self.state.client_ping_timeout_msec.set(msec);
+    }
+
+    fn handle_set_max_clients(&self, max: usize) {
+        self.state.max_clients.set(max);
+    }
+
+    fn handle_set_max_client_objects(&self, max: usize) {
+        self.state.max_client_objects.set(max);
+    }
+
     fn handle_set_explicit_sync_enabled(&self, enabled: bool) {
         self.state.explicit_sync_enabled.set(enabled);
     }
@@ -1081,10 +1294,10 @@ impl ConfigProxyHandler {
     }
 
     fn handle_connector_set_scale(&self, connector: Connector, scale: f64) -> Result<(), CphError> {
-        if scale < 0.1 {
+        if scale < MIN_OUTPUT_SCALE {
             return Err(CphError::ScaleTooSmall(scale));
         }
-        if scale > 1000.0 {
+        if scale > MAX_OUTPUT_SCALE {
             return Err(CphError::ScaleTooLarge(scale));
         }
         let scale = Scale::from_f64(scale);
@@ -1174,6 +1387,73 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_connector_get_vrr_enabled(&self, connector: Connector) -> Result<(), CphError> {
+        let connector = self.get_output_node(connector)?;
+        self.respond(Response::ConnectorGetVrrEnabled {
+            enabled: connector.schedule.vrr_enabled(),
+        });
+        Ok(())
+    }
+
+    fn handle_connector_get_transform(&self, connector: Connector) -> Result<(), CphError> {
+        let connector = self.get_output_node(connector)?;
+        self.respond(Response::ConnectorGetTransform {
+            transform: connector.global.persistent.transform.get(),
+        });
+        Ok(())
+    }
+
+    fn handle_connector_set_background(
+        &self,
+        connector: Connector,
+        path: &str,
+        mode: BackgroundMode,
+    ) -> Result<(), CphError> {
+        let res = self.set_connector_background(connector, path, mode);
+        let response = match &res {
+            Ok(()) => Ok(()),
+            Err(e) => Err(ErrorFmt(e).to_string()),
+        };
+        self.respond(Response::ConnectorSetBackground { res: response });
+        res
+    }
+
+    fn set_connector_background(
+        &self,
+        connector: Connector,
+        path: &str,
+        mode: BackgroundMode,
+    ) -> Result<(), CphError> {
+        let output = self.get_output_node(connector)?;
+        let persistent = &output.global.persistent;
+        if let Some(pending) = persistent.pending_wallpaper.take() {
+            pending.detach();
+        }
+        let state = self.state.clone();
+        let id = output.global.connector.connector.id();
+        let weak = Rc::downgrade(persistent);
+        let pending = Wallpaper::load(&self.state.cpu_worker, path, mode, move |res| {
+            let Some(persistent) = weak.upgrade() else {
+                return;
+            };
+            persistent.pending_wallpaper.take();
+            let res = res.and_then(|w| w.create_texture(&state).map(|_| w));
+            let wallpaper = match res {
+                Ok(w) => w,
+                Err(e) => {
+                    log::error!("Could not load the background image: {}", ErrorFmt(e));
+                    return;
+                }
+            };
+            persistent.wallpaper.set(Some(Rc::new(wallpaper)));
+            if let Some(output) = state.root.outputs.get(&id) {
+                state.damage(output.global.pos.get());
+            }
+        });
+        persistent.pending_wallpaper.set(Some(pending));
+        Ok(())
+    }
+
     fn handle_connector_set_position(
         &self,
         connector: Connector,
@@ -1188,6 +1468,29 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_mirror_output(&self, src: Connector, dst: Connector) -> Result<(), CphError> {
+        let src_node = self.get_output_node(src)?;
+        let dst_node = self.get_output_node(dst)?;
+        if src_node.id == dst_node.id || src_node.mirror.is_some() {
+            return Err(CphError::CannotMirror(dst, src));
+        }
+        for output in self.state.root.outputs.lock().values() {
+            if let Some(mirror) = output.mirror.get() {
+                if mirror.source.id == dst_node.id {
+                    return Err(CphError::CannotMirror(dst, src));
+                }
+            }
+        }
+        dst_node.set_mirror_source(Some(&src_node));
+        Ok(())
+    }
+
+    fn handle_unmirror_output(&self, dst: Connector) -> Result<(), CphError> {
+        let dst = self.get_output_node(dst)?;
+        dst.set_mirror_source(None);
+        Ok(())
+    }
+
     fn handle_connector_get_position(&self, connector: Connector) -> Result<(), CphError> {
         let connector = self.get_output_node(connector)?;
         let (x, y) = connector.global.pos.get().position();
@@ -1265,6 +1568,20 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_get_stacked(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        self.respond(Response::GetStacked {
+            stacked: seat.get_stacked().unwrap_or(false),
+        });
+        Ok(())
+    }
+
+    fn handle_set_stacked(&self, seat: Seat, stacked: bool) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_stacked(stacked);
+        Ok(())
+    }
+
     fn handle_get_split(&self, seat: Seat) -> Result<(), CphError> {
         let seat = self.get_seat(seat)?;
         self.respond(Response::GetSplit {
@@ -1282,6 +1599,30 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_get_seat_container(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        let container = match seat.kb_parent_container() {
+            Some(c) => Container(c.node_id().raw() as _),
+            _ => Container(0),
+        };
+        self.respond(Response::GetSeatContainer { container });
+        Ok(())
+    }
+
+    fn handle_get_container_split(&self, container: Container) -> Result<(), CphError> {
+        let container = self.get_container(container)?;
+        self.respond(Response::GetSplit {
+            axis: container.split.get().into(),
+        });
+        Ok(())
+    }
+
+    fn handle_set_container_split(&self, container: Container, axis: Axis) -> Result<(), CphError> {
+        let container = self.get_container(container)?;
+        container.set_split(axis.into());
+        Ok(())
+    }
+
     fn handle_add_shortcut(
         &self,
         seat: Seat,
@@ -1308,6 +1649,55 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_set_shortcut_repeat(
+        &self,
+        seat: Seat,
+        mods: Modifiers,
+        sym: KeySym,
+        app_mod: AppMod,
+        repeat: bool,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_shortcut_repeat(mods, sym, app_mod, repeat);
+        Ok(())
+    }
+
+    fn handle_send_to_scratch(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.send_to_scratch();
+        Ok(())
+    }
+
+    fn handle_fetch_from_scratch(&self, seat: Seat, app_id: &str) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.fetch_from_scratch(app_id);
+        Ok(())
+    }
+
+    fn handle_add_pointer_binding(
+        &self,
+        seat: Seat,
+        mods: Modifiers,
+        input: PointerInput,
+        context: PointerContext,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.add_pointer_binding(mods, input, context);
+        Ok(())
+    }
+
+    fn handle_remove_pointer_binding(
+        &self,
+        seat: Seat,
+        mods: Modifiers,
+        input: PointerInput,
+        context: PointerContext,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.remove_pointer_binding(mods, input, context);
+        Ok(())
+    }
+
     fn handle_get_input_devices(&self, seat: Option<Seat>) {
         let id = seat.map(|s| SeatId::from_raw(s.0 as _));
         let matches = |dhd: &DeviceHandlerData| {
@@ -1332,6 +1722,14 @@ impl ConfigProxyHandler {
         self.respond(Response::GetInputDevices { devices: res });
     }
 
+    /// Seats cannot be removed. The request is rejected so that the config does not
+    /// assume that the seat is gone.
+    fn handle_remove_seat(&self, seat: Seat) -> Result<(), CphError> {
+        self.get_seat(seat)?;
+        self.respond(Response::NotSupported);
+        Err(CphError::RemoveSeat(seat))
+    }
+
     fn handle_get_seats(&self) {
         let seats = {
             let seats = self.state.globals.seats.lock();
@@ -1344,11 +1742,13 @@ impl ConfigProxyHandler {
     }
 
     fn handle_run(
-        &self,
+        self: &Rc<Self>,
         prog: &str,
         args: Vec<String>,
         env: Vec<(String, String)>,
         fds: Vec<(i32, i32)>,
+        log_output: bool,
+        kill_on_reload: bool,
     ) -> Result<(), CphError> {
         let fds: Vec<_> = fds
             .into_iter()
@@ -1359,7 +1759,110 @@ impl ConfigProxyHandler {
             _ => return Err(CphError::NoForker),
         };
         let env = env.into_iter().map(|(k, v)| (k, Some(v))).collect();
-        forker.spawn(prog.to_string(), args, env, fds);
+        if !kill_on_reload {
+            forker.spawn(prog.to_string(), args, env, fds, log_output);
+            return Ok(());
+        }
+        let id = self.next_id.fetch_add(1);
+        let prog = prog.to_string();
+        let slf = self.clone();
+        let future = self.state.eng.spawn("config run", async move {
+            let res = forker.spawn_with_pidfd(prog, args, env, fds, log_output);
+            if let Ok(pidfd) = res.await {
+                slf.kill_on_reload.set(id, pidfd.clone());
+                if let Err(e) = slf.state.ring.readable(&pidfd).await {
+                    log::error!("Could not wait for a spawned program: {}", ErrorFmt(e));
+                }
+                slf.kill_on_reload.remove(&id);
+            }
+            slf.pending_spawns.remove(&id);
+        });
+        self.pending_spawns.set(id, future);
+        Ok(())
+    }
+
+    fn handle_run_with_output(
+        self: &Rc<Self>,
+        prog: &str,
+        args: Vec<String>,
+        env: Vec<(String, String)>,
+        fds: Vec<(i32, i32)>,
+        id: u64,
+    ) -> Result<(), CphError> {
+        let mut fds: Vec<_> = fds
+            .into_iter()
+            .map(|(a, b)| (a, Rc::new(OwnedFd::new(b))))
+            .collect();
+        let forker = match self.state.forker.get() {
+            Some(f) => f,
+            _ => return Err(CphError::NoForker),
+        };
+        let (read, write) = match uapi::pipe2(c::O_CLOEXEC) {
+            Ok(p) => p,
+            Err(e) => return Err(CphError::CreatePipe(e.into())),
+        };
+        if let Err(e) = set_nonblock(read.raw()) {
+            return Err(CphError::CreatePipe(e));
+        }
+        fds.retain(|(idx, _)| *idx != 1);
+        fds.push((1, Rc::new(write)));
+        let prog = prog.to_string();
+        let env = env.into_iter().map(|(k, v)| (k, Some(v))).collect();
+        let slf = self.clone();
+        let future = self.state.eng.spawn("config run", async move {
+            let status = forker.spawn_and_wait(prog, args, env, fds);
+            let output = collect_command_output(&slf.state.ring, Rc::new(read), status);
+            let (status, stdout) = output.await;
+            let code = status.filter(|&s| c::WIFEXITED(s)).map(c::WEXITSTATUS);
+            slf.send(&ServerMessage::RunCompleted { id, code, stdout });
+            slf.running_commands.remove(&id);
+        });
+        self.running_commands.set(id, future);
+        Ok(())
+    }
+
+    fn handle_update_client_capabilities(
+        &self,
+        pid: i32,
+        caps: ClientCapabilities,
+        grant: bool,
+    ) -> Result<(), CphError> {
+        let caps = ClientCaps(caps.0) & ClientCaps::all();
+        let clients: Vec<_> = self
+            .state
+            .clients
+            .clients
+            .borrow()
+            .values()
+            .filter(|c| c.data.pid_info.pid == pid)
+            .map(|c| c.data.clone())
+            .collect();
+        if clients.is_empty() {
+            return Err(CphError::NoClientWithPid(pid));
+        }
+        for client in clients {
+            let mut effective = client.effective_caps.get();
+            match grant {
+                true => effective |= caps,
+                false => effective &= !caps,
+            }
+            client.set_effective_caps(effective);
+        }
+        Ok(())
+    }
+
+    fn handle_set_client_protocol_tracing(&self, pid: i32, enabled: bool) -> Result<(), CphError> {
+        let clients = self.state.clients.clients.borrow();
+        let mut found = false;
+        for client in clients.values() {
+            if client.data.pid_info.pid == pid {
+                client.data.trace.set(enabled);
+                found = true;
+            }
+        }
+        if !found {
+            return Err(CphError::NoClientWithPid(pid));
+        }
         Ok(())
     }
 
@@ -1395,8 +1898,7 @@ impl ConfigProxyHandler {
     }
 
     fn handle_quit(&self) {
-        log::info!("Quitting");
-        self.state.ring.stop();
+        self.state.quit();
     }
 
     fn handle_switch_to(&self, vtnr: u32) {
@@ -1526,6 +2028,8 @@ impl ConfigProxyHandler {
         let sized = match sized {
             TITLE_HEIGHT => ThemeSized::title_height,
             BORDER_WIDTH => ThemeSized::border_width,
+            INNER_GAP => ThemeSized::inner_gap,
+            OUTER_GAP => ThemeSized::outer_gap,
             _ => return Err(CphError::UnknownSized(sized.0)),
         };
         Ok(sized)
@@ -1592,6 +2096,7 @@ impl ConfigProxyHandler {
             BAR_BACKGROUND_COLOR => &colors.bar_background,
             SEPARATOR_COLOR => &colors.separator,
             BORDER_COLOR => &colors.border,
+            FOCUSED_BORDER_COLOR => &colors.focused_border,
             UNFOCUSED_TITLE_TEXT_COLOR => &colors.unfocused_title_text,
             FOCUSED_TITLE_TEXT_COLOR => &colors.focused_title_text,
             FOCUSED_INACTIVE_TITLE_TEXT_COLOR => &colors.focused_inactive_title_text,
@@ -1625,16 +2130,33 @@ impl ConfigProxyHandler {
         self.keymaps.remove(&keymap);
     }
 
-    pub fn handle_request(self: &Rc<Self>, msg: &[u8]) {
+    pub fn handle_request(self: &Rc<Self>, mut msg: &[u8]) {
+        let mut request_id = None;
+        if self.request_ids.get() {
+            if msg.len() < 8 {
+                log::error!("Client request does not contain a request id");
+                return;
+            }
+            let (id, rest) = msg.split_at(8);
+            request_id = Some(u64::from_le_bytes(id.try_into().unwrap()));
+            msg = rest;
+        }
         if let Err(e) = self.handle_request_(msg) {
-            log::error!("Could not handle client request: {}", ErrorFmt(e));
+            let msg = ErrorFmt(e).to_string();
+            log::error!("Could not handle client request: {}", msg);
+            if let Some(request_id) = request_id {
+                self.send(&ServerMessage::Error { request_id, msg });
+            }
         }
     }
 
     fn handle_request_(self: &Rc<Self>, msg: &[u8]) -> Result<(), CphError> {
         let request = match bincode_ops().deserialize::<ClientMessage>(msg) {
             Ok(msg) => msg,
-            Err(e) => return Err(CphError::ParsingFailed(e)),
+            Err(e) => {
+                self.respond(Response::NotSupported);
+                return Err(CphError::ParsingFailed(e));
+            }
         };
         match request {
             ClientMessage::Log {
@@ -1692,10 +2214,12 @@ impl ConfigProxyHandler {
             }
             ClientMessage::GetInputDevices { seat } => self.handle_get_input_devices(seat),
             ClientMessage::GetSeats => self.handle_get_seats(),
-            ClientMessage::RemoveSeat { .. } => {}
-            ClientMessage::Run { prog, args, env } => {
-                self.handle_run(prog, args, env, vec![]).wrn("run")?
+            ClientMessage::RemoveSeat { seat } => {
+                self.handle_remove_seat(seat).wrn("remove_seat")?
             }
+            ClientMessage::Run { prog, args, env } => self
+                .handle_run(prog, args, env, vec![], false, false)
+                .wrn("run")?,
             ClientMessage::GrabKb { kb, grab } => self.handle_grab(kb, grab).wrn("grab")?,
             ClientMessage::SetColor { colorable, color } => {
                 self.handle_set_color(colorable, color).wrn("set_color")?
@@ -1899,7 +2423,9 @@ impl ConfigProxyHandler {
                 args,
                 env,
                 fds,
-            } => self.handle_run(prog, args, env, fds).wrn("run")?,
+            } => self
+                .handle_run(prog, args, env, fds, false, false)
+                .wrn("run")?,
             ClientMessage::DisableDefaultSeat => self.state.create_default_seat.set(false),
             ClientMessage::DestroyKeymap { keymap } => self.handle_destroy_keymap(keymap),
             ClientMessage::GetConnectorName { connector } => self
@@ -2009,6 +2535,171 @@ impl ConfigProxyHandler {
             ClientMessage::SetAppMod { seat, app_mod } => self
                 .handle_set_app_mod(seat, app_mod)
                 .wrn("set_app_mod")?,
+            ClientMessage::Run3 {
+                prog,
+                args,
+                env,
+                fds,
+                log_output,
+            } => self
+                .handle_run(prog, args, env, fds, log_output, false)
+                .wrn("run")?,
+            ClientMessage::GrantClientCapabilities { pid, caps } => self
+                .handle_update_client_capabilities(pid, caps, true)
+                .wrn("grant_client_capabilities")?,
+            ClientMessage::RevokeClientCapabilities { pid, caps } => self
+                .handle_update_client_capabilities(pid, caps, false)
+                .wrn("revoke_client_capabilities")?,
+            ClientMessage::ConfigureFailed => self.configure_failed.set(true),
+            ClientMessage::GetSeatContainer { seat } => self
+                .handle_get_seat_container(seat)
+                .wrn("get_seat_container")?,
+            ClientMessage::GetContainerSplit { container } => self
+                .handle_get_container_split(container)
+                .wrn("get_container_split")?,
+            ClientMessage::SetContainerSplit { container, axis } => self
+                .handle_set_container_split(container, axis)
+                .wrn("set_container_split")?,
+            ClientMessage::SetProtocolTracing { enabled } => self.state.trace_protocol.set(enabled),
+            ClientMessage::SetClientProtocolTracing { pid, enabled } => self
+                .handle_set_client_protocol_tracing(pid, enabled)
+                .wrn("set_client_protocol_tracing")?,
+            ClientMessage::SetFocusFollowsMouseDelay { seat, msec } => self
+                .handle_set_focus_follows_mouse_delay(seat, msec)
+                .wrn("set_focus_follows_mouse_delay")?,
+            ClientMessage::Kill { seat } => self.handle_kill(seat).wrn("kill")?,
+            ClientMessage::SetWarpPointerToFocus { seat, warp } => self
+                .handle_set_warp_pointer_to_focus(seat, warp)
+                .wrn("set_warp_pointer_to_focus")?,
+            ClientMessage::SetSeatDevices { seat, devices } => self
+                .handle_set_seat_devices(seat, devices)
+                .wrn("set_seat_devices")?,
+            ClientMessage::SetDefaultSeat { seat } => self
+                .handle_set_default_seat(seat)
+                .wrn("set_default_seat")?,
+            ClientMessage::ConnectorSetBackground {
+                connector,
+                path,
+                mode,
+            } => self
+                .handle_connector_set_background(connector, &path, mode)
+                .wrn("connector_set_background")?,
+            ClientMessage::SetAnimationsEnabled { enabled } => {
+                self.handle_set_animations_enabled(enabled)
+            }
+            ClientMessage::SetAnimationDuration { duration } => {
+                self.handle_set_animation_duration(duration)
+            }
+            ClientMessage::SetAnimationCurve { curve } => self.handle_set_animation_curve(curve),
+            ClientMessage::SetWindowOpacity { seat, opacity } => self
+                .handle_set_window_opacity(seat, opacity)
+                .wrn("set_window_opacity")?,
+            ClientMessage::SetDimUnfocused { factor } => self.handle_set_dim_unfocused(factor),
+            ClientMessage::FocusTab { seat, direction } => self
+                .handle_focus_tab(seat, direction)
+                .wrn("focus_tab")?,
+            ClientMessage::AddPointerBinding {
+                seat,
+                mods,
+                input,
+                context,
+            } => self
+                .handle_add_pointer_binding(seat, mods, input, context)
+                .wrn("add_pointer_binding")?,
+            ClientMessage::RemovePointerBinding {
+                seat,
+                mods,
+                input,
+                context,
+            } => self
+                .handle_remove_pointer_binding(seat, mods, input, context)
+                .wrn("remove_pointer_binding")?,
+            ClientMessage::SendToScratch { seat } => self
+                .handle_send_to_scratch(seat)
+                .wrn("send_to_scratch")?,
+            ClientMessage::FetchFromScratch { seat, app_id } => self
+                .handle_fetch_from_scratch(seat, app_id)
+                .wrn("fetch_from_scratch")?,
+            ClientMessage::SetShortcutRepeat {
+                seat,
+                mods,
+                sym,
+                app_mod,
+                repeat,
+            } => self
+                .handle_set_shortcut_repeat(seat, mods, sym, app_mod, repeat)
+                .wrn("set_shortcut_repeat")?,
+            ClientMessage::GetWorkspaceName { workspace } => self
+                .handle_get_workspace_name(workspace)
+                .wrn("get_workspace_name")?,
+            ClientMessage::RunWithOutput {
+                prog,
+                args,
+                env,
+                fds,
+                id,
+            } => self
+                .handle_run_with_output(prog, args, env, fds, id)
+                .wrn("run_with_output")?,
+            ClientMessage::AdoptOrphanedWorkspaces { connector } => self
+                .handle_adopt_orphaned_workspaces(connector)
+                .wrn("adopt_orphaned_workspaces")?,
+            ClientMessage::FocusPrevious { seat } => self
+                .handle_focus_previous(seat)
+                .wrn("focus_previous")?,
+            ClientMessage::ConnectorGetTransform { connector } => self
+                .handle_connector_get_transform(connector)
+                .wrn("connector_get_transform")?,
+            ClientMessage::ConnectorGetVrrEnabled { connector } => self
+                .handle_connector_get_vrr_enabled(connector)
+                .wrn("connector_get_vrr_enabled")?,
+            ClientMessage::EnterIdle => self.handle_enter_idle(),
+            ClientMessage::SetClientSendTimeoutMsec { msec } => {
+                self.handle_set_client_send_timeout(msec)
+            }
+            ClientMessage::MirrorOutput { src, dst } => self
+                .handle_mirror_output(src, dst)
+                .wrn("mirror_output")?,
+            ClientMessage::UnmirrorOutput { dst } => self
+                .handle_unmirror_output(dst)
+                .wrn("unmirror_output")?,
+            ClientMessage::SetClientPingTimeoutMsec { msec } => {
+                self.handle_set_client_ping_timeout(msec)
+            }
+            ClientMessage::SetAppModEscape { seat, escape } => self
+                .handle_set_app_mod_escape(seat, escape)
+                .wrn("set_app_mod_escape")?,
+            ClientMessage::SetAppModForwardKeys {
+                seat,
+                app_mod,
+                forward,
+            } => self
+                .handle_set_app_mod_forward_keys(seat, app_mod, forward)
+                .wrn("set_app_mod_forward_keys")?,
+            ClientMessage::StartPointerMove { seat } => self
+                .handle_start_pointer_move(seat)
+                .wrn("start_pointer_move")?,
+            ClientMessage::Run4 {
+                prog,
+                args,
+                env,
+                fds,
+                log_output,
+                kill_on_reload,
+            } => self
+                .handle_run(prog, args, env, fds, log_output, kill_on_reload)
+                .wrn("run")?,
+            ClientMessage::GetStacked { seat } => {
+                self.handle_get_stacked(seat).wrn("get_stacked")?
+            }
+            ClientMessage::SetStacked { seat, stacked } => self
+                .handle_set_stacked(seat, stacked)
+                .wrn("set_stacked")?,
+            ClientMessage::SetShortcutsInhibitEscape { seat, escape } => self
+                .handle_set_shortcuts_inhibit_escape(seat, escape)
+                .wrn("set_shortcuts_inhibit_escape")?,
+            ClientMessage::SetMaxClients { max } => self.handle_set_max_clients(max),
+            ClientMessage::SetMaxClientObjects { max } => self.handle_set_max_client_objects(max),
         }
         Ok(())
     }
@@ -2024,6 +2715,8 @@ enum CphError {
     InvalidSize(i32, ThemeSized),
     #[error("The ol' forker is not available")]
     NoForker,
+    #[error("Could not create a pipe")]
+    CreatePipe(#[source] OsError),
     #[error("Repeat rate is negative")]
     NegativeRepeatRate,
     #[error("Repeat delay is negative")]
@@ -2042,10 +2735,14 @@ enum CphError {
     OutputIsNotDesktop(Connector),
     #[error("{0}x{1} is not a valid connector position")]
     InvalidConnectorPosition(i32, i32),
+    #[error("Connector {0:?} cannot mirror connector {1:?}")]
+    CannotMirror(Connector, Connector),
     #[error("Keymap {0:?} does not exist")]
     KeymapDoesNotExist(Keymap),
     #[error("Seat {0:?} does not exist")]
     SeatDoesNotExist(Seat),
+    #[error("Node {} does not exist or is not a container", .0.0)]
+    NotAContainer(Container),
     #[error("DRM device {0:?} does not exist")]
     DrmDeviceDoesNotExist(DrmDevice),
     #[error("Workspace {0:?} does not exist")]
@@ -2080,6 +2777,10 @@ enum CphError {
     UnknownFormat(ConfigFormat),
     #[error("Unknown x scaling mode {0:?}")]
     UnknownXScalingMode(XScalingMode),
+    #[error("There is no client with pid {0}")]
+    NoClientWithPid(i32),
+    #[error("Seat {0:?} cannot be removed")]
+    RemoveSeat(Seat),
 }
 
 trait WithRequestName {
@@ -2091,3 +2792,55 @@ impl WithRequestName for Result<(), CphError> {
         self.map_err(move |e| CphError::FailedRequest(request, Box::new(e)))
     }
 }
+
+/// Waits for a command to exit and returns its wait status and output.
+///
+/// `fd` must be the non-blocking read end of the pipe connected to the stdout of the
+/// command. The result is available as soon as the command has exited, even if processes
+/// spawned by the command keep the write end open. Output written after that is ignored.
+async fn collect_command_output(
+    ring: &IoUring,
+    fd: Rc<OwnedFd>,
+    status: impl Future<Output = Option<c::c_int>>,
+) -> (Option<c::c_int>, Vec<u8>) {
+    let output = RefCell::new(vec![]);
+    let reader = async {
+        loop {
+            if let Err(e) = ring.readable(&fd).await {
+                log::warn!("Could not wait for command output: {}", ErrorFmt(e));
+                return;
+            }
+            if !read_command_output(&fd, &mut output.borrow_mut()) {
+                return;
+            }
+        }
+    };
+    let status = match future::select(pin!(status), pin!(reader)).await {
+        Either::Left((status, _)) => status,
+        Either::Right(((), status)) => status.await,
+    };
+    read_command_output(&fd, &mut output.borrow_mut());
+    (status, output.into_inner())
+}
+
+/// Appends the data that is currently available in `fd` to `output`.
+///
+/// Returns `false` if the write end has been closed or an error occurred.
+fn read_command_output(fd: &OwnedFd, output: &mut Vec<u8>) -> bool {
+    let mut buf = [0u8; 4096];
+    loop {
+        match uapi::read(fd.raw(), &mut buf[..]) {
+            Ok([]) => return false,
+            Ok(data) => {
+                let n = data.len().min(MAX_COMMAND_OUTPUT - output.len());
+                output.extend_from_slice(&data[..n]);
+            }
+            Err(Errno(c::EAGAIN)) => return true,
+            Err(e) => {
+                let e = OsError::from(e);
+                log::warn!("Could not read the output of a command: {}", ErrorFmt(e));
+                return false;
+            }
+        }
+    }
+}
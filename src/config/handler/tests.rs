@@ -0,0 +1,44 @@
+use {
+    crate::{
+        async_engine::AsyncEngine, config::handler::collect_command_output, io_uring::IoUring,
+        utils::nonblock::set_nonblock, wheel::Wheel,
+    },
+    std::{cell::Cell, rc::Rc},
+    uapi::c,
+};
+
+fn collect(keep_write_end: bool) -> (Option<c::c_int>, Vec<u8>) {
+    let eng = AsyncEngine::new();
+    let ring = IoUring::new(&eng, 32).unwrap();
+    let wheel = Wheel::new(&eng, &ring).unwrap();
+    let (read, write) = uapi::pipe2(c::O_CLOEXEC).unwrap();
+    set_nonblock(read.raw()).unwrap();
+    uapi::write(write.raw(), b"hello").unwrap();
+    // An open write end behaves like a grandchild that inherited stdout.
+    let write = keep_write_end.then_some(write);
+    let res = Rc::new(Cell::new(None));
+    let res2 = res.clone();
+    let ring2 = ring.clone();
+    let _fut = eng.spawn("", async move {
+        let status = async {
+            wheel.timeout(1).await.unwrap();
+            Some(0)
+        };
+        let output = collect_command_output(&ring2, Rc::new(read), status);
+        res2.set(Some(output.await));
+        ring2.stop();
+    });
+    ring.run().unwrap();
+    drop(write);
+    res.take().unwrap()
+}
+
+#[test]
+fn completes_at_eof() {
+    assert_eq!(collect(false), (Some(0), b"hello".to_vec()));
+}
+
+#[test]
+fn completes_when_process_exits() {
+    assert_eq!(collect(true), (Some(0), b"hello".to_vec()));
+}
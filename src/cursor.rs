@@ -111,6 +111,8 @@ pub struct ServerCursors {
     pub all_scroll: ServerCursorTemplate,
     pub zoom_in: ServerCursorTemplate,
     pub zoom_out: ServerCursorTemplate,
+    pub dnd_ask: ServerCursorTemplate,
+    pub all_resize: ServerCursorTemplate,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, FromPrimitive)]
@@ -149,6 +151,8 @@ pub enum KnownCursor {
     AllScroll,
     ZoomIn,
     ZoomOut,
+    DndAsk,
+    AllResize,
 }
 
 impl ServerCursors {
@@ -201,6 +205,8 @@ impl ServerCursors {
             all_scroll: load(&["all-scroll", "grabbing"])?,
             zoom_in: load(&["zoom-in"])?,
             zoom_out: load(&["zoom-out"])?,
+            dnd_ask: load(&["dnd-ask", "copy"])?,
+            all_resize: load(&["all-resize", "move", "fleur"])?,
         }))
     }
 }
@@ -382,6 +388,7 @@ fn render_img(image: &InstantiatedCursorImage, renderer: &mut Renderer, x: Fixed
         renderer.base.render_texture(
             &img.tex,
             None,
+            false,
             extents.x1(),
             extents.y1(),
             None,
@@ -405,6 +412,7 @@ impl Cursor for StaticCursor {
             renderer.base.render_texture(
                 &img.tex,
                 None,
+                false,
                 0,
                 0,
                 None,
@@ -446,6 +454,7 @@ impl Cursor for AnimatedCursor {
             renderer.base.render_texture(
                 &img.tex,
                 None,
+                false,
                 0,
                 0,
                 None,
@@ -328,6 +328,8 @@ impl CursorUser {
             KnownCursor::AllScroll => &cursors.all_scroll,
             KnownCursor::ZoomIn => &cursors.zoom_in,
             KnownCursor::ZoomOut => &cursors.zoom_out,
+            KnownCursor::DndAsk => &cursors.dnd_ask,
+            KnownCursor::AllResize => &cursors.all_resize,
         };
         self.set_cursor2(Some(
             tpl.instantiate(&self.group.state, self.group.size.get()),
@@ -418,6 +420,11 @@ impl CursorUser {
                     .damage2(true, extents.move_(old_x_int, old_y_int));
                 self.group.state.damage2(true, extents.move_(x_int, y_int));
             }
+        } else if self.hardware_cursor() {
+            let (old_x, old_y) = self.pos.get();
+            let state = &self.group.state;
+            state.damage_mirrors(old_x.round_down(), old_y.round_down());
+            state.damage_mirrors(x_int, y_int);
         }
         self.pos.set((x, y));
         self.update_hardware_cursor_(false);
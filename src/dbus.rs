@@ -8,8 +8,9 @@ use {
         },
         io_uring::{IoUring, IoUringError},
         utils::{
+            asyncevent::AsyncEvent,
             buf::DynamicBuf,
-            bufio::{BufIo, BufIoError},
+            bufio::{BufIo, BufIoError, BufIoMessage},
             clonecell::CloneCell,
             copyhashmap::CopyHashMap,
             numcell::NumCell,
@@ -28,6 +29,7 @@ use {
     std::{
         borrow::{Borrow, Cow},
         cell::{Cell, RefCell},
+        collections::VecDeque,
         fmt::{Debug, Display},
         future::Future,
         marker::PhantomData,
@@ -50,6 +52,7 @@ mod outgoing;
 mod parser;
 mod property;
 mod socket;
+mod timeout;
 mod types;
 
 #[derive(Debug)]
@@ -104,6 +107,8 @@ pub enum DbusError {
     PodArrayLength,
     #[error("Peer did not send enough fds")]
     TooFewFds,
+    #[error("Peer sent too many fds")]
+    TooManyFds,
     #[error("Variant signature is not a single type")]
     TrailingVariantSignature,
     #[error("Dict signature does not contain a terminating '}}'")]
@@ -132,6 +137,24 @@ pub enum DbusError {
     InvalidSignatureType,
     #[error("The signal already has a handler")]
     AlreadyHandled,
+    #[error("The peer did not reply in time")]
+    Timeout,
+    #[error("Object path is not valid")]
+    InvalidObjectPath,
+    #[error("Signature is longer than 255 bytes")]
+    SignatureTooLong,
+    #[error("Signature nests arrays or structs too deeply")]
+    SignatureTooDeep,
+    #[error("Signature contains an empty struct")]
+    EmptyStruct,
+    #[error("Dict key is not a basic type")]
+    InvalidDictKey,
+    #[error("Message is larger than 128 MiB")]
+    MessageTooLarge,
+    #[error("Array is larger than 64 MiB")]
+    ArrayTooLarge,
+    #[error("Reply has an invalid signature: expected: {expected}, actual: {actual}")]
+    InvalidReplySignature { expected: String, actual: String },
     #[error(transparent)]
     BufIoError(#[from] BufIoError),
     #[error(transparent)]
@@ -207,22 +230,33 @@ unsafe trait ReplyHandler {
 
 pub struct DbusSocket {
     bus_name: &'static str,
-    fd: Rc<OwnedFd>,
+    addr: String,
+    fd: CloneCell<Rc<OwnedFd>>,
     ring: Rc<IoUring>,
     in_bufs: Stack<Vec<u8>>,
-    bufio: Rc<BufIo>,
+    bufio: CloneCell<Rc<BufIo>>,
     eng: Rc<AsyncEngine>,
     next_serial: NumCell<u32>,
     unique_name: CloneCell<Rc<String>>,
     reply_handlers: CopyHashMap<u32, Box<dyn ReplyHandler>>,
+    reply_deadlines: RefCell<VecDeque<(u64, u32)>>,
+    reply_deadline_added: AsyncEvent,
+    timeouts: Cell<Option<SpawnedFuture<()>>>,
     incoming: Cell<Option<SpawnedFuture<()>>>,
     outgoing_: Cell<Option<SpawnedFuture<()>>>,
     auth: Cell<Option<SpawnedFuture<()>>>,
     dead: Cell<bool>,
+    closed: Cell<bool>,
+    reconnect: Cell<Option<SpawnedFuture<()>>>,
+    reconnect_delay_ms: Cell<u64>,
+    reconnect_handler_ids: NumCell<u64>,
+    reconnect_handlers: CopyHashMap<u64, Rc<dyn Fn(&Rc<DbusSocket>)>>,
     headers: RefCell<VecStorage<(u8, Variant<'static>)>>,
     run_toplevel: Rc<RunToplevel>,
     signal_handlers: RefCell<AHashMap<(&'static str, &'static str), InterfaceSignalHandlers>>,
     objects: CopyHashMap<Cow<'static, str>, Rc<DbusObjectData>>,
+    owned_names: RefCell<Vec<(String, u32)>>,
+    queued_messages: RefCell<VecDeque<BufIoMessage>>,
 }
 
 #[derive(Hash, Eq, PartialEq)]
@@ -258,6 +292,9 @@ const TY_ARRAY: u8 = b'a';
 const TY_VARIANT: u8 = b'v';
 const TY_UNIX_FD: u8 = b'h';
 
+/// The maximum size of an array in bytes.
+const MAX_ARRAY_LEN: usize = 1 << 26;
+
 const HDR_PATH: u8 = 1;
 const HDR_INTERFACE: u8 = 2;
 const HDR_MEMBER: u8 = 3;
@@ -290,7 +327,6 @@ pub const DBUS_REQUEST_NAME_REPLY_PRIMARY_OWNER: u32 = 1;
 pub const DBUS_REQUEST_NAME_REPLY_IN_QUEUE: u32 = 2;
 #[expect(dead_code)]
 pub const DBUS_REQUEST_NAME_REPLY_EXISTS: u32 = 3;
-#[expect(dead_code)]
 pub const DBUS_REQUEST_NAME_REPLY_ALREADY_OWNER: u32 = 4;
 
 pub const BUS_DEST: &str = "org.freedesktop.DBus";
@@ -332,6 +368,7 @@ impl DbusHolder {
 impl Drop for DbusHolder {
     fn drop(&mut self) {
         if let Some(socket) = self.socket.take() {
+            socket.reconnect.take();
             socket.auth.take();
             socket.outgoing_.take();
             socket.incoming.take();
@@ -360,6 +397,7 @@ pub enum DynamicType {
     Struct(Vec<DynamicType>),
 }
 
+#[derive(Clone)]
 pub struct Parser<'a> {
     buf: &'a [u8],
     pos: usize,
@@ -529,6 +567,7 @@ impl<T: Property> Future for AsyncProperty<T> {
 
 struct SignalHandlerData<T, F> {
     path: Option<String>,
+    arg0: Option<String>,
     rule: String,
     handler: F,
     _phantom: PhantomData<T>,
@@ -539,6 +578,7 @@ trait SignalHandlerApi {
     fn member(&self) -> &'static str;
     fn signature(&self) -> &'static str;
     fn path(&self) -> Option<&str>;
+    fn arg0(&self) -> Option<&str>;
     fn rule(&self) -> &str;
     fn handle(&self, parser: &mut Parser) -> Result<(), DbusError>;
 }
@@ -564,6 +604,10 @@ where
         self.path.as_deref()
     }
 
+    fn arg0(&self) -> Option<&str> {
+        self.arg0.as_deref()
+    }
+
     fn rule(&self) -> &str {
         &self.rule
     }
@@ -586,9 +630,43 @@ impl Drop for SignalHandler {
     }
 }
 
+/// Keeps a callback registered with [`DbusSocket::on_reconnect`] alive.
+pub struct ReconnectHandler {
+    socket: Rc<DbusSocket>,
+    id: u64,
+}
+
+impl Drop for ReconnectHandler {
+    fn drop(&mut self) {
+        self.socket.reconnect_handlers.remove(&self.id);
+    }
+}
+
+/// Restricts the signals that are delivered to a signal handler.
+///
+/// Each field that is set is added to the match rule sent to the bus daemon.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SignalMatch<'a> {
+    pub sender: Option<&'a str>,
+    pub path: Option<&'a str>,
+    pub arg0: Option<&'a str>,
+}
+
+#[derive(Default)]
 struct InterfaceSignalHandlers {
-    unconditional: Option<Rc<dyn SignalHandlerApi>>,
-    conditional: AHashMap<String, Rc<dyn SignalHandlerApi>>,
+    handlers: Vec<Rc<dyn SignalHandlerApi>>,
+}
+
+impl InterfaceSignalHandlers {
+    fn find(&self, path: &str, arg0: Option<&str>) -> Option<&Rc<dyn SignalHandlerApi>> {
+        fn matches(cond: Option<&str>, value: Option<&str>) -> bool {
+            cond.is_none() || cond == value
+        }
+        self.handlers
+            .iter()
+            .filter(|h| matches(h.path(), Some(path)) && matches(h.arg0(), arg0))
+            .max_by_key(|h| h.path().is_some() as u8 + h.arg0().is_some() as u8)
+    }
 }
 
 struct DbusObjectData {
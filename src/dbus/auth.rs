@@ -99,11 +99,8 @@ impl Auth {
             }
             self.buf_start = 0;
             self.buf_stop = 0;
-            let res = self
-                .socket
-                .ring
-                .read(&self.socket.fd, self.buf.clone())
-                .await;
+            let fd = self.socket.fd.get();
+            let res = self.socket.ring.read(&fd, self.buf.clone()).await;
             match res {
                 Ok(n) => self.buf_stop = n,
                 Err(e) => return Err(DbusError::ReadError(e)),
@@ -114,10 +111,11 @@ impl Auth {
     async fn write_buf(&mut self, mut buf: Buf) -> Result<(), DbusError> {
         let mut start = 0;
         while start < buf.len() {
+            let fd = self.socket.fd.get();
             let res = self
                 .socket
                 .ring
-                .write(&self.socket.fd, buf.slice(start..), None)
+                .write(&fd, buf.slice(start..), None)
                 .await;
             match res {
                 Ok(n) => start += n,
@@ -1,7 +1,8 @@
 use {
     super::{
-        TY_ARRAY, TY_BOOLEAN, TY_BYTE, TY_DOUBLE, TY_INT16, TY_INT32, TY_INT64, TY_OBJECT_PATH,
-        TY_SIGNATURE, TY_STRING, TY_UINT16, TY_UINT32, TY_UINT64, TY_UNIX_FD, TY_VARIANT,
+        MAX_ARRAY_LEN, TY_ARRAY, TY_BOOLEAN, TY_BYTE, TY_DOUBLE, TY_INT16, TY_INT32, TY_INT64,
+        TY_OBJECT_PATH, TY_SIGNATURE, TY_STRING, TY_UINT16, TY_UINT32, TY_UINT64, TY_UNIX_FD,
+        TY_VARIANT,
     },
     crate::{
         dbus::{types::Variant, DbusError, DynamicType, Parser},
@@ -149,8 +150,11 @@ impl DynamicType {
             DynamicType::Fd => Variant::Fd(parser.read_fd()?),
             DynamicType::Array(el) => {
                 let len: u32 = parser.read_pod()?;
-                parser.align_to(el.alignment())?;
                 let len = len as usize;
+                if len > MAX_ARRAY_LEN {
+                    return Err(DbusError::ArrayTooLarge);
+                }
+                parser.align_to(el.alignment())?;
                 if parser.buf.len() - parser.pos < len {
                     return Err(DbusError::UnexpectedEof);
                 }
@@ -1,15 +1,24 @@
+#[cfg(test)]
+mod tests;
+
 use {
     crate::{
         async_engine::AsyncEngine,
-        dbus::{auth::handle_auth, DbusError, DbusHolder, DbusSocket},
+        dbus::{auth::handle_auth, timeout::handle_timeouts, DbusError, DbusHolder, DbusSocket},
         io_uring::IoUring,
-        utils::{bufio::BufIo, errorfmt::ErrorFmt, numcell::NumCell, run_toplevel::RunToplevel},
+        utils::{
+            bufio::BufIo, clonecell::CloneCell, errorfmt::ErrorFmt, numcell::NumCell,
+            run_toplevel::RunToplevel,
+        },
         wire_dbus::org,
     },
     std::{cell::Cell, rc::Rc},
-    uapi::c,
+    uapi::{c, OwnedFd},
 };
 
+const RECONNECT_MIN_DELAY_MS: u64 = 100;
+const RECONNECT_MAX_DELAY_MS: u64 = 60_000;
+
 impl DbusHolder {
     pub(super) async fn get(
         self: &Rc<Self>,
@@ -19,11 +28,7 @@ impl DbusHolder {
         name: &'static str,
     ) -> Result<Rc<DbusSocket>, DbusError> {
         if let Some(c) = self.socket.get() {
-            if c.dead.get() {
-                self.socket.take();
-            } else {
-                return Ok(c);
-            }
+            return Ok(c);
         }
         let socket = connect(eng, ring, addr, name, &self.run_toplevel).await?;
         self.socket.set(Some(socket.clone()));
@@ -31,13 +36,7 @@ impl DbusHolder {
     }
 }
 
-async fn connect(
-    eng: &Rc<AsyncEngine>,
-    ring: &Rc<IoUring>,
-    addr: &str,
-    name: &'static str,
-    run_toplevel: &Rc<RunToplevel>,
-) -> Result<Rc<DbusSocket>, DbusError> {
+async fn connect_fd(ring: &Rc<IoUring>, addr: &str) -> Result<Rc<OwnedFd>, DbusError> {
     let fd = match uapi::socket(c::AF_UNIX, c::SOCK_STREAM | c::SOCK_CLOEXEC, 0) {
         Ok(s) => Rc::new(s),
         Err(e) => return Err(DbusError::Socket(e.into())),
@@ -49,42 +48,107 @@ async fn connect(
     if let Err(e) = ring.connect(&fd, &sadr).await {
         return Err(DbusError::Connect(e));
     }
+    Ok(fd)
+}
+
+async fn connect(
+    eng: &Rc<AsyncEngine>,
+    ring: &Rc<IoUring>,
+    addr: &str,
+    name: &'static str,
+    run_toplevel: &Rc<RunToplevel>,
+) -> Result<Rc<DbusSocket>, DbusError> {
+    let fd = connect_fd(ring, addr).await?;
     let socket = Rc::new(DbusSocket {
         bus_name: name,
-        fd: fd.clone(),
+        addr: addr.to_string(),
+        fd: CloneCell::new(fd.clone()),
         ring: ring.clone(),
         in_bufs: Default::default(),
-        bufio: Rc::new(BufIo::new(&fd, ring)),
+        bufio: CloneCell::new(Rc::new(BufIo::new(&fd, ring))),
         eng: eng.clone(),
         next_serial: NumCell::new(1),
         unique_name: Default::default(),
         reply_handlers: Default::default(),
+        reply_deadlines: Default::default(),
+        reply_deadline_added: Default::default(),
+        timeouts: Default::default(),
         incoming: Default::default(),
         outgoing_: Default::default(),
         auth: Default::default(),
         dead: Cell::new(false),
+        closed: Cell::new(false),
+        reconnect: Default::default(),
+        reconnect_delay_ms: Cell::new(0),
+        reconnect_handler_ids: Default::default(),
+        reconnect_handlers: Default::default(),
         headers: Default::default(),
         run_toplevel: run_toplevel.clone(),
         signal_handlers: Default::default(),
         objects: Default::default(),
+        owned_names: Default::default(),
+        queued_messages: Default::default(),
     });
-    let skt = socket.clone();
-    socket.call(
-        "org.freedesktop.DBus",
-        "/org/freedesktop/dbus",
-        org::freedesktop::dbus::Hello,
-        move |res| match res {
-            Ok(name) => {
-                log::info!("{}: Acquired unique name {}", skt.bus_name, name.name);
-                let _ = skt.unique_name.set(Rc::new(name.name.to_string()));
-            }
-            Err(e) => {
-                log::error!("{}: Hello call failed: {}", skt.bus_name, ErrorFmt(e));
-                skt.kill();
-            }
-        },
-    );
-    let future = eng.spawn("dbus auth", handle_auth(socket.clone()));
-    socket.auth.set(Some(future));
+    socket.start();
     Ok(socket)
 }
+
+pub(super) async fn reconnect(socket: Rc<DbusSocket>) {
+    let fd = loop {
+        let delay = socket.reconnect_delay_ms.get();
+        let next_delay = (delay * 2).clamp(RECONNECT_MIN_DELAY_MS, RECONNECT_MAX_DELAY_MS);
+        socket.reconnect_delay_ms.set(next_delay);
+        if delay > 0 {
+            let timeout = socket.eng.now().nsec() + delay * 1_000_000;
+            if let Err(e) = socket.ring.timeout(timeout).await {
+                log::error!(
+                    "{}: Could not wait for the reconnect timeout: {}",
+                    socket.bus_name,
+                    ErrorFmt(e)
+                );
+                return;
+            }
+        }
+        match connect_fd(&socket.ring, &socket.addr).await {
+            Ok(fd) => break fd,
+            Err(e) => log::warn!("{}: Could not reconnect: {}", socket.bus_name, ErrorFmt(e)),
+        }
+    };
+    log::info!("{}: Reconnected", socket.bus_name);
+    let bufio = Rc::new(BufIo::new(&fd, &socket.ring));
+    socket.bufio.set(bufio);
+    socket.fd.set(fd);
+    socket.dead.set(false);
+    socket.start();
+    socket.send_queued();
+    socket.restore();
+    socket.reconnect.take();
+}
+
+impl DbusSocket {
+    fn start(self: &Rc<Self>) {
+        let skt = self.clone();
+        self.call(
+            "org.freedesktop.DBus",
+            "/org/freedesktop/dbus",
+            org::freedesktop::dbus::Hello,
+            move |res| match res {
+                Ok(name) => {
+                    log::info!("{}: Acquired unique name {}", skt.bus_name, name.name);
+                    let _ = skt.unique_name.set(Rc::new(name.name.to_string()));
+                    skt.reconnect_delay_ms.set(0);
+                }
+                Err(e) => {
+                    log::error!("{}: Hello call failed: {}", skt.bus_name, ErrorFmt(e));
+                    skt.kill();
+                }
+            },
+        );
+        let future = self.eng.spawn("dbus auth", handle_auth(self.clone()));
+        self.auth.set(Some(future));
+        let future = self
+            .eng
+            .spawn("dbus timeouts", handle_timeouts(self.clone()));
+        self.timeouts.set(Some(future));
+    }
+}
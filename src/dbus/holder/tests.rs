@@ -0,0 +1,116 @@
+use {
+    crate::{
+        async_engine::AsyncEngine,
+        dbus::{DbusError, DbusHolder, BUS_DEST, BUS_PATH},
+        io_uring::IoUring,
+        utils::run_toplevel::RunToplevel,
+        wire_dbus::org,
+    },
+    std::{
+        cell::Cell,
+        env, fs,
+        io::{Read, Write},
+        os::unix::net::{UnixListener, UnixStream},
+        process,
+        rc::Rc,
+        sync::mpsc::{self, TryRecvError},
+        thread,
+        time::Duration,
+    },
+};
+
+/// Accepts a connection and performs the server side of the authentication.
+fn accept(listener: &UnixListener) -> (UnixStream, Vec<u8>) {
+    let (mut stream, _) = listener.accept().unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    let mut buf = vec![];
+    read_until(&mut stream, &mut buf, b"\r\n");
+    stream.write_all(b"OK 0123456789abcdef\r\n").unwrap();
+    read_until(&mut stream, &mut buf, b"NEGOTIATE_UNIX_FD\r\n");
+    stream.write_all(b"AGREE_UNIX_FD\r\n").unwrap();
+    read_until(&mut stream, &mut buf, b"BEGIN\r\n");
+    (stream, buf)
+}
+
+fn read_until(stream: &mut UnixStream, buf: &mut Vec<u8>, needle: &[u8]) {
+    while find(buf, needle).is_none() {
+        let mut chunk = [0; 1024];
+        let n = stream.read(&mut chunk).unwrap();
+        assert!(n > 0, "unexpected end of stream");
+        buf.extend_from_slice(&chunk[..n]);
+    }
+}
+
+fn find(buf: &[u8], needle: &[u8]) -> Option<usize> {
+    buf.windows(needle.len()).position(|w| w == needle)
+}
+
+/// Waits for a message from the fake bus without blocking the event loop.
+async fn recv<T>(eng: &AsyncEngine, ring: &IoUring, rx: &mpsc::Receiver<T>) -> Option<T> {
+    loop {
+        match rx.try_recv() {
+            Ok(t) => return Some(t),
+            Err(TryRecvError::Disconnected) => return None,
+            Err(TryRecvError::Empty) => {}
+        }
+        let timeout = eng.now().nsec() + 1_000_000;
+        ring.timeout(timeout).await.ok()?;
+    }
+}
+
+#[test]
+fn reconnect() {
+    let path = env::temp_dir().join(format!("jay-dbus-reconnect-{}", process::id()));
+    let _ = fs::remove_file(&path);
+    let listener = UnixListener::bind(&path).unwrap();
+    let (tx, rx) = mpsc::channel();
+    let bus = thread::spawn(move || {
+        for needle in [&b"Hello"[..], b"test rule"] {
+            let (mut stream, mut buf) = accept(&listener);
+            read_until(&mut stream, &mut buf, needle);
+            tx.send(buf).unwrap();
+        }
+    });
+
+    let eng = AsyncEngine::new();
+    let ring = IoUring::new(&eng, 32).unwrap();
+    let (_run_toplevel_future, run_toplevel) = RunToplevel::install(&eng);
+    let holder = Rc::new(DbusHolder::new(&run_toplevel));
+    let killed = Rc::new(Cell::new(false));
+    let reconnected = Rc::new(Cell::new(false));
+    let second = Rc::new(Cell::new(None));
+    let (eng2, ring2) = (eng.clone(), ring.clone());
+    let (killed2, reconnected2, second2) = (killed.clone(), reconnected.clone(), second.clone());
+    let addr = path.to_str().unwrap().to_string();
+    let _fut = eng.spawn("", async move {
+        let socket = holder.get(&eng2, &ring2, &addr, "Test bus").await;
+        let socket = socket.unwrap();
+        let _handler = socket.on_reconnect(move |_| reconnected2.set(true));
+        recv(&eng2, &ring2, &rx).await;
+        socket.kill();
+        // Calls that expect a reply fail immediately. Calls without a reply are sent
+        // after the reconnect.
+        let res = socket
+            .call_async(BUS_DEST, BUS_PATH, org::freedesktop::dbus::Hello)
+            .await;
+        killed2.set(matches!(res, Err(DbusError::Killed)));
+        let msg = org::freedesktop::dbus::AddMatch {
+            rule: "test rule".into(),
+        };
+        socket.call_noreply(BUS_DEST, BUS_PATH, msg);
+        second2.set(recv(&eng2, &ring2, &rx).await);
+        ring2.stop();
+    });
+    ring.run().unwrap();
+    let _ = fs::remove_file(&path);
+    bus.join().unwrap();
+    assert!(killed.get());
+    assert!(reconnected.get());
+    // The Hello call must be the first message on the new connection.
+    let buf = second.take().unwrap();
+    let hello = find(&buf, b"Hello").unwrap();
+    let rule = find(&buf, b"test rule").unwrap();
+    assert!(hello < rule);
+}
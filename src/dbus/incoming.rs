@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod tests;
+
 use {
     super::{
         HDR_DESTINATION, HDR_ERROR_NAME, HDR_INTERFACE, HDR_MEMBER, HDR_PATH, HDR_REPLY_SERIAL,
@@ -17,12 +20,52 @@ use {
         },
         wire_dbus::org::freedesktop::dbus::properties::{Get, GetAll},
     },
-    std::{cell::UnsafeCell, ops::Deref, rc::Rc},
+    std::{cell::UnsafeCell, collections::VecDeque, ops::Deref, rc::Rc},
+    uapi::OwnedFd,
 };
 
+/// The maximum size of a message including its headers.
+const MAX_MESSAGE_SIZE: usize = 1 << 27;
+const FIXED_HEADER_SIZE: usize = 16;
+
+fn message_remaining(dyn_header_len: usize, body_len: u32) -> Result<usize, DbusError> {
+    let remaining = dyn_header_len + body_len as usize;
+    if FIXED_HEADER_SIZE + remaining > MAX_MESSAGE_SIZE {
+        return Err(DbusError::MessageTooLarge);
+    }
+    Ok(remaining)
+}
+
+/// The maximum number of fds attached to a single message.
+///
+/// This is the number of fds the kernel accepts in a single `sendmsg` call (`SCM_MAX_FD`).
+const MAX_UNIX_FDS: usize = 253;
+
+/// Removes the fds announced in the `UNIX_FDS` header from the queue of received fds.
+///
+/// Fds that remain in the queue can only belong to the next message. If there are more
+/// of them than a single message can carry, the peer sent fds that no message claims.
+fn take_fds(
+    queue: &mut VecDeque<Rc<OwnedFd>>,
+    unix_fds: Option<u32>,
+) -> Result<Vec<Rc<OwnedFd>>, DbusError> {
+    let unix_fds = unix_fds.unwrap_or(0) as usize;
+    if unix_fds > MAX_UNIX_FDS {
+        return Err(DbusError::TooManyFds);
+    }
+    if queue.len() < unix_fds {
+        return Err(DbusError::TooFewFds);
+    }
+    let fds = queue.drain(..unix_fds).collect();
+    if queue.len() > MAX_UNIX_FDS {
+        return Err(DbusError::TooManyFds);
+    }
+    Ok(fds)
+}
+
 pub async fn handle_incoming(socket: Rc<DbusSocket>) {
     let mut incoming = Incoming {
-        incoming: socket.bufio.incoming(),
+        incoming: socket.bufio.get().incoming(),
         socket,
     };
     incoming.run().await;
@@ -55,7 +98,6 @@ impl Incoming {
         let msg_buf_data = UnsafeCell::new(self.socket.in_bufs.pop().unwrap_or_default());
         let msg_buf = unsafe { msg_buf_data.get().deref_mut() };
         msg_buf.clear();
-        const FIXED_HEADER_SIZE: usize = 16;
         self.incoming
             .fill_msg_buf(FIXED_HEADER_SIZE, msg_buf)
             .await?;
@@ -72,24 +114,18 @@ impl Incoming {
         let mut fields2 = [0u32; 3];
         uapi::pod_write(&msg_buf[4..], &mut fields2[..]).unwrap();
         let [body_len, serial, headers_len] = fields2;
-        let dyn_header_len = headers_len + (headers_len.wrapping_neg() & 7);
-        let remaining = dyn_header_len + body_len;
-        self.incoming
-            .fill_msg_buf(remaining as usize, msg_buf)
-            .await?;
+        let dyn_header_len = headers_len as usize + (headers_len.wrapping_neg() & 7) as usize;
+        let remaining = message_remaining(dyn_header_len, body_len)?;
+        self.incoming.fill_msg_buf(remaining, msg_buf).await?;
         #[expect(dropping_references)]
         drop(msg_buf);
         let msg_buf = unsafe { msg_buf_data.get().deref().deref() };
         let headers = &msg_buf[FIXED_HEADER_SIZE..FIXED_HEADER_SIZE + headers_len as usize];
         let headers = self.parse_headers(headers)?;
-        let unix_fds = headers.unix_fds.unwrap_or(0) as usize;
-        if self.incoming.fds.len() < unix_fds {
-            return Err(DbusError::TooFewFds);
-        }
-        let fds: Vec<_> = self.incoming.fds.drain(..unix_fds).collect();
+        let fds = take_fds(&mut self.incoming.fds, headers.unix_fds)?;
         let mut parser = Parser {
             buf: msg_buf,
-            pos: FIXED_HEADER_SIZE + dyn_header_len as usize,
+            pos: FIXED_HEADER_SIZE + dyn_header_len,
             fds: &fds,
         };
         match msg_ty {
@@ -180,12 +216,11 @@ impl Incoming {
                     } else {
                         let sig = headers.signature.as_deref().unwrap_or("");
                         if sig != reply.signature() {
-                            log::error!(
-                                "{}: Message reply has an invalid signature: expected: {}, actual: {}",
-                                self.socket.bus_name,
-                                reply.signature(),
-                                sig,
-                            );
+                            let error = DbusError::InvalidReplySignature {
+                                expected: reply.signature().to_string(),
+                                actual: sig.to_string(),
+                            };
+                            reply.handle_error(&self.socket, error);
                         } else {
                             let buf = unsafe { std::mem::take(msg_buf_data.get().deref_mut()) };
                             if let Err(e) = reply.handle(&self.socket, &headers, &mut parser, buf) {
@@ -205,14 +240,14 @@ impl Incoming {
                         (Some(i), Some(m), Some(p)) => (i, m, p),
                         _ => return Err(DbusError::MissingSignalHeaders),
                     };
+                let sig = headers.signature.as_deref().unwrap_or("");
+                let arg0 = match sig.as_bytes().first() {
+                    Some(b's' | b'o') => parser.clone().read_string().ok(),
+                    _ => None,
+                };
                 let handlers = self.socket.signal_handlers.borrow_mut();
                 if let Some(handler) = handlers.get(&(interface.deref(), member.deref())) {
-                    let handler = handler
-                        .conditional
-                        .get(path.deref())
-                        .or(handler.unconditional.as_ref());
-                    if let Some(handler) = handler {
-                        let sig = headers.signature.as_deref().unwrap_or("");
+                    if let Some(handler) = handler.find(path, arg0.as_deref()) {
                         if sig != handler.signature() {
                             log::error!(
                                 "{}: Signal has an invalid signature: expected: {}, actual: {}",
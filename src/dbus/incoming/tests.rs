@@ -0,0 +1,49 @@
+use {
+    crate::dbus::{
+        incoming::{message_remaining, take_fds, MAX_MESSAGE_SIZE, MAX_UNIX_FDS},
+        DbusError, Parser, MAX_ARRAY_LEN,
+    },
+    std::{collections::VecDeque, rc::Rc},
+    uapi::c,
+};
+
+#[test]
+fn oversized_message() {
+    let res = message_remaining(8, u32::MAX);
+    assert!(matches!(res, Err(DbusError::MessageTooLarge)));
+    let res = message_remaining(8, (MAX_MESSAGE_SIZE - 24) as u32);
+    assert!(matches!(res, Ok(n) if n == MAX_MESSAGE_SIZE - 16));
+}
+
+#[test]
+fn oversized_array() {
+    let len = (MAX_ARRAY_LEN + 1) as u32;
+    let buf = uapi::as_bytes(&len);
+    let mut parser = Parser::new(buf, &[]);
+    let res = parser.read_array::<u32>();
+    assert!(matches!(res, Err(DbusError::ArrayTooLarge)));
+}
+
+#[test]
+fn unix_fds() {
+    let (read, write) = uapi::pipe2(c::O_CLOEXEC).unwrap();
+    let mut queue = VecDeque::from([Rc::new(read), Rc::new(write)]);
+    let res = take_fds(&mut queue, Some(MAX_UNIX_FDS as u32 + 1));
+    assert!(matches!(res, Err(DbusError::TooManyFds)));
+    let res = take_fds(&mut queue, Some(3));
+    assert!(matches!(res, Err(DbusError::TooFewFds)));
+    let res = take_fds(&mut queue, None);
+    assert!(matches!(res, Ok(fds) if fds.is_empty()));
+    let res = take_fds(&mut queue, Some(1));
+    assert!(matches!(res, Ok(fds) if fds.len() == 1));
+    assert_eq!(queue.len(), 1);
+}
+
+#[test]
+fn unclaimed_fds() {
+    let (read, _write) = uapi::pipe2(c::O_CLOEXEC).unwrap();
+    let read = Rc::new(read);
+    let mut queue: VecDeque<_> = (0..MAX_UNIX_FDS + 1).map(|_| read.clone()).collect();
+    let res = take_fds(&mut queue, Some(0));
+    assert!(matches!(res, Err(DbusError::TooManyFds)));
+}
@@ -4,7 +4,7 @@ use {
 };
 
 pub async fn handle_outgoing(socket: Rc<DbusSocket>) {
-    if let Err(e) = socket.bufio.clone().outgoing().await {
+    if let Err(e) = socket.bufio.get().outgoing().await {
         log::error!("{}: {}", socket.bus_name, ErrorFmt(e));
     }
     socket.kill();
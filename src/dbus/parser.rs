@@ -1,7 +1,7 @@
 use {
     crate::dbus::{
         types::{Bool, ObjectPath, Signature, Variant, FALSE, TRUE},
-        DbusError, DbusType, DynamicType, Parser,
+        DbusError, DbusType, DynamicType, Parser, MAX_ARRAY_LEN,
     },
     bstr::ByteSlice,
     std::{borrow::Cow, rc::Rc},
@@ -60,7 +60,9 @@ impl<'a> Parser<'a> {
     }
 
     pub fn read_object_path(&mut self) -> Result<ObjectPath<'a>, DbusError> {
-        self.read_string().map(ObjectPath)
+        let path = self.read_string()?;
+        ObjectPath::check(&path)?;
+        Ok(ObjectPath(path))
     }
 
     pub fn read_string(&mut self) -> Result<Cow<'a, str>, DbusError> {
@@ -72,6 +74,7 @@ impl<'a> Parser<'a> {
     pub fn read_signature(&mut self) -> Result<Signature<'a>, DbusError> {
         let len: u8 = self.read_pod()?;
         let s = self.read_string_(len as usize)?;
+        Signature::check(s)?;
         Ok(Signature(Cow::Borrowed(s)))
     }
 
@@ -90,6 +93,9 @@ impl<'a> Parser<'a> {
     pub fn read_array<T: DbusType<'a>>(&mut self) -> Result<Cow<'a, [T]>, DbusError> {
         let len: u32 = self.read_pod()?;
         let len = len as usize;
+        if len > MAX_ARRAY_LEN {
+            return Err(DbusError::ArrayTooLarge);
+        }
         self.align_to(T::ALIGNMENT)?;
         if self.buf.len() - self.pos < len {
             return Err(DbusError::UnexpectedEof);
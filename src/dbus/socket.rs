@@ -1,18 +1,24 @@
 use {
     crate::{
         dbus::{
+            holder::reconnect,
             property::Get,
-            types::{ObjectPath, Signature, Variant},
+            timeout::CALL_TIMEOUT_NSEC,
+            types::{DictEntry, ObjectPath, Signature, Variant},
             AsyncProperty, AsyncReply, AsyncReplySlot, DbusError, DbusObject, DbusObjectData,
-            DbusSocket, DbusType, ErrorMessage, Formatter, Headers, InterfaceSignalHandlers,
-            Message, MethodCall, Parser, Property, Reply, ReplyHandler, Signal, SignalHandler,
-            SignalHandlerApi, SignalHandlerData, BUS_DEST, BUS_PATH, HDR_DESTINATION,
-            HDR_ERROR_NAME, HDR_INTERFACE, HDR_MEMBER, HDR_PATH, HDR_REPLY_SERIAL, HDR_SIGNATURE,
-            HDR_UNIX_FDS, MSG_ERROR, MSG_METHOD_CALL, MSG_METHOD_RETURN, MSG_SIGNAL,
+            DbusSocket, DbusType, ErrorMessage, Formatter, Headers, Message, MethodCall, Parser,
+            Property, ReconnectHandler, Reply, ReplyHandler, Signal, SignalHandler,
+            SignalHandlerApi, SignalHandlerData, SignalMatch, BUS_DEST, BUS_PATH,
+            DBUS_REQUEST_NAME_REPLY_ALREADY_OWNER, DBUS_REQUEST_NAME_REPLY_PRIMARY_OWNER,
+            HDR_DESTINATION, HDR_ERROR_NAME, HDR_INTERFACE, HDR_MEMBER, HDR_PATH, HDR_REPLY_SERIAL,
+            HDR_SIGNATURE, HDR_UNIX_FDS, MSG_ERROR, MSG_METHOD_CALL, MSG_METHOD_RETURN, MSG_SIGNAL,
             NO_REPLY_EXPECTED,
         },
         utils::{bufio::BufIoMessage, errorfmt::ErrorFmt},
-        wire_dbus::org,
+        wire_dbus::{
+            org,
+            org::freedesktop::dbus::properties::{GetAll, PropertiesChanged},
+        },
     },
     std::{
         borrow::Cow, cell::Cell, collections::hash_map::Entry, fmt::Write, marker::PhantomData,
@@ -21,32 +27,137 @@ use {
     uapi::c,
 };
 
+const MAX_QUEUED_MESSAGES: usize = 1024;
+
 impl DbusSocket {
     pub fn clear(&self) {
+        self.closed.set(true);
+        self.reconnect.take();
+        self.reconnect_handlers.clear();
         self.auth.take();
         self.incoming.take();
         self.outgoing_.take();
+        self.timeouts.take();
         self.reply_handlers.clear();
+        self.reply_deadlines.borrow_mut().clear();
         self.signal_handlers.borrow_mut().clear();
         self.objects.clear();
+        self.queued_messages.borrow_mut().clear();
     }
 
     pub(super) fn kill(self: &Rc<Self>) {
-        self.dead.set(true);
+        if self.dead.replace(true) {
+            return;
+        }
         self.auth.take();
         self.incoming.take();
         self.outgoing_.take();
-        let _ = uapi::shutdown(self.fd.raw(), c::SHUT_RDWR);
+        self.timeouts.take();
+        self.reply_deadlines.borrow_mut().clear();
+        let _ = uapi::shutdown(self.fd.get().raw(), c::SHUT_RDWR);
         let replies = mem::take(self.reply_handlers.lock().deref_mut());
         for (_, handler) in replies {
             handler.handle_error(self, DbusError::Killed);
         }
+        if !self.closed.get() {
+            log::warn!("{}: Lost the connection. Reconnecting.", self.bus_name);
+            let future = self
+                .eng
+                .spawn("dbus reconnect", reconnect(self.clone()));
+            self.reconnect.set(Some(future));
+        }
     }
 
-    pub fn call_noreply<'a, T: MethodCall<'a>>(&self, destination: &str, path: &str, msg: T) {
-        if !self.dead.get() {
-            self.send_call(path, destination, NO_REPLY_EXPECTED, &msg);
+    /// Registers a callback that is invoked after the connection has been re-established.
+    ///
+    /// Signal handlers, objects, and names acquired with [`Self::request_name`] are
+    /// restored automatically before the callback runs.
+    ///
+    /// While the connection is down, method calls that expect a reply fail with
+    /// [`DbusError::Killed`]. Signals and calls without a reply are queued and sent
+    /// after the connection has been re-established.
+    pub fn on_reconnect<F>(self: &Rc<Self>, f: F) -> ReconnectHandler
+    where
+        F: Fn(&Rc<DbusSocket>) + 'static,
+    {
+        let id = self.reconnect_handler_ids.fetch_add(1);
+        self.reconnect_handlers.set(id, Rc::new(f));
+        ReconnectHandler {
+            socket: self.clone(),
+            id,
+        }
+    }
+
+    pub(super) fn restore(self: &Rc<Self>) {
+        let rules: Vec<_> = self
+            .signal_handlers
+            .borrow()
+            .values()
+            .flat_map(|h| &h.handlers)
+            .map(|h| h.rule().to_owned())
+            .collect();
+        for rule in rules {
+            self.add_match(&rule);
+        }
+        let names = self.owned_names.borrow().clone();
+        for (name, flags) in names {
+            let slf = self.clone();
+            let msg = org::freedesktop::dbus::RequestName {
+                name: name.clone().into(),
+                flags,
+            };
+            self.call(BUS_DEST, BUS_PATH, msg, move |res| match res {
+                Ok(r) if r.rv == DBUS_REQUEST_NAME_REPLY_PRIMARY_OWNER => {}
+                Ok(r) => log::error!(
+                    "{}: Could not re-acquire the name {}: {}",
+                    slf.bus_name,
+                    name,
+                    r.rv
+                ),
+                Err(e) => log::error!(
+                    "{}: Could not re-acquire the name {}: {}",
+                    slf.bus_name,
+                    name,
+                    ErrorFmt(e)
+                ),
+            });
+        }
+        let handlers: Vec<_> = self
+            .reconnect_handlers
+            .lock()
+            .values()
+            .cloned()
+            .collect();
+        for handler in handlers {
+            handler(self);
+        }
+    }
+
+    /// Requests a well-known name and re-requests it after a reconnect.
+    pub async fn request_name(self: &Rc<Self>, name: &str, flags: u32) -> Result<u32, DbusError> {
+        let msg = org::freedesktop::dbus::RequestName {
+            name: name.into(),
+            flags,
+        };
+        let rv = self
+            .call_async(BUS_DEST, BUS_PATH, msg)
+            .await?
+            .get()
+            .rv;
+        if matches!(
+            rv,
+            DBUS_REQUEST_NAME_REPLY_PRIMARY_OWNER | DBUS_REQUEST_NAME_REPLY_ALREADY_OWNER
+        ) {
+            let mut names = self.owned_names.borrow_mut();
+            if names.iter().all(|(n, _)| n != name) {
+                names.push((name.to_string(), flags));
+            }
         }
+        Ok(rv)
+    }
+
+    pub fn call_noreply<'a, T: MethodCall<'a>>(&self, destination: &str, path: &str, msg: T) {
+        self.send_call(path, destination, NO_REPLY_EXPECTED, &msg);
     }
 
     fn serial(&self) -> u32 {
@@ -59,16 +170,34 @@ impl DbusSocket {
         F: for<'b> FnOnce(Result<&<T::Reply as Message<'static>>::Generic<'b>, DbusError>)
             + 'static,
     {
-        if self.dead.get() {
-            self.run_toplevel
-                .schedule(move || f(Err(DbusError::Killed)));
+        if let Err(e) = self.check_call(path) {
+            self.run_toplevel.schedule(move || f(Err(e)));
             return;
         }
         let serial = self.send_call(path, destination, 0, &msg);
         self.reply_handlers
             .set(serial, Box::new(SyncReplyHandler(f, PhantomData)));
+        self.add_reply_deadline(serial);
+    }
+
+    fn check_call(&self, path: &str) -> Result<(), DbusError> {
+        if self.dead.get() {
+            return Err(DbusError::Killed);
+        }
+        ObjectPath::check(path)
+    }
+
+    fn add_reply_deadline(&self, serial: u32) {
+        let deadline = self.eng.now().nsec() + CALL_TIMEOUT_NSEC;
+        self.reply_deadlines
+            .borrow_mut()
+            .push_back((deadline, serial));
+        self.reply_deadline_added.trigger();
     }
 
+    /// Calls a method and returns a future that resolves to the decoded reply.
+    ///
+    /// File descriptors contained in the reply are owned by the returned [`Reply`].
     pub fn call_async<'a, T>(
         self: &Rc<Self>,
         destination: &str,
@@ -78,12 +207,12 @@ impl DbusSocket {
     where
         T: MethodCall<'a>,
     {
-        if self.dead.get() {
+        if let Err(e) = self.check_call(path) {
             return AsyncReply {
                 socket: self.clone(),
                 serial: self.serial(),
                 slot: Rc::new(AsyncReplySlot {
-                    data: Cell::new(Some(Err(DbusError::Killed))),
+                    data: Cell::new(Some(Err(e))),
                     waker: Cell::new(None),
                 }),
             };
@@ -95,6 +224,7 @@ impl DbusSocket {
         });
         self.reply_handlers
             .set(serial, Box::new(AsyncReplyHandler(slot.clone())));
+        self.add_reply_deadline(serial);
         AsyncReply {
             socket: self.clone(),
             serial,
@@ -134,11 +264,49 @@ impl DbusSocket {
         }
     }
 
+    pub fn get_all<F>(&self, destination: &str, path: &str, interface: &str, f: F)
+    where
+        F: for<'a, 'b> FnOnce(Result<&'a [DictEntry<Cow<'b, str>, Variant<'b>>], DbusError>)
+            + 'static,
+    {
+        let msg = GetAll {
+            interface_name: interface.into(),
+        };
+        self.call(destination, path, msg, move |res| {
+            f(res.map(|v| &*v.props));
+        });
+    }
+
+    /// Registers a handler for `PropertiesChanged` signals of an interface.
+    ///
+    /// The handler is invoked with the changed properties and the names of the invalidated
+    /// properties.
+    pub fn handle_properties_changed<F>(
+        self: &Rc<Self>,
+        sender: &str,
+        path: &str,
+        interface: &str,
+        handler: F,
+    ) -> Result<SignalHandler, DbusError>
+    where
+        F: for<'a> Fn(&[DictEntry<Cow<'a, str>, Variant<'a>>], &[Cow<'a, str>]) + 'static,
+    {
+        let matches = SignalMatch {
+            sender: Some(sender),
+            path: Some(path),
+            arg0: Some(interface),
+        };
+        self.handle_signal::<PropertiesChanged, _>(matches, move |msg| {
+            handler(&msg.changed_properties, &msg.invalidated_properties);
+        })
+    }
+
     pub fn add_object(
         self: &Rc<Self>,
         object: impl Into<Cow<'static, str>>,
     ) -> Result<DbusObject, DbusError> {
         let object = object.into();
+        ObjectPath::check(&object)?;
         let data = Rc::new(DbusObjectData {
             path: object.clone(),
             methods: Default::default(),
@@ -158,27 +326,34 @@ impl DbusSocket {
 
     pub fn handle_signal<T, F>(
         self: &Rc<Self>,
-        sender: Option<&str>,
-        path: Option<&str>,
+        matches: SignalMatch<'_>,
         handler: F,
     ) -> Result<SignalHandler, DbusError>
     where
         T: Signal<'static>,
         F: for<'a> Fn(T::Generic<'a>) + 'static,
     {
+        if let Some(path) = matches.path {
+            ObjectPath::check(path)?;
+        }
         let mut rule = format!(
             "type='signal',interface='{}',member='{}'",
             T::INTERFACE,
             T::MEMBER
         );
-        if let Some(sender) = sender {
-            let _ = write!(rule, ",sender='{}'", sender);
-        }
-        if let Some(path) = path {
-            let _ = write!(rule, ",path='{}'", path);
-        }
+        let mut add = |key: &str, value: Option<&str>| {
+            if let Some(value) = value {
+                // Single quotes cannot be escaped inside of quoted values.
+                let value = value.replace('\'', r"'\''");
+                let _ = write!(rule, ",{}='{}'", key, value);
+            }
+        };
+        add("sender", matches.sender);
+        add("path", matches.path);
+        add("arg0", matches.arg0);
         let shd: SignalHandlerData<T, _> = SignalHandlerData {
-            path: path.map(|s| s.to_owned()),
+            path: matches.path.map(|s| s.to_owned()),
+            arg0: matches.arg0.map(|s| s.to_owned()),
             rule,
             handler,
             _phantom: Default::default(),
@@ -193,26 +368,25 @@ impl DbusSocket {
         let mut sh = self.signal_handlers.borrow_mut();
         let entry = sh
             .entry((handler.interface(), handler.member()))
-            .or_insert_with(|| InterfaceSignalHandlers {
-                unconditional: Default::default(),
-                conditional: Default::default(),
-            });
-        match handler.path() {
-            Some(p) => match entry.conditional.entry(p.to_owned()) {
-                Entry::Occupied(_) => return Err(DbusError::AlreadyHandled),
-                Entry::Vacant(v) => {
-                    v.insert(handler.clone());
-                }
-            },
-            _ if entry.unconditional.is_some() => return Err(DbusError::AlreadyHandled),
-            _ => entry.unconditional = Some(handler.clone()),
+            .or_default();
+        let same_condition =
+            |h: &Rc<dyn SignalHandlerApi>| h.path() == handler.path() && h.arg0() == handler.arg0();
+        if entry.handlers.iter().any(same_condition) {
+            return Err(DbusError::AlreadyHandled);
         }
+        entry.handlers.push(handler.clone());
+        self.add_match(handler.rule());
+        Ok(SignalHandler {
+            socket: self.clone(),
+            data: handler,
+        })
+    }
+
+    fn add_match(self: &Rc<Self>, rule: &str) {
         self.call(
             BUS_DEST,
             BUS_PATH,
-            org::freedesktop::dbus::AddMatch {
-                rule: handler.rule().into(),
-            },
+            org::freedesktop::dbus::AddMatch { rule: rule.into() },
             {
                 let slf = self.clone();
                 move |res| {
@@ -226,10 +400,6 @@ impl DbusSocket {
                 }
             },
         );
-        Ok(SignalHandler {
-            socket: self.clone(),
-            data: handler,
-        })
     }
 
     pub(super) fn remove_signal_handler(self: &Rc<Self>, handler: &dyn SignalHandlerApi) {
@@ -238,13 +408,11 @@ impl DbusSocket {
             Entry::Occupied(o) => o,
             Entry::Vacant(_) => return,
         };
-        match handler.path() {
-            Some(p) => {
-                entry.get_mut().conditional.remove(p);
-            }
-            _ => entry.get_mut().unconditional = None,
-        }
-        if entry.get().unconditional.is_none() && entry.get().conditional.is_empty() {
+        entry
+            .get_mut()
+            .handlers
+            .retain(|h| h.path() != handler.path() || h.arg0() != handler.arg0());
+        if entry.get().handlers.is_empty() {
             entry.remove();
         }
         self.call(
@@ -270,13 +438,13 @@ impl DbusSocket {
 
     pub fn emit_signal<'a, T: Signal<'a>>(&self, path: &str, msg: &T) -> u32 {
         let (msg, serial) = self.format_signal(path, msg);
-        self.bufio.send(msg);
+        self.send(msg);
         serial
     }
 
     pub fn send_error(&self, destination: &str, reply_serial: u32, msg: &str) -> u32 {
         let (msg, serial) = self.format_error(destination, reply_serial, msg);
-        self.bufio.send(msg);
+        self.bufio.get().send(msg);
         serial
     }
 
@@ -287,7 +455,7 @@ impl DbusSocket {
         msg: &T,
     ) -> u32 {
         let (msg, serial) = self.format_reply(destination, reply_serial, msg);
-        self.bufio.send(msg);
+        self.bufio.get().send(msg);
         serial
     }
 
@@ -299,10 +467,37 @@ impl DbusSocket {
         msg: &T,
     ) -> u32 {
         let (msg, serial) = self.format_call(path, destination, flags, msg);
-        self.bufio.send(msg);
+        self.send(msg);
         serial
     }
 
+    fn send(&self, msg: BufIoMessage) {
+        if !self.dead.get() {
+            self.bufio.get().send(msg);
+            return;
+        }
+        if self.closed.get() {
+            return;
+        }
+        let mut queued = self.queued_messages.borrow_mut();
+        if queued.len() >= MAX_QUEUED_MESSAGES {
+            log::warn!(
+                "{}: Too many messages queued while disconnected. Dropping the oldest one.",
+                self.bus_name
+            );
+            queued.pop_front();
+        }
+        queued.push_back(msg);
+    }
+
+    pub(super) fn send_queued(&self) {
+        let queued = self.queued_messages.take();
+        let bufio = self.bufio.get();
+        for msg in queued {
+            bufio.send(msg);
+        }
+    }
+
     fn format_signal<'a, T: Signal<'a>>(&self, path: &str, msg: &T) -> (BufIoMessage, u32) {
         self.format_generic(MSG_SIGNAL, Some(path), None, None, 0, msg, None, true, true)
     }
@@ -376,7 +571,7 @@ impl DbusSocket {
         let num_fds = msg.num_fds();
         let mut fds = Vec::with_capacity(num_fds as _);
         let serial = self.serial();
-        let mut buf = self.bufio.buf();
+        let mut buf = self.bufio.get().buf();
         let mut fmt = Formatter::new(&mut fds, &mut buf);
         let interface = match include_interface {
             true => Some(T::INTERFACE),
@@ -493,7 +688,14 @@ where
         parser: &mut Parser<'a>,
         buf: Vec<u8>,
     ) -> Result<(), DbusError> {
-        let msg = <T::Generic<'a> as Message>::unmarshal(parser)?;
+        let msg = match <T::Generic<'a> as Message>::unmarshal(parser) {
+            Ok(msg) => msg,
+            Err(e) => {
+                let e = Rc::new(e);
+                (self.0)(Err(DbusError::DbusError(e.clone())));
+                return Err(DbusError::DbusError(e));
+            }
+        };
         (self.0)(Ok(&msg));
         socket.in_bufs.push(buf);
         Ok(())
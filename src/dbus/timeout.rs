@@ -0,0 +1,34 @@
+use {
+    crate::{
+        dbus::{DbusError, DbusSocket},
+        utils::errorfmt::ErrorFmt,
+    },
+    std::rc::Rc,
+};
+
+/// The time after which a method call without a reply fails.
+///
+/// This is the default timeout used by libdbus.
+pub const CALL_TIMEOUT_NSEC: u64 = 25_000_000_000;
+
+pub async fn handle_timeouts(socket: Rc<DbusSocket>) {
+    loop {
+        let next = socket.reply_deadlines.borrow().front().copied();
+        let Some((deadline, serial)) = next else {
+            socket.reply_deadline_added.triggered().await;
+            continue;
+        };
+        if let Err(e) = socket.ring.timeout(deadline).await {
+            log::error!(
+                "{}: Could not wait for a reply timeout: {}",
+                socket.bus_name,
+                ErrorFmt(e)
+            );
+            return;
+        }
+        socket.reply_deadlines.borrow_mut().pop_front();
+        if let Some(handler) = socket.reply_handlers.remove(&serial) {
+            handler.handle_error(&socket, DbusError::Timeout);
+        }
+    }
+}
@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod tests;
+
 use {
     crate::{
         dbus::{
@@ -216,6 +219,85 @@ impl<'a> Deref for Signature<'a> {
     }
 }
 
+const MAX_SIGNATURE_LEN: usize = 255;
+const MAX_ARRAY_DEPTH: usize = 32;
+const MAX_STRUCT_DEPTH: usize = 32;
+
+impl Signature<'_> {
+    /// Checks that `sig` is a valid signature.
+    ///
+    /// A valid signature is a sequence of complete types that is at most 255 bytes long
+    /// and does not nest arrays or structs more than 32 levels deep.
+    pub fn check(sig: &str) -> Result<(), DbusError> {
+        if sig.len() > MAX_SIGNATURE_LEN {
+            return Err(DbusError::SignatureTooLong);
+        }
+        let mut s = sig.as_bytes();
+        while !s.is_empty() {
+            check_complete_type(&mut s, 0, 0)?;
+        }
+        Ok(())
+    }
+}
+
+fn check_complete_type(s: &mut &[u8], arrays: usize, structs: usize) -> Result<(), DbusError> {
+    let Some((&first, rem)) = s.split_first() else {
+        return Err(DbusError::EmptySignature);
+    };
+    *s = rem;
+    match first {
+        TY_BYTE | TY_BOOLEAN | TY_INT16 | TY_UINT16 | TY_INT32 | TY_UINT32 | TY_INT64
+        | TY_UINT64 | TY_DOUBLE | TY_STRING | TY_OBJECT_PATH | TY_SIGNATURE | TY_VARIANT
+        | TY_UNIX_FD => Ok(()),
+        TY_ARRAY => {
+            if arrays == MAX_ARRAY_DEPTH {
+                return Err(DbusError::SignatureTooDeep);
+            }
+            if s.first() != Some(&b'{') {
+                return check_complete_type(s, arrays + 1, structs);
+            }
+            if structs == MAX_STRUCT_DEPTH {
+                return Err(DbusError::SignatureTooDeep);
+            }
+            *s = &s[1..];
+            match s.first() {
+                Some(&(TY_ARRAY | TY_VARIANT | b'(' | b'{')) => {
+                    return Err(DbusError::InvalidDictKey);
+                }
+                _ => check_complete_type(s, arrays + 1, structs + 1)?,
+            }
+            check_complete_type(s, arrays + 1, structs + 1)?;
+            match s.split_first() {
+                Some((b'}', rem)) => {
+                    *s = rem;
+                    Ok(())
+                }
+                Some(_) => Err(DbusError::DictTrailing),
+                None => Err(DbusError::UnterminatedDict),
+            }
+        }
+        b'(' => {
+            if structs == MAX_STRUCT_DEPTH {
+                return Err(DbusError::SignatureTooDeep);
+            }
+            if s.first() == Some(&b')') {
+                return Err(DbusError::EmptyStruct);
+            }
+            loop {
+                match s.split_first() {
+                    Some((b')', rem)) => {
+                        *s = rem;
+                        return Ok(());
+                    }
+                    Some(_) => check_complete_type(s, arrays, structs + 1)?,
+                    None => return Err(DbusError::UnterminatedStruct),
+                }
+            }
+        }
+        _ => Err(DbusError::InvalidSignatureType),
+    }
+}
+
 unsafe impl<'a> DbusType<'a> for Signature<'a> {
     const ALIGNMENT: usize = 1;
     const IS_POD: bool = false;
@@ -243,6 +325,28 @@ impl<'a> Deref for ObjectPath<'a> {
     }
 }
 
+impl ObjectPath<'_> {
+    /// Checks that `path` is a valid object path.
+    ///
+    /// A valid object path is either `/` or a sequence of `/`-prefixed, non-empty
+    /// elements consisting of ASCII alphanumerics and `_`.
+    pub fn check(path: &str) -> Result<(), DbusError> {
+        if path == "/" {
+            return Ok(());
+        }
+        let Some(path) = path.strip_prefix('/') else {
+            return Err(DbusError::InvalidObjectPath);
+        };
+        let valid = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+        for element in path.split('/') {
+            if element.is_empty() || !element.bytes().all(valid) {
+                return Err(DbusError::InvalidObjectPath);
+            }
+        }
+        Ok(())
+    }
+}
+
 unsafe impl<'a> DbusType<'a> for ObjectPath<'a> {
     const ALIGNMENT: usize = 4;
     const IS_POD: bool = false;
@@ -0,0 +1,72 @@
+use crate::dbus::{
+    types::{ObjectPath, Signature},
+    DbusError,
+};
+
+#[test]
+fn valid_object_paths() {
+    for path in ["/", "/org", "/org/freedesktop/DBus", "/a_b/c1/_"] {
+        assert!(ObjectPath::check(path).is_ok(), "{path}");
+    }
+}
+
+#[test]
+fn invalid_object_paths() {
+    for path in [
+        "",
+        "org",
+        "/org/",
+        "//org",
+        "/org//DBus",
+        "/org.freedesktop",
+        "/ä",
+    ] {
+        assert!(
+            matches!(ObjectPath::check(path), Err(DbusError::InvalidObjectPath)),
+            "{path}"
+        );
+    }
+}
+
+#[test]
+fn valid_signatures() {
+    for sig in [
+        "",
+        "s",
+        "a{sv}",
+        "(ia{s(ub)})",
+        "aai",
+        "vhgo",
+        "a{oa{sa{sv}}}",
+    ] {
+        assert!(Signature::check(sig).is_ok(), "{sig}");
+    }
+}
+
+#[test]
+fn invalid_signatures() {
+    let deep_array = "a".repeat(33) + "i";
+    let deep_struct = "(".repeat(33) + "i" + &")".repeat(33);
+    let long = "i".repeat(256);
+    let cases = [
+        ("a", DbusError::EmptySignature),
+        ("z", DbusError::InvalidSignatureType),
+        ("()", DbusError::EmptyStruct),
+        ("(i", DbusError::UnterminatedStruct),
+        ("a{s", DbusError::EmptySignature),
+        ("a{si", DbusError::UnterminatedDict),
+        ("a{sii}", DbusError::DictTrailing),
+        ("a{vs}", DbusError::InvalidDictKey),
+        ("{sv}", DbusError::InvalidSignatureType),
+        (&deep_array, DbusError::SignatureTooDeep),
+        (&deep_struct, DbusError::SignatureTooDeep),
+        (&long, DbusError::SignatureTooLong),
+    ];
+    for (sig, expected) in cases {
+        let res = Signature::check(sig);
+        assert!(
+            matches!(&res, Err(e) if e.to_string() == expected.to_string()),
+            "{sig}: {res:?}"
+        );
+    }
+}
@@ -80,6 +80,7 @@ impl DrmFeedback {
                     indices.push(*idx);
                 }
             }
+            indices.sort_unstable();
             if indices.len() > 0 {
                 tranches.push(DrmFeedbackTranche {
                     device: devnum,
@@ -100,18 +101,21 @@ impl DrmFeedback {
 }
 
 fn create_fd_data(ctx: &dyn GfxContext) -> (Vec<u8>, AHashMap<(u32, Modifier), u16>) {
-    let mut vec = vec![];
-    let mut map = AHashMap::new();
-    let mut pos = 0;
+    let mut pairs = vec![];
     for (format, info) in &*ctx.formats() {
         for modifier in &info.read_modifiers {
-            vec.write_u32::<NativeEndian>(*format).unwrap();
-            vec.write_u32::<NativeEndian>(0).unwrap();
-            vec.write_u64::<NativeEndian>(*modifier).unwrap();
-            map.insert((*format, *modifier), pos);
-            pos += 1;
+            pairs.push((*format, *modifier));
         }
     }
+    pairs.sort_unstable();
+    let mut vec = vec![];
+    let mut map = AHashMap::new();
+    for (pos, (format, modifier)) in pairs.into_iter().enumerate() {
+        vec.write_u32::<NativeEndian>(format).unwrap();
+        vec.write_u32::<NativeEndian>(0).unwrap();
+        vec.write_u64::<NativeEndian>(modifier).unwrap();
+        map.insert((format, modifier), pos as u16);
+    }
     (vec, map)
 }
 
@@ -1,5 +1,6 @@
 use {
     crate::{
+        acceptor::accept_client,
         async_engine::SpawnedFuture,
         state::State,
         utils::{errorfmt::ErrorFmt, oserror::OsError, xrd::xrd},
@@ -140,7 +141,7 @@ impl EiAcceptor {
 
 async fn accept(fd: Rc<OwnedFd>, state: Rc<State>) {
     loop {
-        let fd = match state.ring.accept(&fd, c::SOCK_CLOEXEC).await {
+        let fd = match accept_client(&state, &fd).await {
             Ok(fd) => fd,
             Err(e) => {
                 log::error!("Could not accept a client: {}", ErrorFmt(e));
@@ -1,4 +1,6 @@
 mod io;
+#[cfg(test)]
+mod tests;
 
 use {
     crate::{
@@ -12,7 +14,9 @@ use {
             clone3::{fork_with_pidfd, Forked},
             copyhashmap::CopyHashMap,
             errorfmt::ErrorFmt,
+            line_logger::log_lines,
             numcell::NumCell,
+            oserror::OsError,
             process_name::set_process_name,
             queue::AsyncQueue,
         },
@@ -20,11 +24,14 @@ use {
     },
     ahash::AHashMap,
     bincode::Options,
+    bstr::ByteSlice,
+    futures_util::future::{join, join_all},
     jay_config::_private::bincode_ops,
     log::Level,
     serde::{Deserialize, Serialize},
     std::{
         cell::{Cell, RefCell},
+        collections::BTreeMap,
         env,
         ffi::OsStr,
         io::{Read, Write},
@@ -45,15 +52,28 @@ pub struct ForkerProxy {
     task_proc: Cell<Option<SpawnedFuture<()>>>,
     outgoing: AsyncQueue<ServerMessage>,
     next_id: NumCell<u32>,
-    pending_pidfds: CopyHashMap<u32, Weak<PidfdHandoff>>,
+    pending_pidfds: CopyHashMap<u32, Weak<Handoff<PidfdResult>>>,
+    pending_exits: CopyHashMap<u32, Weak<Handoff<Option<c::c_int>>>>,
     fds: RefCell<Vec<Rc<OwnedFd>>>,
+    children: CopyHashMap<c::pid_t, ()>,
 }
 
-struct PidfdHandoff {
-    pidfd: Cell<Option<Result<(Rc<OwnedFd>, c::pid_t), ForkerError>>>,
+type PidfdResult = Result<(Rc<OwnedFd>, c::pid_t), ForkerError>;
+
+struct Handoff<T> {
+    value: Cell<Option<T>>,
     waiter: Cell<Option<Waker>>,
 }
 
+impl<T> Handoff<T> {
+    fn complete(&self, value: T) {
+        self.value.set(Some(value));
+        if let Some(w) = self.waiter.take() {
+            w.wake();
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum ForkerError {
     #[error("Could not create a socketpair")]
@@ -98,7 +118,9 @@ impl ForkerProxy {
                 outgoing: Default::default(),
                 next_id: Default::default(),
                 pending_pidfds: Default::default(),
+                pending_exits: Default::default(),
                 fds: Default::default(),
+                children: Default::default(),
             }),
             Forked::Child { .. } => {
                 drop(parent);
@@ -107,6 +129,16 @@ impl ForkerProxy {
         }
     }
 
+    /// Sends SIGTERM to the process groups of all programs that are still running.
+    pub fn terminate_children(&self) {
+        for &pid in self.children.lock().keys() {
+            match kill_process_group(pid, c::SIGTERM) {
+                Ok(()) | Err(OsError(c::ESRCH)) => {}
+                Err(e) => log::error!("Could not terminate process group {}: {}", pid, ErrorFmt(e)),
+            }
+        }
+    }
+
     pub fn install(self: &Rc<Self>, state: &Rc<State>) {
         state.forker.set(Some(self.clone()));
         self.task_proc.set(Some(state.eng.spawn(
@@ -139,15 +171,15 @@ impl ForkerProxy {
         })
     }
 
-    async fn pidfd(&self, id: u32) -> Result<(Rc<OwnedFd>, c::pid_t), ForkerError> {
-        let handoff = Rc::new(PidfdHandoff {
-            pidfd: Cell::new(None),
+    async fn handoff<T>(pending: &CopyHashMap<u32, Weak<Handoff<T>>>, id: u32) -> T {
+        let handoff = Rc::new(Handoff {
+            value: Cell::new(None),
             waiter: Cell::new(None),
         });
-        self.pending_pidfds.set(id, Rc::downgrade(&handoff));
+        pending.set(id, Rc::downgrade(&handoff));
         futures_util::future::poll_fn(|ctx| {
-            if let Some(pidfd) = handoff.pidfd.take() {
-                Poll::Ready(pidfd)
+            if let Some(value) = handoff.value.take() {
+                Poll::Ready(value)
             } else {
                 handoff.waiter.set(Some(ctx.waker().clone()));
                 Poll::Pending
@@ -178,8 +210,8 @@ impl ForkerProxy {
             (6, waylandfd),
         ];
         let pidfd_id = self.next_id.fetch_add(1);
-        self.spawn_(prog, args, env, fds, Some(pidfd_id));
-        self.pidfd(pidfd_id).await
+        self.spawn_(prog, args, env, fds, false, Some(pidfd_id), None);
+        Self::handoff(&self.pending_pidfds, pidfd_id).await
     }
 
     pub fn spawn(
@@ -188,8 +220,40 @@ impl ForkerProxy {
         args: Vec<String>,
         env: Vec<(String, Option<String>)>,
         fds: Vec<(i32, Rc<OwnedFd>)>,
+        log_output: bool,
     ) {
-        self.spawn_(prog, args, env, fds, None)
+        self.spawn_(prog, args, env, fds, log_output, None, None)
+    }
+
+    /// Spawns a program and returns a pidfd referring to it.
+    pub async fn spawn_with_pidfd(
+        &self,
+        prog: String,
+        args: Vec<String>,
+        env: Vec<(String, Option<String>)>,
+        fds: Vec<(i32, Rc<OwnedFd>)>,
+        log_output: bool,
+    ) -> Result<Rc<OwnedFd>, ForkerError> {
+        let pidfd_id = self.next_id.fetch_add(1);
+        self.spawn_(prog, args, env, fds, log_output, Some(pidfd_id), None);
+        let (pidfd, _) = Self::handoff(&self.pending_pidfds, pidfd_id).await?;
+        Ok(pidfd)
+    }
+
+    /// Spawns a program and waits for it to exit.
+    ///
+    /// Returns the wait status of the process or `None` if it could not be spawned or
+    /// its status could not be retrieved.
+    pub async fn spawn_and_wait(
+        &self,
+        prog: String,
+        args: Vec<String>,
+        env: Vec<(String, Option<String>)>,
+        fds: Vec<(i32, Rc<OwnedFd>)>,
+    ) -> Option<c::c_int> {
+        let exit_id = self.next_id.fetch_add(1);
+        self.spawn_(prog, args, env, fds, false, None, Some(exit_id));
+        Self::handoff(&self.pending_exits, exit_id).await
     }
 
     fn spawn_(
@@ -198,7 +262,9 @@ impl ForkerProxy {
         args: Vec<String>,
         env: Vec<(String, Option<String>)>,
         fds: Vec<(i32, Rc<OwnedFd>)>,
+        log_output: bool,
         pidfd_id: Option<u32>,
+        exit_id: Option<u32>,
     ) {
         for (_, fd) in &fds {
             self.fds.borrow_mut().push(fd.clone());
@@ -209,7 +275,9 @@ impl ForkerProxy {
             args,
             env,
             fds,
+            log_output,
             pidfd_id,
+            exit_id,
         })
     }
 
@@ -232,6 +300,11 @@ impl ForkerProxy {
         match msg {
             ForkerMessage::Log { level, msg } => self.handle_log(level, &msg),
             ForkerMessage::PidFd { id, success, pid } => self.handle_pidfd(id, success, io, pid),
+            ForkerMessage::Exited { id, status } => self.handle_exited(id, status),
+            ForkerMessage::ChildSpawned { pid } => self.children.set(pid, ()),
+            ForkerMessage::ChildExited { pid } => {
+                self.children.remove(&pid);
+            }
         }
     }
 
@@ -242,10 +315,15 @@ impl ForkerProxy {
         };
         if let Some(handoff) = self.pending_pidfds.remove(&id) {
             if let Some(handoff) = handoff.upgrade() {
-                handoff.pidfd.set(Some(res));
-                if let Some(w) = handoff.waiter.take() {
-                    w.wake();
-                }
+                handoff.complete(res);
+            }
+        }
+    }
+
+    fn handle_exited(&self, id: u32, status: Option<c::c_int>) {
+        if let Some(handoff) = self.pending_exits.remove(&id) {
+            if let Some(handoff) = handoff.upgrade() {
+                handoff.complete(status);
             }
         }
     }
@@ -304,7 +382,9 @@ enum ServerMessage {
         args: Vec<String>,
         env: Vec<(String, Option<String>)>,
         fds: Vec<i32>,
+        log_output: bool,
         pidfd_id: Option<u32>,
+        exit_id: Option<u32>,
     },
 }
 
@@ -319,6 +399,16 @@ enum ForkerMessage {
         success: bool,
         pid: c::pid_t,
     },
+    Exited {
+        id: u32,
+        status: Option<c::c_int>,
+    },
+    ChildSpawned {
+        pid: c::pid_t,
+    },
+    ChildExited {
+        pid: c::pid_t,
+    },
 }
 
 struct Forker {
@@ -336,6 +426,9 @@ impl Forker {
             env::set_var("XDG_SESSION_TYPE", "wayland");
             env::remove_var(DISPLAY);
             env::remove_var(WAYLAND_DISPLAY);
+            // Tokens inherited from the program that started the compositor are stale.
+            env::remove_var(XDG_ACTIVATION_TOKEN);
+            env::remove_var(DESKTOP_STARTUP_ID);
         }
         set_process_name("the ol' forker");
         setup_deathsig(ppid);
@@ -404,8 +497,10 @@ impl Forker {
                 args,
                 env,
                 fds,
+                log_output,
                 pidfd_id,
-            } => self.handle_spawn(prog, args, env, fds, io, pidfd_id),
+                exit_id,
+            } => self.handle_spawn(prog, args, env, fds, log_output, io, pidfd_id, exit_id),
         }
     }
 
@@ -425,14 +520,17 @@ impl Forker {
         args: Vec<String>,
         env: Vec<(String, Option<String>)>,
         fds: Vec<i32>,
+        log_output: bool,
         io: &mut IoIn,
         pidfd_id: Option<u32>,
+        exit_id: Option<u32>,
     ) {
         let fds = fds
             .into_iter()
             .map(|a| (a, Rc::try_unwrap(io.pop_fd().unwrap()).unwrap()))
             .collect();
-        self.spawn(prog, args, env, fds, pidfd_id)
+        let env = merge_env(env);
+        self.spawn(prog, args, env, fds, log_output, pidfd_id, exit_id)
     }
 
     fn spawn(
@@ -440,9 +538,35 @@ impl Forker {
         prog: String,
         args: Vec<String>,
         env: Vec<(String, Option<String>)>,
-        fds: Vec<(i32, OwnedFd)>,
+        mut fds: Vec<(i32, OwnedFd)>,
+        log_output: bool,
         pidfd_id: Option<u32>,
+        exit_id: Option<u32>,
     ) {
+        let mut output = vec![];
+        if log_output {
+            for (idx, level) in [(1, Level::Info), (2, Level::Warn)] {
+                if fds.iter().any(|f| f.0 == idx) {
+                    continue;
+                }
+                match pipe2(c::O_CLOEXEC) {
+                    Ok((read, write)) => {
+                        fds.push((idx, write));
+                        output.push((read, level));
+                    }
+                    Err(e) => {
+                        self.outgoing.push(ForkerMessage::Log {
+                            level: Level::Error as usize,
+                            msg: format!(
+                                "Could not create an output pipe for `{}`: {}",
+                                prog,
+                                ErrorFmt(OsError::from(e))
+                            ),
+                        });
+                    }
+                }
+            }
+        }
         let (read, mut write) = pipe2(c::O_CLOEXEC).unwrap();
         let res = match fork_with_pidfd(false) {
             Ok(o) => o,
@@ -454,6 +578,10 @@ impl Forker {
                         pid: 0,
                     });
                 }
+                if let Some(id) = exit_id {
+                    self.outgoing
+                        .push(ForkerMessage::Exited { id, status: None });
+                }
                 self.outgoing.push(ForkerMessage::Log {
                     level: log::Level::Error as usize,
                     msg: ErrorFmt(e).to_string(),
@@ -463,15 +591,23 @@ impl Forker {
         };
         match res {
             Forked::Parent { pid, pidfd } => {
+                let pidfd = Rc::new(pidfd);
                 if let Some(id) = pidfd_id {
-                    self.fds.borrow_mut().push(Rc::new(pidfd));
+                    self.fds.borrow_mut().push(pidfd.clone());
                     self.outgoing.push(ForkerMessage::PidFd {
                         id,
                         success: true,
                         pid,
                     });
                 }
+                self.outgoing.push(ForkerMessage::ChildSpawned { pid });
                 drop(write);
+                drop(fds);
+                let prog = Rc::new(prog);
+                let output: Vec<_> = output
+                    .into_iter()
+                    .map(|(fd, level)| self.clone().log_output(prog.clone(), fd, level))
+                    .collect();
                 let slf = self.clone();
                 let spawn = self.ae.spawn("await spawn", async move {
                     let read = Rc::new(read);
@@ -490,6 +626,21 @@ impl Forker {
                             });
                         }
                     }
+                    let exited = async {
+                        if let Err(e) = slf.ring.readable(&pidfd).await {
+                            log::error!(
+                                "Cannot wait for the child pidfd to become readable: {}",
+                                ErrorFmt(e)
+                            );
+                            return None;
+                        }
+                        uapi::waitpid(pid, 0).ok().map(|(_, status)| status)
+                    };
+                    let (status, _) = join(exited, join_all(output)).await;
+                    if let Some(id) = exit_id {
+                        slf.outgoing.push(ForkerMessage::Exited { id, status });
+                    }
+                    slf.outgoing.push(ForkerMessage::ChildExited { pid });
                     slf.pending_spawns.remove(&pid);
                 });
                 self.pending_spawns.set(pid, spawn);
@@ -515,6 +666,9 @@ impl Forker {
                     }
                     unsafe {
                         c::signal(c::SIGCHLD, c::SIG_DFL);
+                        // Detach from the process group of the compositor so that signals sent
+                        // by the program to its own process group cannot reach the compositor.
+                        c::setsid();
                     }
                     for (key, val) in env {
                         unsafe {
@@ -544,6 +698,49 @@ impl Forker {
     }
 }
 
+impl Forker {
+    async fn log_output(self: Rc<Self>, prog: Rc<String>, fd: OwnedFd, level: Level) {
+        let res = log_lines(&self.ring, &Rc::new(fd), |left, right| {
+            self.outgoing.push(ForkerMessage::Log {
+                level: level as usize,
+                msg: format!("{}: {}{}", prog, left.as_bstr(), right.as_bstr()),
+            });
+        })
+        .await;
+        if let Err(e) = res {
+            self.outgoing.push(ForkerMessage::Log {
+                level: Level::Error as usize,
+                msg: format!("Could not read the output of `{}`: {}", prog, ErrorFmt(e)),
+            });
+        }
+    }
+}
+
+const XDG_ACTIVATION_TOKEN: &str = "XDG_ACTIVATION_TOKEN";
+const DESKTOP_STARTUP_ID: &str = "DESKTOP_STARTUP_ID";
+
+/// Returns the per-spawn environment overrides in the order in which they are applied.
+///
+/// The overrides are applied on top of the environment of the forker, which contains the
+/// variables set by the compositor. If a variable occurs multiple times, the last
+/// occurrence wins. The result is sorted by name.
+fn merge_env(env: Vec<(String, Option<String>)>) -> Vec<(String, Option<String>)> {
+    let env: BTreeMap<_, _> = env.into_iter().collect();
+    env.into_iter().collect()
+}
+
+/// Sends a signal to the process group with the id `pgid`.
+///
+/// Programs spawned by the forker are the leaders of their own process groups. Their pid
+/// can therefore be used to signal them together with the processes they spawned.
+pub fn kill_process_group(pgid: c::pid_t, sig: c::c_int) -> Result<(), OsError> {
+    if pgid <= 1 || pgid == uapi::getpgrp() {
+        return Err(OsError(c::EINVAL));
+    }
+    uapi::kill(-pgid, sig)?;
+    Ok(())
+}
+
 #[derive(Debug, Error)]
 enum SpawnError {
     #[error("exec failed")]
@@ -575,7 +772,6 @@ fn reset_signals() {
         for sig in 1..=NSIG {
             c::signal(sig, c::SIG_DFL);
         }
-        c::signal(c::SIGCHLD, c::SIG_IGN);
     }
 }
 
@@ -0,0 +1,93 @@
+use {
+    crate::forker::{kill_process_group, merge_env},
+    std::{
+        env, fs,
+        io::{BufRead, BufReader},
+        os::unix::process::CommandExt,
+        process::{Command, Stdio},
+        thread,
+        time::{Duration, Instant},
+    },
+    uapi::c,
+};
+
+const HELPER: &str = "JAY_FORKER_TEST_HELPER";
+
+#[test]
+fn merge_env_last_wins() {
+    let env = vec![
+        ("B".to_string(), Some("1".to_string())),
+        ("A".to_string(), None),
+        ("B".to_string(), Some("2".to_string())),
+    ];
+    assert_eq!(
+        merge_env(env),
+        vec![
+            ("A".to_string(), None),
+            ("B".to_string(), Some("2".to_string())),
+        ],
+    );
+}
+
+#[test]
+fn own_group_is_not_killed() {
+    assert!(kill_process_group(uapi::getpgrp(), 0).is_err());
+    assert!(kill_process_group(1, 0).is_err());
+}
+
+/// Spawns two grandchildren, prints their pids, and sleeps.
+///
+/// This does nothing unless it is executed by `kill_group_reaches_grandchildren`.
+#[test]
+fn group_helper() {
+    if env::var_os(HELPER).is_none() {
+        return;
+    }
+    for _ in 0..2 {
+        let child = Command::new("sleep").arg("1000").spawn().unwrap();
+        println!("grandchild {}", child.id());
+    }
+    thread::sleep(Duration::from_secs(1000));
+}
+
+#[test]
+fn kill_group_reaches_grandchildren() {
+    let mut helper = Command::new(env::current_exe().unwrap());
+    helper
+        .args(["--exact", "forker::tests::group_helper", "--nocapture"])
+        .env(HELPER, "1")
+        .stdout(Stdio::piped());
+    // Like the forker, start the helper in its own session.
+    unsafe {
+        helper.pre_exec(|| {
+            c::setsid();
+            Ok(())
+        });
+    }
+    let mut helper = helper.spawn().unwrap();
+    let stdout = BufReader::new(helper.stdout.take().unwrap());
+    let grandchildren: Vec<c::pid_t> = stdout
+        .lines()
+        .map(|l| l.unwrap())
+        .filter_map(|l| Some(l.strip_prefix("grandchild ")?.parse().unwrap()))
+        .take(2)
+        .collect();
+    assert_eq!(grandchildren.len(), 2);
+    kill_process_group(helper.id() as _, c::SIGKILL).unwrap();
+    helper.wait().unwrap();
+    for pid in grandchildren {
+        assert!(has_exited(pid), "grandchild {pid} is still running");
+    }
+}
+
+fn has_exited(pid: c::pid_t) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+        match fs::read_to_string(format!("/proc/{pid}/stat")) {
+            // Grandchildren are zombies until their new parent reaps them.
+            Ok(stat) if !stat.contains(") Z ") => thread::sleep(Duration::from_millis(10)),
+            _ => return true,
+        }
+    }
+    false
+}
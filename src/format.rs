@@ -34,6 +34,7 @@ pub struct Format {
     pub wl_id: Option<u32>,
     pub external_only_guess: bool,
     pub has_alpha: bool,
+    pub yuv: bool,
     pub pipewire: SpaVideoFormat,
     pub opaque: Option<&'static Format>,
     pub shm_info: Option<FormatShmInfo>,
@@ -48,6 +49,7 @@ const fn default(config: ConfigFormat) -> Format {
         wl_id: None,
         external_only_guess: false,
         has_alpha: false,
+        yuv: false,
         pipewire: SPA_VIDEO_FORMAT_UNKNOWN,
         opaque: None,
         shm_info: None,
@@ -424,6 +426,22 @@ static XBGR16161616F: &Format = &Format {
     ..default(ConfigFormat::XBGR16161616F)
 };
 
+static NV12: &Format = &Format {
+    name: "nv12",
+    drm: fourcc_code('N', 'V', '1', '2'),
+    external_only_guess: true,
+    yuv: true,
+    ..default(ConfigFormat::NV12)
+};
+
+static YUV420: &Format = &Format {
+    name: "yuv420",
+    drm: fourcc_code('Y', 'U', '1', '2'),
+    external_only_guess: true,
+    yuv: true,
+    ..default(ConfigFormat::YUV420)
+};
+
 pub static FORMATS: &[Format] = &[
     *ARGB8888,
     *XRGB8888,
@@ -473,4 +491,6 @@ pub static FORMATS: &[Format] = &[
     *ABGR16161616F,
     #[cfg(target_endian = "little")]
     *XBGR16161616F,
+    *NV12,
+    *YUV420,
 ];
@@ -20,7 +20,7 @@ use {
     jay_config::video::{GfxApi, Transform},
     std::{
         any::Any,
-        cell::Cell,
+        cell::{Cell, RefCell},
         error::Error,
         ffi::CString,
         fmt::{Debug, Formatter},
@@ -116,7 +116,7 @@ impl SampleRect {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct FramebufferRect {
     pub x1: f32,
     pub x2: f32,
@@ -162,6 +162,13 @@ impl FramebufferRect {
         }
     }
 
+    /// Returns whether `other` lies within this rectangle.
+    ///
+    /// Both rectangles must use the same output transform.
+    pub fn contains(&self, other: &FramebufferRect) -> bool {
+        self.x1 <= other.x1 && self.y1 <= other.y1 && self.x2 >= other.x2 && self.y2 >= other.y2
+    }
+
     pub fn is_covering(&self) -> bool {
         self.x1 == -1.0 && self.y1 == -1.0 && self.x2 == 1.0 && self.y2 == 1.0
     }
@@ -181,6 +188,7 @@ pub struct CopyTexture {
     pub acquire_sync: AcquireSync,
     pub release_sync: ReleaseSync,
     pub alpha: Option<f32>,
+    pub opaque: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -334,6 +342,7 @@ impl dyn GfxFramebuffer {
         renderer.render_texture(
             texture,
             None,
+            false,
             x,
             y,
             None,
@@ -375,8 +384,10 @@ impl dyn GfxFramebuffer {
         visualizer: Option<&DamageVisualizer>,
     ) -> GfxRenderPass {
         create_render_pass(
+            vec![],
             self.physical_size(),
             node,
+            (0, 0),
             state,
             cursor_rect,
             scale,
@@ -403,22 +414,17 @@ impl dyn GfxFramebuffer {
         release_sync: ReleaseSync,
         node: &OutputNode,
         state: &State,
-        cursor_rect: Option<Rect>,
-        scale: Scale,
         render_hardware_cursor: bool,
     ) -> Result<Option<SyncFile>, GfxError> {
-        self.render_node(
-            acquire_sync,
-            release_sync,
+        let pass = create_output_render_pass(
+            vec![],
+            self.physical_size(),
             node,
             state,
-            cursor_rect,
-            scale,
-            true,
             render_hardware_cursor,
-            node.has_fullscreen(),
-            node.global.persistent.transform.get(),
-        )
+            None,
+        );
+        self.perform_render_pass(acquire_sync, release_sync, &pass)
     }
 
     pub fn render_node(
@@ -466,6 +472,7 @@ impl dyn GfxFramebuffer {
                 let (width, height) = self.logical_size(transform);
                 Rect::new(0, 0, width, height).unwrap()
             },
+            alpha: None,
         };
         cursor.render_hardware_cursor(&mut renderer);
         self.render(acquire_sync, release_sync, &ops, Some(&Color::TRANSPARENT))
@@ -713,9 +720,72 @@ impl Drop for PendingShmTransfer {
     }
 }
 
+/// Creates the render pass for an output.
+///
+/// If the output mirrors another output, the contents of the other output are scaled to
+/// fit and centered.
+pub fn create_output_render_pass(
+    ops: Vec<GfxApiOpt>,
+    physical_size: (i32, i32),
+    output: &OutputNode,
+    state: &State,
+    render_hardware_cursor: bool,
+    visualizer: Option<&DamageVisualizer>,
+) -> GfxRenderPass {
+    let transform = output.global.persistent.transform.get();
+    let source = match output.mirror.get() {
+        Some(mirror) if !mirror.source.global.pos.get().is_empty() => mirror.source.clone(),
+        _ => {
+            return create_render_pass(
+                ops,
+                physical_size,
+                output,
+                (0, 0),
+                state,
+                Some(output.global.pos.get()),
+                output.global.persistent.scale.get(),
+                true,
+                render_hardware_cursor,
+                output.has_fullscreen(),
+                transform,
+                visualizer,
+            );
+        }
+    };
+    let pos = source.global.pos.get();
+    let (width, height) = logical_size(physical_size, transform);
+    let (width, height) = (width as f64, height as f64);
+    let scale = (width / pos.width() as f64).min(height / pos.height() as f64);
+    let x = ((width / scale - pos.width() as f64) / 2.0).round() as i32;
+    let y = ((height / scale - pos.height() as f64) / 2.0).round() as i32;
+    create_render_pass(
+        ops,
+        physical_size,
+        &*source,
+        (x, y),
+        state,
+        Some(pos.move_(-x, -y)),
+        Scale::from_f64(scale),
+        true,
+        // The hardware cursor of this output does not follow the cursor on the source.
+        true,
+        true,
+        transform,
+        visualizer,
+    )
+}
+
+/// Creates a render pass for a node.
+///
+/// The operations are written to `ops`, which is cleared first, so that callers can reuse
+/// its allocation from one frame to the next.
+///
+/// The node is rendered at `(x, y)` in the logical coordinate space of the render pass.
 pub fn create_render_pass(
+    mut ops: Vec<GfxApiOpt>,
     physical_size: (i32, i32),
     node: &dyn Node,
+    (x, y): (i32, i32),
     state: &State,
     cursor_rect: Option<Rect>,
     scale: Scale,
@@ -725,17 +795,18 @@ pub fn create_render_pass(
     transform: Transform,
     visualizer: Option<&DamageVisualizer>,
 ) -> GfxRenderPass {
-    let mut ops = vec![];
+    ops.clear();
     let mut renderer = Renderer {
         base: renderer_base(physical_size, &mut ops, scale, transform),
         state,
-        logical_extents: node.node_absolute_position().at_point(0, 0),
+        logical_extents: node.node_absolute_position().at_point(x, y),
         pixel_extents: {
             let (width, height) = logical_size(physical_size, transform);
             Rect::new(0, 0, width, height).unwrap()
         },
+        alpha: None,
     };
-    node.node_render(&mut renderer, 0, 0, None);
+    node.node_render(&mut renderer, x, y, None);
     if let Some(rect) = cursor_rect {
         let seats = state.globals.lock_seats();
         for seat in seats.values() {
@@ -782,6 +853,7 @@ pub fn create_render_pass(
             visualizer.render(&cursor_rect, &mut renderer.base);
         }
     }
+    cull_occluded_ops(&state.render_scratch, &mut ops);
     let c = match black_background {
         true => Color::SOLID_BLACK,
         false => state.theme.colors.background.get(),
@@ -792,6 +864,46 @@ pub fn create_render_pass(
     }
 }
 
+/// Buffers that are reused by all render passes so that they do not have to be
+/// allocated for every frame.
+#[derive(Default)]
+pub struct RenderScratch {
+    occluders: RefCell<Vec<FramebufferRect>>,
+    visible: RefCell<Vec<bool>>,
+}
+
+/// Removes operations that are completely covered by an opaque texture that is drawn
+/// after them.
+///
+/// Only textures are used as occluders since renderers draw all fills between two
+/// [`GfxApiOpt::Sync`] operations before the textures.
+fn cull_occluded_ops(scratch: &RenderScratch, ops: &mut Vec<GfxApiOpt>) {
+    let occluders = &mut *scratch.occluders.borrow_mut();
+    let visible = &mut *scratch.visible.borrow_mut();
+    occluders.clear();
+    visible.clear();
+    for op in ops.iter().rev() {
+        let rect = match op {
+            GfxApiOpt::Sync => {
+                visible.push(true);
+                continue;
+            }
+            GfxApiOpt::FillRect(f) => &f.rect,
+            GfxApiOpt::CopyTexture(c) => &c.target,
+        };
+        let occluded = occluders.iter().any(|o| o.contains(rect));
+        visible.push(!occluded);
+        if let GfxApiOpt::CopyTexture(c) = op {
+            let opaque = c.opaque || !c.tex.format().has_alpha;
+            if !occluded && opaque && c.alpha.is_none() {
+                occluders.push(c.target);
+            }
+        }
+    }
+    let mut visible = visible.iter().rev();
+    ops.retain(|_| *visible.next().unwrap());
+}
+
 pub fn renderer_base<'a>(
     physical_size: (i32, i32),
     ops: &'a mut Vec<GfxApiOpt>,
@@ -301,6 +301,12 @@ fn run_ops(fb: &Framebuffer, ops: &[GfxApiOpt]) -> Option<SyncFile> {
 fn fill_boxes3(ctx: &GlRenderContext, boxes: &[[f32; 2]], color: &Color) {
     let gles = ctx.ctx.dpy.gles;
     unsafe {
+        // Textures that are drawn before might have disabled blending.
+        if color.a < 1.0 {
+            (gles.glEnable)(GL_BLEND);
+        } else {
+            (gles.glDisable)(GL_BLEND);
+        }
         (gles.glUseProgram)(ctx.fill_prog.prog);
         (gles.glUniform4f)(ctx.fill_prog_color, color.r, color.g, color.b, color.a);
         (gles.glVertexAttribPointer)(
@@ -349,7 +355,7 @@ fn render_texture(ctx: &GlRenderContext, tex: &CopyTexture) {
             true => TexCopyType::Multiply,
             false => TexCopyType::Identity,
         };
-        let source_type = match texture.gl.format.has_alpha {
+        let source_type = match texture.gl.format.has_alpha && !tex.opaque {
             true => TexSourceType::HasAlpha,
             false => TexSourceType::Opaque,
         };
@@ -7,6 +7,7 @@ use {
                 sys::{EGLContext, EGLSurface, EGL_FALSE, EGL_TRUE},
             },
             ext::{GlExt, EXT_CREATE_CONTEXT_ROBUSTNESS},
+            gl::texture::TexturePool,
             sys::{
                 GL_GUILTY_CONTEXT_RESET_ARB, GL_INNOCENT_CONTEXT_RESET_ARB,
                 GL_UNKNOWN_CONTEXT_RESET_ARB,
@@ -24,6 +25,7 @@ pub struct EglContext {
     pub ext: GlExt,
     pub ctx: EGLContext,
     pub formats: Rc<AHashMap<u32, GfxFormat>>,
+    pub texture_pool: Rc<TexturePool>,
 }
 
 impl Drop for EglContext {
@@ -164,6 +164,7 @@ impl EglDisplay {
             ext: GlExt::none(),
             ctx,
             formats: Default::default(),
+            texture_pool: Default::default(),
         };
         ctx.ext = ctx.with_current(get_gl_ext)?;
         if !ctx.ext.contains(GL_OES_EGL_IMAGE) {
@@ -176,13 +177,17 @@ impl EglDisplay {
                 if format.implicit_external_only && !supports_external_only {
                     continue;
                 }
+                // YUV images are converted to RGB when they are sampled as external images.
+                if format.format.yuv && !supports_external_only {
+                    continue;
+                }
                 let mut read_modifiers = IndexSet::new();
                 let mut write_modifiers = IndexMap::new();
                 for modifier in format.modifiers.values() {
                     if modifier.external_only && !supports_external_only {
                         continue;
                     }
-                    if !modifier.external_only {
+                    if !modifier.external_only && !format.format.yuv {
                         write_modifiers.insert(
                             modifier.modifier,
                             GfxWriteModifier {
@@ -290,7 +295,7 @@ impl EglDisplay {
         Ok(Rc::new(EglImage {
             dpy: self.clone(),
             img,
-            external_only: format.external_only,
+            external_only: format.external_only || buf.format.yuv,
             dmabuf: buf.clone(),
         }))
     }
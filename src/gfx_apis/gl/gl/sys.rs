@@ -97,6 +97,17 @@ dynload! {
             ty: GLenum,
             pixels: *const c::c_void,
         ),
+        glTexSubImage2D: unsafe fn(
+            target: GLenum,
+            level: GLint,
+            xoffset: GLint,
+            yoffset: GLint,
+            width: GLsizei,
+            height: GLsizei,
+            format: GLenum,
+            ty: GLenum,
+            pixels: *const c::c_void,
+        ),
 
         glEnable: unsafe fn(cap: GLenum),
         glDisable: unsafe fn(cap: GLenum),
@@ -11,10 +11,21 @@ use {
             sys::{GLeglImageOES, GLenum, GL_TEXTURE_EXTERNAL_OES},
             RenderError,
         },
+        rect::{Rect, Region},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        collections::VecDeque,
+        rc::Rc,
     },
-    std::{cell::Cell, rc::Rc},
 };
 
+#[cfg(test)]
+mod tests;
+
+/// The maximum number of unused shm textures that are kept per context.
+const MAX_POOLED_TEXTURES: usize = 16;
+
 pub struct GlTexture {
     pub(crate) ctx: Rc<EglContext>,
     pub img: Option<Rc<EglImage>>,
@@ -25,6 +36,72 @@ pub struct GlTexture {
     pub external_only: bool,
     pub format: &'static Format,
     pub contents_valid: Cell<bool>,
+    pub has_storage: Cell<bool>,
+}
+
+/// Unused shm textures whose storage can be reused by textures of the same size and format.
+#[derive(Debug, Default)]
+pub struct TexturePool {
+    textures: RefCell<VecDeque<PooledTexture>>,
+}
+
+#[derive(Debug)]
+struct PooledTexture {
+    width: i32,
+    height: i32,
+    format: u32,
+    tex: GLuint,
+}
+
+impl TexturePool {
+    /// Takes a texture with storage of the given size and format out of the pool.
+    pub fn take(&self, width: i32, height: i32, format: &Format) -> Option<GLuint> {
+        let textures = &mut *self.textures.borrow_mut();
+        let idx = textures
+            .iter()
+            .position(|t| t.width == width && t.height == height && t.format == format.drm)?;
+        textures.remove(idx).map(|t| t.tex)
+    }
+
+    /// Adds an unused texture to the pool.
+    ///
+    /// If the pool is full, the oldest texture is removed from the pool and returned so that
+    /// the caller can delete it.
+    pub fn put(&self, width: i32, height: i32, format: &Format, tex: GLuint) -> Option<GLuint> {
+        let textures = &mut *self.textures.borrow_mut();
+        textures.push_back(PooledTexture {
+            width,
+            height,
+            format: format.drm,
+            tex,
+        });
+        if textures.len() > MAX_POOLED_TEXTURES {
+            return textures.pop_front().map(|t| t.tex);
+        }
+        None
+    }
+}
+
+/// Returns the parts of `damage` that lie inside a texture of the given size and the offsets
+/// of their first pixels in the shm buffer.
+pub fn damage_uploads(
+    damage: &Region,
+    width: i32,
+    height: i32,
+    stride: i32,
+    bpp: u32,
+) -> Vec<(Rect, usize)> {
+    let bounds = Rect::new_sized_unchecked(0, 0, width, height);
+    let mut res = vec![];
+    for rect in damage.rects() {
+        let rect = rect.intersect(bounds);
+        if rect.is_empty() {
+            continue;
+        }
+        let offset = rect.y1() as usize * stride as usize + rect.x1() as usize * bpp as usize;
+        res.push((rect, offset));
+    }
+    res
 }
 
 pub fn image_target(external_only: bool) -> GLenum {
@@ -66,6 +143,7 @@ impl GlTexture {
             external_only: img.external_only,
             format: img.dmabuf.format,
             contents_valid: Cell::new(true),
+            has_storage: Cell::new(true),
         })
     }
 
@@ -84,24 +162,45 @@ impl GlTexture {
             return Err(RenderError::SmallImageBuffer);
         }
         let gles = ctx.dpy.gles;
+        let pooled = ctx.texture_pool.take(width, height, format);
         let tex = ctx.with_current(|| unsafe {
-            let mut tex = 0;
-            (gles.glGenTextures)(1, &mut tex);
+            let tex = match pooled {
+                Some(tex) => tex,
+                None => {
+                    let mut tex = 0;
+                    (gles.glGenTextures)(1, &mut tex);
+                    tex
+                }
+            };
             (gles.glBindTexture)(GL_TEXTURE_2D, tex);
             (gles.glTexParameteri)(GL_TEXTURE_2D, GL_TEXTURE_WRAP_S, GL_CLAMP_TO_EDGE);
             (gles.glTexParameteri)(GL_TEXTURE_2D, GL_TEXTURE_WRAP_T, GL_CLAMP_TO_EDGE);
             (gles.glPixelStorei)(GL_UNPACK_ROW_LENGTH_EXT, stride / shm_info.bpp as GLint);
-            (gles.glTexImage2D)(
-                GL_TEXTURE_2D,
-                0,
-                shm_info.gl_format,
-                width,
-                height,
-                0,
-                shm_info.gl_format as _,
-                shm_info.gl_type as _,
-                data.as_ptr() as _,
-            );
+            if pooled.is_some() {
+                (gles.glTexSubImage2D)(
+                    GL_TEXTURE_2D,
+                    0,
+                    0,
+                    0,
+                    width,
+                    height,
+                    shm_info.gl_format as _,
+                    shm_info.gl_type as _,
+                    data.as_ptr() as _,
+                );
+            } else {
+                (gles.glTexImage2D)(
+                    GL_TEXTURE_2D,
+                    0,
+                    shm_info.gl_format,
+                    width,
+                    height,
+                    0,
+                    shm_info.gl_format as _,
+                    shm_info.gl_type as _,
+                    data.as_ptr() as _,
+                );
+            }
             (gles.glPixelStorei)(GL_UNPACK_ROW_LENGTH_EXT, 0);
             (gles.glBindTexture)(GL_TEXTURE_2D, 0);
             Ok(tex)
@@ -116,14 +215,23 @@ impl GlTexture {
             external_only: false,
             format,
             contents_valid: Cell::new(true),
+            has_storage: Cell::new(true),
         })
     }
 }
 
 impl Drop for GlTexture {
     fn drop(&mut self) {
+        let mut tex = self.tex;
+        if self.img.is_none() && self.has_storage.get() {
+            let pool = &self.ctx.texture_pool;
+            match pool.put(self.width, self.height, self.format, tex) {
+                Some(evicted) => tex = evicted,
+                _ => return,
+            }
+        }
         let _ = self.ctx.with_current(|| unsafe {
-            (self.ctx.dpy.gles.glDeleteTextures)(1, &self.tex);
+            (self.ctx.dpy.gles.glDeleteTextures)(1, &tex);
             Ok(())
         });
     }
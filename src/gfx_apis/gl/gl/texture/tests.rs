@@ -0,0 +1,57 @@
+use crate::{
+    format::{ARGB8888, XRGB8888},
+    gfx_apis::gl::gl::texture::{damage_uploads, TexturePool, MAX_POOLED_TEXTURES},
+    rect::{Rect, Region},
+};
+
+#[test]
+fn pool_matches_size_and_format() {
+    let pool = TexturePool::default();
+    assert_eq!(pool.put(10, 10, ARGB8888, 1), None);
+    assert_eq!(pool.put(10, 20, ARGB8888, 2), None);
+    assert_eq!(pool.take(10, 10, XRGB8888), None);
+    assert_eq!(pool.take(10, 20, ARGB8888), Some(2));
+    assert_eq!(pool.take(10, 20, ARGB8888), None);
+    assert_eq!(pool.take(10, 10, ARGB8888), Some(1));
+}
+
+#[test]
+fn pool_evicts_oldest() {
+    let pool = TexturePool::default();
+    for tex in 0..MAX_POOLED_TEXTURES as u32 {
+        assert_eq!(pool.put(10, 10, ARGB8888, tex), None);
+    }
+    assert_eq!(pool.put(10, 10, ARGB8888, 100), Some(0));
+    assert_eq!(pool.take(10, 10, ARGB8888), Some(1));
+}
+
+#[test]
+fn small_damage_uploads_little() {
+    const WIDTH: i32 = 3840;
+    const HEIGHT: i32 = 2160;
+    let stride = WIDTH * 4;
+    let damage = Region::from_rects2(&[
+        Rect::new_sized_unchecked(100, 100, 10, 10),
+        Rect::new_sized_unchecked(WIDTH - 5, HEIGHT - 5, 10, 10),
+        Rect::new_sized_unchecked(WIDTH + 10, 0, 10, 10),
+    ]);
+    let uploads = damage_uploads(&damage, WIDTH, HEIGHT, stride, 4);
+    assert_eq!(
+        uploads,
+        [
+            (
+                Rect::new_sized_unchecked(100, 100, 10, 10),
+                100 * stride as usize + 100 * 4,
+            ),
+            (
+                Rect::new_sized_unchecked(WIDTH - 5, HEIGHT - 5, 5, 5),
+                (HEIGHT - 5) as usize * stride as usize + (WIDTH - 5) as usize * 4,
+            ),
+        ],
+    );
+    let bytes: usize = uploads
+        .iter()
+        .map(|(r, _)| (r.width() * r.height() * 4) as usize)
+        .sum();
+    assert!(bytes * 100 < (stride * HEIGHT) as usize);
+}
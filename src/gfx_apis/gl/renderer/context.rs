@@ -292,11 +292,15 @@ impl GfxContext for GlRenderContext {
         stride: i32,
         _cpu_worker: &Rc<CpuWorker>,
     ) -> Result<Rc<dyn AsyncShmGfxTexture>, GfxError> {
-        let tex = self.ctx.with_current(|| unsafe {
-            let mut tex = 0;
-            (self.ctx.dpy.gles.glGenTextures)(1, &mut tex);
-            Ok(tex)
-        })?;
+        let pooled = self.ctx.texture_pool.take(width, height, format);
+        let tex = match pooled {
+            Some(tex) => tex,
+            None => self.ctx.with_current(|| unsafe {
+                let mut tex = 0;
+                (self.ctx.dpy.gles.glGenTextures)(1, &mut tex);
+                Ok(tex)
+            })?,
+        };
         Ok(Rc::new(Texture {
             gl: GlTexture {
                 ctx: self.ctx.clone(),
@@ -308,6 +312,7 @@ impl GfxContext for GlRenderContext {
                 external_only: false,
                 format,
                 contents_valid: Cell::new(false),
+                has_storage: Cell::new(pooled.is_some()),
             },
             ctx: self,
             format,
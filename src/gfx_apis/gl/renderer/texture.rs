@@ -6,7 +6,7 @@ use {
             PendingShmTransfer, ShmGfxTexture, ShmMemory,
         },
         gfx_apis::gl::{
-            gl::texture::GlTexture,
+            gl::texture::{damage_uploads, GlTexture},
             renderer::context::GlRenderContext,
             sys::{
                 GLint, GL_CLAMP_TO_EDGE, GL_TEXTURE_2D, GL_TEXTURE_WRAP_S, GL_TEXTURE_WRAP_T,
@@ -14,7 +14,7 @@ use {
             },
             RenderError,
         },
-        rect::Region,
+        rect::{Rect, Region},
         video::dmabuf::DmaBuf,
     },
     std::{
@@ -81,46 +81,69 @@ impl AsyncShmGfxTexture for Texture {
         _staging: &Rc<dyn GfxStagingBuffer>,
         _callback: Rc<dyn AsyncShmGfxTextureCallback>,
         mem: Rc<dyn ShmMemory>,
-        _damage: Region,
+        damage: Region,
     ) -> Result<Option<PendingShmTransfer>, GfxError> {
         let mut res = Ok(());
         mem.access(&mut |data| {
-            res = self.clone().sync_upload(data, Region::default());
+            res = self.clone().sync_upload(data, damage.clone());
         })
         .map_err(RenderError::AccessFailed)?;
         res.map(|_| None)
     }
 
-    fn sync_upload(self: Rc<Self>, data: &[Cell<u8>], _damage: Region) -> Result<(), GfxError> {
+    fn sync_upload(self: Rc<Self>, data: &[Cell<u8>], damage: Region) -> Result<(), GfxError> {
         let shm_info = self.format.shm_info.as_ref().unwrap();
         if (self.gl.stride * self.gl.height) as usize > data.len() {
             return Err(RenderError::SmallImageBuffer.into());
         }
         let gles = self.ctx.ctx.dpy.gles;
+        let gl = &self.gl;
+        let bpp = shm_info.bpp;
+        let full = Region::new2(Rect::new_sized_unchecked(0, 0, gl.width, gl.height));
+        let damage = match gl.contents_valid.get() {
+            true => &damage,
+            false => &full,
+        };
         self.ctx.ctx.with_current(|| unsafe {
-            (gles.glBindTexture)(GL_TEXTURE_2D, self.gl.tex);
+            (gles.glBindTexture)(GL_TEXTURE_2D, gl.tex);
             (gles.glTexParameteri)(GL_TEXTURE_2D, GL_TEXTURE_WRAP_S, GL_CLAMP_TO_EDGE);
             (gles.glTexParameteri)(GL_TEXTURE_2D, GL_TEXTURE_WRAP_T, GL_CLAMP_TO_EDGE);
-            (gles.glPixelStorei)(
-                GL_UNPACK_ROW_LENGTH_EXT,
-                self.gl.stride / shm_info.bpp as GLint,
-            );
-            (gles.glTexImage2D)(
-                GL_TEXTURE_2D,
-                0,
-                shm_info.gl_format,
-                self.gl.width,
-                self.gl.height,
-                0,
-                shm_info.gl_format as _,
-                shm_info.gl_type as _,
-                data.as_ptr() as _,
-            );
+            (gles.glPixelStorei)(GL_UNPACK_ROW_LENGTH_EXT, gl.stride / bpp as GLint);
+            if gl.has_storage.get() {
+                // The texture storage already exists. Only upload the damaged parts or, if the
+                // contents are not valid, the whole buffer.
+                for (rect, offset) in damage_uploads(damage, gl.width, gl.height, gl.stride, bpp) {
+                    (gles.glTexSubImage2D)(
+                        GL_TEXTURE_2D,
+                        0,
+                        rect.x1(),
+                        rect.y1(),
+                        rect.width(),
+                        rect.height(),
+                        shm_info.gl_format as _,
+                        shm_info.gl_type as _,
+                        data[offset..].as_ptr() as _,
+                    );
+                }
+            } else {
+                (gles.glTexImage2D)(
+                    GL_TEXTURE_2D,
+                    0,
+                    shm_info.gl_format,
+                    gl.width,
+                    gl.height,
+                    0,
+                    shm_info.gl_format as _,
+                    shm_info.gl_type as _,
+                    data.as_ptr() as _,
+                );
+            }
             (gles.glPixelStorei)(GL_UNPACK_ROW_LENGTH_EXT, 0);
             (gles.glBindTexture)(GL_TEXTURE_2D, 0);
             Ok(())
         })?;
         self.gl.contents_valid.set(true);
+        self.gl.has_storage.set(true);
         Ok(())
     }
 
@@ -97,6 +97,9 @@ impl VulkanInstance {
         format: &'static Format,
         dst: &mut AHashMap<u32, VulkanFormat>,
     ) -> Result<(), VulkanError> {
+        if format.vk_format == vk::Format::UNDEFINED {
+            return Ok(());
+        }
         let mut modifier_props = DrmFormatModifierPropertiesListEXT::default();
         let mut format_properties = FormatProperties2::default().push_next(&mut modifier_props);
         unsafe {
@@ -553,7 +553,7 @@ impl VulkanRenderer {
                         true => TexCopyType::Multiply,
                         false => TexCopyType::Identity,
                     };
-                    let source_type = match tex.format.has_alpha {
+                    let source_type = match tex.format.has_alpha && !c.opaque {
                         true => TexSourceType::HasAlpha,
                         false => TexSourceType::Opaque,
                     };
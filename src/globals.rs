@@ -54,9 +54,11 @@ use {
             xdg_toplevel_drag_manager_v1::XdgToplevelDragManagerV1Global,
             xdg_wm_base::XdgWmBaseGlobal,
             xdg_wm_dialog_v1::XdgWmDialogV1Global,
+            zwlr_gamma_control_manager_v1::ZwlrGammaControlManagerV1Global,
             zwlr_layer_shell_v1::ZwlrLayerShellV1Global,
             zwlr_screencopy_manager_v1::ZwlrScreencopyManagerV1Global,
             zwp_idle_inhibit_manager_v1::ZwpIdleInhibitManagerV1Global,
+            zwp_keyboard_shortcuts_inhibit_manager_v1::ZwpKeyboardShortcutsInhibitManagerV1Global,
             zxdg_decoration_manager_v1::ZxdgDecorationManagerV1Global,
             zxdg_output_manager_v1::ZxdgOutputManagerV1Global,
         },
@@ -192,6 +194,7 @@ impl Globals {
         add_singleton!(XdgActivationV1Global);
         add_singleton!(ExtForeignToplevelListV1Global);
         add_singleton!(ZwpIdleInhibitManagerV1Global);
+        add_singleton!(ZwpKeyboardShortcutsInhibitManagerV1Global);
         add_singleton!(ExtIdleNotifierV1Global);
         add_singleton!(XdgToplevelDragManagerV1Global);
         add_singleton!(ZwlrDataControlManagerV1Global);
@@ -211,6 +214,7 @@ impl Globals {
         add_singleton!(WpFifoManagerV1Global);
         add_singleton!(WpCommitTimingManagerV1Global);
         add_singleton!(ExtDataControlManagerV1Global);
+        add_singleton!(ZwlrGammaControlManagerV1Global);
     }
 
     pub fn add_backend_singletons(&self, backend: &Rc<dyn Backend>) {
@@ -283,8 +287,20 @@ impl Globals {
         self.seats.lock()
     }
 
+    /// Returns the seat with the given name.
+    ///
+    /// Seat names are unique because seats are only created by name if no seat with that
+    /// name exists.
+    pub fn seat_by_name(&self, name: &str) -> Option<Rc<WlSeatGlobal>> {
+        self.seats
+            .lock()
+            .values()
+            .find(|s| s.seat_name() == name)
+            .cloned()
+    }
+
     pub fn notify_all(&self, registry: &Rc<WlRegistry>) {
-        let caps = registry.client.effective_caps;
+        let caps = registry.client.effective_caps.get();
         let xwayland = registry.client.is_xwayland;
         let globals = self.registry.lock();
         macro_rules! emit {
@@ -304,6 +320,29 @@ impl Globals {
         emit!(false);
     }
 
+    pub fn update_client_caps(&self, client: &Client, old: ClientCaps, new: ClientCaps) {
+        let globals = self.registry.lock();
+        let registries = client.lock_registries();
+        for global in globals.values() {
+            if global.xwayland_only() && !client.is_xwayland {
+                continue;
+            }
+            let required = global.required_caps();
+            let was_visible = old.contains(required);
+            let is_visible = new.contains(required);
+            if was_visible == is_visible {
+                continue;
+            }
+            for registry in registries.values() {
+                if is_visible {
+                    registry.send_global(global);
+                } else {
+                    registry.send_global_remove(global.name());
+                }
+            }
+        }
+    }
+
     fn broadcast<F: Fn(&Rc<WlRegistry>)>(
         &self,
         state: &State,
@@ -9,8 +9,10 @@ pub mod ext_output_image_capture_source_manager_v1;
 pub mod ext_session_lock_manager_v1;
 pub mod ext_session_lock_v1;
 pub mod ipc;
+pub mod jay_battery_watcher;
 pub mod jay_compositor;
 pub mod jay_damage_tracking;
+pub mod jay_debug;
 pub mod jay_ei_session;
 pub mod jay_ei_session_builder;
 pub mod jay_idle;
@@ -73,10 +75,13 @@ pub mod xdg_toplevel_drag_manager_v1;
 pub mod xdg_toplevel_drag_v1;
 pub mod xdg_wm_base;
 pub mod xdg_wm_dialog_v1;
+pub mod zwlr_gamma_control_manager_v1;
+pub mod zwlr_gamma_control_v1;
 pub mod zwlr_layer_shell_v1;
 pub mod zwlr_screencopy_frame_v1;
 pub mod zwlr_screencopy_manager_v1;
 pub mod zwp_idle_inhibit_manager_v1;
+pub mod zwp_keyboard_shortcuts_inhibit_manager_v1;
 pub mod zwp_linux_buffer_params_v1;
 pub mod zwp_linux_dmabuf_feedback_v1;
 pub mod zwp_linux_dmabuf_v1;
@@ -20,14 +20,21 @@ pub struct ExtIdleNotificationV1 {
     pub task: Cell<Option<SpawnedFuture<()>>>,
     pub seat: Rc<WlSeatGlobal>,
     pub duration_usec: u64,
+    pub ignore_inhibitors: bool,
     pub version: Version,
 }
 
 impl ExtIdleNotificationV1 {
     fn detach(&self) {
         self.seat.remove_idle_notification(self);
+        let idle = &self.client.state.idle;
+        idle.remove_inhibited_notification(self);
         self.task.take();
     }
+
+    pub fn is_inhibited(&self) -> bool {
+        !self.ignore_inhibitors && self.client.state.idle.inhibitors.is_not_empty()
+    }
 }
 
 impl ExtIdleNotificationV1RequestHandler for ExtIdleNotificationV1 {
@@ -6,7 +6,7 @@ use {
         leaks::Tracker,
         object::{Object, Version},
         utils::errorfmt::ErrorFmt,
-        wire::{ext_idle_notifier_v1::*, ExtIdleNotifierV1Id},
+        wire::{ext_idle_notifier_v1::*, ExtIdleNotificationV1Id, ExtIdleNotifierV1Id, WlSeatId},
     },
     std::{cell::Cell, rc::Rc},
     thiserror::Error,
@@ -46,28 +46,24 @@ pub struct ExtIdleNotifierV1 {
     pub version: Version,
 }
 
-impl ExtIdleNotifierV1RequestHandler for ExtIdleNotifierV1 {
-    type Error = ExtIdleNotifierV1Error;
-
-    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
-        self.client.remove_obj(self)?;
-        Ok(())
-    }
-
-    fn get_idle_notification(
+impl ExtIdleNotifierV1 {
+    fn create_notification(
         &self,
-        req: GetIdleNotification,
-        _slf: &Rc<Self>,
-    ) -> Result<(), Self::Error> {
-        let seat = self.client.lookup(req.seat)?;
+        id: ExtIdleNotificationV1Id,
+        timeout: u32,
+        seat: WlSeatId,
+        ignore_inhibitors: bool,
+    ) -> Result<(), ExtIdleNotifierV1Error> {
+        let seat = self.client.lookup(seat)?;
         let notification = Rc::new(ExtIdleNotificationV1 {
-            id: req.id,
+            id,
             client: self.client.clone(),
             tracker: Default::default(),
             resume: Default::default(),
             task: Cell::new(None),
             seat: seat.global.clone(),
-            duration_usec: (req.timeout as u64).max(1000).saturating_mul(1000),
+            duration_usec: (timeout as u64).max(1000).saturating_mul(1000),
+            ignore_inhibitors,
             version: self.version,
         });
         track!(self.client, notification);
@@ -82,10 +78,37 @@ impl ExtIdleNotifierV1RequestHandler for ExtIdleNotifierV1 {
     }
 }
 
+impl ExtIdleNotifierV1RequestHandler for ExtIdleNotifierV1 {
+    type Error = ExtIdleNotifierV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn get_idle_notification(
+        &self,
+        req: GetIdleNotification,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        self.create_notification(req.id, req.timeout, req.seat, false)
+    }
+
+    fn get_input_idle_notification(
+        &self,
+        req: GetInputIdleNotification,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        self.create_notification(req.id, req.timeout, req.seat, true)
+    }
+}
+
 async fn run(n: Rc<ExtIdleNotificationV1>) {
+    let mut start = n.seat.last_input();
     loop {
+        start = start.max(n.seat.last_input());
         let now = n.client.state.now_usec();
-        let elapsed = now.saturating_sub(n.seat.last_input());
+        let elapsed = now.saturating_sub(start);
         if elapsed < n.duration_usec {
             let res = n
                 .client
@@ -97,6 +120,10 @@ async fn run(n: Rc<ExtIdleNotificationV1>) {
                 log::error!("Could not wait for idle timeout to elapse: {}", ErrorFmt(e));
                 return;
             }
+        } else if n.is_inhibited() {
+            n.client.state.idle.add_inhibited_notification(&n);
+            n.resume.triggered().await;
+            start = n.client.state.now_usec();
         } else {
             n.send_idled();
             n.seat.add_idle_notification(&n);
@@ -118,7 +145,7 @@ impl Global for ExtIdleNotifierV1Global {
     }
 
     fn version(&self) -> u32 {
-        1
+        2
     }
 
     fn required_caps(&self) -> ClientCaps {
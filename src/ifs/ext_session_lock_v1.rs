@@ -12,6 +12,10 @@ use {
     thiserror::Error,
 };
 
+const INVALID_DESTROY: u32 = 0;
+const INVALID_UNLOCK: u32 = 1;
+const DUPLICATE_OUTPUT: u32 = 3;
+
 pub struct ExtSessionLockV1 {
     pub id: ExtSessionLockV1Id,
     pub client: Rc<Client>,
@@ -34,14 +38,26 @@ impl ExtSessionLockV1 {
         self.send_finished();
         self.finished.set(true);
     }
+
+    fn is_active(&self) -> bool {
+        match self.client.state.lock.lock.get() {
+            Some(lock) => lock.client.id == self.client.id && lock.id == self.id,
+            None => false,
+        }
+    }
 }
 
 impl ExtSessionLockV1RequestHandler for ExtSessionLockV1 {
     type Error = ExtSessionLockV1Error;
 
     fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
-        if !self.finished.get() {
-            self.client.state.lock.lock.take();
+        if self.is_active() {
+            self.client.protocol_error(
+                self,
+                INVALID_DESTROY,
+                "Cannot destroy a session lock without unlocking the session",
+            );
+            return Err(ExtSessionLockV1Error::InvalidDestroy);
         }
         self.client.remove_obj(self)?;
         Ok(())
@@ -67,6 +83,14 @@ impl ExtSessionLockV1RequestHandler for ExtSessionLockV1 {
         if !self.finished.get() {
             if let Some(node) = output.global.node() {
                 if node.lock_surface.is_some() {
+                    self.client.protocol_error(
+                        self,
+                        DUPLICATE_OUTPUT,
+                        &format!(
+                            "Output {} already has a lock surface",
+                            node.global.connector.name
+                        ),
+                    );
                     return Err(ExtSessionLockV1Error::OutputAlreadyLocked);
                 }
                 node.set_lock_surface(Some(new.clone()));
@@ -85,9 +109,14 @@ impl ExtSessionLockV1RequestHandler for ExtSessionLockV1 {
         _slf: &Rc<Self>,
     ) -> Result<(), Self::Error> {
         if !self.did_lock {
+            self.client.protocol_error(
+                self,
+                INVALID_UNLOCK,
+                "Cannot unlock a session lock that was never locked",
+            );
             return Err(ExtSessionLockV1Error::NeverLocked);
         }
-        if !self.finished.get() {
+        if self.is_active() {
             self.client.state.do_unlock();
         }
         self.client.remove_obj(self)?;
@@ -102,7 +131,7 @@ object_base! {
 
 impl Object for ExtSessionLockV1 {
     fn break_loops(&self) {
-        if !self.finished.get() {
+        if self.is_active() {
             self.client.state.lock.lock.take();
         }
     }
@@ -116,6 +145,8 @@ pub enum ExtSessionLockV1Error {
     ClientError(Box<ClientError>),
     #[error("The lock was not accepted")]
     NeverLocked,
+    #[error("The lock was destroyed while the session was locked")]
+    InvalidDestroy,
     #[error("The output already has a lock surface attached")]
     OutputAlreadyLocked,
     #[error(transparent)]
@@ -1,6 +1,6 @@
 use {
     crate::{
-        client::{Client, ClientError, ClientId},
+        client::{Client, ClientError, ClientId, GRAB_SERIAL_KINDS, SELECTION_SERIAL_KINDS},
         fixed::Fixed,
         ifs::{
             ipc::{
@@ -103,7 +103,12 @@ impl WlDataDeviceRequestHandler for WlDataDevice {
     type Error = WlDataDeviceError;
 
     fn start_drag(&self, req: StartDrag, _slf: &Rc<Self>) -> Result<(), Self::Error> {
-        let Some(serial) = self.client.map_serial(req.serial) else {
+        let Some(serial) = self.client.validate_input_serial(
+            "start_drag",
+            req.serial,
+            self.seat.id(),
+            GRAB_SERIAL_KINDS,
+        ) else {
             log::warn!("Client tried to start_drag with an invalid serial");
             return Ok(());
         };
@@ -124,7 +129,12 @@ impl WlDataDeviceRequestHandler for WlDataDevice {
     }
 
     fn set_selection(&self, req: SetSelection, _slf: &Rc<Self>) -> Result<(), Self::Error> {
-        let Some(serial) = self.client.map_serial(req.serial) else {
+        let Some(serial) = self.client.validate_input_serial(
+            "set_selection",
+            req.serial,
+            self.seat.id(),
+            SELECTION_SERIAL_KINDS,
+        ) else {
             log::warn!("Client tried to set_selection with an invalid serial");
             return Ok(());
         };
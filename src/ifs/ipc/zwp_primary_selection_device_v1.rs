@@ -1,6 +1,6 @@
 use {
     crate::{
-        client::{Client, ClientError, ClientId},
+        client::{Client, ClientError, ClientId, SELECTION_SERIAL_KINDS},
         ifs::{
             ipc::{
                 break_device_loops, destroy_data_device,
@@ -69,7 +69,12 @@ impl ZwpPrimarySelectionDeviceV1RequestHandler for ZwpPrimarySelectionDeviceV1 {
     type Error = ZwpPrimarySelectionDeviceV1Error;
 
     fn set_selection(&self, req: SetSelection, _slf: &Rc<Self>) -> Result<(), Self::Error> {
-        let Some(serial) = self.client.map_serial(req.serial) else {
+        let Some(serial) = self.client.validate_input_serial(
+            "set_selection",
+            req.serial,
+            self.seat.id(),
+            SELECTION_SERIAL_KINDS,
+        ) else {
             log::warn!("Client tried to set_selection with an invalid serial");
             return Ok(());
         };
@@ -0,0 +1,67 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        leaks::Tracker,
+        object::{Object, Version},
+        upower::BatteryStatus,
+        wire::{jay_battery_watcher::*, JayBatteryWatcherId},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct JayBatteryWatcher {
+    pub id: JayBatteryWatcherId,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+}
+
+impl JayBatteryWatcher {
+    pub fn send_status(&self, status: &BatteryStatus) {
+        self.client.event(Status {
+            self_id: self.id,
+            percentage: status.percentage,
+            state: status.state,
+            time_to_empty: status.time_to_empty,
+        });
+    }
+
+    fn remove_from_state(&self) {
+        self.client
+            .state
+            .upower
+            .watchers
+            .remove(&(self.client.id, self.id));
+    }
+}
+
+impl JayBatteryWatcherRequestHandler for JayBatteryWatcher {
+    type Error = JayBatteryWatcherError;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.remove_from_state();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = JayBatteryWatcher;
+    version = self.version;
+}
+
+impl Object for JayBatteryWatcher {
+    fn break_loops(&self) {
+        self.remove_from_state();
+    }
+}
+
+simple_add_obj!(JayBatteryWatcher);
+
+#[derive(Debug, Error)]
+pub enum JayBatteryWatcherError {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(JayBatteryWatcherError, ClientError);
@@ -4,6 +4,8 @@ use {
         client::{Client, ClientCaps, ClientError, CAP_JAY_COMPOSITOR},
         globals::{Global, GlobalName},
         ifs::{
+            jay_battery_watcher::JayBatteryWatcher,
+            jay_debug::JayDebug,
             jay_ei_session_builder::JayEiSessionBuilder,
             jay_idle::JayIdle,
             jay_input::JayInput,
@@ -72,7 +74,7 @@ impl Global for JayCompositorGlobal {
     }
 
     fn version(&self) -> u32 {
-        12
+        14
     }
 
     fn required_caps(&self) -> ClientCaps {
@@ -175,8 +177,7 @@ impl JayCompositorRequestHandler for JayCompositor {
     }
 
     fn quit(&self, _req: Quit, _slf: &Rc<Self>) -> Result<(), Self::Error> {
-        log::info!("Quitting");
-        self.client.state.ring.stop();
+        self.client.state.quit();
         Ok(())
     }
 
@@ -438,6 +439,37 @@ impl JayCompositorRequestHandler for JayCompositor {
         obj.done(tl);
         Ok(())
     }
+
+    fn get_debug(&self, req: GetDebug, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let obj = Rc::new(JayDebug {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        Ok(())
+    }
+
+    fn watch_battery(&self, req: WatchBattery, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let obj = Rc::new(JayBatteryWatcher {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        let upower = &self.client.state.upower;
+        let key = (self.client.id, req.id);
+        upower.watchers.set(key, obj.clone());
+        if let Some(status) = upower.status.get() {
+            obj.send_status(&status);
+        }
+        upower.start(&self.client.state);
+        Ok(())
+    }
 }
 
 object_base! {
@@ -0,0 +1,94 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        leaks::Tracker,
+        object::{Object, Version},
+        tree::dump_tree,
+        wire::{jay_debug::*, JayDebugId},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct JayDebug {
+    pub id: JayDebugId,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+}
+
+impl JayDebug {
+    fn send_line(&self, line: &str) {
+        self.client.event(Line {
+            self_id: self.id,
+            line,
+        });
+    }
+
+    fn send_done(&self) {
+        self.client.event(Done { self_id: self.id });
+    }
+}
+
+impl JayDebugRequestHandler for JayDebug {
+    type Error = JayDebugError;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn dump_objects(&self, _req: DumpObjects, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let mut clients: Vec<_> = self
+            .client
+            .state
+            .clients
+            .clients
+            .borrow()
+            .values()
+            .map(|c| c.data.clone())
+            .collect();
+        clients.sort_by_key(|c| c.id);
+        for client in clients {
+            let objects = client.objects.count_by_interface();
+            let total: usize = objects.iter().map(|(_, n)| n).sum();
+            self.send_line(&format!(
+                "client {}: pid={} comm={:?} objects={} queued_bytes={}",
+                client.id,
+                client.pid_info.pid,
+                client.pid_info.comm,
+                total,
+                client.queued_bytes(),
+            ));
+            for (interface, count) in objects {
+                self.send_line(&format!("  {}: {}", interface, count));
+            }
+        }
+        self.send_done();
+        Ok(())
+    }
+
+    fn dump_tree(&self, _req: DumpTree, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        for line in dump_tree(&self.client.state.root) {
+            self.send_line(&line);
+        }
+        self.send_done();
+        Ok(())
+    }
+}
+
+object_base! {
+    self = JayDebug;
+    version = self.version;
+}
+
+impl Object for JayDebug {}
+
+simple_add_obj!(JayDebug);
+
+#[derive(Debug, Error)]
+pub enum JayDebugError {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(JayDebugError, ClientError);
@@ -40,12 +40,11 @@ impl JayInput {
     }
 
     fn seat(&self, name: &str) -> Result<Rc<WlSeatGlobal>, JayInputError> {
-        for seat in self.client.state.globals.seats.lock().values() {
-            if seat.seat_name() == name {
-                return Ok(seat.clone());
-            }
-        }
-        Err(JayInputError::SeatDoesNotExist(name.to_string()))
+        self.client
+            .state
+            .globals
+            .seat_by_name(name)
+            .ok_or_else(|| JayInputError::SeatDoesNotExist(name.to_string()))
     }
 
     fn or_error(&self, f: impl FnOnce() -> Result<(), JayInputError>) -> Result<(), JayInputError> {
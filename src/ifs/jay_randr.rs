@@ -6,7 +6,7 @@ use {
         format::named_formats,
         leaks::Tracker,
         object::{Object, Version},
-        scale::Scale,
+        scale::{Scale, MAX_OUTPUT_SCALE, MIN_OUTPUT_SCALE},
         state::{ConnectorData, DrmDevData, OutputData},
         tree::{OutputNode, TearingMode, VrrMode},
         utils::{gfx_api_ext::GfxApiExt, transform_ext::TransformExt},
@@ -298,10 +298,18 @@ impl JayRandrRequestHandler for JayRandr {
     }
 
     fn set_scale(&self, req: SetScale, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let scale = Scale::from_wl(req.scale);
+        if !(MIN_OUTPUT_SCALE..=MAX_OUTPUT_SCALE).contains(&scale.to_f64()) {
+            self.send_error(&format!(
+                "Scale {} is outside of the supported range {}..={}",
+                scale, MIN_OUTPUT_SCALE, MAX_OUTPUT_SCALE,
+            ));
+            return Ok(());
+        }
         let Some(c) = self.get_output_node(req.output) else {
             return Ok(());
         };
-        c.set_preferred_scale(Scale::from_wl(req.scale));
+        c.set_preferred_scale(scale);
         Ok(())
     }
 
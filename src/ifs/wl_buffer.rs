@@ -226,7 +226,18 @@ impl WlBuffer {
 
     pub fn update_texture_or_log(&self, surface: &WlSurface, sync_shm: bool) {
         if let Err(e) = self.update_texture(surface, sync_shm) {
-            log::warn!("Could not update texture: {}", ErrorFmt(e));
+            if self.shm_sigbus_occurred() {
+                self.client.error(e);
+            } else {
+                log::warn!("Could not update texture: {}", ErrorFmt(e));
+            }
+        }
+    }
+
+    pub fn shm_sigbus_occurred(&self) -> bool {
+        match &*self.storage.borrow() {
+            Some(WlBufferStorage::Shm { mem, .. }) => mem.pool().sigbus_occurred(),
+            _ => false,
         }
     }
 
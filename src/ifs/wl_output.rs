@@ -4,6 +4,7 @@ use {
     crate::{
         backend,
         client::{Client, ClientError, ClientId},
+        cpu_worker::PendingJob,
         format::{Format, XRGB8888},
         globals::{Global, GlobalName},
         ifs::{wl_surface::WlSurface, zxdg_output_v1::ZxdgOutputV1},
@@ -16,6 +17,7 @@ use {
             cell_ext::CellExt, clonecell::CloneCell, copyhashmap::CopyHashMap,
             transform_ext::TransformExt,
         },
+        wallpaper::Wallpaper,
         wire::{wl_output::*, WlOutputId, ZxdgOutputV1Id},
     },
     ahash::AHashMap,
@@ -101,6 +103,8 @@ pub struct PersistentOutputState {
     pub vrr_mode: Cell<&'static VrrMode>,
     pub vrr_cursor_hz: Cell<Option<f64>>,
     pub tearing_mode: Cell<&'static TearingMode>,
+    pub wallpaper: CloneCell<Option<Rc<Wallpaper>>>,
+    pub pending_wallpaper: Cell<Option<PendingJob>>,
 }
 
 #[derive(Eq, PartialEq, Hash, Debug)]
@@ -234,6 +238,7 @@ impl WlOutputGlobal {
         }
         if obj.version >= SEND_NAME_SINCE {
             obj.send_name();
+            obj.send_description();
         }
         if obj.version >= SEND_DONE_SINCE {
             obj.send_done();
@@ -241,6 +246,22 @@ impl WlOutputGlobal {
         Ok(())
     }
 
+    /// Returns a human-readable description of the monitor, e.g. `Dell Inc. U2415 (DP-1)`.
+    ///
+    /// Falls back to the connector name if the EDID did not identify the monitor.
+    pub fn description(&self) -> String {
+        let id = &self.output_id;
+        let parts: Vec<&str> = [&id.manufacturer, &id.model, &id.serial_number]
+            .into_iter()
+            .map(|p| p.as_str())
+            .filter(|p| !p.is_empty())
+            .collect();
+        if parts.is_empty() {
+            return self.connector.name.clone();
+        }
+        format!("{} ({})", parts.join(" "), self.connector.name)
+    }
+
     pub fn pixel_size(&self) -> (i32, i32) {
         let mode = self.mode.get();
         self.persistent
@@ -286,13 +307,12 @@ impl WlOutput {
         if self.version >= SEND_SCALE_SINCE {
             self.send_scale();
         }
+        for xdg in self.xdg_outputs.lock().values() {
+            xdg.send_updates();
+        }
         if self.version >= SEND_DONE_SINCE {
             self.send_done();
         }
-        let xdg = self.xdg_outputs.lock();
-        for xdg in xdg.values() {
-            xdg.send_updates();
-        }
     }
 
     fn send_geometry(&self) {
@@ -358,6 +378,16 @@ impl WlOutput {
         });
     }
 
+    fn send_description(&self) {
+        let Some(global) = self.global.get() else {
+            return;
+        };
+        self.client.event(Description {
+            self_id: self.id,
+            description: &global.description(),
+        });
+    }
+
     pub fn send_done(&self) {
         let event = Done { self_id: self.id };
         self.client.event(event);
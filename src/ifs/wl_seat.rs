@@ -24,7 +24,7 @@ use {
     crate::{
         async_engine::SpawnedFuture,
         backend::KeyState,
-        client::{Client, ClientError, ClientId},
+        client::{Client, ClientError, ClientId, InputSerialKind},
         cursor_user::{CursorUser, CursorUserGroup, CursorUserOwner},
         ei::ei_ifs::ei_seat::EiSeat,
         fixed::Fixed,
@@ -68,6 +68,7 @@ use {
                 dnd_icon::DndIcon,
                 tray::{DynTrayItem, TrayItemId},
                 xdg_surface::xdg_popup::XdgPopup,
+                zwp_keyboard_shortcuts_inhibitor_v1::ZwpKeyboardShortcutsInhibitorV1,
                 WlSurface,
             },
             xdg_toplevel_drag_v1::XdgToplevelDragV1,
@@ -83,7 +84,7 @@ use {
         utils::{
             asyncevent::AsyncEvent, bindings::PerClientBindings, clonecell::CloneCell,
             copyhashmap::CopyHashMap, errorfmt::ErrorFmt, linkedlist::LinkedNode, numcell::NumCell,
-            rc_eq::rc_eq, smallmap::SmallMap,
+            rc_eq::rc_eq, smallmap::SmallMap, transform_ext::TransformExt,
         },
         wire::{
             wl_seat::*, ExtIdleNotificationV1Id, WlDataDeviceId, WlKeyboardId, WlPointerId,
@@ -93,12 +94,16 @@ use {
         wire_ei::EiSeatId,
         xkbcommon::{DynKeyboardState, KeyboardState, KeymapId, XkbKeymap, XkbState},
     },
-    ahash::AHashMap,
-    jay_config::keyboard::{AppMod, ModifiedKeySym},
+    ahash::{AHashMap, AHashSet},
+    jay_config::{
+        input::{DeviceMatch, PointerContext, PointerInput},
+        keyboard::{mods::Modifiers, syms::KeySym, AppMod, ModifiedKeySym},
+        video::Transform,
+    },
     smallvec::SmallVec,
     std::{
         cell::{Cell, RefCell},
-        collections::hash_map::Entry,
+        collections::{hash_map::Entry, VecDeque},
         mem,
         ops::{Deref, DerefMut},
         rc::{Rc, Weak},
@@ -133,6 +138,8 @@ const TOUCH: u32 = 4;
 #[expect(dead_code)]
 const MISSING_CAPABILITY: u32 = 0;
 
+const FOCUS_HISTORY_DEPTH: usize = 32;
+
 pub const BTN_LEFT: u32 = 0x110;
 pub const BTN_RIGHT: u32 = 0x111;
 
@@ -215,6 +222,9 @@ pub struct WlSeatGlobal {
     current_shortcuts: RefCell<ShortcutsOrTunnels>,
     current_app_mod: RefCell<AppMod>,
     current_top_app_name: RefCell<String>,
+    app_mod_escape: Cell<Option<ModifiedKeySym>>,
+    shortcuts_inhibit_escape: Cell<Option<ModifiedKeySym>>,
+    app_mod_forward_keys: RefCell<AHashMap<AppMod, bool>>,
     queue_link: RefCell<Option<LinkedNode<Rc<Self>>>>,
     tree_changed_handler: Cell<Option<SpawnedFuture<()>>>,
     changes: NumCell<u32>,
@@ -227,6 +237,10 @@ pub struct WlSeatGlobal {
     input_method_grab: CloneCell<Option<Rc<ZwpInputMethodKeyboardGrabV2>>>,
     forward: Cell<bool>,
     focus_follows_mouse: Cell<bool>,
+    focus_follows_mouse_delay_msec: Cell<u64>,
+    focus_follows_mouse_task: Cell<Option<SpawnedFuture<()>>>,
+    warp_pointer_to_focus: Cell<bool>,
+    device_matches: RefCell<Vec<DeviceMatch>>,
     swipe_bindings: PerClientBindings<ZwpPointerGestureSwipeV1>,
     pinch_bindings: PerClientBindings<ZwpPointerGesturePinchV1>,
     hold_bindings: PerClientBindings<ZwpPointerGestureHoldV1>,
@@ -235,6 +249,11 @@ pub struct WlSeatGlobal {
     ui_drag_highlight: Cell<Option<Rect>>,
     keyboard_node_serial: Cell<u64>,
     tray_popups: CopyHashMap<(TrayItemId, XdgPopupId), Rc<dyn DynTrayItem>>,
+    shortcuts_inhibitor: CloneCell<Option<Rc<ZwpKeyboardShortcutsInhibitorV1>>>,
+    pointer_bindings: RefCell<AHashSet<(Modifiers, PointerInput, PointerContext)>>,
+    repeating_shortcuts: RefCell<AHashSet<(Modifiers, KeySym, AppMod)>>,
+    shortcut_repeat: Cell<Option<SpawnedFuture<()>>>,
+    focus_history: RefCell<VecDeque<Weak<dyn ToplevelNode>>>,
 }
 
 const CHANGE_CURSOR_MOVED: u32 = 1 << 0;
@@ -309,6 +328,9 @@ impl WlSeatGlobal {
                 AppMod::default(),
             )])),
             current_top_app_name: RefCell::new(AppMod::default().app_name),
+            app_mod_escape: Default::default(),
+            shortcuts_inhibit_escape: Default::default(),
+            app_mod_forward_keys: Default::default(),
             queue_link: Default::default(),
             tree_changed_handler: Cell::new(None),
             changes: NumCell::new(CHANGE_CURSOR_MOVED | CHANGE_TREE),
@@ -322,6 +344,10 @@ impl WlSeatGlobal {
             input_method_grab: Default::default(),
             forward: Cell::new(false),
             focus_follows_mouse: Cell::new(true),
+            focus_follows_mouse_delay_msec: Cell::new(0),
+            focus_follows_mouse_task: Default::default(),
+            warp_pointer_to_focus: Cell::new(false),
+            device_matches: Default::default(),
             swipe_bindings: Default::default(),
             pinch_bindings: Default::default(),
             hold_bindings: Default::default(),
@@ -329,6 +355,11 @@ impl WlSeatGlobal {
             ei_seats: Default::default(),
             ui_drag_highlight: Default::default(),
             tray_popups: Default::default(),
+            shortcuts_inhibitor: Default::default(),
+            pointer_bindings: Default::default(),
+            repeating_shortcuts: Default::default(),
+            shortcut_repeat: Default::default(),
+            focus_history: Default::default(),
         });
         slf.pointer_cursor.set_owner(slf.clone());
         let seat = slf.clone();
@@ -459,6 +490,30 @@ impl WlSeatGlobal {
         }
     }
 
+    #[cfg_attr(not(feature = "it"), expect(dead_code))]
+    pub fn app_mod(&self) -> AppMod {
+        self.current_app_mod.borrow().clone()
+    }
+
+    pub fn set_app_mod_escape(&self, escape: Option<ModifiedKeySym>) {
+        self.app_mod_escape.set(escape);
+    }
+
+    pub fn set_app_mod_forward_keys(&self, app_mod: AppMod, forward: bool) {
+        self.app_mod_forward_keys
+            .borrow_mut()
+            .insert(app_mod, forward);
+    }
+
+    /// Returns whether keys that don't trigger a shortcut are sent to the focused client.
+    fn app_mod_forwards_keys(&self) -> bool {
+        let app_mod = self.current_app_mod.borrow();
+        match self.app_mod_forward_keys.borrow().get(&*app_mod) {
+            Some(forward) => *forward,
+            None => app_mod.is_insert(),
+        }
+    }
+
     pub fn for_each_x_data_device(&self, mut f: impl FnMut(&Rc<XIpcDevice>)) {
         for (_, dev) in &self.x_data_devices {
             f(&dev);
@@ -599,6 +654,12 @@ impl WlSeatGlobal {
         }
     }
 
+    pub fn set_window_opacity(&self, opacity: f32) {
+        if let Some(tl) = self.keyboard_node.get().node_toplevel() {
+            tl.tl_data().set_opacity(opacity);
+        }
+    }
+
     pub fn get_fullscreen(&self) -> bool {
         if let Some(tl) = self.keyboard_node.get().node_toplevel() {
             return tl.tl_data().is_fullscreen.get();
@@ -622,16 +683,31 @@ impl WlSeatGlobal {
         self.for_each_ei_seat(|ei_seat| {
             ei_seat.handle_xkb_state_change(old.kb_state.id, &new.kb_state);
         });
-        let Some(surface) = self.keyboard_node.get().node_into_surface() else {
-            return;
-        };
-        let serial = surface.client.next_serial();
-        self.surface_kb_event(Version::ALL, &surface, |kb| {
-            if kb.kb_state_id() == old.kb_state.id {
-                kb.send_leave(serial, surface.id);
-                kb.enter(serial, surface.id, &new.kb_state);
+        let surface = self.keyboard_node.get().node_into_surface();
+        if let Some(surface) = &surface {
+            let serial = surface.client.next_serial();
+            let kind = InputSerialKind::KeyboardEnter;
+            surface.client.add_input_serial(self.id, kind, serial);
+            self.surface_kb_event(Version::ALL, surface, |kb| {
+                if kb.kb_state_id() == old.kb_state.id {
+                    kb.send_leave(serial, surface.id);
+                    kb.enter(serial, surface.id, &new.kb_state);
+                }
+            });
+        }
+        let focus = surface.map(|s| s.client.id);
+        for (client, seats) in self.bindings.borrow().iter() {
+            if Some(*client) == focus {
+                continue;
             }
-        });
+            for seat in seats.values() {
+                for kb in seat.keyboards.lock().values() {
+                    if kb.kb_state_id() == old.kb_state.id {
+                        kb.update_keymap(&new.kb_state);
+                    }
+                }
+            }
+        }
     }
 
     pub fn create_tunnel(
@@ -722,6 +798,10 @@ impl WlSeatGlobal {
         self.kb_parent_container().map(|c| c.mono_child.is_some())
     }
 
+    pub fn get_stacked(&self) -> Option<bool> {
+        self.kb_parent_container().map(|c| c.stacked.get())
+    }
+
     pub fn get_split(&self) -> Option<ContainerSplit> {
         self.kb_parent_container().map(|c| c.split.get())
     }
@@ -737,6 +817,12 @@ impl WlSeatGlobal {
         }
     }
 
+    pub fn set_stacked(&self, stacked: bool) {
+        if let Some(c) = self.kb_parent_container() {
+            c.set_stacked(stacked);
+        }
+    }
+
     pub fn set_split(&self, axis: ContainerSplit) {
         if let Some(c) = self.kb_parent_container() {
             c.set_split(axis);
@@ -812,6 +898,71 @@ impl WlSeatGlobal {
         }
     }
 
+    pub fn send_to_scratch(self: &Rc<Self>) {
+        let Some(scratch) = self.state.scratch_workspace.get() else {
+            return;
+        };
+        let tl = match self.keyboard_node.get().node_toplevel() {
+            Some(tl) => tl,
+            _ => return,
+        };
+        let data = tl.tl_data();
+        if data.is_fullscreen.get() {
+            return;
+        }
+        let Some(old_ws) = data.workspace.get() else {
+            return;
+        };
+        if old_ws.id == scratch.id {
+            return;
+        }
+        let Some(parent) = data.parent.get() else {
+            return;
+        };
+        let (width, height) = data.float_size(&old_ws);
+        let kb_foci = collect_kb_foci(tl.clone().tl_into_node());
+        parent.cnode_remove_child2(tl.tl_as_node(), true);
+        for focus in kb_foci {
+            old_ws
+                .clone()
+                .node_do_focus(&focus, Direction::Unspecified);
+        }
+        // The scratch workspace has an empty area. Pass an explicit position so that
+        // the window keeps its size.
+        self.state
+            .map_floating(tl, width, height, &scratch, Some((0, 0)));
+    }
+
+    /// Moves the window that was most recently sent to the scratchpad and whose app id
+    /// contains `app_id` to the current workspace and focuses it.
+    pub fn fetch_from_scratch(self: &Rc<Self>, app_id: &str) {
+        let Some(scratch) = self.state.scratch_workspace.get() else {
+            return;
+        };
+        let mut tl = None;
+        for stacked in scratch.stacked.iter() {
+            let node = stacked.deref().clone().stacked_into_node();
+            let Some(child) = node.node_into_float().and_then(|f| f.child.get()) else {
+                continue;
+            };
+            if child.tl_data().app_id.borrow().contains(app_id) {
+                tl = Some(child);
+            }
+        }
+        let Some(tl) = tl else {
+            return;
+        };
+        let Some(parent) = tl.tl_data().parent.get() else {
+            return;
+        };
+        let ws = self.get_output().ensure_workspace();
+        parent.cnode_remove_child2(tl.tl_as_node(), true);
+        let (width, height) = tl.tl_data().float_size(&ws);
+        self.state
+            .map_floating(tl.clone(), width, height, &ws, None);
+        self.focus_node(tl.tl_into_node());
+    }
+
     pub fn get_rate(&self) -> (i32, i32) {
         self.repeat_rate.get()
     }
@@ -836,8 +987,17 @@ impl WlSeatGlobal {
 
     pub fn close(self: &Rc<Self>) {
         let kb_node = self.keyboard_node.get();
-        if let Some(tl) = kb_node.node_toplevel() {
-            tl.tl_close();
+        match kb_node.node_toplevel() {
+            Some(tl) => tl.tl_close(),
+            None => log::info!("Cannot close a window because no window is focused"),
+        }
+    }
+
+    pub fn kill(self: &Rc<Self>) {
+        let kb_node = self.keyboard_node.get();
+        match kb_node.node_toplevel() {
+            Some(tl) => tl.tl_kill(),
+            None => log::info!("Cannot kill a window because no window is focused"),
         }
     }
 
@@ -847,12 +1007,88 @@ impl WlSeatGlobal {
             _ => return,
         };
         if direction == Direction::Down && tl.node_is_container() {
-            tl.node_do_focus(self, direction);
+            tl.clone().node_do_focus(self, direction);
         } else if let Some(p) = tl.tl_data().parent.get() {
             if let Some(c) = p.node_into_container() {
                 c.move_focus_from_child(self, tl.deref(), direction);
             }
         }
+        if self.warp_pointer_to_focus.get() {
+            if let Some(new) = self.keyboard_node.get().node_toplevel() {
+                if new.node_id() != tl.node_id() {
+                    self.warp_pointer_to_toplevel(&new);
+                }
+            }
+        }
+    }
+
+    pub fn focus_tab(self: &Rc<Self>, next: bool) {
+        let Some(mut tl) = self.keyboard_node.get().node_toplevel() else {
+            return;
+        };
+        loop {
+            let Some(parent) = tl.tl_data().parent.get() else {
+                return;
+            };
+            let Some(c) = parent.node_into_container() else {
+                return;
+            };
+            if c.mono_child.is_some() {
+                c.focus_tab(self, next);
+                return;
+            }
+            tl = c;
+        }
+    }
+
+    fn push_focus_history(&self, node: &Rc<dyn Node>) {
+        let Some(tl) = node.clone().node_toplevel() else {
+            return;
+        };
+        if tl.node_is_container() || tl.node_is_placeholder() {
+            return;
+        }
+        let mut history = self.focus_history.borrow_mut();
+        history.retain(|e| match e.upgrade() {
+            Some(e) => e.node_id() != tl.node_id(),
+            _ => false,
+        });
+        history.push_front(Rc::downgrade(&tl));
+        history.truncate(FOCUS_HISTORY_DEPTH);
+    }
+
+    /// Focuses the most recently focused toplevel other than the current one.
+    ///
+    /// If the toplevel is on a workspace that is not currently visible, that workspace is
+    /// shown first. The pointer is moved to the center of the toplevel.
+    pub fn focus_previous(self: &Rc<Self>) {
+        let current = self.keyboard_node.get().node_toplevel();
+        let current = current.map(|tl| tl.node_id());
+        let tl = {
+            let history = self.focus_history.borrow();
+            history.iter().filter_map(|e| e.upgrade()).find(|tl| {
+                let data = tl.tl_data();
+                Some(tl.node_id()) != current
+                    && data.parent.is_some()
+                    && data.workspace.get().is_some_and(|ws| !ws.is_dummy)
+            })
+        };
+        let Some(tl) = tl else {
+            return;
+        };
+        let data = tl.tl_data();
+        if !data.visible.get() {
+            if let Some(ws) = data.workspace.get() {
+                let output = ws.output.get();
+                if output.show_workspace(&ws) {
+                    ws.flush_jay_workspaces();
+                    output.schedule_update_render_data();
+                    self.state.tree_changed();
+                }
+            }
+        }
+        self.focus_toplevel(tl.clone());
+        self.warp_pointer_to_toplevel(&tl);
     }
 
     pub fn move_focused(self: &Rc<Self>, direction: Direction) {
@@ -952,6 +1188,10 @@ impl WlSeatGlobal {
         }
     }
 
+    pub fn start_pointer_move(self: &Rc<Self>) {
+        self.pointer_owner.start_pointer_move(self);
+    }
+
     pub fn cancel_dnd(self: &Rc<Self>) {
         self.pointer_owner.cancel_dnd(self);
     }
@@ -1046,6 +1286,43 @@ impl WlSeatGlobal {
         self.pointer_owner.remove_dnd_icon();
     }
 
+    #[cfg_attr(not(feature = "it"), expect(dead_code))]
+    pub fn dnd_client(&self) -> Option<ClientId> {
+        self.pointer_owner.dnd_client()
+    }
+
+    pub fn client_removed(self: &Rc<Self>, client: ClientId) {
+        if self.pointer_owner.dnd_client() == Some(client) {
+            self.cancel_dnd();
+        }
+        let dropped = {
+            let mut dropped = self.dropped_dnd.borrow_mut();
+            match &*dropped {
+                Some(d) if d.dnd.client.id == client => dropped.take(),
+                _ => None,
+            }
+        };
+        drop(dropped);
+        if self.pointer_owner.grab_client() == Some(client) {
+            self.pointer_owner.revert_to_default(self);
+        }
+        let owned_by = |src: Option<Rc<dyn DynDataSource>>| match src {
+            Some(src) => src.source_data().client.id == client,
+            None => false,
+        };
+        if owned_by(self.selection.get()) {
+            self.unset_selection();
+        }
+        if owned_by(self.primary_selection.get()) {
+            self.unset_primary_selection();
+        }
+    }
+
+    #[cfg_attr(not(feature = "it"), expect(dead_code))]
+    pub fn grab_client(&self) -> Option<ClientId> {
+        self.pointer_owner.grab_client()
+    }
+
     pub fn pointer_cursor(&self) -> &Rc<CursorUser> {
         &self.pointer_cursor
     }
@@ -1077,6 +1354,9 @@ impl WlSeatGlobal {
         *self.dropped_dnd.borrow_mut() = None;
         self.queue_link.take();
         self.tree_changed_handler.set(None);
+        self.focus_follows_mouse_task.take();
+        self.shortcut_repeat.take();
+        self.focus_history.borrow_mut().clear();
         self.constraint.take();
         self.text_inputs.borrow_mut().clear();
         self.text_input.take();
@@ -1088,6 +1368,7 @@ impl WlSeatGlobal {
         self.cursor_user_group.detach();
         self.tablet_clear();
         self.ei_seats.clear();
+        self.shortcuts_inhibitor.take();
     }
 
     pub fn id(&self) -> SeatId {
@@ -1166,6 +1447,31 @@ impl WlSeatGlobal {
         self.forward.set(forward);
     }
 
+    pub fn keyboard_node(&self) -> Rc<dyn Node> {
+        self.keyboard_node.get()
+    }
+
+    pub fn set_shortcuts_inhibitor(&self, inhibitor: Option<Rc<ZwpKeyboardShortcutsInhibitorV1>>) {
+        self.shortcuts_inhibitor.set(inhibitor);
+    }
+
+    pub fn set_shortcuts_inhibit_escape(&self, escape: Option<ModifiedKeySym>) {
+        self.shortcuts_inhibit_escape.set(escape);
+    }
+
+    fn deactivate_shortcuts_inhibitor(&self) {
+        if let Some(inhibitor) = self.shortcuts_inhibitor.get() {
+            inhibitor.deactivate();
+        }
+    }
+
+    pub fn shortcuts_inhibited(&self) -> bool {
+        match self.shortcuts_inhibitor.get() {
+            Some(inhibitor) => inhibitor.surface.node_id() == self.keyboard_node.get().node_id(),
+            None => false,
+        }
+    }
+
     pub fn select_toplevel(self: &Rc<Self>, selector: impl ToplevelSelector) {
         self.pointer_owner.select_toplevel(self, selector);
     }
@@ -1176,6 +1482,38 @@ impl WlSeatGlobal {
 
     pub fn set_focus_follows_mouse(&self, focus_follows_mouse: bool) {
         self.focus_follows_mouse.set(focus_follows_mouse);
+        if !focus_follows_mouse {
+            self.focus_follows_mouse_task.take();
+        }
+    }
+
+    pub fn set_focus_follows_mouse_delay(&self, msec: u64) {
+        self.focus_follows_mouse_delay_msec.set(msec);
+    }
+
+    pub fn set_warp_pointer_to_focus(&self, warp: bool) {
+        self.warp_pointer_to_focus.set(warp);
+    }
+
+    pub fn set_device_matches(self: &Rc<Self>, matches: Vec<DeviceMatch>) {
+        *self.device_matches.borrow_mut() = matches;
+        let devices: Vec<_> = self
+            .state
+            .input_device_handlers
+            .borrow()
+            .values()
+            .map(|d| d.data.clone())
+            .collect();
+        for dev in devices {
+            if self.claims_device(&dev) {
+                dev.set_seat(Some(self.clone()));
+            }
+        }
+    }
+
+    pub fn claims_device(&self, dev: &DeviceHandlerData) -> bool {
+        let matches = self.device_matches.borrow();
+        matches.iter().any(|m| dev.matches(m))
     }
 
     pub fn set_window_management_enabled(self: &Rc<Self>, enabled: bool) {
@@ -1464,6 +1802,14 @@ impl DeviceHandlerData {
         self.update_xkb_state();
     }
 
+    pub fn matches(&self, m: &DeviceMatch) -> bool {
+        match m {
+            DeviceMatch::Name(name) => *self.device.name() == *name,
+            DeviceMatch::Syspath(syspath) => self.syspath.as_ref() == Some(syspath),
+            DeviceMatch::Devnode(devnode) => self.devnode.as_ref() == Some(devnode),
+        }
+    }
+
     fn get_effective_xkb_state(&self, seat: &WlSeatGlobal) -> Rc<RefCell<XkbState>> {
         match self.xkb_state.get() {
             Some(s) => s,
@@ -1510,4 +1856,24 @@ impl DeviceHandlerData {
         }
         state.root.extents.get()
     }
+
+    pub fn get_touch_position(
+        &self,
+        state: &State,
+        x_normed: Fixed,
+        y_normed: Fixed,
+    ) -> (Fixed, Fixed) {
+        let mut rect = state.root.extents.get();
+        let mut transform = Transform::None;
+        if let Some(output) = self.output.get() {
+            if let Some(output) = output.get() {
+                rect = output.pos.get();
+                transform = output.persistent.transform.get();
+            }
+        }
+        let (x, y) = transform.unapply_normalized_point((x_normed.to_f64(), y_normed.to_f64()));
+        let x = Fixed::from_f64(rect.x1() as f64 + rect.width() as f64 * x);
+        let y = Fixed::from_f64(rect.y1() as f64 + rect.height() as f64 * y);
+        (x, y)
+    }
 }
@@ -4,7 +4,7 @@ use {
         backend::{
             AxisSource, ConnectorId, InputDeviceId, InputEvent, KeyState, ScrollAxis, AXIS_120,
         },
-        client::ClientId,
+        client::{ClientId, InputSerialKind, KEYBOARD_SERIAL_KINDS, POINTER_SERIAL_KINDS},
         config::InvokedShortcut,
         ei::ei_ifs::ei_seat::EiSeat,
         fixed::Fixed,
@@ -35,16 +35,18 @@ use {
             wl_surface::{xdg_surface::xdg_popup::XdgPopup, WlSurface},
         },
         object::Version,
-        rect::Rect,
         state::DeviceHandlerData,
-        tree::{Direction, Node, ToplevelNode},
-        utils::{bitflags::BitflagsExt, hash_map_ext::HashMapExt, smallmap::SmallMap},
+        tree::{Direction, Node, NodeId, ToplevelNode},
+        utils::{
+            bitflags::BitflagsExt, errorfmt::ErrorFmt, hash_map_ext::HashMapExt, smallmap::SmallMap,
+        },
         wire::WlDataOfferId,
         xkbcommon::{KeyboardState, XkbKeyDirection, XkbState, XKB_KEY_DOWN, XKB_KEY_UP},
     },
+    ahash::AHashMap,
     isnt::std_1::primitive::{IsntSlice2Ext, IsntSliceExt},
     jay_config::{
-        input::SwitchEvent,
+        input::{PointerContext, PointerInput, SwitchEvent},
         keyboard::{
             mods::{Modifiers, CAPS, NUM, RELEASE},
             syms::{KeySym, SYM_Escape},
@@ -276,6 +278,7 @@ impl<'seat> KeyEventState<'seat> {
     ) where
         F: FnMut() -> Rc<RefCell<XkbState>>,
     {
+        seat.shortcut_repeat.take();
         let xkb_state_rc = get_state();
         let key_event_state = &mut Self {
             seat,
@@ -285,7 +288,7 @@ impl<'seat> KeyEventState<'seat> {
             xkb_state_rc,
             new_mods: false,
             shortcuts: Default::default(),
-            forward: seat.current_app_mod.borrow().is_insert(),
+            forward: seat.app_mod_forwards_keys(),
             // Will be setup in key_event_state.prepare() :
             xkb_dir: XKB_KEY_UP,
             state: Default::default(),
@@ -308,13 +311,26 @@ impl<'seat> KeyEventState<'seat> {
         //     self.xkb_state_rc.borrow().kb_state.pressed_keys
         // );
         let get_state = &mut get_state;
-        if self.handle_shortcut_modal(get_state) {
-            // Tunnel handled, nothing more to do.
-            return;
-        };
-        if self.handle_shortcut_global(get_state) {
-            // Tunnel handled, nothing more to do.
-            return;
+        if seat.shortcuts_inhibited() {
+            if self.handle_shortcuts_inhibit_escape() {
+                // The key press is consumed and its release is ignored.
+                return;
+            }
+            // The focused client has asked for all keys, including shortcuts.
+            self.forward = true;
+        } else {
+            if self.handle_app_mod_escape() {
+                // The key press is consumed and its release is ignored.
+                return;
+            }
+            if self.handle_shortcut_modal(get_state) {
+                // Tunnel handled, nothing more to do.
+                return;
+            };
+            if self.handle_shortcut_global(get_state) {
+                // Tunnel handled, nothing more to do.
+                return;
+            }
         }
         self.handle_key_event(get_state);
         self.clean_up();
@@ -360,6 +376,40 @@ impl<'seat> KeyEventState<'seat> {
         self.xkb_dir = xkb_dir;
         false
     }
+    fn handle_app_mod_escape(&self) -> bool {
+        let seat = self.seat;
+        let Some(escape) = seat.app_mod_escape.get() else {
+            return false;
+        };
+        if self.state != wl_keyboard::PRESSED || seat.state.lock.locked.get() {
+            return false;
+        }
+        if *seat.current_app_mod.borrow() == AppMod::default() {
+            return false;
+        }
+        if !self.is_key(escape) {
+            return false;
+        }
+        seat.set_app_mod(AppMod::default());
+        true
+    }
+    fn handle_shortcuts_inhibit_escape(&self) -> bool {
+        let seat = self.seat;
+        let Some(escape) = seat.shortcuts_inhibit_escape.get() else {
+            return false;
+        };
+        if self.state != wl_keyboard::PRESSED || !self.is_key(escape) {
+            return false;
+        }
+        seat.deactivate_shortcuts_inhibitor();
+        true
+    }
+    fn is_key(&self, key: ModifiedKeySym) -> bool {
+        let xkb_state = self.xkb_state_rc.borrow();
+        let mods = xkb_state.mods().mods_effective & !(CAPS.0 | NUM.0);
+        let keysyms = xkb_state.unmodified_keysyms(self.key);
+        mods == key.mods.0 && keysyms.contains(&key.sym.0)
+    }
     fn handle_shortcut_modal<F>(&mut self, get_state: &mut F) -> bool
     where
         F: FnMut() -> Rc<RefCell<XkbState>>,
@@ -546,6 +596,9 @@ impl<'seat> KeyEventState<'seat> {
                 for shortcut in shortcuts {
                     config.invoke_shortcut(seat.id(), &shortcut);
                 }
+                if state == wl_keyboard::PRESSED {
+                    seat.start_shortcut_repeat(shortcuts);
+                }
                 xkb_state_rc = get_state();
                 xkb_state = xkb_state_rc.borrow_mut();
                 if id != xkb_state.kb_state.id {
@@ -796,14 +849,20 @@ impl WlSeatGlobal {
                 id,
                 x_normed,
                 y_normed,
-            } => self.touch_down(time_usec, id, dev.get_rect(&self.state), x_normed, y_normed),
+            } => {
+                let (x, y) = dev.get_touch_position(&self.state, x_normed, y_normed);
+                self.touch_down_at(time_usec, id, x, y)
+            }
             InputEvent::TouchUp { time_usec, id } => self.touch_up(time_usec, id),
             InputEvent::TouchMotion {
                 time_usec,
                 id,
                 x_normed,
                 y_normed,
-            } => self.touch_motion(time_usec, id, dev.get_rect(&self.state), x_normed, y_normed),
+            } => {
+                let (x, y) = dev.get_touch_position(&self.state, x_normed, y_normed);
+                self.touch_motion_at(time_usec, id, x, y)
+            }
             InputEvent::TouchCancel { time_usec, id } => self.touch_cancel(time_usec, id),
             InputEvent::TouchFrame { time_usec } => self.touch_frame(time_usec),
         }
@@ -827,6 +886,26 @@ impl WlSeatGlobal {
         (x, y)
     }
 
+    pub fn warp_pointer_to_toplevel(self: &Rc<Self>, tl: &Rc<dyn ToplevelNode>) {
+        if !self.pointer_owner.allows_warp() {
+            return;
+        }
+        let rect = tl.node_absolute_position();
+        let (x, y) = self.pointer_cursor.position_int();
+        if rect.is_empty() || rect.contains(x, y) {
+            return;
+        }
+        let (x, y) = rect.center();
+        let (x, y) = self.set_pointer_cursor_position(Fixed::from_int(x), Fixed::from_int(y));
+        if let Some(c) = self.constraint.get() {
+            if c.ty == ConstraintType::Lock || !c.contains(x.round_down(), y.round_down()) {
+                c.deactivate();
+            }
+        }
+        self.pos_time_usec.set(self.state.now_usec());
+        self.apply_changes();
+    }
+
     fn connector_position_event(
         self: &Rc<Self>,
         time_usec: u64,
@@ -1051,19 +1130,6 @@ impl WlSeatGlobal {
         }
     }
 
-    fn touch_down(
-        self: &Rc<Self>,
-        time_usec: u64,
-        id: i32,
-        rect: Rect,
-        x_normed: Fixed,
-        y_normed: Fixed,
-    ) {
-        let x = Fixed::from_f64(rect.x1() as f64 + rect.width() as f64 * x_normed.to_f64());
-        let y = Fixed::from_f64(rect.y1() as f64 + rect.height() as f64 * y_normed.to_f64());
-        self.touch_down_at(time_usec, id, x, y);
-    }
-
     pub fn touch_down_at(self: &Rc<Self>, time_usec: u64, id: i32, x: Fixed, y: Fixed) {
         self.for_each_ei_seat(|ei_seat| {
             ei_seat.handle_touch_down(id as _, x, y);
@@ -1085,19 +1151,6 @@ impl WlSeatGlobal {
         self.touch_owner.up(self, time_usec, id);
     }
 
-    fn touch_motion(
-        self: &Rc<Self>,
-        time_usec: u64,
-        id: i32,
-        rect: Rect,
-        x_normed: Fixed,
-        y_normed: Fixed,
-    ) {
-        let x = Fixed::from_f64(rect.x1() as f64 + rect.width() as f64 * x_normed.to_f64());
-        let y = Fixed::from_f64(rect.y1() as f64 + rect.height() as f64 * y_normed.to_f64());
-        self.touch_motion_at(time_usec, id, x, y);
-    }
-
     pub fn touch_motion_at(self: &Rc<Self>, time_usec: u64, id: i32, x: Fixed, y: Fixed) {
         self.for_each_ei_seat(|ei_seat| {
             ei_seat.handle_touch_motion(id as _, x, y);
@@ -1206,7 +1259,7 @@ impl WlSeatGlobal {
         self.focus_node(node);
     }
 
-    fn ungrab_kb(self: &Rc<Self>) {
+    pub fn ungrab_kb(self: &Rc<Self>) {
         self.kb_owner.ungrab(self);
     }
 
@@ -1371,7 +1424,32 @@ impl WlSeatGlobal {
     pub fn clear_shortcuts(&self) {
         self.global_shortcuts.borrow_mut().clear();
         self.modal_shortcuts.borrow_mut().clear();
-        self.current_shortcuts.borrow_mut().take();
+        self.pointer_bindings.borrow_mut().clear();
+        self.repeating_shortcuts.borrow_mut().clear();
+        self.shortcut_repeat.take();
+        self.app_mod_escape.take();
+        self.app_mod_forward_keys.borrow_mut().clear();
+        self.reset_app_mod();
+    }
+
+    /// Returns to the default mode.
+    ///
+    /// The current shortcuts must stay linked to the entry in the modal shortcuts so that
+    /// shortcuts added for the default mode by a newly loaded config take effect.
+    fn reset_app_mod(&self) {
+        let app_mod = AppMod::default();
+        let shortcuts = self
+            .modal_shortcuts
+            .borrow_mut()
+            .entry(app_mod.app_name.clone())
+            .or_default()
+            .entry(app_mod.mod_name.clone())
+            .or_default()
+            .clone();
+        *self.current_shortcuts.borrow_mut() = shortcuts;
+        *self.last_app_mods.borrow_mut() =
+            AHashMap::from([(AppMod::APP_NAME_JAY.to_string(), app_mod.clone())]);
+        *self.current_app_mod.borrow_mut() = app_mod;
     }
 
     pub fn add_shortcut(
@@ -1422,6 +1500,9 @@ impl WlSeatGlobal {
     }
 
     pub fn remove_shortcut(&self, mods: Modifiers, keysym: KeySym, app_mod: AppMod) {
+        self.repeating_shortcuts
+            .borrow_mut()
+            .remove(&(mods, keysym, app_mod.clone()));
         if app_mod.is_global() {
             if let Entry::Occupied(mut oe) = self.global_shortcuts.borrow_mut().entry(keysym.0) {
                 match oe.get_mut() {
@@ -1459,6 +1540,148 @@ impl WlSeatGlobal {
         }
     }
 
+    pub fn set_shortcut_repeat(
+        &self,
+        mods: Modifiers,
+        keysym: KeySym,
+        app_mod: AppMod,
+        repeat: bool,
+    ) {
+        let mut repeating = self.repeating_shortcuts.borrow_mut();
+        if repeat {
+            repeating.insert((mods, keysym, app_mod));
+        } else {
+            repeating.remove(&(mods, keysym, app_mod));
+        }
+    }
+
+    fn start_shortcut_repeat(self: &Rc<Self>, shortcuts: &[InvokedShortcut]) {
+        let shortcuts: Vec<_> = {
+            let repeating = self.repeating_shortcuts.borrow();
+            shortcuts
+                .iter()
+                .filter(|s| repeating.contains(&(s.effective_mods, s.sym, s.app_mod.clone())))
+                .cloned()
+                .collect()
+        };
+        let (rate, delay) = self.repeat_rate.get();
+        if shortcuts.is_empty() || rate <= 0 {
+            return;
+        }
+        let future = repeat_shortcuts(self.clone(), shortcuts, rate as u64, delay.max(0) as u64);
+        let task = self.state.eng.spawn("shortcut repeat", future);
+        self.shortcut_repeat.set(Some(task));
+    }
+
+    pub fn add_pointer_binding(
+        &self,
+        mods: Modifiers,
+        input: PointerInput,
+        context: PointerContext,
+    ) {
+        self.pointer_bindings
+            .borrow_mut()
+            .insert((mods, input, context));
+    }
+
+    pub fn remove_pointer_binding(
+        &self,
+        mods: Modifiers,
+        input: PointerInput,
+        context: PointerContext,
+    ) {
+        self.pointer_bindings
+            .borrow_mut()
+            .remove(&(mods, input, context));
+    }
+
+    /// Returns the pointer binding that applies to `input` over `node`, if any.
+    ///
+    /// If a binding is returned, the event must not be delivered to the node.
+    pub(super) fn find_pointer_binding(
+        &self,
+        node: &Rc<dyn Node>,
+        input: PointerInput,
+    ) -> Option<(Modifiers, PointerInput, PointerContext)> {
+        if self.state.lock.locked.get() || self.shortcuts_inhibited() {
+            return None;
+        }
+        if self.pointer_bindings.borrow().is_empty() {
+            return None;
+        }
+        let context = if node.node_is_container() || node.node_is_float() {
+            PointerContext::Title
+        } else if node.node_is_placeholder() {
+            PointerContext::Window
+        } else if let Some(surface) = node.clone().node_into_surface() {
+            if surface.get_toplevel().is_none() {
+                // Popups and layer-shell surfaces are not part of any context.
+                return None;
+            }
+            PointerContext::Window
+        } else {
+            PointerContext::Root
+        };
+        let kb_state = self.latest_kb_state.get();
+        let mods = Modifiers(kb_state.borrow().mods.mods_effective & !(CAPS.0 | NUM.0));
+        let binding = (mods, input, context);
+        if !self.pointer_bindings.borrow().contains(&binding) {
+            return None;
+        }
+        Some(binding)
+    }
+
+    pub(super) fn invoke_pointer_binding(
+        &self,
+        (mods, input, context): (Modifiers, PointerInput, PointerContext),
+    ) {
+        if let Some(config) = self.state.config.get() {
+            config.invoke_pointer_binding(self.id, mods, input, context);
+        }
+    }
+
+    pub(super) fn send_pointer_binding_motion(&self) {
+        if let Some(config) = self.state.config.get() {
+            let (x, y) = self.pointer_cursor.position();
+            config.pointer_binding_motion(self.id, x.round_down(), y.round_down());
+        }
+    }
+
+    pub(super) fn send_pointer_binding_released(&self) {
+        if let Some(config) = self.state.config.get() {
+            config.pointer_binding_released(self.id);
+        }
+    }
+
+    /// Invokes the pointer bindings for the discrete scroll events in `pending`.
+    pub(super) fn handle_axis_binding(&self, node: &Rc<dyn Node>, pending: &PendingScroll) -> bool {
+        let axes = [
+            (
+                ScrollAxis::Vertical,
+                PointerInput::ScrollUp,
+                PointerInput::ScrollDown,
+            ),
+            (
+                ScrollAxis::Horizontal,
+                PointerInput::ScrollLeft,
+                PointerInput::ScrollRight,
+            ),
+        ];
+        let mut handled = false;
+        for (axis, negative, positive) in axes {
+            let input = match pending.v120[axis as usize].get() {
+                Some(v) if v < 0 => negative,
+                Some(v) if v > 0 => positive,
+                _ => continue,
+            };
+            if let Some(binding) = self.find_pointer_binding(node, input) {
+                self.invoke_pointer_binding(binding);
+                handled = true;
+            }
+        }
+        handled
+    }
+
     pub fn trigger_tree_changed(&self, needs_layout: bool) {
         // log::info!("trigger_tree_changed");
         if needs_layout {
@@ -1490,6 +1713,8 @@ impl WlSeatGlobal {
             KeyState::Released => (wl_pointer::RELEASED, false),
             KeyState::Pressed => {
                 surface.client.focus_stealing_serial.set(Some(serial));
+                let kind = InputSerialKind::ButtonPress;
+                surface.client.add_input_serial(self.id, kind, serial);
                 (wl_pointer::PRESSED, true)
             }
         };
@@ -1583,6 +1808,58 @@ impl WlSeatGlobal {
     }
 }
 
+async fn repeat_shortcuts(
+    seat: Rc<WlSeatGlobal>,
+    shortcuts: Vec<InvokedShortcut>,
+    rate: u64,
+    delay: u64,
+) {
+    let period = (1000 / rate).max(1);
+    let mut interval = seat.state.wheel.interval(delay, period);
+    loop {
+        if let Err(e) = interval.tick().await {
+            log::error!("Could not create a timeout: {}", ErrorFmt(e));
+            return;
+        }
+        if seat.state.lock.locked.get() {
+            return;
+        }
+        let Some(config) = seat.state.config.get() else {
+            return;
+        };
+        for shortcut in &shortcuts {
+            config.invoke_shortcut(seat.id(), shortcut);
+        }
+    }
+}
+
+async fn focus_toplevel_after_delay(
+    seat: Rc<WlSeatGlobal>,
+    tl: Rc<dyn ToplevelNode>,
+    msec: u64,
+    focus: NodeId,
+) {
+    if let Err(e) = seat.state.wheel.timeout(msec).await {
+        log::error!("Could not create a timeout: {}", ErrorFmt(e));
+        return;
+    }
+    if seat.keyboard_node.get().node_id() != focus {
+        // The focus was changed explicitly while we were waiting.
+        return;
+    }
+    if seat.pointer_owner.buttons_pressed() {
+        // Changing the focus in the middle of a click or drag would be surprising.
+        return;
+    }
+    let under_pointer = seat
+        .pointer_node()
+        .and_then(|n| n.node_toplevel())
+        .map(|n| n.node_id());
+    if under_pointer == Some(tl.node_id()) {
+        seat.focus_toplevel(tl);
+    }
+}
+
 // Enter callbacks
 impl WlSeatGlobal {
     pub fn enter_toplevel(self: &Rc<Self>, n: Rc<dyn ToplevelNode>) {
@@ -1590,7 +1867,15 @@ impl WlSeatGlobal {
             && self.changes.get().contains(CHANGE_CURSOR_MOVED)
             && self.focus_follows_mouse.get()
         {
-            self.focus_toplevel(n);
+            let delay = self.focus_follows_mouse_delay_msec.get();
+            if delay == 0 {
+                self.focus_toplevel(n);
+            } else {
+                let focus = self.keyboard_node.get().node_id();
+                let future = focus_toplevel_after_delay(self.clone(), n, delay, focus);
+                let task = self.state.eng.spawn("focus follows mouse", future);
+                self.focus_follows_mouse_task.set(Some(task));
+            }
         }
     }
 
@@ -1616,6 +1901,8 @@ impl WlSeatGlobal {
 impl WlSeatGlobal {
     pub fn leave_surface(&self, n: &WlSurface) {
         let serial = n.client.next_serial();
+        n.client
+            .remove_input_serials(self.id, POINTER_SERIAL_KINDS);
         for (_, constraint) in &n.constraints {
             constraint.deactivate();
         }
@@ -1639,7 +1926,14 @@ impl WlSeatGlobal {
             }
         }
 
+        if let Some(inhibitor) = surface.shortcuts_inhibitors.get(&self.id) {
+            inhibitor.deactivate();
+        }
+
         let serial = surface.client.next_serial();
+        surface
+            .client
+            .remove_input_serials(self.id, KEYBOARD_SERIAL_KINDS);
         self.surface_kb_event(Version::ALL, surface, |k| k.send_leave(serial, surface.id))
     }
 }
@@ -1650,6 +1944,8 @@ impl WlSeatGlobal {
         let kb_state = self.latest_kb_state.get();
         let kb_state = &*kb_state.borrow();
         let serial = surface.client.next_serial();
+        let kind = InputSerialKind::KeyboardEnter;
+        surface.client.add_input_serial(self.id, kind, serial);
         self.surface_kb_event(Version::ALL, surface, |k| {
             k.enter(serial, surface.id, kb_state);
         });
@@ -1671,6 +1967,10 @@ impl WlSeatGlobal {
                 ti.send_done();
             }
         }
+
+        if let Some(inhibitor) = surface.shortcuts_inhibitors.get(&self.id) {
+            inhibitor.activate();
+        }
     }
 }
 
@@ -1685,6 +1985,10 @@ impl WlSeatGlobal {
         kb_state: &KeyboardState,
     ) {
         let serial = surface.client.next_serial();
+        if state == wl_keyboard::PRESSED {
+            let kind = InputSerialKind::KeyPress;
+            surface.client.add_input_serial(self.id, kind, serial);
+        }
         let time = (time_usec / 1000) as _;
         self.surface_kb_event(Version::ALL, surface, |k| {
             k.on_key(serial, time, key, state, surface.id, kb_state);
@@ -1714,6 +2018,8 @@ impl WlSeatGlobal {
     ) {
         let serial = surface.client.next_serial();
         surface.client.focus_stealing_serial.set(Some(serial));
+        let kind = InputSerialKind::TouchDown;
+        surface.client.add_input_serial(self.id, kind, serial);
         let time = (time_usec / 1000) as _;
         self.surface_touch_event(Version::ALL, surface, |t| {
             t.send_down(serial, time, surface.id, id, x, y)
@@ -81,6 +81,7 @@ impl KbOwner for DefaultKbOwner {
         node.clone().node_on_focus(seat);
         seat.keyboard_node_serial.set(serial);
         seat.keyboard_node.set(node.clone());
+        seat.push_focus_history(&node);
         seat.tablet_on_keyboard_node_change();
     }
 }
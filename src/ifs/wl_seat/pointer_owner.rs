@@ -1,6 +1,7 @@
 use {
     crate::{
         backend::{AxisSource, KeyState, ScrollAxis, AXIS_120},
+        client::ClientId,
         cursor::KnownCursor,
         fixed::Fixed,
         ifs::{
@@ -21,6 +22,7 @@ use {
         },
         utils::{clonecell::CloneCell, smallmap::SmallMap},
     },
+    jay_config::input::PointerInput,
     std::{
         cell::Cell,
         rc::{Rc, Weak},
@@ -92,7 +94,11 @@ impl PointerOwnerHolder {
         seat.state.for_each_seat_tester(|t| {
             t.send_axis(seat.id, time_usec, &pending);
         });
-        if let Some(node) = self.owner.get().axis_node(seat) {
+        let owner = self.owner.get();
+        if let Some(node) = owner.axis_node(seat) {
+            if owner.allows_pointer_bindings() && seat.handle_axis_binding(&node, &pending) {
+                return;
+            }
             node.node_on_axis_event(seat, &pending);
         }
     }
@@ -151,10 +157,26 @@ impl PointerOwnerHolder {
         self.owner.get().dnd_target_removed(seat);
     }
 
+    pub fn allows_warp(&self) -> bool {
+        self.owner.get().allows_warp()
+    }
+
+    pub fn buttons_pressed(&self) -> bool {
+        self.owner.get().buttons_pressed()
+    }
+
     pub fn dnd_icon(&self) -> Option<Rc<DndIcon>> {
         self.owner.get().dnd_icon()
     }
 
+    pub fn dnd_client(&self) -> Option<ClientId> {
+        self.owner.get().dnd_client()
+    }
+
+    pub fn grab_client(&self) -> Option<ClientId> {
+        self.owner.get().grab_client()
+    }
+
     pub fn toplevel_drag(&self) -> Option<Rc<XdgToplevelDragV1>> {
         self.owner.get().toplevel_drag()
     }
@@ -172,6 +194,21 @@ impl PointerOwnerHolder {
         seat.changes.or_assign(CHANGE_CURSOR_MOVED);
     }
 
+    fn start_binding_grab(&self, seat: &Rc<WlSeatGlobal>, button: u32, node: &Rc<dyn Node>) {
+        {
+            let mut stack = seat.pointer_stack.borrow_mut();
+            for node in stack.drain(1..).rev() {
+                node.node_on_leave(seat);
+                node.node_seat_state().leave(seat);
+            }
+        }
+        let owner = Rc::new(BindingGrabPointerOwner {
+            button,
+            node: node.clone(),
+        });
+        self.owner.set(owner);
+    }
+
     fn select_element(&self, seat: &Rc<WlSeatGlobal>, usecase: impl SimplePointerOwnerUsecase) {
         self.revert_to_default(seat);
         if let Some(node) = seat.pointer_stack.borrow().last() {
@@ -215,6 +252,10 @@ impl PointerOwnerHolder {
     pub fn start_workspace_drag(&self, seat: &Rc<WlSeatGlobal>, ws: &Rc<WorkspaceNode>) {
         self.owner.get().start_workspace_drag(seat, ws);
     }
+
+    pub fn start_pointer_move(&self, seat: &Rc<WlSeatGlobal>) {
+        self.owner.get().start_pointer_move(seat);
+    }
 }
 
 trait PointerOwner {
@@ -253,6 +294,22 @@ trait PointerOwner {
     fn dnd_icon(&self) -> Option<Rc<DndIcon>> {
         None
     }
+    fn dnd_client(&self) -> Option<ClientId> {
+        None
+    }
+    fn grab_client(&self) -> Option<ClientId> {
+        None
+    }
+    fn allows_warp(&self) -> bool {
+        false
+    }
+    fn allows_pointer_bindings(&self) -> bool {
+        false
+    }
+    /// Grabs last until their button is released.
+    fn buttons_pressed(&self) -> bool {
+        true
+    }
     fn toplevel_drag(&self) -> Option<Rc<XdgToplevelDragV1>> {
         None
     }
@@ -274,6 +331,10 @@ trait PointerOwner {
         let _ = seat;
         let _ = ws;
     }
+
+    fn start_pointer_move(&self, seat: &Rc<WlSeatGlobal>) {
+        let _ = seat;
+    }
 }
 
 struct SimplePointerOwner<T> {
@@ -296,6 +357,19 @@ struct DndPointerOwner {
     pos_y: Cell<Fixed>,
 }
 
+/// Swallows all pointer events until the button of a pointer binding is released.
+struct BindingGrabPointerOwner {
+    button: u32,
+    node: Rc<dyn Node>,
+}
+
+/// Moves a floating window on behalf of a pointer binding.
+struct BindingMovePointerOwner {
+    button: u32,
+    tl: Rc<dyn ToplevelNode>,
+    usecase: MoveToplevelGrabPointerOwner,
+}
+
 #[derive(Copy, Clone)]
 struct DefaultPointerUsecase;
 
@@ -323,6 +397,15 @@ impl<T: SimplePointerOwnerUsecase> PointerOwner for SimplePointerOwner<T> {
             Some(n) => n,
             _ => return,
         };
+        if T::IS_DEFAULT {
+            let input = PointerInput::Button(button);
+            if let Some(binding) = seat.find_pointer_binding(&pn, input) {
+                seat.pointer_owner
+                    .start_binding_grab(seat, button, &pn);
+                seat.invoke_pointer_binding(binding);
+                return;
+            }
+        }
         if self.usecase.default_button(self, seat, button, &pn) {
             return;
         }
@@ -343,6 +426,18 @@ impl<T: SimplePointerOwnerUsecase> PointerOwner for SimplePointerOwner<T> {
         seat.pointer_node()
     }
 
+    fn allows_warp(&self) -> bool {
+        true
+    }
+
+    fn buttons_pressed(&self) -> bool {
+        false
+    }
+
+    fn allows_pointer_bindings(&self) -> bool {
+        T::IS_DEFAULT
+    }
+
     fn apply_changes(&self, seat: &Rc<WlSeatGlobal>) {
         let (x, y) = seat.pointer_cursor.position();
         let mut found_tree = seat.found_tree.borrow_mut();
@@ -482,6 +577,10 @@ impl<T: SimplePointerOwnerUsecase> PointerOwner for SimpleGrabPointerOwner<T> {
         seat.pointer_owner.set_default_pointer_owner(seat);
     }
 
+    fn grab_client(&self) -> Option<ClientId> {
+        self.node.node_client_id()
+    }
+
     fn start_tile_drag(&self, seat: &Rc<WlSeatGlobal>, tl: &Rc<dyn ToplevelNode>) {
         self.usecase.start_tile_drag(self, seat, tl);
     }
@@ -593,6 +692,10 @@ impl PointerOwner for DndPointerOwner {
         self.icon.get()
     }
 
+    fn dnd_client(&self) -> Option<ClientId> {
+        Some(self.dnd.client.id)
+    }
+
     fn toplevel_drag(&self) -> Option<Rc<XdgToplevelDragV1>> {
         if let Some(src) = &self.dnd.src {
             src.toplevel_drag.get()
@@ -1375,3 +1478,80 @@ impl UiDragUsecase for WorkspaceDragUsecase {
         }
     }
 }
+
+impl PointerOwner for BindingGrabPointerOwner {
+    fn button(&self, seat: &Rc<WlSeatGlobal>, _time_usec: u64, button: u32, state: KeyState) {
+        if button == self.button && state == KeyState::Released {
+            seat.send_pointer_binding_released();
+            self.revert_to_default(seat);
+        }
+    }
+
+    fn apply_changes(&self, seat: &Rc<WlSeatGlobal>) {
+        if seat.changes.get().contains(CHANGE_CURSOR_MOVED) {
+            seat.send_pointer_binding_motion();
+        }
+    }
+
+    fn revert_to_default(&self, seat: &Rc<WlSeatGlobal>) {
+        seat.pointer_owner.set_default_pointer_owner(seat);
+        seat.changes.or_assign(CHANGE_TREE);
+        seat.apply_changes();
+    }
+
+    fn start_pointer_move(&self, seat: &Rc<WlSeatGlobal>) {
+        let tl = match self.node.clone().node_into_float() {
+            Some(float) => float.child.get(),
+            None => self.node.clone().node_toplevel(),
+        };
+        let Some(tl) = tl else {
+            return;
+        };
+        let Some(parent) = tl.tl_data().parent.get() else {
+            return;
+        };
+        if !parent.node_is_float() {
+            return;
+        }
+        let (x, y) = seat.pointer_cursor.position();
+        let pos = tl.node_absolute_position();
+        let (dx, dy) = pos.translate(x.round_down(), y.round_down());
+        seat.pointer_cursor.set_known(KnownCursor::Move);
+        tl.node_seat_state().add_pointer_grab(seat);
+        seat.pointer_owner
+            .owner
+            .set(Rc::new(BindingMovePointerOwner {
+                button: self.button,
+                tl,
+                usecase: MoveToplevelGrabPointerOwner { dx, dy },
+            }));
+    }
+}
+
+impl PointerOwner for BindingMovePointerOwner {
+    fn button(&self, seat: &Rc<WlSeatGlobal>, _time_usec: u64, button: u32, state: KeyState) {
+        if button == self.button && state == KeyState::Released {
+            seat.send_pointer_binding_released();
+            self.tl.node_seat_state().remove_pointer_grab(seat);
+            self.revert_to_default(seat);
+        }
+    }
+
+    fn apply_changes(&self, seat: &Rc<WlSeatGlobal>) {
+        if !seat.changes.get().contains(CHANGE_CURSOR_MOVED) {
+            return;
+        }
+        seat.send_pointer_binding_motion();
+        let Some(parent) = self.tl.tl_data().parent.get() else {
+            return;
+        };
+        self.usecase.apply_changes(seat, parent, &self.tl);
+    }
+
+    fn revert_to_default(&self, seat: &Rc<WlSeatGlobal>) {
+        seat.pointer_cursor.set_known(KnownCursor::Default);
+        seat.pointer_owner.set_default_pointer_owner(seat);
+        seat.changes.or_assign(CHANGE_TREE);
+        seat.apply_changes();
+    }
+}
@@ -1,5 +1,6 @@
 use {
     crate::{
+        client::InputSerialKind,
         cursor::KnownCursor,
         fixed::Fixed,
         ifs::{
@@ -212,6 +213,9 @@ impl TabletTool {
         });
         if state == ToolButtonState::Pressed {
             n.client.focus_stealing_serial.set(Some(serial.get()));
+            let seat = self.tablet.seat.id;
+            let kind = InputSerialKind::ButtonPress;
+            n.client.add_input_serial(seat, kind, serial.get());
             if let Some(node) = n.get_focus_node(self.tablet.seat.id) {
                 self.tablet.seat.focus_node_with_serial(node, serial.get());
             }
@@ -261,6 +265,9 @@ impl TabletTool {
         if let Some(changes) = changes {
             if changes.down == Some(true) {
                 n.client.focus_stealing_serial.set(Some(serial.get()));
+                let seat = self.tablet.seat.id;
+                let kind = InputSerialKind::TabletToolDown;
+                n.client.add_input_serial(seat, kind, serial.get());
                 if let Some(node) = n.get_focus_node(self.tablet.seat.id) {
                     self.tablet.seat.focus_node_with_serial(node, serial.get());
                 }
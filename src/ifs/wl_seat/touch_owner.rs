@@ -2,7 +2,7 @@ use {
     crate::{
         fixed::Fixed,
         ifs::wl_seat::WlSeatGlobal,
-        tree::{FindTreeUsecase, FoundNode, Node},
+        tree::{FindTreeUsecase, FoundNode, Node, NodeId},
         utils::{clonecell::CloneCell, smallmap::SmallMap},
     },
     std::rc::Rc,
@@ -54,9 +54,10 @@ impl TouchOwnerHolder {
 
 struct DefaultTouchOwner;
 
+#[derive(Default)]
 struct GrabTouchOwner {
-    node: Rc<dyn Node>,
-    down_ids: SmallMap<i32, (), 10>,
+    points: SmallMap<i32, Rc<dyn Node>, 10>,
+    frame_nodes: SmallMap<NodeId, Rc<dyn Node>, 4>,
 }
 
 trait TouchOwner {
@@ -67,30 +68,30 @@ trait TouchOwner {
     fn cancel(&self, seat: &Rc<WlSeatGlobal>);
 }
 
+fn find_node(seat: &Rc<WlSeatGlobal>, x: Fixed, y: Fixed) -> Option<Rc<dyn Node>> {
+    let mut found_tree = seat.found_tree.borrow_mut();
+    let x_int = x.round_down();
+    let y_int = y.round_down();
+    found_tree.push(FoundNode {
+        node: seat.state.root.clone(),
+        x: x_int,
+        y: y_int,
+    });
+    seat.state
+        .root
+        .node_find_tree_at(x_int, y_int, &mut found_tree, FindTreeUsecase::None);
+    let node = found_tree.pop();
+    found_tree.clear();
+    node.map(|n| n.node)
+}
+
 impl TouchOwner for DefaultTouchOwner {
     fn down(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32, x: Fixed, y: Fixed) {
-        let mut found_tree = seat.found_tree.borrow_mut();
-        let x_int = x.round_down();
-        let y_int = y.round_down();
-        found_tree.push(FoundNode {
-            node: seat.state.root.clone(),
-            x: x_int,
-            y: y_int,
-        });
-        seat.state
-            .root
-            .node_find_tree_at(x_int, y_int, &mut found_tree, FindTreeUsecase::None);
-        let node = found_tree.pop();
-        found_tree.clear();
-        drop(found_tree);
-        if let Some(node) = node {
-            node.node.node_seat_state().touch_begin(seat);
-            let owner = Rc::new(GrabTouchOwner {
-                node: node.node,
-                down_ids: Default::default(),
-            });
-            seat.touch_owner.owner.set(owner.clone());
-            owner.down(seat, time_usec, id, x, y);
+        let owner = Rc::new(GrabTouchOwner::default());
+        seat.touch_owner.owner.set(owner.clone());
+        owner.down(seat, time_usec, id, x, y);
+        if owner.points.is_empty() {
+            seat.touch_owner.set_default_owner();
         }
     }
 
@@ -112,56 +113,75 @@ impl TouchOwner for DefaultTouchOwner {
 }
 
 impl GrabTouchOwner {
-    fn translate(&self, x: Fixed, y: Fixed) -> (Fixed, Fixed) {
+    fn translate(node: &dyn Node, x: Fixed, y: Fixed) -> (Fixed, Fixed) {
         let x_int = x.round_down();
         let y_int = y.round_down();
-        let (x_int, y_int) = self.node.node_absolute_position().translate(x_int, y_int);
+        let (x_int, y_int) = node.node_absolute_position().translate(x_int, y_int);
         (x.apply_fract(x_int), y.apply_fract(y_int))
     }
 
-    fn revert_to_default(&self, seat: &Rc<WlSeatGlobal>) {
-        self.node.node_seat_state().touch_end(seat);
-        seat.touch_owner.set_default_owner();
+    fn mark_frame(&self, node: &Rc<dyn Node>) {
+        self.frame_nodes.insert(node.node_id(), node.clone());
+    }
+
+    fn is_touched(&self, node: &dyn Node) -> bool {
+        let id = node.node_id();
+        self.points.iter().any(|(_, n)| n.node_id() == id)
     }
 }
 
 impl TouchOwner for GrabTouchOwner {
     fn down(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32, x: Fixed, y: Fixed) {
-        if self.down_ids.insert(id, ()).is_some() {
+        if self.points.contains(&id) {
             return;
         }
-        let (x, y) = self.translate(x, y);
-        self.node
-            .clone()
-            .node_on_touch_down(seat, time_usec, id, x, y);
+        let Some(node) = find_node(seat, x, y) else {
+            return;
+        };
+        node.node_seat_state().touch_begin(seat);
+        self.points.insert(id, node.clone());
+        self.mark_frame(&node);
+        let (x, y) = Self::translate(&*node, x, y);
+        node.node_on_touch_down(seat, time_usec, id, x, y);
     }
 
     fn up(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32) {
-        if self.down_ids.remove(&id).is_none() {
+        let Some(node) = self.points.remove(&id) else {
             return;
-        }
-        self.node.clone().node_on_touch_up(seat, time_usec, id);
+        };
+        self.mark_frame(&node);
+        node.node_on_touch_up(seat, time_usec, id);
     }
 
     fn motion(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32, x: Fixed, y: Fixed) {
-        if !self.down_ids.contains(&id) {
+        let Some(node) = self.points.get(&id) else {
             return;
-        }
-        let (x, y) = self.translate(x, y);
-        self.node
-            .clone()
-            .node_on_touch_motion(seat, time_usec, id, x, y);
+        };
+        self.mark_frame(&node);
+        let (x, y) = Self::translate(&*node, x, y);
+        node.node_on_touch_motion(seat, time_usec, id, x, y);
     }
 
     fn frame(&self, seat: &Rc<WlSeatGlobal>) {
-        self.node.node_on_touch_frame(seat);
-        if self.down_ids.is_empty() {
-            self.revert_to_default(seat);
+        for (_, node) in self.frame_nodes.take() {
+            node.node_on_touch_frame(seat);
+            if !self.is_touched(&*node) {
+                node.node_seat_state().touch_end(seat);
+            }
+        }
+        if self.points.is_empty() {
+            seat.touch_owner.set_default_owner();
         }
     }
 
     fn cancel(&self, seat: &Rc<WlSeatGlobal>) {
-        self.node.node_on_touch_cancel(seat);
-        self.revert_to_default(seat);
+        for (_, node) in self.points.take() {
+            self.mark_frame(&node);
+        }
+        for (_, node) in self.frame_nodes.take() {
+            node.node_on_touch_cancel(seat);
+            node.node_seat_state().touch_end(seat);
+        }
+        seat.touch_owner.set_default_owner();
     }
 }
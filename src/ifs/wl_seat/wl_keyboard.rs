@@ -58,6 +58,14 @@ impl WlKeyboard {
         self.send_modifiers(serial, &kb_state.mods);
     }
 
+    /// Sends the keymap of the state to a keyboard that does not have focus.
+    ///
+    /// The modifiers are sent with the next enter event.
+    pub fn update_keymap(&self, kb_state: &KeyboardState) {
+        self.kb_state_id.set(kb_state.id);
+        self.send_keymap(kb_state);
+    }
+
     fn send_keymap(&self, state: &KeyboardState) {
         let fd = match self.seat.keymap_fd(state) {
             Ok(fd) => fd,
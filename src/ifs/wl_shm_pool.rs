@@ -84,9 +84,13 @@ impl WlShmPoolRequestHandler for WlShmPool {
         if req.size < 0 {
             return Err(WlShmPoolError::NegativeSize);
         }
-        if (req.size as usize) < self.mem.get().len() {
+        let len = self.mem.get().len();
+        if (req.size as usize) < len {
             return Err(WlShmPoolError::CannotShrink);
         }
+        if req.size as usize == len {
+            return Ok(());
+        }
         self.mem.set(Rc::new(ClientMem::new(
             &self.fd,
             req.size as usize,
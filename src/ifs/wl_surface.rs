@@ -17,6 +17,7 @@ pub mod xwayland_shell_v1;
 pub mod zwlr_layer_surface_v1;
 pub mod zwp_idle_inhibitor_v1;
 pub mod zwp_input_popup_surface_v2;
+pub mod zwp_keyboard_shortcuts_inhibitor_v1;
 
 use {
     crate::{
@@ -104,6 +105,7 @@ use {
     },
     thiserror::Error,
     zwp_idle_inhibitor_v1::ZwpIdleInhibitorV1,
+    zwp_keyboard_shortcuts_inhibitor_v1::ZwpKeyboardShortcutsInhibitorV1,
 };
 
 #[expect(dead_code)]
@@ -189,6 +191,14 @@ impl NodeVisitorBase for SurfaceSendPreferredScaleVisitor {
     }
 }
 
+pub struct SurfaceUpdateOutputsVisitor;
+impl NodeVisitorBase for SurfaceUpdateOutputsVisitor {
+    fn visit_surface(&mut self, node: &Rc<WlSurface>) {
+        node.update_entered_outputs();
+        node.node_visit_children(self);
+    }
+}
+
 pub struct SurfaceSendPreferredTransformVisitor;
 impl NodeVisitorBase for SurfaceSendPreferredTransformVisitor {
     fn visit_surface(&mut self, node: &Rc<WlSurface>) {
@@ -277,7 +287,11 @@ pub struct WlSurface {
     role: Cell<SurfaceRole>,
     pending: RefCell<Box<PendingState>>,
     input_region: CloneCell<Option<Rc<Region>>>,
-    opaque_region: Cell<Option<Rc<Region>>>,
+    opaque_region: CloneCell<Option<Rc<Region>>>,
+    /// The size of the surface in surface-local coordinates, i.e. before the wire scale
+    /// is applied.
+    surface_size: Cell<(i32, i32)>,
+    pub opaque: Cell<bool>,
     buffer_points: RefCell<BufferPoints>,
     pub buffer_points_norm: RefCell<SampleRect>,
     damage_matrix: Cell<DamageMatrix>,
@@ -306,8 +320,11 @@ pub struct WlSurface {
     idle_inhibitors: SmallMap<ZwpIdleInhibitorV1Id, Rc<ZwpIdleInhibitorV1>, 1>,
     viewporter: CloneCell<Option<Rc<WpViewport>>>,
     output: CloneCell<Rc<OutputNode>>,
+    entered_outputs: RefCell<Vec<Rc<OutputNode>>>,
+    preferred_buffer_scale: Cell<Option<i32>>,
     fractional_scale: CloneCell<Option<Rc<WpFractionalScaleV1>>>,
     pub constraints: SmallMap<SeatId, Rc<SeatConstraint>, 1>,
+    pub shortcuts_inhibitors: SmallMap<SeatId, Rc<ZwpKeyboardShortcutsInhibitorV1>, 1>,
     xwayland_serial: Cell<Option<u64>>,
     tearing_control: CloneCell<Option<Rc<WpTearingControlV1>>>,
     pub tearing: Cell<bool>,
@@ -612,6 +629,8 @@ impl WlSurface {
             pending: Default::default(),
             input_region: Default::default(),
             opaque_region: Default::default(),
+            surface_size: Default::default(),
+            opaque: Cell::new(false),
             buffer_points: Default::default(),
             buffer_points_norm: Default::default(),
             damage_matrix: Default::default(),
@@ -643,8 +662,11 @@ impl WlSurface {
             idle_inhibitors: Default::default(),
             viewporter: Default::default(),
             output: CloneCell::new(client.state.dummy_output.get().unwrap()),
+            entered_outputs: Default::default(),
+            preferred_buffer_scale: Default::default(),
             fractional_scale: Default::default(),
             constraints: Default::default(),
+            shortcuts_inhibitors: Default::default(),
             xwayland_serial: Default::default(),
             tearing_control: Default::default(),
             tearing: Cell::new(false),
@@ -700,8 +722,7 @@ impl WlSurface {
         if self.visible.get() {
             self.attach_events_to_output(output);
         }
-        output.global.send_enter(self);
-        old.global.send_leave(self);
+        self.update_entered_outputs();
         if old.global.persistent.scale.get() != output.global.persistent.scale.get() {
             self.on_scale_change();
         }
@@ -716,6 +737,27 @@ impl WlSurface {
         }
     }
 
+    /// Enters the output of the surface and the outputs that mirror it.
+    pub fn update_entered_outputs(&self) {
+        let output = self.output.get();
+        let mut outputs = vec![output.clone()];
+        outputs.extend(output.mirrors.lock().values().cloned());
+        let old = self.entered_outputs.replace(outputs);
+        let new = self.entered_outputs.borrow();
+        for o in new.iter() {
+            if old.iter().all(|e| e.id != o.id) {
+                o.global.send_enter(self);
+            }
+        }
+        for o in &old {
+            if new.iter().all(|e| e.id != o.id) {
+                o.global.send_leave(self);
+            }
+        }
+        drop(new);
+        self.send_preferred_buffer_scale();
+    }
+
     fn on_scale_change(&self) {
         if let Some(fs) = self.fractional_scale.get() {
             fs.send_preferred_scale();
@@ -804,8 +846,18 @@ impl WlSurface {
         if self.version >= BUFFER_SCALE_SINCE {
             let factor = match self.client.wire_scale.is_some() {
                 true => 1,
-                false => self.output.get().global.legacy_scale.get() as _,
+                false => self
+                    .entered_outputs
+                    .borrow()
+                    .iter()
+                    .map(|o| o.global.legacy_scale.get())
+                    .max()
+                    .unwrap_or_else(|| self.output.get().global.legacy_scale.get())
+                    as _,
             };
+            if self.preferred_buffer_scale.replace(Some(factor)) == Some(factor) {
+                return;
+            }
             self.client.event(PreferredBufferScale {
                 self_id: self.id,
                 factor,
@@ -996,6 +1048,9 @@ impl WlSurfaceRequestHandler for WlSurface {
         self.client.remove_obj(self)?;
         self.idle_inhibitors.clear();
         self.constraints.take();
+        for (_, inhibitor) in self.shortcuts_inhibitors.take() {
+            inhibitor.deactivate();
+        }
         self.destroyed.set(true);
         Ok(())
     }
@@ -1280,6 +1335,9 @@ impl WlSurface {
                 }
             }
             let (mut width, mut height) = new_size.unwrap_or_default();
+            if self.surface_size.replace((width, height)) != (width, height) {
+                self.update_opaque();
+            }
             client_wire_scale_to_logical!(self.client, width, height);
             let (old_width, old_height) = buffer_abs_pos.size();
             if (width, height) != (old_width, old_height) {
@@ -1311,16 +1369,19 @@ impl WlSurface {
             }
             if let Some(region) = pending.opaque_region.take() {
                 self.opaque_region.set(region);
+                self.update_opaque();
             }
         }
-        let mut tearing_changed = false;
+        let mut presentation_type_changed = false;
         if let Some(tearing) = pending.tearing.take() {
             if self.tearing.replace(tearing) != tearing {
-                tearing_changed = true;
+                presentation_type_changed = true;
             }
         }
         if let Some(content_type) = pending.content_type.take() {
-            self.content_type.set(content_type);
+            if self.content_type.replace(content_type) != content_type {
+                presentation_type_changed = true;
+            }
         }
         if let Some(xwayland_serial) = pending.xwayland_serial.take() {
             self.xwayland_serial.set(Some(xwayland_serial));
@@ -1379,6 +1440,12 @@ impl WlSurface {
                 self.client.state.damage(rect);
             }
         } else {
+            if has_new_frame_requests {
+                // Invisible surfaces still receive frame callbacks at a low rate so that
+                // clients waiting for them don't stall indefinitely.
+                self.vblank_listener
+                    .attach(&self.client.state.const_1hz_vblank);
+            }
             if fifo_barrier_set {
                 self.latch_listener
                     .attach(&self.client.state.const_40hz_latch);
@@ -1388,7 +1455,7 @@ impl WlSurface {
         pending.surface_damage.clear();
         pending.damage_full = false;
         pending.fifo_barrier_wait = false;
-        if tearing_changed {
+        if presentation_type_changed {
             if let Some(tl) = self.toplevel.get() {
                 if tl.tl_data().is_fullscreen.get() {
                     self.output.get().update_presentation_type();
@@ -1484,6 +1551,18 @@ impl WlSurface {
         }
     }
 
+    fn update_opaque(&self) {
+        let mut opaque = false;
+        if let Some(region) = self.opaque_region.get() {
+            let (width, height) = self.surface_size.get();
+            if let Some(rect) = Rect::new_sized(0, 0, width, height) {
+                let uncovered = Region::new(rect).subtract(&region);
+                opaque = uncovered.extents().is_empty();
+            }
+        }
+        self.opaque.set(opaque);
+    }
+
     fn accepts_input_at(&self, mut x: i32, mut y: i32) -> bool {
         let rect = self.buffer_abs_pos.get().at_point(0, 0);
         if !rect.contains(x, y) {
@@ -1556,8 +1635,17 @@ impl WlSurface {
         if self.visible.replace(visible) == visible {
             return;
         }
+        let has_frame_requests = self.frame_requests.borrow().is_not_empty();
         if visible {
-            self.attach_events_to_output(&self.output.get());
+            let output = self.output.get();
+            self.attach_events_to_output(&output);
+            if has_frame_requests {
+                // Flush the callbacks that were throttled while the surface was invisible.
+                output.global.connector.damage();
+            }
+        } else if has_frame_requests {
+            self.vblank_listener
+                .attach(&self.client.state.const_1hz_vblank);
         }
         for (_, inhibitor) in &self.idle_inhibitors {
             if visible {
@@ -1625,6 +1713,24 @@ impl WlSurface {
         }
     }
 
+    pub fn activate(&self, seat: &Rc<WlSeatGlobal>) {
+        let Some(tl) = self.toplevel.get() else {
+            return;
+        };
+        let data = tl.tl_data();
+        if !data.visible.get() {
+            if let Some(ws) = data.workspace.get() {
+                let output = ws.output.get();
+                if output.show_workspace(&ws) {
+                    ws.flush_jay_workspaces();
+                    output.schedule_update_render_data();
+                    self.client.state.tree_changed();
+                }
+            }
+        }
+        seat.focus_toplevel(tl);
+    }
+
     pub fn send_feedback(&self, fb: &DrmFeedback) {
         for consumer in self.drm_feedback.lock().values() {
             consumer.send_feedback(fb);
@@ -1664,6 +1770,7 @@ impl Object for WlSurface {
         self.buffer.set(None);
         self.toplevel.set(None);
         self.idle_inhibitors.clear();
+        self.shortcuts_inhibitors.clear();
         mem::take(self.pending.borrow_mut().deref_mut());
         self.presentation_feedback.borrow_mut().clear();
         self.latched_presentation_feedback.borrow_mut().clear();
@@ -2146,12 +2253,10 @@ impl DamageMatrix {
 
 impl VblankListener for WlSurface {
     fn after_vblank(self: Rc<Self>) {
-        if self.visible.get() {
-            let now = self.client.state.now_msec();
-            for fr in self.frame_requests.borrow_mut().drain(..) {
-                fr.send_done(now as _);
-                let _ = fr.client.remove_obj(&*fr);
-            }
+        let now = self.client.state.now_msec();
+        for fr in self.frame_requests.borrow_mut().drain(..) {
+            fr.send_done(now as _);
+            let _ = fr.client.remove_obj(&*fr);
         }
         if self.clear_fifo_on_vblank.take() {
             self.commit_timeline.clear_fifo_barrier();
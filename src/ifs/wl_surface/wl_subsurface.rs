@@ -282,6 +282,7 @@ impl WlSubsurfaceRequestHandler for WlSubsurface {
             }
         }
         if !self.surface.extents.get().is_empty() {
+            self.parent.need_extents_update.set(true);
             let mut parent_opt = Some(self.parent.clone());
             while let Some(parent) = parent_opt.take() {
                 if !parent.need_extents_update.get() {
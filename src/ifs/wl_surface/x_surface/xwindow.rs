@@ -290,6 +290,8 @@ impl Xwindow {
             Change::Map => {
                 if override_redirect {
                     self.tl_set_visible(true);
+                } else {
+                    self.toplevel_data.start_open_animation();
                 }
                 self.toplevel_data.broadcast(self.clone());
             }
@@ -445,6 +447,14 @@ impl ToplevelNodeBase for Xwindow {
             .push(XWaylandEvent::Close(self.data.clone()));
     }
 
+    fn tl_kill(self: Rc<Self>) {
+        self.data
+            .state
+            .xwayland
+            .queue
+            .push(XWaylandEvent::Kill(self.data.clone()));
+    }
+
     fn tl_set_visible_impl(&self, visible: bool) {
         self.x.surface.set_visible(visible);
     }
@@ -180,6 +180,10 @@ pub trait XdgSurfaceExt: Debug {
         // nothing
     }
 
+    fn before_unmap(&self) {
+        // nothing
+    }
+
     fn focus_node(&self) -> Option<Rc<dyn Node>> {
         None
     }
@@ -515,6 +519,13 @@ impl SurfaceExt for XdgSurface {
                 self.have_initial_commit.set(true);
             }
         }
+        if let Some(None) = pending.buffer {
+            if self.surface.buffer.is_some() {
+                if let Some(ext) = self.ext.get() {
+                    ext.before_unmap();
+                }
+            }
+        }
         if let Some(pending) = &mut pending.xdg_surface {
             if let Some(geometry) = pending.geometry.take() {
                 let prev = self.geometry.replace(Some(geometry));
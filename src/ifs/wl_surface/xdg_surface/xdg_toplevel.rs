@@ -448,6 +448,7 @@ impl XdgToplevel {
                 self.map_tiled();
             }
             self.extents_changed();
+            self.toplevel_data.start_open_animation();
             if let Some(workspace) = self.xdg.workspace.get() {
                 let output = workspace.output.get();
                 surface.set_output(&output);
@@ -511,6 +512,9 @@ impl Node for XdgToplevel {
 
     fn node_active_changed(&self, active: bool) {
         self.toplevel_data.update_self_active(self, active);
+        if active {
+            self.xdg.base.ping();
+        }
     }
 
     fn node_find_tree_at(
@@ -604,7 +608,16 @@ impl ToplevelNodeBase for XdgToplevel {
     }
 
     fn tl_close(self: Rc<Self>) {
+        let base = &self.xdg.base;
+        if !base.request_close() {
+            return;
+        }
         self.send_close();
+        base.ping();
+    }
+
+    fn tl_kill(self: Rc<Self>) {
+        self.xdg.base.kill_client();
     }
 
     fn tl_set_visible_impl(&self, visible: bool) {
@@ -708,6 +721,12 @@ impl XdgSurfaceExt for XdgToplevel {
             .state
             .damage(self.node_absolute_position());
     }
+
+    fn before_unmap(&self) {
+        if self.is_mapped.get() {
+            self.toplevel_data.start_close_animation();
+        }
+    }
 }
 
 #[derive(Debug, Error)]
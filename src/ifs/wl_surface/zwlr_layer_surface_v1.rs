@@ -8,12 +8,13 @@ use {
                 xdg_surface::xdg_popup::{XdgPopup, XdgPopupParent},
                 PendingState, SurfaceExt, SurfaceRole, WlSurface, WlSurfaceError,
             },
-            zwlr_layer_shell_v1::{ZwlrLayerShellV1, OVERLAY},
+            zwlr_layer_shell_v1::{ZwlrLayerShellV1, BACKGROUND, OVERLAY},
         },
         leaks::Tracker,
         object::Object,
         rect::Rect,
         renderer::Renderer,
+        state::State,
         tree::{
             FindTreeResult, FindTreeUsecase, FoundNode, Node, NodeId, NodeVisitor, OutputNode,
             StackedNode,
@@ -70,6 +71,7 @@ pub struct ZwlrLayerSurfaceV1 {
     last_configure: Cell<(i32, i32)>,
     exclusive_edge: Cell<Option<u32>>,
     exclusive_size: Cell<ExclusiveSize>,
+    exclusive_offset: Cell<ExclusiveSize>,
     popups: CopyHashMap<XdgPopupId, Rc<Popup>>,
 }
 
@@ -90,14 +92,22 @@ impl ExclusiveSize {
         !self.is_empty()
     }
 
-    pub fn max(&self, other: &Self) -> Self {
+    pub fn add(&self, other: &Self) -> Self {
         Self {
-            top: self.top.max(other.top),
-            right: self.right.max(other.right),
-            bottom: self.bottom.max(other.bottom),
-            left: self.left.max(other.left),
+            top: self.top + other.top,
+            right: self.right + other.right,
+            bottom: self.bottom + other.bottom,
+            left: self.left + other.left,
         }
     }
+
+    pub fn shrink(&self, rect: Rect) -> Rect {
+        let x1 = rect.x1() + self.left;
+        let y1 = rect.y1() + self.top;
+        let width = (rect.width() - self.left - self.right).max(0);
+        let height = (rect.height() - self.top - self.bottom).max(0);
+        Rect::new_sized_unchecked(x1, y1, width, height)
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -176,6 +186,7 @@ impl ZwlrLayerSurfaceV1 {
             last_configure: Default::default(),
             exclusive_edge: Default::default(),
             exclusive_size: Default::default(),
+            exclusive_offset: Default::default(),
             popups: Default::default(),
         }
     }
@@ -330,12 +341,7 @@ impl ZwlrLayerSurfaceV1RequestHandler for ZwlrLayerSurfaceV1 {
         if req.edge.count_ones() > 1 {
             return Err(ZwlrLayerSurfaceV1Error::TooManyExclusiveEdges);
         }
-        let mut pending = self.pending();
-        if req.edge == 0 {
-            pending.exclusive_edge = None;
-        } else {
-            pending.exclusive_edge = Some(req.edge);
-        }
+        self.pending().exclusive_edge = Some(req.edge);
         Ok(())
     }
 }
@@ -345,6 +351,25 @@ impl ZwlrLayerSurfaceV1 {
         self.exclusive_size.get()
     }
 
+    /// Sets the exclusive zones acquired by the layer surfaces below this one.
+    pub fn set_exclusive_offset(&self, offset: ExclusiveSize) {
+        if self.exclusive_offset.replace(offset) == offset {
+            return;
+        }
+        if let ExclusiveZone::Acquire(_) = self.exclusive_zone.get() {
+            self.output_resized();
+        }
+    }
+
+    fn available_rect(&self, output: &OutputNode) -> Rect {
+        let opos = output.global.pos.get();
+        match self.exclusive_zone.get() {
+            ExclusiveZone::MoveSelf => output.non_exclusive_rect.get(),
+            ExclusiveZone::FixedSelf => opos,
+            ExclusiveZone::Acquire(_) => self.exclusive_offset.get().shrink(opos),
+        }
+    }
+
     fn update_exclusive_size(&self) {
         let exclusive_edge = {
             if let Some(ee) = self.exclusive_edge.get() {
@@ -406,7 +431,7 @@ impl ZwlrLayerSurfaceV1 {
             self.layer.set(layer);
         }
         if let Some(edge) = pending.exclusive_edge.take() {
-            self.exclusive_edge.set(Some(edge));
+            self.exclusive_edge.set((edge != 0).then_some(edge));
         }
         let anchor = self.anchor.get();
         let (width, height) = self.size.get();
@@ -431,10 +456,7 @@ impl ZwlrLayerSurfaceV1 {
         };
         let (mut width, mut height) = self.size.get();
         let (mt, mr, mb, ml) = self.margin.get();
-        let (mut available_width, mut available_height) = match self.exclusive_zone.get() {
-            ExclusiveZone::MoveSelf => node.non_exclusive_rect.get().size(),
-            _ => node.global.pos.get().size(),
-        };
+        let (mut available_width, mut available_height) = self.available_rect(&node).size();
         let anchor = self.anchor.get();
         if anchor.contains(LEFT) {
             available_width -= ml;
@@ -478,10 +500,7 @@ impl ZwlrLayerSurfaceV1 {
         }
         let (mt, mr, mb, ml) = self.margin.get();
         let opos = output.global.pos.get();
-        let rect = match self.exclusive_zone.get() {
-            ExclusiveZone::MoveSelf => output.non_exclusive_rect.get(),
-            _ => opos,
-        };
+        let rect = self.available_rect(&output);
         let (owidth, oheight) = rect.size();
         let mut x1 = 0;
         let mut y1 = 0;
@@ -541,6 +560,9 @@ impl ZwlrLayerSurfaceV1 {
         for popup in self.popups.lock().drain_values() {
             popup.popup.destroy_node();
         }
+        if self.keyboard_interactivity.get() == KI_EXCLUSIVE {
+            grab_exclusive_surface(&self.client.state);
+        }
     }
 
     pub fn set_visible(&self, visible: bool) {
@@ -554,6 +576,28 @@ impl ZwlrLayerSurfaceV1 {
     }
 }
 
+/// Gives exclusive keyboard focus to the top-most layer surface that requests it.
+fn grab_exclusive_surface(state: &State) {
+    let mut exclusive = None;
+    'find: for layer in (BACKGROUND..=OVERLAY).rev() {
+        for output in state.root.outputs.lock().values() {
+            for surface in output.layers[layer as usize].rev_iter() {
+                if surface.keyboard_interactivity.get() == KI_EXCLUSIVE {
+                    exclusive = Some(surface.surface.clone());
+                    break 'find;
+                }
+            }
+        }
+    }
+    let Some(surface) = exclusive else {
+        return;
+    };
+    for seat in state.globals.seats.lock().values() {
+        seat.ungrab_kb();
+        seat.grab(surface.clone());
+    }
+}
+
 impl SurfaceExt for ZwlrLayerSurfaceV1 {
     fn before_apply_commit(
         self: Rc<Self>,
@@ -603,14 +647,10 @@ impl SurfaceExt for ZwlrLayerSurfaceV1 {
                     }
                 }
                 KI_ON_DEMAND => self.surface.seat_state.release_kb_grab(),
-                KI_EXCLUSIVE => {
-                    let seats = self.client.state.globals.seats.lock();
-                    for seat in seats.values() {
-                        seat.grab(self.surface.clone());
-                    }
-                }
+                KI_EXCLUSIVE => {}
                 _ => unreachable!(),
             }
+            grab_exclusive_surface(&self.client.state);
         }
     }
 
@@ -0,0 +1,86 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::{wl_seat::WlSeatGlobal, wl_surface::WlSurface},
+        leaks::Tracker,
+        object::{Object, Version},
+        tree::Node,
+        wire::{zwp_keyboard_shortcuts_inhibitor_v1::*, ZwpKeyboardShortcutsInhibitorV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct ZwpKeyboardShortcutsInhibitorV1 {
+    pub id: ZwpKeyboardShortcutsInhibitorV1Id,
+    pub client: Rc<Client>,
+    pub surface: Rc<WlSurface>,
+    pub seat: Rc<WlSeatGlobal>,
+    pub active: Cell<bool>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+}
+
+impl ZwpKeyboardShortcutsInhibitorV1 {
+    pub fn install(self: &Rc<Self>) {
+        self.surface
+            .shortcuts_inhibitors
+            .insert(self.seat.id(), self.clone());
+        if self.seat.keyboard_node().node_id() == self.surface.node_id() {
+            self.activate();
+        }
+    }
+
+    pub fn activate(self: &Rc<Self>) {
+        if self.active.replace(true) {
+            return;
+        }
+        self.seat.set_shortcuts_inhibitor(Some(self.clone()));
+        self.client.event(Active { self_id: self.id });
+    }
+
+    pub fn deactivate(&self) {
+        if !self.active.replace(false) {
+            return;
+        }
+        self.seat.set_shortcuts_inhibitor(None);
+        self.client.event(Inactive { self_id: self.id });
+    }
+
+    fn detach(&self) {
+        self.deactivate();
+        self.surface
+            .shortcuts_inhibitors
+            .remove(&self.seat.id());
+    }
+}
+
+impl ZwpKeyboardShortcutsInhibitorV1RequestHandler for ZwpKeyboardShortcutsInhibitorV1 {
+    type Error = ZwpKeyboardShortcutsInhibitorV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwpKeyboardShortcutsInhibitorV1;
+    version = self.version;
+}
+
+impl Object for ZwpKeyboardShortcutsInhibitorV1 {
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ZwpKeyboardShortcutsInhibitorV1);
+
+#[derive(Debug, Error)]
+pub enum ZwpKeyboardShortcutsInhibitorV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwpKeyboardShortcutsInhibitorV1Error, ClientError);
@@ -1,8 +1,10 @@
 use {
     crate::{
-        client::{Client, ClientError},
+        client::{Client, ClientError, ClientId, ACTIVATION_SERIAL_KINDS},
+        ifs::wl_seat::SeatId,
         leaks::Tracker,
         object::{Object, Version},
+        tree::Node,
         utils::activation_token::{activation_token, ActivationToken},
         wire::{xdg_activation_token_v1::*, XdgActivationTokenV1Id},
     },
@@ -12,11 +14,22 @@ use {
 
 const MAX_TOKENS_PER_CLIENT: usize = 8;
 
+/// Tokens older than this no longer grant focus.
+pub const ACTIVATION_TOKEN_MAX_AGE_NSEC: u64 = 10_000_000_000;
+
+#[derive(Copy, Clone, Debug)]
+pub struct ActivationTokenOrigin {
+    pub seat: SeatId,
+    pub client: ClientId,
+    pub time_nsec: u64,
+}
+
 pub struct XdgActivationTokenV1 {
     pub id: XdgActivationTokenV1Id,
     pub client: Rc<Client>,
     pub tracker: Tracker<Self>,
     already_used: Cell<bool>,
+    seat: Cell<Option<SeatId>>,
     version: Version,
 }
 
@@ -27,6 +40,7 @@ impl XdgActivationTokenV1 {
             client: client.clone(),
             tracker: Default::default(),
             already_used: Cell::new(false),
+            seat: Default::default(),
             version,
         }
     }
@@ -35,7 +49,24 @@ impl XdgActivationTokenV1 {
 impl XdgActivationTokenV1RequestHandler for XdgActivationTokenV1 {
     type Error = XdgActivationTokenV1Error;
 
-    fn set_serial(&self, _req: SetSerial, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+    fn set_serial(&self, req: SetSerial, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let seat = self.client.lookup(req.seat)?;
+        let serial = self.client.validate_input_serial(
+            "xdg_activation_token_v1.set_serial",
+            req.serial,
+            seat.global.id(),
+            ACTIVATION_SERIAL_KINDS,
+        );
+        let mut seat_id = serial.map(|_| seat.global.id());
+        let focused = seat.global.keyboard_node().node_client_id() == Some(self.client.id);
+        if seat_id.is_some() && !focused {
+            log::debug!(
+                "Client {} sent xdg_activation_token_v1.set_serial without keyboard focus",
+                self.client.id,
+            );
+            seat_id = None;
+        }
+        self.seat.set(seat_id);
         Ok(())
     }
 
@@ -53,7 +84,12 @@ impl XdgActivationTokenV1RequestHandler for XdgActivationTokenV1 {
             return Err(XdgActivationTokenV1Error::AlreadyUsed);
         }
         let token = activation_token();
-        self.client.state.activation_tokens.set(token, ());
+        let origin = self.seat.get().map(|seat| ActivationTokenOrigin {
+            seat,
+            client: self.client.id,
+            time_nsec: self.client.state.now_nsec(),
+        });
+        self.client.state.activation_tokens.set(token, origin);
         let mut tokens = self.client.activation_tokens.borrow_mut();
         if tokens.len() >= MAX_TOKENS_PER_CLIENT {
             if let Some(oldest) = tokens.pop_front() {
@@ -2,9 +2,10 @@ use {
     crate::{
         client::{Client, ClientError},
         globals::{Global, GlobalName},
-        ifs::xdg_activation_token_v1::XdgActivationTokenV1,
+        ifs::xdg_activation_token_v1::{XdgActivationTokenV1, ACTIVATION_TOKEN_MAX_AGE_NSEC},
         leaks::Tracker,
         object::{Object, Version},
+        tree::Node,
         utils::{activation_token::ActivationToken, errorfmt::ErrorFmt, opaque::OpaqueError},
         wire::{xdg_activation_v1::*, XdgActivationV1Id},
     },
@@ -92,14 +93,29 @@ impl XdgActivationV1RequestHandler for XdgActivationV1 {
             }
         };
         let surface = self.client.lookup(req.surface)?;
-        if self.client.state.activation_tokens.remove(&token).is_none() {
+        let state = &self.client.state;
+        let Some(origin) = state.activation_tokens.remove(&token) else {
             log::warn!(
                 "Client requested activation with unknown token {}",
                 req.token
             );
             return Ok(());
+        };
+        let now = state.now_nsec();
+        let seat = origin
+            .filter(|o| now.saturating_sub(o.time_nsec) <= ACTIVATION_TOKEN_MAX_AGE_NSEC)
+            .and_then(|o| {
+                let seats = state.globals.seats.lock();
+                seats
+                    .values()
+                    .find(|s| s.id() == o.seat)
+                    .filter(|s| s.keyboard_node().node_client_id() == Some(o.client))
+                    .cloned()
+            });
+        match seat {
+            Some(seat) => surface.activate(&seat),
+            _ => surface.request_activation(),
         }
-        surface.request_activation();
         Ok(())
     }
 }
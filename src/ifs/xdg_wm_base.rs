@@ -1,5 +1,6 @@
 use {
     crate::{
+        async_engine::SpawnedFuture,
         client::{Client, ClientError},
         globals::{Global, GlobalName},
         ifs::{
@@ -8,10 +9,10 @@ use {
         },
         leaks::Tracker,
         object::{Object, Version},
-        utils::copyhashmap::CopyHashMap,
+        utils::{copyhashmap::CopyHashMap, errorfmt::ErrorFmt},
         wire::{xdg_wm_base::*, XdgSurfaceId, XdgWmBaseId},
     },
-    std::rc::Rc,
+    std::{cell::Cell, rc::Rc},
     thiserror::Error,
 };
 
@@ -27,6 +28,8 @@ const INVALID_SURFACE_STATE: u32 = 4;
 #[expect(dead_code)]
 const INVALID_POSITIONER: u32 = 5;
 
+const PING_INTERVAL_MS: u64 = 10000;
+
 pub struct XdgWmBaseGlobal {
     name: GlobalName,
 }
@@ -37,6 +40,10 @@ pub struct XdgWmBase {
     pub version: Version,
     pub(super) surfaces: CopyHashMap<XdgSurfaceId, Rc<XdgSurface>>,
     pub tracker: Tracker<Self>,
+    ping_serial: Cell<Option<u32>>,
+    ping_task: Cell<Option<SpawnedFuture<()>>>,
+    unresponsive: Cell<bool>,
+    close_requested: Cell<bool>,
 }
 
 impl XdgWmBaseGlobal {
@@ -56,6 +63,10 @@ impl XdgWmBaseGlobal {
             version,
             surfaces: Default::default(),
             tracker: Default::default(),
+            ping_serial: Default::default(),
+            ping_task: Default::default(),
+            unresponsive: Default::default(),
+            close_requested: Default::default(),
         });
         track!(client, obj);
         client.add_client_obj(&obj)?;
@@ -63,6 +74,100 @@ impl XdgWmBaseGlobal {
     }
 }
 
+impl XdgWmBase {
+    pub fn ping(self: &Rc<Self>) {
+        if self.ping_serial.get().is_some() {
+            return;
+        }
+        let serial = self.client.next_serial() as u32;
+        self.ping_serial.set(Some(serial));
+        self.client.event(Ping {
+            self_id: self.id,
+            serial,
+        });
+        let eng = &self.client.state.eng;
+        let task = eng.spawn("xdg ping timeout", ping_timeout(self.clone(), serial));
+        self.ping_task.set(Some(task));
+    }
+
+    fn schedule_ping(self: &Rc<Self>) {
+        let eng = &self.client.state.eng;
+        let task = eng.spawn("xdg ping interval", ping_interval(self.clone()));
+        self.ping_task.set(Some(task));
+    }
+
+    fn has_active_toplevel(&self) -> bool {
+        self.surfaces
+            .lock()
+            .values()
+            .filter_map(|s| s.surface.get_toplevel())
+            .any(|tl| tl.tl_data().active())
+    }
+
+    fn set_unresponsive(&self, unresponsive: bool) {
+        if self.unresponsive.replace(unresponsive) == unresponsive {
+            return;
+        }
+        if unresponsive {
+            log::warn!("Client {} is not responding", self.client.id);
+        }
+        let toplevels: Vec<_> = self
+            .surfaces
+            .lock()
+            .values()
+            .filter_map(|s| s.surface.get_toplevel())
+            .collect();
+        for tl in toplevels {
+            let node = tl.tl_as_node();
+            tl.tl_data().set_unresponsive(node, unresponsive);
+        }
+    }
+
+    /// Requests that a window of this client be closed.
+    ///
+    /// Returns `false` if the client has not responded to a ping since a window was last
+    /// closed. The client is then disconnected.
+    pub fn request_close(&self) -> bool {
+        if self.unresponsive.get() && self.close_requested.get() {
+            log::warn!("Disconnecting unresponsive client {}", self.client.id);
+            self.client.state.clients.kill(self.client.id);
+            return false;
+        }
+        self.close_requested.set(true);
+        true
+    }
+
+    /// Disconnects the client.
+    ///
+    /// The pid reported by SO_PEERCRED can belong to a different process, such as a
+    /// sandbox wrapper, so it is not signaled.
+    pub fn kill_client(&self) {
+        log::warn!("Disconnecting client {}", self.client.id);
+        self.client.state.clients.kill(self.client.id);
+    }
+}
+
+async fn ping_timeout(base: Rc<XdgWmBase>, serial: u32) {
+    let timeout = base.client.state.client_ping_timeout_msec.get();
+    if let Err(e) = base.client.state.wheel.timeout(timeout).await {
+        log::error!("Could not create a timeout: {}", ErrorFmt(e));
+        return;
+    }
+    if base.ping_serial.get() == Some(serial) {
+        base.set_unresponsive(true);
+    }
+}
+
+async fn ping_interval(base: Rc<XdgWmBase>) {
+    if let Err(e) = base.client.state.wheel.timeout(PING_INTERVAL_MS).await {
+        log::error!("Could not create a timeout: {}", ErrorFmt(e));
+        return;
+    }
+    if base.has_active_toplevel() {
+        base.ping();
+    }
+}
+
 impl XdgWmBaseRequestHandler for XdgWmBase {
     type Error = XdgWmBaseError;
 
@@ -99,7 +204,14 @@ impl XdgWmBaseRequestHandler for XdgWmBase {
         Ok(())
     }
 
-    fn pong(&self, _req: Pong, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+    fn pong(&self, req: Pong, slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.ping_serial.get() != Some(req.serial) {
+            return Ok(());
+        }
+        self.ping_serial.take();
+        self.close_requested.set(false);
+        self.set_unresponsive(false);
+        slf.schedule_ping();
         Ok(())
     }
 }
@@ -128,6 +240,7 @@ dedicated_add_obj!(XdgWmBase, XdgWmBaseId, xdg_wm_bases);
 impl Object for XdgWmBase {
     fn break_loops(&self) {
         self.surfaces.clear();
+        self.ping_task.take();
     }
 }
 
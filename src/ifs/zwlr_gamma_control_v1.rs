@@ -0,0 +1,160 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        clientmem::{ClientMem, ClientMemError},
+        ifs::wl_output::OutputGlobalOpt,
+        leaks::Tracker,
+        object::{Object, Version},
+        utils::{errorfmt::ErrorFmt, oserror::OsError},
+        wire::{zwlr_gamma_control_v1::*, ZwlrGammaControlV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct ZwlrGammaControlV1 {
+    pub id: ZwlrGammaControlV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub output: Rc<OutputGlobalOpt>,
+    pub gamma_size: Cell<u32>,
+    pub active: Cell<bool>,
+}
+
+impl ZwlrGammaControlV1 {
+    fn send_gamma_size(&self, size: u32) {
+        self.client.event(GammaSize {
+            self_id: self.id,
+            size,
+        });
+    }
+
+    pub fn send_failed(&self) {
+        self.client.event(Failed { self_id: self.id });
+    }
+
+    pub fn install(self: &Rc<Self>) {
+        let Some(node) = self.output.node() else {
+            self.send_failed();
+            return;
+        };
+        if node.gamma_control.is_some() {
+            self.send_failed();
+            return;
+        }
+        let Some(size) = node.global.connector.connector.gamma_lut_size() else {
+            self.send_failed();
+            return;
+        };
+        node.gamma_control.set(Some(self.clone()));
+        self.gamma_size.set(size);
+        self.active.set(true);
+        self.send_gamma_size(size);
+    }
+
+    /// Called when the output is removed while this object controls its gamma.
+    pub fn output_removed(&self) {
+        if self.active.replace(false) {
+            self.send_failed();
+        }
+    }
+
+    fn detach(&self) {
+        if !self.active.replace(false) {
+            return;
+        }
+        if let Some(node) = self.output.node() {
+            node.gamma_control.take();
+            node.global.connector.connector.set_gamma_lut(None);
+        }
+    }
+
+    fn fail(&self) {
+        self.detach();
+        self.send_failed();
+    }
+
+    fn read_lut(&self, req: &SetGamma) -> Result<Vec<[u16; 3]>, ZwlrGammaControlV1Error> {
+        let size = self.gamma_size.get() as usize;
+        let len = size * 3 * 2;
+        let stat =
+            uapi::fstat(req.fd.raw()).map_err(|e| ZwlrGammaControlV1Error::Stat(e.into()))?;
+        if (stat.st_size as u64) < len as u64 {
+            return Err(ZwlrGammaControlV1Error::TooSmall);
+        }
+        let mem = ClientMem::new(&req.fd, len, true, Some(&self.client), None)
+            .map(Rc::new)
+            .map_err(ZwlrGammaControlV1Error::MapGamma)?;
+        let mut data = vec![];
+        mem.offset(0)
+            .read(&mut data)
+            .map_err(ZwlrGammaControlV1Error::ReadGamma)?;
+        let value = |idx: usize| u16::from_ne_bytes([data[2 * idx], data[2 * idx + 1]]);
+        let lut = (0..size)
+            .map(|i| [value(i), value(size + i), value(2 * size + i)])
+            .collect();
+        Ok(lut)
+    }
+}
+
+impl ZwlrGammaControlV1RequestHandler for ZwlrGammaControlV1 {
+    type Error = ZwlrGammaControlV1Error;
+
+    fn set_gamma(&self, req: SetGamma, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if !self.active.get() {
+            return Ok(());
+        }
+        let lut = match self.read_lut(&req) {
+            Ok(lut) => lut,
+            Err(e) => {
+                log::warn!("Could not read gamma ramps: {}", ErrorFmt(&e));
+                self.fail();
+                return Ok(());
+            }
+        };
+        let Some(node) = self.output.node() else {
+            self.fail();
+            return Ok(());
+        };
+        let connector = &node.global.connector.connector;
+        if !connector.set_gamma_lut(Some(&lut)) {
+            self.fail();
+        }
+        Ok(())
+    }
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwlrGammaControlV1;
+    version = self.version;
+}
+
+impl Object for ZwlrGammaControlV1 {
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ZwlrGammaControlV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrGammaControlV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Could not stat the gamma ramps file")]
+    Stat(#[source] OsError),
+    #[error("The gamma ramps file is smaller than the gamma size")]
+    TooSmall,
+    #[error("Could not map the gamma ramps")]
+    MapGamma(#[source] ClientMemError),
+    #[error("Could not read the gamma ramps")]
+    ReadGamma(#[source] ClientMemError),
+}
+efrom!(ZwlrGammaControlV1Error, ClientError);
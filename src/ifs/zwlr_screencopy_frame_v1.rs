@@ -26,7 +26,7 @@ pub struct ZwlrScreencopyFrameV1 {
     pub tracker: Tracker<Self>,
     pub output: Rc<OutputGlobalOpt>,
     pub rect: Rect,
-    pub _overlay_cursor: bool,
+    pub overlay_cursor: bool,
     pub used: Cell<bool>,
     pub with_damage: Cell<bool>,
     pub buffer: Cell<Option<Rc<WlBuffer>>>,
@@ -49,16 +49,13 @@ impl ZwlrScreencopyFrameV1 {
     }
 
     pub fn send_damage(&self) {
-        if let Some(output) = self.output.get() {
-            let pos = output.pos.get();
-            self.client.event(Damage {
-                self_id: self.id,
-                x: 0,
-                y: 0,
-                width: pos.width() as _,
-                height: pos.height() as _,
-            });
-        }
+        self.client.event(Damage {
+            self_id: self.id,
+            x: 0,
+            y: 0,
+            width: self.rect.width() as _,
+            height: self.rect.height() as _,
+        });
     }
 
     pub fn send_buffer(&self) {
@@ -0,0 +1,117 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        globals::{Global, GlobalName},
+        ifs::wl_surface::zwp_keyboard_shortcuts_inhibitor_v1::ZwpKeyboardShortcutsInhibitorV1,
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{
+            zwp_keyboard_shortcuts_inhibit_manager_v1::*, ZwpKeyboardShortcutsInhibitManagerV1Id,
+        },
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct ZwpKeyboardShortcutsInhibitManagerV1Global {
+    name: GlobalName,
+}
+
+impl ZwpKeyboardShortcutsInhibitManagerV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ZwpKeyboardShortcutsInhibitManagerV1Id,
+        client: &Rc<Client>,
+        version: Version,
+    ) -> Result<(), ZwpKeyboardShortcutsInhibitManagerV1Error> {
+        let obj = Rc::new(ZwpKeyboardShortcutsInhibitManagerV1 {
+            id,
+            client: client.clone(),
+            version,
+            tracker: Default::default(),
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        Ok(())
+    }
+}
+
+global_base!(
+    ZwpKeyboardShortcutsInhibitManagerV1Global,
+    ZwpKeyboardShortcutsInhibitManagerV1,
+    ZwpKeyboardShortcutsInhibitManagerV1Error
+);
+
+impl Global for ZwpKeyboardShortcutsInhibitManagerV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+}
+
+simple_add_global!(ZwpKeyboardShortcutsInhibitManagerV1Global);
+
+pub struct ZwpKeyboardShortcutsInhibitManagerV1 {
+    pub id: ZwpKeyboardShortcutsInhibitManagerV1Id,
+    pub client: Rc<Client>,
+    pub version: Version,
+    pub tracker: Tracker<Self>,
+}
+
+impl ZwpKeyboardShortcutsInhibitManagerV1RequestHandler for ZwpKeyboardShortcutsInhibitManagerV1 {
+    type Error = ZwpKeyboardShortcutsInhibitManagerV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn inhibit_shortcuts(&self, req: InhibitShortcuts, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let surface = self.client.lookup(req.surface)?;
+        let seat = self.client.lookup(req.seat)?;
+        if surface
+            .shortcuts_inhibitors
+            .contains(&seat.global.id())
+        {
+            return Err(ZwpKeyboardShortcutsInhibitManagerV1Error::AlreadyInhibited);
+        }
+        let inhibitor = Rc::new(ZwpKeyboardShortcutsInhibitorV1 {
+            id: req.id,
+            client: self.client.clone(),
+            surface,
+            seat: seat.global.clone(),
+            active: Cell::new(false),
+            tracker: Default::default(),
+            version: self.version,
+        });
+        track!(self.client, inhibitor);
+        self.client.add_client_obj(&inhibitor)?;
+        inhibitor.install();
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwpKeyboardShortcutsInhibitManagerV1;
+    version = self.version;
+}
+
+impl Object for ZwpKeyboardShortcutsInhibitManagerV1 {}
+
+simple_add_obj!(ZwpKeyboardShortcutsInhibitManagerV1);
+
+#[derive(Debug, Error)]
+pub enum ZwpKeyboardShortcutsInhibitManagerV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("The surface already has a shortcuts inhibitor for the seat")]
+    AlreadyInhibited,
+}
+efrom!(ZwpKeyboardShortcutsInhibitManagerV1Error, ClientError);
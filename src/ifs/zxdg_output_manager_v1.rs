@@ -64,7 +64,7 @@ impl ZxdgOutputManagerV1RequestHandler for ZxdgOutputManagerV1 {
         });
         track!(self.client, xdg_output);
         self.client.add_client_obj(&xdg_output)?;
-        xdg_output.send_updates();
+        xdg_output.send_initial_state();
         output.xdg_outputs.set(req.id, xdg_output);
         Ok(())
     }
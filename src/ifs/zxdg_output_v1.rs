@@ -11,7 +11,6 @@ use {
 };
 
 pub const NAME_SINCE: Version = Version(2);
-#[expect(dead_code)]
 pub const DESCRIPTION_SINCE: Version = Version(2);
 pub const NO_DONE_SINCE: Version = Version(3);
 
@@ -53,7 +52,6 @@ impl ZxdgOutputV1 {
         });
     }
 
-    #[expect(dead_code)]
     pub fn send_description(&self, description: &str) {
         self.client.event(Description {
             self_id: self.id,
@@ -61,24 +59,38 @@ impl ZxdgOutputV1 {
         });
     }
 
-    pub fn send_updates(&self) {
+    fn send_state(&self, initial: bool) {
         let Some(global) = self.output.global.get() else {
             return;
         };
         let pos = global.position();
         self.send_logical_position(pos.x1(), pos.y1());
         self.send_logical_size(pos.width(), pos.height());
-        if self.version >= NAME_SINCE {
-            self.send_name(&global.connector.name);
-        }
-        if self.version >= NO_DONE_SINCE {
-            if self.output.version >= SEND_DONE_SINCE {
-                self.output.send_done();
+        // The name must only be sent once and the description never changes.
+        if initial {
+            if self.version >= NAME_SINCE {
+                self.send_name(&global.connector.name);
+            }
+            if self.version >= DESCRIPTION_SINCE {
+                self.send_description(&global.description());
             }
-        } else {
+        }
+        if self.version < NO_DONE_SINCE {
             self.send_done();
         }
     }
+
+    pub fn send_initial_state(&self) {
+        self.send_state(true);
+        if self.version >= NO_DONE_SINCE && self.output.version >= SEND_DONE_SINCE {
+            self.output.send_done();
+        }
+    }
+
+    /// Sends the changed state. The caller must send `wl_output.done` afterwards.
+    pub fn send_updates(&self) {
+        self.send_state(false);
+    }
 }
 
 impl ZxdgOutputV1RequestHandler for ZxdgOutputV1 {
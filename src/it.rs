@@ -28,6 +28,7 @@ mod test_error;
 mod test_object;
 #[macro_use]
 mod test_macros;
+mod test_alloc_counter;
 pub mod test_backend;
 mod test_client;
 pub mod test_config;
@@ -0,0 +1,53 @@
+//! Counts the heap allocations performed by each thread.
+//!
+//! The leak tracker installs its own global allocator, so nothing is counted if the
+//! `rc_tracking` feature is enabled.
+
+use std::cell::Cell;
+
+thread_local! {
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Returns the number of heap allocations performed by this thread so far.
+pub fn allocations() -> u64 {
+    ALLOCATIONS.get()
+}
+
+#[cfg(not(feature = "rc_tracking"))]
+mod allocator {
+    use {
+        super::ALLOCATIONS,
+        std::alloc::{GlobalAlloc, Layout, System},
+    };
+
+    struct CountingAllocator;
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+
+    fn count() {
+        let _ = ALLOCATIONS.try_with(|a| a.set(a.get() + 1));
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            count();
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            count();
+            unsafe { System.alloc_zeroed(layout) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) }
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            count();
+            unsafe { System.realloc(ptr, layout, new_size) }
+        }
+    }
+}
@@ -3,17 +3,17 @@ use {
         allocator::{Allocator, AllocatorError},
         async_engine::SpawnedFuture,
         backend::{
-            AxisSource, Backend, BackendEvent, Connector, ConnectorEvent, ConnectorId,
-            ConnectorKernelId, DrmDeviceId, InputDevice, InputDeviceAccelProfile,
-            InputDeviceCapability, InputDeviceId, InputEvent, KeyState, Mode, MonitorInfo,
-            ScrollAxis, TransformMatrix,
+            AxisSource, Backend, BackendDrmDevice, BackendEvent, Connector, ConnectorEvent,
+            ConnectorId, ConnectorKernelId, DrmDeviceId, DrmEvent, InputDevice,
+            InputDeviceAccelProfile, InputDeviceCapability, InputDeviceId, InputEvent, KeyState,
+            Mode, MonitorInfo, ScrollAxis, TransformMatrix,
         },
         compositor::TestFuture,
         drm_feedback::DrmFeedback,
         fixed::Fixed,
         gfx_api::GfxError,
         gfx_apis::create_vulkan_allocator,
-        ifs::wl_output::OutputId,
+        ifs::{wl_output::OutputId, wp_content_type_v1::ContentType},
         it::{
             test_error::TestResult, test_gfx_api::TestGfxCtx, test_utils::test_expected_event::TEEH,
         },
@@ -24,11 +24,12 @@ use {
             on_change::OnChange, oserror::OsError, syncqueue::SyncQueue,
         },
         video::{
-            drm::{ConnectorType, Drm, DrmError},
+            drm::{ConnectorType, Drm, DrmError, DrmVersion},
             gbm::{GbmDevice, GbmError},
         },
     },
     bstr::ByteSlice,
+    jay_config::video::GfxApi,
     std::{any::Any, cell::Cell, error::Error, io, os::unix::ffi::OsStrExt, pin::Pin, rc::Rc},
     thiserror::Error,
     uapi::c,
@@ -76,6 +77,8 @@ impl TestBackend {
             },
             events: Default::default(),
             feedback: Default::default(),
+            content_type: Default::default(),
+            drm_dev: None,
         });
         let default_mouse = Rc::new(TestBackendMouse {
             common: TestInputDeviceCommon {
@@ -302,6 +305,8 @@ pub struct TestConnector {
     pub kernel_id: ConnectorKernelId,
     pub events: OnChange<ConnectorEvent>,
     pub feedback: CloneCell<Option<Rc<DrmFeedback>>>,
+    pub content_type: Cell<Option<ContentType>>,
+    pub drm_dev: Option<DrmDeviceId>,
 }
 
 impl Connector for TestConnector {
@@ -326,7 +331,7 @@ impl Connector for TestConnector {
     }
 
     fn drm_dev(&self) -> Option<DrmDeviceId> {
-        None
+        self.drm_dev
     }
 
     fn set_mode(&self, _mode: Mode) {
@@ -336,6 +341,64 @@ impl Connector for TestConnector {
     fn drm_feedback(&self) -> Option<Rc<DrmFeedback>> {
         self.feedback.get()
     }
+
+    fn set_content_type(&self, content_type: Option<ContentType>) {
+        self.content_type.set(content_type);
+    }
+}
+
+pub struct TestDrmDevice {
+    pub id: DrmDeviceId,
+    pub events: OnChange<DrmEvent>,
+}
+
+impl BackendDrmDevice for TestDrmDevice {
+    fn id(&self) -> DrmDeviceId {
+        self.id
+    }
+
+    fn event(&self) -> Option<DrmEvent> {
+        self.events.events.pop()
+    }
+
+    fn on_change(&self, cb: Rc<dyn Fn()>) {
+        self.events.on_change.set(Some(cb));
+    }
+
+    fn dev_t(&self) -> c::dev_t {
+        0
+    }
+
+    fn make_render_device(&self) {
+        // nothing
+    }
+
+    fn set_gfx_api(&self, _api: GfxApi) {
+        // nothing
+    }
+
+    fn gtx_api(&self) -> GfxApi {
+        GfxApi::OpenGl
+    }
+
+    fn version(&self) -> Result<DrmVersion, DrmError> {
+        Ok(DrmVersion {
+            version_major: 1,
+            version_minor: 0,
+            version_patchlevel: 0,
+            name: "jay-test".into(),
+            date: Default::default(),
+            desc: Default::default(),
+        })
+    }
+
+    fn set_direct_scanout_enabled(&self, _enabled: bool) {
+        // nothing
+    }
+
+    fn is_render_device(&self) -> bool {
+        false
+    }
 }
 
 pub struct TestMouseClick {
@@ -9,8 +9,9 @@ use {
                 test_compositor::TestCompositor, test_cursor_shape_manager::TestCursorShapeManager,
                 test_data_device_manager::TestDataDeviceManager,
                 test_jay_compositor::TestJayCompositor, test_keyboard::TestKeyboard,
-                test_pointer::TestPointer, test_registry::TestRegistry, test_seat::TestSeat,
-                test_shm::TestShm, test_single_pixel_buffer_manager::TestSinglePixelBufferManager,
+                test_output::TestOutput, test_pointer::TestPointer, test_registry::TestRegistry,
+                test_seat::TestSeat, test_shm::TestShm,
+                test_single_pixel_buffer_manager::TestSinglePixelBufferManager,
                 test_subcompositor::TestSubcompositor, test_viewporter::TestViewporter,
                 test_xdg_activation::TestXdgActivation, test_xdg_base::TestXdgWmBase,
             },
@@ -19,6 +20,7 @@ use {
             testrun::TestRun,
         },
         theme::Color,
+        tree::OutputNode,
     },
     std::{cell::Cell, rc::Rc},
 };
@@ -84,6 +86,21 @@ impl TestClient {
         })
     }
 
+    pub async fn get_output(&self, output: &OutputNode) -> TestResult<Rc<TestOutput>> {
+        let obj = Rc::new(TestOutput {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            scale: Cell::new(1),
+            name: Default::default(),
+            description: Default::default(),
+            done: Cell::new(0),
+        });
+        self.registry.bind(&obj, output.global.name.raw(), 4)?;
+        self.tran.sync().await;
+        Ok(obj)
+    }
+
     pub async fn sync(&self) {
         self.run.sync().await;
         self.tran.sync().await;
@@ -1,6 +1,6 @@
 use {
     crate::{
-        backend::InputDeviceId,
+        backend::{ConnectorId, InputDeviceId},
         ifs::wl_seat::SeatId,
         it::test_error::{TestError, TestResult},
         tree::OutputNode,
@@ -11,15 +11,22 @@ use {
     jay_config::{
         _private::{
             bincode_ops,
-            ipc::{ClientMessage, Response, ServerMessage},
+            ipc::{ClientMessage, Response, ServerFeature, ServerMessage},
             ConfigEntry, VERSION,
         },
-        input::{InputDevice, Seat},
-        keyboard::{Keymap, ModifiedKeySym},
-        video::{Connector, Transform},
-        Axis, Direction,
+        input::{DeviceMatch, InputDevice, PointerContext, PointerInput, Seat},
+        keyboard::{mods::Modifiers, AppMod, Keymap, ModifiedKeySym},
+        theme::{colors::Colorable, sized::Resizable, Color},
+        video::{BackgroundMode, Connector, ContentType, Transform},
+        Axis, Container, Direction, TabDirection,
+    },
+    std::{
+        cell::{Cell, RefCell},
+        ops::Deref,
+        ptr,
+        rc::Rc,
+        time::Duration,
     },
-    std::{cell::Cell, ops::Deref, ptr, rc::Rc, time::Duration},
 };
 
 pub static TEST_CONFIG_ENTRY: ConfigEntry = ConfigEntry {
@@ -41,7 +48,14 @@ where
         srv: Cell::new(None),
         responses: Default::default(),
         invoked_shortcuts: Default::default(),
+        invoked_pointer_bindings: Default::default(),
+        last_invoked_app_mod: Default::default(),
+        pointer_binding_motion: Default::default(),
+        pointer_binding_releases: Default::default(),
+        content_type_changes: Default::default(),
         graphics_initialized: Cell::new(false),
+        request_ids: Cell::new(false),
+        next_request_id: Cell::new(0),
     });
     let old = CONFIG.get();
     CONFIG.set(tc.deref());
@@ -95,19 +109,27 @@ unsafe extern "C" fn handle_msg(data: *const u8, msg: *const u8, size: usize) {
         ServerMessage::Response { response } => {
             tc.responses.push(response);
         }
-        ServerMessage::InvokeShortcut { seat, mods, sym } => {
+        ServerMessage::InvokeShortcut {
+            seat,
+            mods,
+            sym,
+            app_mod,
+        } => {
             tc.invoked_shortcuts
                 .set((SeatId::from_raw(seat.0 as _), mods | sym), ());
+            *tc.last_invoked_app_mod.borrow_mut() = Some(app_mod);
         }
         ServerMessage::InvokeShortcut2 {
             seat,
             unmasked_mods,
             effective_mods,
             sym,
+            app_mod,
         } => {
             let _ = unmasked_mods;
             tc.invoked_shortcuts
                 .set((SeatId::from_raw(seat.0 as _), effective_mods | sym), ());
+            *tc.last_invoked_app_mod.borrow_mut() = Some(app_mod);
         }
         ServerMessage::NewInputDevice { .. } => {}
         ServerMessage::DelInputDevice { .. } => {}
@@ -123,8 +145,39 @@ unsafe extern "C" fn handle_msg(data: *const u8, msg: *const u8, size: usize) {
         ServerMessage::Idle => {}
         ServerMessage::DevicesEnumerated => {}
         ServerMessage::InterestReady { .. } => {}
-        ServerMessage::Features { .. } => {}
+        ServerMessage::Features { features } => {
+            if features.contains(&ServerFeature::REQUEST_IDS) {
+                tc.request_ids.set(true);
+            }
+        }
         ServerMessage::SwitchEvent { .. } => {}
+        ServerMessage::Error { .. } => {}
+        ServerMessage::InvokePointerBinding {
+            seat,
+            mods,
+            input,
+            context,
+        } => {
+            let seat = SeatId::from_raw(seat.0 as _);
+            tc.invoked_pointer_bindings
+                .set((seat, mods, input, context), ());
+        }
+        ServerMessage::RunCompleted { .. } => {}
+        ServerMessage::PointerBindingMotion { x, y, .. } => {
+            tc.pointer_binding_motion.set(Some((x, y)));
+        }
+        ServerMessage::PointerBindingReleased { .. } => {
+            tc.pointer_binding_releases
+                .set(tc.pointer_binding_releases.get() + 1);
+        }
+        ServerMessage::ContentTypeChanged {
+            connector,
+            content_type,
+        } => {
+            tc.content_type_changes
+                .borrow_mut()
+                .push((ConnectorId::from_raw(connector.0 as _), content_type));
+        }
     }
 }
 
@@ -139,7 +192,15 @@ pub struct TestConfig {
     srv: Cell<Option<ServerData>>,
     responses: Stack<Response>,
     pub invoked_shortcuts: CopyHashMap<(SeatId, ModifiedKeySym), ()>,
+    pub invoked_pointer_bindings:
+        CopyHashMap<(SeatId, Modifiers, PointerInput, PointerContext), ()>,
+    pub last_invoked_app_mod: RefCell<Option<AppMod>>,
+    pub pointer_binding_motion: Cell<Option<(i32, i32)>>,
+    pub pointer_binding_releases: Cell<usize>,
+    pub content_type_changes: RefCell<Vec<(ConnectorId, ContentType)>>,
     pub graphics_initialized: Cell<bool>,
+    request_ids: Cell<bool>,
+    next_request_id: Cell<u64>,
 }
 
 macro_rules! get_response {
@@ -164,6 +225,11 @@ impl TestConfig {
             _ => bail!("srv not set"),
         };
         let mut buf = vec![];
+        if self.request_ids.get() {
+            let id = self.next_request_id.get();
+            self.next_request_id.set(id + 1);
+            buf.extend_from_slice(&id.to_le_bytes());
+        }
         bincode_ops().serialize_into(&mut buf, msg).unwrap();
         unsafe {
             (srv.srv_handler)(srv.srv_data, buf.as_ptr(), buf.len());
@@ -189,6 +255,14 @@ impl TestConfig {
         Ok(SeatId::from_raw(seat.0 as _))
     }
 
+    /// Returns whether the compositor accepted the request.
+    pub fn remove_seat(&self, seat: SeatId) -> Result<bool, TestError> {
+        let reply = self.send_with_reply(ClientMessage::RemoveSeat {
+            seat: Seat(seat.raw() as _),
+        })?;
+        Ok(!matches!(reply, Response::NotSupported))
+    }
+
     pub fn show_workspace(&self, seat: SeatId, name: &str) -> Result<(), TestError> {
         let reply = self.send_with_reply(ClientMessage::GetWorkspace { name })?;
         get_response!(reply, GetWorkspace { workspace });
@@ -198,6 +272,18 @@ impl TestConfig {
         })
     }
 
+    pub fn workspace_names(&self) -> Result<Vec<String>, TestError> {
+        let reply = self.send_with_reply(ClientMessage::GetWorkspaces)?;
+        get_response!(reply, GetWorkspaces { workspaces });
+        let mut names = vec![];
+        for workspace in workspaces {
+            let reply = self.send_with_reply(ClientMessage::GetWorkspaceName { workspace })?;
+            get_response!(reply, GetWorkspaceName { name });
+            names.push(name);
+        }
+        Ok(names)
+    }
+
     pub fn parse_keymap(&self, keymap: &str) -> Result<Keymap, TestError> {
         let reply = self.send_with_reply(ClientMessage::ParseKeymap { keymap })?;
         get_response!(reply, ParseKeymap { keymap });
@@ -228,6 +314,20 @@ impl TestConfig {
         })
     }
 
+    pub fn set_container_split(&self, container: u64, axis: Axis) -> TestResult {
+        self.send(ClientMessage::SetContainerSplit {
+            container: Container(container),
+            axis,
+        })
+    }
+
+    pub fn set_stacked(&self, seat: SeatId, stacked: bool) -> TestResult {
+        self.send(ClientMessage::SetStacked {
+            seat: Seat(seat.raw() as _),
+            stacked,
+        })
+    }
+
     pub fn add_shortcut<T: Into<ModifiedKeySym>>(
         &self,
         seat: SeatId,
@@ -238,6 +338,78 @@ impl TestConfig {
             seat: Seat(seat.raw() as _),
             mods: key.mods,
             sym: key.sym,
+            app_mod: AppMod::global(),
+            tunnel: None,
+        })
+    }
+
+    pub fn add_modal_shortcut<T: Into<ModifiedKeySym>>(
+        &self,
+        seat: SeatId,
+        key: T,
+        app_mod: AppMod,
+    ) -> TestResult {
+        let key = key.into();
+        self.send(ClientMessage::AddShortcut {
+            seat: Seat(seat.raw() as _),
+            mods: key.mods,
+            sym: key.sym,
+            app_mod,
+            tunnel: None,
+        })
+    }
+
+    pub fn set_app_mod(&self, seat: SeatId, app_mod: AppMod) -> TestResult {
+        self.send(ClientMessage::SetAppMod {
+            seat: Seat(seat.raw() as _),
+            app_mod,
+        })
+    }
+
+    pub fn set_app_mod_escape<T: Into<ModifiedKeySym>>(&self, seat: SeatId, key: T) -> TestResult {
+        self.send(ClientMessage::SetAppModEscape {
+            seat: Seat(seat.raw() as _),
+            escape: Some(key.into()),
+        })
+    }
+
+    pub fn set_shortcuts_inhibit_escape<T: Into<ModifiedKeySym>>(
+        &self,
+        seat: SeatId,
+        key: T,
+    ) -> TestResult {
+        self.send(ClientMessage::SetShortcutsInhibitEscape {
+            seat: Seat(seat.raw() as _),
+            escape: Some(key.into()),
+        })
+    }
+
+    pub fn set_app_mod_forward_keys(
+        &self,
+        seat: SeatId,
+        app_mod: AppMod,
+        forward: bool,
+    ) -> TestResult {
+        self.send(ClientMessage::SetAppModForwardKeys {
+            seat: Seat(seat.raw() as _),
+            app_mod,
+            forward,
+        })
+    }
+
+    pub fn set_shortcut_repeat<T: Into<ModifiedKeySym>>(
+        &self,
+        seat: SeatId,
+        key: T,
+        repeat: bool,
+    ) -> TestResult {
+        let key = key.into();
+        self.send(ClientMessage::SetShortcutRepeat {
+            seat: Seat(seat.raw() as _),
+            mods: key.mods,
+            sym: key.sym,
+            app_mod: AppMod::global(),
+            repeat,
         })
     }
 
@@ -266,6 +438,88 @@ impl TestConfig {
         self.send(ClientMessage::SetIdle { timeout })
     }
 
+    pub fn set_client_ping_timeout(&self, msec: u64) -> TestResult {
+        self.send(ClientMessage::SetClientPingTimeoutMsec { msec })
+    }
+
+    pub fn set_max_clients(&self, max: usize) -> TestResult {
+        self.send(ClientMessage::SetMaxClients { max })
+    }
+
+    pub fn set_max_client_objects(&self, max: usize) -> TestResult {
+        self.send(ClientMessage::SetMaxClientObjects { max })
+    }
+
+    pub fn set_animations_enabled(&self, enabled: bool) -> TestResult {
+        self.send(ClientMessage::SetAnimationsEnabled { enabled })
+    }
+
+    pub fn set_window_opacity(&self, seat: SeatId, opacity: f64) -> TestResult {
+        self.send(ClientMessage::SetWindowOpacity {
+            seat: Seat(seat.raw() as _),
+            opacity,
+        })
+    }
+
+    pub fn set_dim_unfocused(&self, factor: f64) -> TestResult {
+        self.send(ClientMessage::SetDimUnfocused { factor })
+    }
+
+    pub fn focus_tab(&self, seat: SeatId, direction: TabDirection) -> TestResult {
+        self.send(ClientMessage::FocusTab {
+            seat: Seat(seat.raw() as _),
+            direction,
+        })
+    }
+
+    pub fn focus_previous(&self, seat: SeatId) -> TestResult {
+        self.send(ClientMessage::FocusPrevious {
+            seat: Seat(seat.raw() as _),
+        })
+    }
+
+    pub fn add_pointer_binding(
+        &self,
+        seat: SeatId,
+        mods: Modifiers,
+        input: PointerInput,
+        context: PointerContext,
+    ) -> TestResult {
+        self.send(ClientMessage::AddPointerBinding {
+            seat: Seat(seat.raw() as _),
+            mods,
+            input,
+            context,
+        })
+    }
+
+    pub fn send_to_scratch(&self, seat: SeatId) -> TestResult {
+        self.send(ClientMessage::SendToScratch {
+            seat: Seat(seat.raw() as _),
+        })
+    }
+
+    pub fn fetch_from_scratch(&self, seat: SeatId, app_id: &str) -> TestResult {
+        self.send(ClientMessage::FetchFromScratch {
+            seat: Seat(seat.raw() as _),
+            app_id,
+        })
+    }
+
+    pub fn set_size(&self, sized: Resizable, size: i32) -> TestResult {
+        self.send(ClientMessage::SetSize { sized, size })
+    }
+
+    pub fn set_color(&self, colorable: Colorable, color: Color) -> TestResult {
+        self.send(ClientMessage::SetColor { colorable, color })
+    }
+
+    pub fn start_pointer_move(&self, seat: SeatId) -> TestResult {
+        self.send(ClientMessage::StartPointerMove {
+            seat: Seat(seat.raw() as _),
+        })
+    }
+
     pub fn set_floating(&self, seat: SeatId, floating: bool) -> TestResult {
         self.send(ClientMessage::SetFloating {
             seat: Seat(seat.raw() as _),
@@ -273,6 +527,33 @@ impl TestConfig {
         })
     }
 
+    pub fn set_focus_follows_mouse_delay(&self, seat: SeatId, msec: u64) -> TestResult {
+        self.send(ClientMessage::SetFocusFollowsMouseDelay {
+            seat: Seat(seat.raw() as _),
+            msec,
+        })
+    }
+
+    pub fn set_seat_devices(&self, seat: SeatId, devices: Vec<DeviceMatch>) -> TestResult {
+        self.send(ClientMessage::SetSeatDevices {
+            seat: Seat(seat.raw() as _),
+            devices,
+        })
+    }
+
+    pub fn set_default_seat(&self, seat: SeatId) -> TestResult {
+        self.send(ClientMessage::SetDefaultSeat {
+            seat: Seat(seat.raw() as _),
+        })
+    }
+
+    pub fn set_warp_pointer_to_focus(&self, seat: SeatId, warp: bool) -> TestResult {
+        self.send(ClientMessage::SetWarpPointerToFocus {
+            seat: Seat(seat.raw() as _),
+            warp,
+        })
+    }
+
     fn clear(&self) {
         unsafe {
             if let Some(srv) = self.srv.take() {
@@ -288,12 +569,62 @@ impl TestConfig {
         })
     }
 
+    pub fn set_output_position(&self, output: &OutputNode, x: i32, y: i32) -> TestResult {
+        self.send(ClientMessage::ConnectorSetPosition {
+            connector: Connector(output.global.connector.connector.id().raw() as _),
+            x,
+            y,
+        })
+    }
+
+    pub fn mirror_output(&self, dst: &OutputNode, src: &OutputNode) -> TestResult {
+        self.send(ClientMessage::MirrorOutput {
+            src: Connector(src.global.connector.connector.id().raw() as _),
+            dst: Connector(dst.global.connector.connector.id().raw() as _),
+        })
+    }
+
+    pub fn unmirror_output(&self, dst: &OutputNode) -> TestResult {
+        self.send(ClientMessage::UnmirrorOutput {
+            dst: Connector(dst.global.connector.connector.id().raw() as _),
+        })
+    }
+
+    pub fn adopt_orphaned_workspaces(&self, connector: ConnectorId) -> TestResult {
+        self.send(ClientMessage::AdoptOrphanedWorkspaces {
+            connector: Connector(connector.raw() as _),
+        })
+    }
+
+    pub fn output_transform(&self, output: &OutputNode) -> Result<Transform, TestError> {
+        let reply = self.send_with_reply(ClientMessage::ConnectorGetTransform {
+            connector: Connector(output.global.connector.connector.id().raw() as _),
+        })?;
+        get_response!(reply, ConnectorGetTransform { transform });
+        Ok(transform)
+    }
+
     pub fn set_output_transform(&self, output: &OutputNode, transform: Transform) -> TestResult {
         self.send(ClientMessage::ConnectorSetTransform {
             connector: Connector(output.global.connector.connector.id().raw() as _),
             transform,
         })
     }
+
+    pub fn set_output_background(
+        &self,
+        output: &OutputNode,
+        path: &str,
+        mode: BackgroundMode,
+    ) -> TestResult<Result<(), String>> {
+        let reply = self.send_with_reply(ClientMessage::ConnectorSetBackground {
+            connector: Connector(output.global.connector.connector.id().raw() as _),
+            path: path.to_string(),
+            mode,
+        })?;
+        get_response!(reply, ConnectorSetBackground { res });
+        Ok(res)
+    }
 }
 
 impl Drop for TestConfig {
@@ -135,6 +135,8 @@ impl GfxContext for TestGfxCtx {
             height,
             stride,
             format,
+            contents_valid: Cell::new(true),
+            uploaded_bytes: Cell::new(0),
         })))
     }
 
@@ -154,6 +156,8 @@ impl GfxContext for TestGfxCtx {
             height,
             stride,
             format,
+            contents_valid: Cell::new(false),
+            uploaded_bytes: Cell::new(0),
         })))
     }
 
@@ -181,6 +185,8 @@ impl GfxContext for TestGfxCtx {
                 height,
                 stride,
                 format,
+                contents_valid: Cell::new(true),
+                uploaded_bytes: Cell::new(0),
             })),
             staging: RefCell::new(vec![Color::TRANSPARENT; (width * height) as usize]),
         }))
@@ -207,6 +213,16 @@ struct TestShmGfxImage {
     height: i32,
     stride: i32,
     format: &'static Format,
+    contents_valid: Cell<bool>,
+    uploaded_bytes: Cell<usize>,
+}
+
+/// Returns the number of bytes that have been uploaded to this shm texture.
+pub fn shm_uploaded_bytes(tex: &dyn GfxTexture) -> usize {
+    match tex.as_any().downcast_ref::<TestGfxImage>() {
+        Some(TestGfxImage::Shm(shm)) => shm.uploaded_bytes.get(),
+        _ => 0,
+    }
 }
 
 struct TestDmaBufGfxImage {
@@ -298,24 +314,48 @@ impl AsyncShmGfxTexture for TestGfxImage {
         _staging: &Rc<dyn GfxStagingBuffer>,
         _callback: Rc<dyn AsyncShmGfxTextureCallback>,
         mem: Rc<dyn ShmMemory>,
-        _damage: Region,
+        damage: Region,
     ) -> Result<Option<PendingShmTransfer>, GfxError> {
         let mut res = Ok(());
         mem.access(&mut |d| {
-            res = self.clone().sync_upload(d, Region::default());
+            res = self.clone().sync_upload(d, damage.clone());
         })
         .map_err(TestGfxError::AccessFailed)?;
         res.map(|_| None)
     }
 
-    fn sync_upload(self: Rc<Self>, mem: &[Cell<u8>], _damage: Region) -> Result<(), GfxError> {
+    fn sync_upload(self: Rc<Self>, mem: &[Cell<u8>], damage: Region) -> Result<(), GfxError> {
         let TestGfxImage::Shm(shm) = &*self else {
             unreachable!();
         };
         let data = &mut *shm.data.borrow_mut();
         assert!(mem.len() >= data.len());
-        unsafe {
-            ptr::copy_nonoverlapping(mem.as_ptr() as _, data.as_mut_ptr(), data.len());
+        let uploaded = &shm.uploaded_bytes;
+        if !shm.contents_valid.replace(true) {
+            unsafe {
+                ptr::copy_nonoverlapping(mem.as_ptr() as _, data.as_mut_ptr(), data.len());
+            }
+            uploaded.set(uploaded.get() + data.len());
+            return Ok(());
+        }
+        let bounds = Rect::new_sized_unchecked(0, 0, shm.width, shm.height);
+        for rect in damage.rects() {
+            let rect = rect.intersect(bounds);
+            if rect.is_empty() {
+                continue;
+            }
+            let len = rect.width() as usize * 4;
+            for y in rect.y1()..rect.y2() {
+                let offset = y as usize * shm.stride as usize + rect.x1() as usize * 4;
+                unsafe {
+                    ptr::copy_nonoverlapping(
+                        mem[offset..].as_ptr() as _,
+                        data[offset..].as_mut_ptr(),
+                        len,
+                    );
+                }
+            }
+            uploaded.set(uploaded.get() + len * rect.height() as usize);
         }
         Ok(())
     }
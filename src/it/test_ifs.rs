@@ -20,17 +20,25 @@ pub mod test_dmabuf;
 pub mod test_dmabuf_feedback;
 pub mod test_ext_foreign_toplevel_handle;
 pub mod test_ext_foreign_toplevel_list;
+pub mod test_gamma_control;
+pub mod test_gamma_control_manager;
 pub mod test_input_method;
 pub mod test_input_method_keyboard_grab;
 pub mod test_input_method_manager;
 pub mod test_input_popup_surface;
 pub mod test_jay_compositor;
 pub mod test_keyboard;
+pub mod test_keyboard_shortcuts_inhibit_manager;
+pub mod test_keyboard_shortcuts_inhibitor;
+pub mod test_output;
 pub mod test_pointer;
 pub mod test_region;
 pub mod test_registry;
+pub mod test_screencopy_manager;
 pub mod test_screenshot;
 pub mod test_seat;
+pub mod test_security_context;
+pub mod test_security_context_manager;
 pub mod test_shm;
 pub mod test_shm_buffer;
 pub mod test_shm_pool;
@@ -52,5 +60,9 @@ pub mod test_virtual_keyboard_manager;
 pub mod test_xdg_activation;
 pub mod test_xdg_activation_token;
 pub mod test_xdg_base;
+pub mod test_xdg_output;
+pub mod test_xdg_output_manager;
+pub mod test_xdg_popup;
+pub mod test_xdg_positioner;
 pub mod test_xdg_surface;
 pub mod test_xdg_toplevel;
@@ -8,10 +8,11 @@ use {
             },
             test_object::TestObject,
             test_transport::TestTransport,
+            test_utils::test_expected_event::TEEH,
             testrun::ParseFull,
         },
         utils::buffd::MsgParser,
-        wire::{wl_data_device::*, WlDataDeviceId, WlSurfaceId},
+        wire::{wl_data_device::*, WlDataDeviceId, WlDataSourceId, WlSurfaceId},
     },
     std::{cell::Cell, rc::Rc},
 };
@@ -20,6 +21,9 @@ pub struct TestDataDevice {
     pub id: WlDataDeviceId,
     pub tran: Rc<TestTransport>,
     pub destroyed: Cell<bool>,
+    pub enter: TEEH<Enter>,
+    pub leave: TEEH<Leave>,
+    pub motion: TEEH<Motion>,
 }
 
 impl TestDataDevice {
@@ -47,6 +51,22 @@ impl TestDataDevice {
         Ok(())
     }
 
+    pub fn start_drag_without_source(
+        &self,
+        origin: &TestSurface,
+        icon: Option<&TestSurface>,
+        serial: u32,
+    ) -> TestResult {
+        self.tran.send(StartDrag {
+            self_id: self.id,
+            source: WlDataSourceId::NONE,
+            origin: origin.id,
+            icon: icon.map(|i| i.id).unwrap_or(WlSurfaceId::NONE),
+            serial,
+        })?;
+        Ok(())
+    }
+
     pub fn set_selection(&self, source: &TestDataSource, serial: u32) -> TestResult {
         self.tran.send(SetSelection {
             self_id: self.id,
@@ -69,17 +89,20 @@ impl TestDataDevice {
     }
 
     fn handle_enter(&self, parser: MsgParser<'_, '_>) -> TestResult {
-        let _ev = Enter::parse_full(parser)?;
+        let ev = Enter::parse_full(parser)?;
+        self.enter.push(ev);
         Ok(())
     }
 
     fn handle_leave(&self, parser: MsgParser<'_, '_>) -> TestResult {
-        let _ev = Leave::parse_full(parser)?;
+        let ev = Leave::parse_full(parser)?;
+        self.leave.push(ev);
         Ok(())
     }
 
     fn handle_motion(&self, parser: MsgParser<'_, '_>) -> TestResult {
-        let _ev = Motion::parse_full(parser)?;
+        let ev = Motion::parse_full(parser)?;
+        self.motion.push(ev);
         Ok(())
     }
 
@@ -47,6 +47,9 @@ impl TestDataDeviceManager {
             id: self.tran.id(),
             tran: self.tran.clone(),
             destroyed: Cell::new(false),
+            enter: Default::default(),
+            leave: Default::default(),
+            motion: Default::default(),
         });
         self.tran.add_obj(data_device.clone())?;
         self.tran.send(GetDataDevice {
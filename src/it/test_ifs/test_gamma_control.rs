@@ -0,0 +1,69 @@
+use {
+    crate::{
+        it::{
+            test_error::TestResult, test_mem::TestMem, test_object::TestObject,
+            test_transport::TestTransport, testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{zwlr_gamma_control_v1::*, ZwlrGammaControlV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestGammaControl {
+    pub id: ZwlrGammaControlV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub gamma_size: Cell<Option<u32>>,
+    pub failed: Cell<bool>,
+}
+
+impl TestGammaControl {
+    pub fn destroy(&self) -> TestResult {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    /// Sets the gamma ramps. `ramps` contains the red, green, and blue ramps in this order.
+    pub fn set_gamma(&self, ramps: &[u16]) -> TestResult {
+        let bytes = uapi::as_bytes(ramps);
+        let mem = TestMem::new(bytes.len())?;
+        for (dst, src) in mem.iter().zip(bytes) {
+            dst.set(*src);
+        }
+        self.tran.send(SetGamma {
+            self_id: self.id,
+            fd: mem.fd.clone(),
+        })?;
+        Ok(())
+    }
+
+    fn handle_gamma_size(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = GammaSize::parse_full(parser)?;
+        self.gamma_size.set(Some(ev.size));
+        Ok(())
+    }
+
+    fn handle_failed(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Failed::parse_full(parser)?;
+        self.failed.set(true);
+        Ok(())
+    }
+}
+
+impl Drop for TestGammaControl {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestGammaControl, ZwlrGammaControlV1;
+
+    GAMMA_SIZE => handle_gamma_size,
+    FAILED => handle_failed,
+}
+
+impl TestObject for TestGammaControl {}
@@ -0,0 +1,49 @@
+use {
+    crate::{
+        it::{
+            test_error::TestResult,
+            test_ifs::{test_gamma_control::TestGammaControl, test_output::TestOutput},
+            test_object::TestObject,
+            test_transport::TestTransport,
+        },
+        wire::{zwlr_gamma_control_manager_v1::*, ZwlrGammaControlManagerV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestGammaControlManager {
+    pub id: ZwlrGammaControlManagerV1Id,
+    pub tran: Rc<TestTransport>,
+}
+
+impl TestGammaControlManager {
+    pub fn new(tran: &Rc<TestTransport>) -> Self {
+        Self {
+            id: tran.id(),
+            tran: tran.clone(),
+        }
+    }
+
+    pub fn get_gamma_control(&self, output: &TestOutput) -> TestResult<Rc<TestGammaControl>> {
+        let obj = Rc::new(TestGammaControl {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            gamma_size: Cell::new(None),
+            failed: Cell::new(false),
+        });
+        self.tran.add_obj(obj.clone())?;
+        self.tran.send(GetGammaControl {
+            self_id: self.id,
+            id: obj.id,
+            output: output.id,
+        })?;
+        Ok(obj)
+    }
+}
+
+test_object! {
+    TestGammaControlManager, ZwlrGammaControlManagerV1;
+}
+
+impl TestObject for TestGammaControlManager {}
@@ -0,0 +1,68 @@
+use {
+    crate::{
+        it::{
+            test_error::TestResult,
+            test_ifs::{
+                test_keyboard_shortcuts_inhibitor::TestKeyboardShortcutsInhibitor,
+                test_seat::TestSeat, test_surface::TestSurface,
+            },
+            test_object::TestObject,
+            test_transport::TestTransport,
+        },
+        wire::{
+            zwp_keyboard_shortcuts_inhibit_manager_v1::*, ZwpKeyboardShortcutsInhibitManagerV1Id,
+        },
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestKeyboardShortcutsInhibitManager {
+    pub id: ZwpKeyboardShortcutsInhibitManagerV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestKeyboardShortcutsInhibitManager {
+    pub fn new(tran: &Rc<TestTransport>) -> Self {
+        Self {
+            id: tran.id(),
+            tran: tran.clone(),
+            destroyed: Cell::new(false),
+        }
+    }
+
+    #[expect(dead_code)]
+    pub fn destroy(&self) -> TestResult {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn inhibit_shortcuts(
+        &self,
+        surface: &TestSurface,
+        seat: &TestSeat,
+    ) -> TestResult<Rc<TestKeyboardShortcutsInhibitor>> {
+        let obj = Rc::new(TestKeyboardShortcutsInhibitor {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            active: Cell::new(false),
+        });
+        self.tran.send(InhibitShortcuts {
+            self_id: self.id,
+            id: obj.id,
+            surface: surface.id,
+            seat: seat.id,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        Ok(obj)
+    }
+}
+
+test_object! {
+    TestKeyboardShortcutsInhibitManager, ZwpKeyboardShortcutsInhibitManagerV1;
+}
+
+impl TestObject for TestKeyboardShortcutsInhibitManager {}
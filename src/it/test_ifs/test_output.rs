@@ -0,0 +1,85 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestError, TestResult},
+            test_object::TestObject,
+            test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{wl_output::*, WlOutputId},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestOutput {
+    pub id: WlOutputId,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub scale: Cell<i32>,
+    pub name: Cell<Option<String>>,
+    pub description: Cell<Option<String>>,
+    pub done: Cell<u32>,
+}
+
+impl TestOutput {
+    pub fn release(&self) -> TestResult {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Release { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    fn handle_geometry(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Geometry::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_mode(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Mode::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_done(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Done::parse_full(parser)?;
+        self.done.set(self.done.get() + 1);
+        Ok(())
+    }
+
+    fn handle_scale(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Scale::parse_full(parser)?;
+        self.scale.set(ev.factor);
+        Ok(())
+    }
+
+    fn handle_name(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Name::parse_full(parser)?;
+        self.name.set(Some(ev.name.to_string()));
+        Ok(())
+    }
+
+    fn handle_description(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Description::parse_full(parser)?;
+        self.description.set(Some(ev.description.to_string()));
+        Ok(())
+    }
+}
+
+impl Drop for TestOutput {
+    fn drop(&mut self) {
+        let _ = self.release();
+    }
+}
+
+test_object! {
+    TestOutput, WlOutput;
+
+    GEOMETRY => handle_geometry,
+    MODE => handle_mode,
+    DONE => handle_done,
+    SCALE => handle_scale,
+    NAME => handle_name,
+    DESCRIPTION => handle_description,
+}
+
+impl TestObject for TestOutput {}
@@ -11,8 +11,11 @@ use {
                 test_data_control_manager::TestDataControlManager,
                 test_data_device_manager::TestDataDeviceManager, test_dmabuf::TestDmabuf,
                 test_ext_foreign_toplevel_list::TestExtForeignToplevelList,
+                test_gamma_control_manager::TestGammaControlManager,
                 test_input_method_manager::TestInputMethodManager,
-                test_jay_compositor::TestJayCompositor, test_shm::TestShm,
+                test_jay_compositor::TestJayCompositor,
+                test_keyboard_shortcuts_inhibit_manager::TestKeyboardShortcutsInhibitManager,
+                test_security_context_manager::TestSecurityContextManager, test_shm::TestShm,
                 test_single_pixel_buffer_manager::TestSinglePixelBufferManager,
                 test_subcompositor::TestSubcompositor, test_syncobj_manager::TestSyncobjManager,
                 test_text_input_manager::TestTextInputManager,
@@ -20,6 +23,7 @@ use {
                 test_viewporter::TestViewporter,
                 test_virtual_keyboard_manager::TestVirtualKeyboardManager,
                 test_xdg_activation::TestXdgActivation, test_xdg_base::TestXdgWmBase,
+                test_xdg_output_manager::TestXdgOutputManager,
             },
             test_object::TestObject,
             test_transport::TestTransport,
@@ -58,6 +62,11 @@ pub struct TestRegistrySingletons {
     pub zwp_virtual_keyboard_manager_v1: u32,
     pub zwp_input_method_manager_v2: u32,
     pub zwp_text_input_manager_v3: u32,
+    pub zxdg_output_manager_v1: u32,
+    pub zwp_keyboard_shortcuts_inhibit_manager_v1: u32,
+    pub wp_security_context_manager_v1: u32,
+    pub zwlr_gamma_control_manager_v1: u32,
+    pub zwlr_screencopy_manager_v1: u32,
 }
 
 pub struct TestRegistry {
@@ -85,6 +94,10 @@ pub struct TestRegistry {
     pub virtual_keyboard_manager: CloneCell<Option<Rc<TestVirtualKeyboardManager>>>,
     pub input_method_manager: CloneCell<Option<Rc<TestInputMethodManager>>>,
     pub text_input_manager: CloneCell<Option<Rc<TestTextInputManager>>>,
+    pub xdg_output_manager: CloneCell<Option<Rc<TestXdgOutputManager>>>,
+    pub shortcuts_inhibit_manager: CloneCell<Option<Rc<TestKeyboardShortcutsInhibitManager>>>,
+    pub security_context_manager: CloneCell<Option<Rc<TestSecurityContextManager>>>,
+    pub gamma_control_manager: CloneCell<Option<Rc<TestGammaControlManager>>>,
     pub seats: CopyHashMap<GlobalName, Rc<WlSeatGlobal>>,
 }
 
@@ -156,6 +169,11 @@ impl TestRegistry {
             zwp_virtual_keyboard_manager_v1,
             zwp_input_method_manager_v2,
             zwp_text_input_manager_v3,
+            zxdg_output_manager_v1,
+            zwp_keyboard_shortcuts_inhibit_manager_v1,
+            wp_security_context_manager_v1,
+            zwlr_gamma_control_manager_v1,
+            zwlr_screencopy_manager_v1,
         };
         self.singletons.set(Some(singletons.clone()));
         Ok(singletons)
@@ -211,7 +229,7 @@ impl TestRegistry {
         get_cursor_shape_manager,
         cursor_shape_manager,
         wp_cursor_shape_manager_v1,
-        1,
+        2,
         TestCursorShapeManager
     );
     create_singleton!(
@@ -271,6 +289,34 @@ impl TestRegistry {
         1,
         TestTextInputManager
     );
+    create_singleton!(
+        get_xdg_output_manager,
+        xdg_output_manager,
+        zxdg_output_manager_v1,
+        3,
+        TestXdgOutputManager
+    );
+    create_singleton!(
+        get_shortcuts_inhibit_manager,
+        shortcuts_inhibit_manager,
+        zwp_keyboard_shortcuts_inhibit_manager_v1,
+        1,
+        TestKeyboardShortcutsInhibitManager
+    );
+    create_singleton!(
+        get_security_context_manager,
+        security_context_manager,
+        wp_security_context_manager_v1,
+        1,
+        TestSecurityContextManager
+    );
+    create_singleton!(
+        get_gamma_control_manager,
+        gamma_control_manager,
+        zwlr_gamma_control_manager_v1,
+        1,
+        TestGammaControlManager
+    );
 
     pub fn bind<O: TestObject>(
         &self,
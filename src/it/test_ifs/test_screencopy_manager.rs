@@ -0,0 +1,23 @@
+use {
+    crate::{
+        it::{test_object::TestObject, test_transport::TestTransport},
+        wire::ZwlrScreencopyManagerV1Id,
+    },
+    std::rc::Rc,
+};
+
+pub struct TestScreencopyManager {
+    pub id: ZwlrScreencopyManagerV1Id,
+}
+
+impl TestScreencopyManager {
+    pub fn new(tran: &Rc<TestTransport>) -> Self {
+        Self { id: tran.id() }
+    }
+}
+
+test_object! {
+    TestScreencopyManager, ZwlrScreencopyManagerV1;
+}
+
+impl TestObject for TestScreencopyManager {}
@@ -0,0 +1,45 @@
+use {
+    crate::{
+        it::{test_error::TestResult, test_object::TestObject, test_transport::TestTransport},
+        wire::{wp_security_context_v1::*, WpSecurityContextV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestSecurityContext {
+    pub id: WpSecurityContextV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestSecurityContext {
+    pub fn destroy(&self) -> TestResult {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn set_app_id(&self, app_id: &str) -> TestResult {
+        self.tran.send(SetAppId {
+            self_id: self.id,
+            app_id,
+        })
+    }
+
+    pub fn commit(&self) -> TestResult {
+        self.tran.send(Commit { self_id: self.id })
+    }
+}
+
+impl Drop for TestSecurityContext {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestSecurityContext, WpSecurityContextV1;
+}
+
+impl TestObject for TestSecurityContext {}
@@ -0,0 +1,61 @@
+use {
+    crate::{
+        it::{
+            test_error::TestResult, test_ifs::test_security_context::TestSecurityContext,
+            test_object::TestObject, test_transport::TestTransport,
+        },
+        wire::{wp_security_context_manager_v1::*, WpSecurityContextManagerV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    uapi::OwnedFd,
+};
+
+pub struct TestSecurityContextManager {
+    pub id: WpSecurityContextManagerV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestSecurityContextManager {
+    pub fn new(tran: &Rc<TestTransport>) -> Self {
+        Self {
+            id: tran.id(),
+            tran: tran.clone(),
+            destroyed: Cell::new(false),
+        }
+    }
+
+    #[expect(dead_code)]
+    pub fn destroy(&self) -> TestResult {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn create_listener(
+        &self,
+        listen_fd: &Rc<OwnedFd>,
+        close_fd: &Rc<OwnedFd>,
+    ) -> TestResult<Rc<TestSecurityContext>> {
+        let obj = Rc::new(TestSecurityContext {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.tran.send(CreateListener {
+            self_id: self.id,
+            id: obj.id,
+            listen_fd: listen_fd.clone(),
+            close_fd: close_fd.clone(),
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        Ok(obj)
+    }
+}
+
+test_object! {
+    TestSecurityContextManager, WpSecurityContextManagerV1;
+}
+
+impl TestObject for TestSecurityContextManager {}
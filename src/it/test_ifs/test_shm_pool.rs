@@ -60,7 +60,6 @@ impl TestShmPool {
         Ok(buffer)
     }
 
-    #[expect(dead_code)]
     pub fn resize(&self, size: usize) -> Result<(), TestError> {
         let mem = self.mem.get().grow(size)?;
         self.mem.set(mem);
@@ -3,7 +3,7 @@ use {
         ifs::wl_surface::WlSurface,
         it::{
             test_error::{TestError, TestResult},
-            test_ifs::test_region::TestRegion,
+            test_ifs::{test_callback::TestCallback, test_region::TestRegion},
             test_object::TestObject,
             test_transport::TestTransport,
             test_utils::test_expected_event::TEEH,
@@ -51,6 +51,22 @@ impl TestSurface {
         Ok(())
     }
 
+    pub fn set_buffer_transform(&self, transform: i32) -> TestResult {
+        self.tran.send(SetBufferTransform {
+            self_id: self.id,
+            transform,
+        })?;
+        Ok(())
+    }
+
+    pub fn set_opaque_region(&self, region: &TestRegion) -> TestResult {
+        self.tran.send(SetOpaqueRegion {
+            self_id: self.id,
+            region: region.id,
+        })?;
+        Ok(())
+    }
+
     pub fn set_input_region(&self, region: &TestRegion) -> TestResult {
         self.tran.send(SetInputRegion {
             self_id: self.id,
@@ -59,6 +75,31 @@ impl TestSurface {
         Ok(())
     }
 
+    pub fn damage_buffer(&self, x: i32, y: i32, width: i32, height: i32) -> TestResult {
+        self.tran.send(DamageBuffer {
+            self_id: self.id,
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+
+    pub fn frame(&self) -> TestResult<Rc<TestCallback>> {
+        let cb = Rc::new(TestCallback {
+            id: self.tran.id(),
+            _tran: self.tran.clone(),
+            handler: Cell::new(None),
+            done: Cell::new(false),
+        });
+        self.tran.add_obj(cb.clone())?;
+        self.tran.send(Frame {
+            self_id: self.id,
+            callback: cb.id,
+        })?;
+        Ok(cb)
+    }
+
     pub fn commit(&self) -> Result<(), TestError> {
         self.tran.send(Commit { self_id: self.id })?;
         Ok(())
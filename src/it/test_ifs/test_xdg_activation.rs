@@ -8,7 +8,7 @@ use {
             test_object::TestObject,
             test_transport::TestTransport,
         },
-        wire::{xdg_activation_v1::*, XdgActivationV1Id},
+        wire::{xdg_activation_v1::*, WlSeatId, XdgActivationV1Id},
     },
     std::{cell::Cell, rc::Rc},
 };
@@ -36,6 +36,18 @@ impl TestXdgActivation {
     }
 
     pub async fn get_token(&self) -> Result<String, TestError> {
+        self.get_token_(None).await
+    }
+
+    pub async fn get_token_with_serial(
+        &self,
+        serial: u32,
+        seat: WlSeatId,
+    ) -> Result<String, TestError> {
+        self.get_token_(Some((serial, seat))).await
+    }
+
+    async fn get_token_(&self, serial: Option<(u32, WlSeatId)>) -> Result<String, TestError> {
         let token = Rc::new(TestXdgActivationToken {
             id: self.tran.id(),
             tran: self.tran.clone(),
@@ -47,6 +59,9 @@ impl TestXdgActivation {
             self_id: self.id,
             id: token.id,
         })?;
+        if let Some((serial, seat)) = serial {
+            token.set_serial(serial, seat)?;
+        }
         let res = token.commit().await?;
         token.destroy()?;
         Ok(res)
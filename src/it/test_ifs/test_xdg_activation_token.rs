@@ -1,11 +1,13 @@
 use {
     crate::{
         it::{
-            test_error::TestError, test_object::TestObject, test_transport::TestTransport,
+            test_error::{TestError, TestResult},
+            test_object::TestObject,
+            test_transport::TestTransport,
             testrun::ParseFull,
         },
         utils::buffd::MsgParser,
-        wire::{xdg_activation_token_v1::*, XdgActivationTokenV1Id},
+        wire::{xdg_activation_token_v1::*, WlSeatId, XdgActivationTokenV1Id},
     },
     std::{cell::Cell, rc::Rc},
 };
@@ -25,6 +27,14 @@ impl TestXdgActivationToken {
         Ok(())
     }
 
+    pub fn set_serial(&self, serial: u32, seat: WlSeatId) -> TestResult {
+        self.tran.send(SetSerial {
+            self_id: self.id,
+            serial,
+            seat,
+        })
+    }
+
     pub async fn commit(&self) -> Result<String, TestError> {
         self.tran.send(Commit { self_id: self.id })?;
         self.tran.sync().await;
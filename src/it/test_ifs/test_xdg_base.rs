@@ -1,8 +1,11 @@
 use {
     crate::{
         it::{
-            test_error::TestError, test_ifs::test_xdg_surface::TestXdgSurface,
-            test_object::TestObject, test_transport::TestTransport, testrun::ParseFull,
+            test_error::TestError,
+            test_ifs::{test_xdg_positioner::TestXdgPositioner, test_xdg_surface::TestXdgSurface},
+            test_object::TestObject,
+            test_transport::TestTransport,
+            testrun::ParseFull,
         },
         utils::buffd::MsgParser,
         wire::{xdg_wm_base::*, WlSurfaceId, XdgWmBaseId},
@@ -14,6 +17,8 @@ pub struct TestXdgWmBase {
     pub id: XdgWmBaseId,
     pub tran: Rc<TestTransport>,
     pub destroyed: Cell<bool>,
+    pub respond_to_ping: Cell<bool>,
+    pub last_ping: Cell<Option<u32>>,
 }
 
 impl TestXdgWmBase {
@@ -22,6 +27,8 @@ impl TestXdgWmBase {
             id: tran.id(),
             tran: tran.clone(),
             destroyed: Cell::new(false),
+            respond_to_ping: Cell::new(true),
+            last_ping: Cell::new(None),
         }
     }
 
@@ -32,6 +39,14 @@ impl TestXdgWmBase {
         Ok(())
     }
 
+    pub fn pong(&self, serial: u32) -> Result<(), TestError> {
+        self.tran.send(Pong {
+            self_id: self.id,
+            serial,
+        })?;
+        Ok(())
+    }
+
     pub async fn create_xdg_surface(
         &self,
         surface: WlSurfaceId,
@@ -56,8 +71,26 @@ impl TestXdgWmBase {
         Ok(xdg)
     }
 
+    pub fn create_positioner(&self) -> Result<Rc<TestXdgPositioner>, TestError> {
+        let positioner = Rc::new(TestXdgPositioner {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.tran.send(CreatePositioner {
+            self_id: self.id,
+            id: positioner.id,
+        })?;
+        self.tran.add_obj(positioner.clone())?;
+        Ok(positioner)
+    }
+
     fn handle_ping(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
-        let _ev = Ping::parse_full(parser)?;
+        let ev = Ping::parse_full(parser)?;
+        self.last_ping.set(Some(ev.serial));
+        if self.respond_to_ping.get() {
+            self.pong(ev.serial)?;
+        }
         Ok(())
     }
 }
@@ -0,0 +1,81 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestError, TestResult},
+            test_object::TestObject,
+            test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{zxdg_output_v1::*, ZxdgOutputV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestXdgOutput {
+    pub id: ZxdgOutputV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub logical_position: Cell<(i32, i32)>,
+    pub logical_size: Cell<(i32, i32)>,
+    pub name: Cell<Option<String>>,
+    pub description: Cell<Option<String>>,
+    pub num_names: Cell<u32>,
+}
+
+impl TestXdgOutput {
+    pub fn destroy(&self) -> TestResult {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    fn handle_logical_position(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = LogicalPosition::parse_full(parser)?;
+        self.logical_position.set((ev.x, ev.y));
+        Ok(())
+    }
+
+    fn handle_logical_size(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = LogicalSize::parse_full(parser)?;
+        self.logical_size.set((ev.width, ev.height));
+        Ok(())
+    }
+
+    fn handle_done(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Done::parse_full(parser)?;
+        bail!("xdg_output.done is deprecated since version 3");
+    }
+
+    fn handle_name(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Name::parse_full(parser)?;
+        self.name.set(Some(ev.name.to_string()));
+        self.num_names.set(self.num_names.get() + 1);
+        Ok(())
+    }
+
+    fn handle_description(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Description::parse_full(parser)?;
+        self.description.set(Some(ev.description.to_string()));
+        Ok(())
+    }
+}
+
+impl Drop for TestXdgOutput {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestXdgOutput, ZxdgOutputV1;
+
+    LOGICAL_POSITION => handle_logical_position,
+    LOGICAL_SIZE => handle_logical_size,
+    DONE => handle_done,
+    NAME => handle_name,
+    DESCRIPTION => handle_description,
+}
+
+impl TestObject for TestXdgOutput {}
@@ -0,0 +1,52 @@
+use {
+    crate::{
+        it::{
+            test_error::TestResult,
+            test_ifs::{test_output::TestOutput, test_xdg_output::TestXdgOutput},
+            test_object::TestObject,
+            test_transport::TestTransport,
+        },
+        wire::{zxdg_output_manager_v1::*, ZxdgOutputManagerV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestXdgOutputManager {
+    pub id: ZxdgOutputManagerV1Id,
+    pub tran: Rc<TestTransport>,
+}
+
+impl TestXdgOutputManager {
+    pub fn new(tran: &Rc<TestTransport>) -> Self {
+        Self {
+            id: tran.id(),
+            tran: tran.clone(),
+        }
+    }
+
+    pub fn get_xdg_output(&self, output: &TestOutput) -> TestResult<Rc<TestXdgOutput>> {
+        let obj = Rc::new(TestXdgOutput {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            logical_position: Default::default(),
+            logical_size: Default::default(),
+            name: Default::default(),
+            description: Default::default(),
+            num_names: Default::default(),
+        });
+        self.tran.add_obj(obj.clone())?;
+        self.tran.send(GetXdgOutput {
+            self_id: self.id,
+            id: obj.id,
+            output: output.id,
+        })?;
+        Ok(obj)
+    }
+}
+
+test_object! {
+    TestXdgOutputManager, ZxdgOutputManagerV1;
+}
+
+impl TestObject for TestXdgOutputManager {}
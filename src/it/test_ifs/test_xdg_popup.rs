@@ -0,0 +1,51 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError, test_object::TestObject, test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{xdg_popup::*, XdgPopupId},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestXdgPopup {
+    pub id: XdgPopupId,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestXdgPopup {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    fn handle_configure(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Configure::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_popup_done(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = PopupDone::parse_full(parser)?;
+        Ok(())
+    }
+}
+
+test_object! {
+    TestXdgPopup, XdgPopup;
+
+    CONFIGURE => handle_configure,
+    POPUP_DONE => handle_popup_done,
+}
+
+impl TestObject for TestXdgPopup {}
+
+impl Drop for TestXdgPopup {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
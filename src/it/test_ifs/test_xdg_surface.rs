@@ -3,7 +3,11 @@ use {
         ifs::wl_surface::xdg_surface::XdgSurface,
         it::{
             test_error::TestError,
-            test_ifs::test_xdg_toplevel::{TestXdgToplevel, TestXdgToplevelCore},
+            test_ifs::{
+                test_xdg_popup::TestXdgPopup,
+                test_xdg_positioner::TestXdgPositioner,
+                test_xdg_toplevel::{TestXdgToplevel, TestXdgToplevelCore},
+            },
             test_object::TestObject,
             test_transport::TestTransport,
             testrun::ParseFull,
@@ -53,6 +57,26 @@ impl TestXdgSurface {
         Ok(tl)
     }
 
+    pub fn create_popup(
+        &self,
+        parent: &TestXdgSurface,
+        positioner: &TestXdgPositioner,
+    ) -> Result<Rc<TestXdgPopup>, TestError> {
+        let popup = Rc::new(TestXdgPopup {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.tran.send(GetPopup {
+            self_id: self.id,
+            id: popup.id,
+            parent: parent.id,
+            positioner: positioner.id,
+        })?;
+        self.tran.add_obj(popup.clone())?;
+        Ok(popup)
+    }
+
     pub fn ack_configure(&self, serial: u32) -> Result<(), TestError> {
         self.tran.send(AckConfigure {
             self_id: self.id,
@@ -73,6 +73,10 @@ impl TestTransport {
             virtual_keyboard_manager: Default::default(),
             input_method_manager: Default::default(),
             text_input_manager: Default::default(),
+            xdg_output_manager: Default::default(),
+            shortcuts_inhibit_manager: Default::default(),
+            security_context_manager: Default::default(),
+            gamma_control_manager: Default::default(),
             seats: Default::default(),
         });
         self.send(wl_display::GetRegistry {
@@ -41,6 +41,35 @@ impl TestRun {
     }
 
     async fn create_client2(self: &Rc<Self>) -> Result<Rc<TestClient>, TestError> {
+        let tran = self.connect(&self.server_addr).await?;
+        let registry = tran.get_registry();
+        let jc = registry.get_jay_compositor().await?;
+        jc.enable_symmetric_delete()?;
+        let client_id = jc.get_client_id().await?;
+        let client = self.state.clients.get(client_id)?;
+        Ok(Rc::new(TestClient {
+            run: self.clone(),
+            _server: client,
+            tran,
+            jc,
+            comp: registry.get_compositor().await?,
+            sub: registry.get_subcompositor().await?,
+            shm: registry.get_shm().await?,
+            spbm: registry.get_spbm().await?,
+            viewporter: registry.get_viewporter().await?,
+            xdg: registry.get_xdg().await?,
+            activation: registry.get_activation().await?,
+            data_device_manager: registry.get_data_device_manager().await?,
+            cursor_shape_manager: registry.get_cursor_shape_manager().await?,
+            registry,
+        }))
+    }
+
+    /// Connects a bare client to the socket at `addr`.
+    pub async fn connect(
+        self: &Rc<Self>,
+        addr: &c::sockaddr_un,
+    ) -> Result<Rc<TestTransport>, TestError> {
         let socket = uapi::socket(c::AF_UNIX, c::SOCK_STREAM | c::SOCK_CLOEXEC, 0)
             .to_os_error()
             .with_context(|| "Could not create a unix socket")?;
@@ -48,7 +77,7 @@ impl TestRun {
         self.backend
             .state
             .ring
-            .connect(&socket, &self.server_addr)
+            .connect(&socket, addr)
             .await
             .with_context(|| "Could not connect to the compositor")?;
         let mut obj_ids = Bitfield::default();
@@ -72,27 +101,7 @@ impl TestRun {
             id: WL_DISPLAY_ID,
         }))?;
         tran.init();
-        let registry = tran.get_registry();
-        let jc = registry.get_jay_compositor().await?;
-        jc.enable_symmetric_delete()?;
-        let client_id = jc.get_client_id().await?;
-        let client = self.state.clients.get(client_id)?;
-        Ok(Rc::new(TestClient {
-            run: self.clone(),
-            _server: client,
-            tran,
-            jc,
-            comp: registry.get_compositor().await?,
-            sub: registry.get_subcompositor().await?,
-            shm: registry.get_shm().await?,
-            spbm: registry.get_spbm().await?,
-            viewporter: registry.get_viewporter().await?,
-            xdg: registry.get_xdg().await?,
-            activation: registry.get_activation().await?,
-            data_device_manager: registry.get_data_device_manager().await?,
-            cursor_shape_manager: registry.get_cursor_shape_manager().await?,
-            registry,
-        }))
+        Ok(tran)
     }
 
     pub fn get_seat(&self, name: &str) -> Result<Rc<WlSeatGlobal>, TestError> {
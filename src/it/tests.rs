@@ -74,6 +74,48 @@ mod t0039_alpha_modifier;
 mod t0040_virtual_keyboard;
 mod t0041_input_method;
 mod t0042_toplevel_select;
+mod t0043_buffer_transform;
+mod t0044_client_teardown;
+mod t0045_unresponsive;
+mod t0046_warp_pointer;
+mod t0047_input_serials;
+mod t0048_seat_devices;
+mod t0049_xdg_activation_focus;
+mod t0050_default_seat;
+mod t0051_shm_damage_upload;
+mod t0052_background;
+mod t0053_animations;
+mod t0054_shm_pool_resize;
+mod t0055_window_opacity;
+mod t0056_focus_tab;
+mod t0057_gaps;
+mod t0058_pointer_bindings;
+mod t0059_scratchpad;
+mod t0060_shortcut_repeat;
+mod t0061_workspace_name;
+mod t0062_adopt_orphaned_workspaces;
+mod t0063_focus_previous;
+mod t0064_dnd_overlapping_windows;
+mod t0065_xdg_output;
+mod t0066_frame_callbacks;
+mod t0067_output_position;
+mod t0068_output_mirror;
+mod t0069_app_mod_escape;
+mod t0070_drm_device_removal;
+mod t0071_remove_seat;
+mod t0072_opaque_occlusion;
+mod t0073_theme_borders;
+mod t0074_stacked_titles;
+mod t0075_shortcuts_inhibit_escape;
+mod t0076_container_id;
+mod t0077_dnd_popup;
+mod t0078_focus_follows_mouse_delay;
+mod t0079_security_context;
+mod t0080_max_clients;
+mod t0081_max_client_objects;
+mod t0082_render_allocations;
+mod t0083_gamma_control;
+mod t0084_subsurface_destroy_extents;
 
 pub trait TestCase: Sync {
     fn name(&self) -> &'static str;
@@ -135,5 +177,47 @@ pub fn tests() -> Vec<&'static dyn TestCase> {
         t0040_virtual_keyboard,
         t0041_input_method,
         t0042_toplevel_select,
+        t0043_buffer_transform,
+        t0044_client_teardown,
+        t0045_unresponsive,
+        t0046_warp_pointer,
+        t0047_input_serials,
+        t0048_seat_devices,
+        t0049_xdg_activation_focus,
+        t0050_default_seat,
+        t0051_shm_damage_upload,
+        t0052_background,
+        t0053_animations,
+        t0054_shm_pool_resize,
+        t0055_window_opacity,
+        t0056_focus_tab,
+        t0057_gaps,
+        t0058_pointer_bindings,
+        t0059_scratchpad,
+        t0060_shortcut_repeat,
+        t0061_workspace_name,
+        t0062_adopt_orphaned_workspaces,
+        t0063_focus_previous,
+        t0064_dnd_overlapping_windows,
+        t0065_xdg_output,
+        t0066_frame_callbacks,
+        t0067_output_position,
+        t0068_output_mirror,
+        t0069_app_mod_escape,
+        t0070_drm_device_removal,
+        t0071_remove_seat,
+        t0072_opaque_occlusion,
+        t0073_theme_borders,
+        t0074_stacked_titles,
+        t0075_shortcuts_inhibit_escape,
+        t0076_container_id,
+        t0077_dnd_popup,
+        t0078_focus_follows_mouse_delay,
+        t0079_security_context,
+        t0080_max_clients,
+        t0081_max_client_objects,
+        t0082_render_allocations,
+        t0083_gamma_control,
+        t0084_subsurface_destroy_extents,
     }
 }
@@ -1,5 +1,10 @@
 use {
-    crate::it::{test_error::TestResult, testrun::TestRun},
+    crate::{
+        backend::{BackendEvent, ConnectorEvent, ConnectorKernelId},
+        ifs::wl_output::OutputId,
+        it::{test_backend::TestConnector, test_error::TestResult, testrun::TestRun},
+        video::drm::ConnectorType,
+    },
     std::rc::Rc,
 };
 
@@ -25,5 +30,63 @@ async fn test(run: Rc<TestRun>) -> TestResult {
     client.sync().await;
     tassert_eq!(scale.next()?, 3);
 
+    run.cfg.set_scale(&ds.output, 2.5)?;
+
+    client.sync().await;
+    tassert!(scale.next().is_err());
+
+    run.cfg.set_scale(&ds.output, 1.0)?;
+
+    client.sync().await;
+    tassert_eq!(scale.next()?, 1);
+
+    let connector = Rc::new(TestConnector {
+        id: run.state.connector_ids.next(),
+        kernel_id: ConnectorKernelId {
+            ty: ConnectorType::VGA,
+            idx: 2,
+        },
+        events: Default::default(),
+        feedback: Default::default(),
+        content_type: Default::default(),
+        drm_dev: None,
+    });
+    let mut monitor_info = run.backend.default_monitor_info.clone();
+    monitor_info.output_id = Rc::new(OutputId {
+        connector: None,
+        manufacturer: "jay".to_string(),
+        model: "jay second connector".to_string(),
+        serial_number: "".to_string(),
+    });
+    run.backend
+        .state
+        .backend_events
+        .push(BackendEvent::NewConnector(connector.clone()));
+    connector
+        .events
+        .send_event(ConnectorEvent::Connected(monitor_info));
+    run.sync().await;
+    let Some(output) = run.state.root.outputs.get(&connector.id) else {
+        bail!("the second output was not created");
+    };
+
+    // The surface is displayed on every output that mirrors its output and prefers the
+    // largest scale among them.
+    run.cfg.set_scale(&output, 2.0)?;
+    client.sync().await;
+    tassert!(scale.next().is_err());
+
+    run.cfg.mirror_output(&output, &ds.output)?;
+    client.sync().await;
+    tassert_eq!(scale.next()?, 2);
+
+    run.cfg.set_scale(&output, 3.0)?;
+    client.sync().await;
+    tassert_eq!(scale.next()?, 3);
+
+    run.cfg.unmirror_output(&output)?;
+    client.sync().await;
+    tassert_eq!(scale.next()?, 1);
+
     Ok(())
 }
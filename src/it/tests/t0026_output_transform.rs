@@ -11,6 +11,10 @@ async fn test(run: Rc<TestRun>) -> TestResult {
 
     run.cfg
         .set_output_transform(&ds.output, Transform::FlipRotate90)?;
+    tassert_eq!(
+        run.cfg.output_transform(&ds.output)?,
+        Transform::FlipRotate90
+    );
 
     let client = run.create_client().await?;
     let win = client.create_window().await?;
@@ -28,5 +28,13 @@ async fn test(run: Rc<TestRun>) -> TestResult {
         Some(KnownCursor::ContextMenu)
     );
 
+    dev.set_shape(enter.last()?.serial, 36)?;
+    client.sync().await;
+
+    tassert_eq!(
+        ds.seat.pointer_cursor().desired_known_cursor(),
+        Some(KnownCursor::AllResize)
+    );
+
     Ok(())
 }
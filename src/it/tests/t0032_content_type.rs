@@ -2,14 +2,16 @@ use {
     crate::{
         ifs::wp_content_type_v1::ContentType,
         it::{test_error::TestResult, testrun::TestRun},
+        tree::Node,
     },
+    jay_config::video,
     std::rc::Rc,
 };
 
 testcase!();
 
 async fn test(run: Rc<TestRun>) -> TestResult {
-    let _ds = run.create_default_setup().await?;
+    let ds = run.create_default_setup().await?;
 
     let client = run.create_client().await?;
     let surface = client.comp.create_surface().await?;
@@ -21,5 +23,32 @@ async fn test(run: Rc<TestRun>) -> TestResult {
 
     tassert_eq!(surface.server.content_type.get(), Some(ContentType::Video));
 
+    let win = client.create_window().await?;
+    win.map2().await?;
+    let (x, y) = win.tl.server.node_absolute_position().center();
+    ds.move_to(x, y);
+    let ct = ctm.get_surface_content_type(&win.surface)?;
+    ct.set_content_type(3)?;
+    win.surface.commit()?;
+    client.sync().await;
+    tassert_eq!(ds.connector.content_type.get(), None);
+    tassert!(run.cfg.content_type_changes.take().is_empty());
+
+    run.cfg.set_fullscreen(ds.seat.id(), true)?;
+    client.sync().await;
+    tassert_eq!(ds.connector.content_type.get(), Some(ContentType::Game));
+    tassert_eq!(
+        run.cfg.content_type_changes.take(),
+        vec![(ds.connector.id, video::ContentType::GAME)]
+    );
+
+    run.cfg.set_fullscreen(ds.seat.id(), false)?;
+    client.sync().await;
+    tassert_eq!(ds.connector.content_type.get(), None);
+    tassert_eq!(
+        run.cfg.content_type_changes.take(),
+        vec![(ds.connector.id, video::ContentType::NONE)]
+    );
+
     Ok(())
 }
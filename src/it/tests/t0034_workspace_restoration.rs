@@ -30,6 +30,8 @@ async fn test(run: Rc<TestRun>) -> TestResult {
         },
         events: Default::default(),
         feedback: Default::default(),
+        content_type: Default::default(),
+        drm_dev: None,
     });
     let new_monitor_info = MonitorInfo {
         modes: vec![],
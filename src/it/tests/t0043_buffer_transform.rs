@@ -0,0 +1,44 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::Node,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let _ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+
+    let buf = client.shm.create_buffer(200, 100)?;
+    let ss = client.comp.create_surface().await?;
+    let sub = client
+        .sub
+        .get_subsurface(ss.id, win.surface.id)
+        .await?;
+    sub.set_desync()?;
+    ss.attach(buf.buffer.id)?;
+    ss.commit()?;
+    win.surface.commit()?;
+    client.sync().await;
+    tassert_eq!(ss.server.node_absolute_position().size(), (200, 100));
+
+    // WL_OUTPUT_TRANSFORM_90
+    ss.set_buffer_transform(1)?;
+    ss.commit()?;
+    client.sync().await;
+    tassert_eq!(ss.server.node_absolute_position().size(), (100, 200));
+
+    // WL_OUTPUT_TRANSFORM_FLIPPED_180
+    ss.set_buffer_transform(6)?;
+    ss.commit()?;
+    client.sync().await;
+    tassert_eq!(ss.server.node_absolute_position().size(), (200, 100));
+
+    Ok(())
+}
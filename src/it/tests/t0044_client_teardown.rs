@@ -0,0 +1,163 @@
+use {
+    crate::{
+        client::{Client, ClientId},
+        ifs::wl_seat::BTN_LEFT,
+        it::{
+            test_error::TestResult,
+            testrun::{DefaultSetup, TestRun},
+        },
+        tree::Node,
+    },
+    std::rc::{Rc, Weak},
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    for with_source in [false, true] {
+        for dropped in [false, true] {
+            let server = kill_origin(&run, &ds, with_source, dropped).await?;
+            run.sync().await;
+            tassert!(server.upgrade().is_none());
+        }
+    }
+
+    kill_target(&run, &ds).await?;
+    kill_grab_owner(&run, &ds).await?;
+
+    Ok(())
+}
+
+async fn kill_origin(
+    run: &Rc<TestRun>,
+    ds: &DefaultSetup,
+    with_source: bool,
+    dropped: bool,
+) -> TestResult<Weak<Client>> {
+    let client = run.create_client().await?;
+    let id = client._server.id;
+    let win = client.create_window().await?;
+    win.map2().await?;
+
+    let seat = client.get_default_seat().await?;
+    let button = seat.pointer.button.expect()?;
+
+    let (x, y) = win.tl.server.node_absolute_position().center();
+    ds.move_to(x, y);
+    let click = ds.mouse.click(BTN_LEFT);
+
+    client.sync().await;
+    let dev = client
+        .data_device_manager
+        .get_data_device(&seat.seat)?;
+    let src = client.data_device_manager.create_data_source()?;
+    src.set_actions(1)?;
+    let serial = button.next()?.serial;
+    if with_source {
+        dev.start_drag(&src, &win.surface, None, serial)?;
+    } else {
+        dev.start_drag_without_source(&win.surface, None, serial)?;
+    }
+
+    client.sync().await;
+    tassert_eq!(ds.seat.dnd_client(), Some(id));
+
+    let click = if dropped {
+        drop(click);
+        client.sync().await;
+        tassert_eq!(ds.seat.dnd_client(), None);
+        None
+    } else {
+        Some(click)
+    };
+
+    client.tran.kill();
+    wait_for_removal(run, id).await?;
+    tassert_eq!(ds.seat.dnd_client(), None);
+    drop(click);
+
+    Ok(Rc::downgrade(&client._server))
+}
+
+async fn kill_target(run: &Rc<TestRun>, ds: &DefaultSetup) -> TestResult {
+    let origin = run.create_client().await?;
+    let target = run.create_client().await?;
+    let origin_id = origin._server.id;
+    let target_id = target._server.id;
+
+    let win1 = origin.create_window().await?;
+    win1.map2().await?;
+    let win2 = target.create_window().await?;
+    win2.map2().await?;
+
+    let seat = origin.get_default_seat().await?;
+    let button = seat.pointer.button.expect()?;
+
+    let (x, y) = win1.tl.server.node_absolute_position().center();
+    ds.move_to(x, y);
+    let click = ds.mouse.click(BTN_LEFT);
+
+    origin.sync().await;
+    let dev = origin
+        .data_device_manager
+        .get_data_device(&seat.seat)?;
+    dev.start_drag_without_source(&win1.surface, None, button.next()?.serial)?;
+    origin.sync().await;
+
+    let (x, y) = win2.tl.server.node_absolute_position().center();
+    ds.move_to(x, y);
+    target.sync().await;
+
+    target.tran.kill();
+    wait_for_removal(run, target_id).await?;
+    tassert_eq!(ds.seat.dnd_client(), Some(origin_id));
+
+    drop(click);
+    origin.sync().await;
+    tassert_eq!(ds.seat.dnd_client(), None);
+
+    Ok(())
+}
+
+async fn kill_grab_owner(run: &Rc<TestRun>, ds: &DefaultSetup) -> TestResult {
+    let client = run.create_client().await?;
+    let id = client._server.id;
+    let seat = client.get_default_seat().await?;
+    let entered = seat.kb.enter.expect()?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+
+    let dev = client
+        .data_device_manager
+        .get_data_device(&seat.seat)?;
+    let src = client.data_device_manager.create_data_source()?;
+    src.offer("text")?;
+    dev.set_selection(&src, entered.next()?.serial)?;
+
+    let (x, y) = win.tl.server.node_absolute_position().center();
+    ds.move_to(x, y);
+    let click = ds.mouse.click(BTN_LEFT);
+    client.sync().await;
+    tassert!(ds.seat.get_selection().is_some());
+    tassert_eq!(ds.seat.grab_client(), Some(id));
+
+    client.tran.kill();
+    wait_for_removal(run, id).await?;
+    tassert!(ds.seat.get_selection().is_none());
+    tassert_eq!(ds.seat.grab_client(), None);
+    drop(click);
+
+    Ok(())
+}
+
+async fn wait_for_removal(run: &Rc<TestRun>, id: ClientId) -> TestResult {
+    for _ in 0..100 {
+        if run.state.clients.get(id).is_err() {
+            return Ok(());
+        }
+        run.sync().await;
+    }
+    bail!("Client {} was not removed", id);
+}
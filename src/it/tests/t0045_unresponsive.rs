@@ -0,0 +1,53 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::ToplevelNodeBase,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    run.create_default_setup().await?;
+    run.cfg.set_client_ping_timeout(50)?;
+
+    let client1 = run.create_client().await?;
+    let win1 = client1.create_window().await?;
+    win1.map2().await?;
+
+    let client2 = run.create_client().await?;
+    let win2 = client2.create_window().await?;
+    win2.map2().await?;
+
+    client1.xdg.respond_to_ping.set(false);
+    client2.xdg.respond_to_ping.set(false);
+    win1.tl.server.clone().tl_close();
+    win2.tl.server.clone().tl_close();
+    client1.sync().await;
+    client2.sync().await;
+    tassert!(client1.xdg.last_ping.get().is_some());
+    tassert!(client2.xdg.last_ping.get().is_some());
+
+    run.state.wheel.timeout(100).await?;
+
+    tassert!(win1.tl.server.tl_data().unresponsive.get());
+    tassert!(win2.tl.server.tl_data().unresponsive.get());
+
+    let serial = client1.xdg.last_ping.get().unwrap();
+    client1.xdg.pong(serial)?;
+    client1.sync().await;
+    tassert!(!win1.tl.server.tl_data().unresponsive.get());
+
+    let id = client1._server.id;
+    win1.tl.server.clone().tl_close();
+    run.sync().await;
+    tassert!(run.state.clients.get(id).is_ok());
+
+    let id = client2._server.id;
+    win2.tl.server.clone().tl_close();
+    run.sync().await;
+    tassert!(run.state.clients.get(id).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::Direction,
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+
+    let (x, y) = win2.tl.server.node_absolute_position().center();
+    ds.move_to(x, y);
+    client.sync().await;
+
+    let enter = seat.pointer.enter.expect()?;
+
+    run.cfg.set_warp_pointer_to_focus(ds.seat.id(), true)?;
+    run.cfg.focus(ds.seat.id(), Direction::Left)?;
+    client.sync().await;
+
+    let center = win1.tl.server.node_absolute_position().center();
+    tassert_eq!(ds.seat.pointer_cursor().position_int(), center);
+    tassert_eq!(enter.next()?.surface, win1.surface.id);
+
+    run.cfg
+        .set_warp_pointer_to_focus(ds.seat.id(), false)?;
+    run.cfg.focus(ds.seat.id(), Direction::Right)?;
+    client.sync().await;
+
+    tassert_eq!(ds.seat.pointer_cursor().position_int(), center);
+    tassert!(enter.next().is_err());
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use {
+    crate::{
+        client::{
+            InputSerialKind, GRAB_SERIAL_KINDS, NUM_CACHED_INPUT_SERIALS, SELECTION_SERIAL_KINDS,
+        },
+        ifs::wl_seat::{SeatId, BTN_LEFT},
+        it::{test_error::TestResult, testrun::TestRun},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let other = run.get_seat("other")?;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let kb_enter = seat.kb.enter.expect()?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+    let enter_serial = kb_enter.next()?.serial;
+
+    let button = seat.pointer.button.expect()?;
+    let (x, y) = win.tl.server.node_absolute_position().center();
+    ds.move_to(x, y);
+    let click = ds.mouse.click(BTN_LEFT);
+    client.sync().await;
+    let button_serial = button.next()?.serial;
+    drop(click);
+    client.sync().await;
+
+    let server = &client._server;
+    let validate = |serial: u32, seat: SeatId, kinds: &[InputSerialKind]| {
+        server
+            .validate_input_serial("test", serial, seat, kinds)
+            .is_some()
+    };
+    tassert!(validate(button_serial, ds.seat.id(), GRAB_SERIAL_KINDS));
+    tassert!(validate(enter_serial, ds.seat.id(), SELECTION_SERIAL_KINDS));
+    tassert!(!validate(enter_serial, ds.seat.id(), GRAB_SERIAL_KINDS));
+    tassert!(!validate(button_serial, other.id(), GRAB_SERIAL_KINDS));
+    tassert!(!validate(
+        button_serial.wrapping_add(1000),
+        ds.seat.id(),
+        GRAB_SERIAL_KINDS
+    ));
+
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+    client.sync().await;
+    let enter_serial2 = kb_enter.last()?.serial;
+    tassert!(!validate(
+        enter_serial,
+        ds.seat.id(),
+        SELECTION_SERIAL_KINDS
+    ));
+    tassert!(validate(
+        enter_serial2,
+        ds.seat.id(),
+        SELECTION_SERIAL_KINDS
+    ));
+
+    for _ in 0..NUM_CACHED_INPUT_SERIALS {
+        let serial = server.next_serial();
+        server.add_input_serial(ds.seat.id(), InputSerialKind::KeyPress, serial);
+    }
+    tassert!(!validate(button_serial, ds.seat.id(), GRAB_SERIAL_KINDS));
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use {
+    crate::{
+        backend::{InputDevice, InputDeviceId},
+        it::{test_error::TestResult, testrun::TestRun},
+    },
+    jay_config::input::DeviceMatch,
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let default = run.get_seat("default")?;
+    let other = run.get_seat("other")?;
+    let kb = DeviceMatch::Name("default-keyboard".to_string());
+    run.cfg
+        .set_seat_devices(other.id(), vec![kb.clone()])?;
+
+    run.backend.install_default()?;
+    run.sync().await;
+    run.sync().await;
+
+    let kb_id = run.backend.default_kb.id();
+    let mouse_id = run.backend.default_mouse.id();
+    let seat_name = |id: InputDeviceId| {
+        let handlers = run.state.input_device_handlers.borrow();
+        let seat = handlers.get(&id).and_then(|h| h.data.seat.get());
+        seat.map(|s| s.seat_name().to_string())
+    };
+    tassert_eq!(seat_name(kb_id).as_deref(), Some("other"));
+    tassert_eq!(seat_name(mouse_id).as_deref(), Some("default"));
+
+    run.cfg.set_seat_devices(default.id(), vec![kb])?;
+    tassert_eq!(seat_name(kb_id).as_deref(), Some("default"));
+
+    Ok(())
+}
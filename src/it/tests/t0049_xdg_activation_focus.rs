@@ -0,0 +1,78 @@
+use {
+    crate::{
+        ifs::wl_seat::BTN_LEFT,
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::Node,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+
+    let button = seat.pointer.button.expect()?;
+    let (x, y) = win1.tl.server.node_absolute_position().center();
+    ds.move_to(x, y);
+    drop(ds.mouse.click(BTN_LEFT));
+    client.sync().await;
+    let serial = button.next()?.serial;
+
+    let focus = || ds.seat.keyboard_node().node_id();
+    tassert_eq!(focus(), win1.surface.server.node_id());
+
+    let token = client.activation.get_token().await?;
+    client.activation.activate(&win2.surface, &token)?;
+    client.sync().await;
+    tassert_eq!(focus(), win1.surface.server.node_id());
+
+    let token = client
+        .activation
+        .get_token_with_serial(serial, seat.seat.id)
+        .await?;
+    client.activation.activate(&win2.surface, &token)?;
+    client.sync().await;
+    tassert_eq!(focus(), win2.surface.server.node_id());
+
+    client.activation.activate(&win1.surface, &token)?;
+    client.sync().await;
+    tassert_eq!(focus(), win2.surface.server.node_id());
+
+    let client2 = run.create_client().await?;
+    let seat2 = client2.get_default_seat().await?;
+    let win3 = client2.create_window().await?;
+    win3.map2().await?;
+
+    let button2 = seat2.pointer.button.expect()?;
+    let (x, y) = win3.tl.server.node_absolute_position().center();
+    ds.move_to(x, y);
+    drop(ds.mouse.click(BTN_LEFT));
+    client2.sync().await;
+    let serial2 = button2.next()?.serial;
+    tassert_eq!(focus(), win3.surface.server.node_id());
+
+    let token = client2
+        .activation
+        .get_token_with_serial(serial2, seat2.seat.id)
+        .await?;
+
+    let (x, y) = win1.tl.server.node_absolute_position().center();
+    ds.move_to(x, y);
+    drop(ds.mouse.click(BTN_LEFT));
+    client.sync().await;
+    tassert_eq!(focus(), win1.surface.server.node_id());
+
+    client2.activation.activate(&win3.surface, &token)?;
+    client2.sync().await;
+    tassert_eq!(focus(), win1.surface.server.node_id());
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use {
+    crate::it::{test_error::TestResult, test_gfx_api::shm_uploaded_bytes, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+const WIDTH: i32 = 3840;
+const HEIGHT: i32 = 2160;
+
+/// Test that shm commits only pass the damaged parts of the buffer to the renderer.
+///
+/// The OpenGL upload of these parts is tested in `gfx_apis::gl::gl::texture::tests`.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let _ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+
+    let buf = client.shm.create_buffer(WIDTH, HEIGHT)?;
+    let ss = client.comp.create_surface().await?;
+    let sub = client
+        .sub
+        .get_subsurface(ss.id, win.surface.id)
+        .await?;
+    sub.set_desync()?;
+    ss.attach(buf.buffer.id)?;
+    ss.damage_buffer(0, 0, WIDTH, HEIGHT)?;
+    ss.commit()?;
+    win.surface.commit()?;
+    client.sync().await;
+
+    let uploaded = || {
+        let mut bytes = 0;
+        for tex in &*ss.server.shm_textures {
+            if let Some(tex) = tex.tex.get() {
+                bytes += shm_uploaded_bytes(&*tex.into_texture());
+            }
+        }
+        bytes
+    };
+    let commit_small_damage = || -> TestResult {
+        ss.attach(buf.buffer.id)?;
+        ss.damage_buffer(100, 100, 10, 10)?;
+        ss.commit()
+    };
+
+    // Make sure that both textures have been allocated.
+    for _ in 0..2 {
+        commit_small_damage()?;
+        client.sync().await;
+    }
+
+    let before = uploaded();
+    commit_small_damage()?;
+    client.sync().await;
+    let bytes = uploaded() - before;
+
+    let full = (WIDTH * HEIGHT * 4) as usize;
+    tassert!(bytes > 0);
+    tassert!(bytes * 100 < full);
+
+    Ok(())
+}
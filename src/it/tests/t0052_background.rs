@@ -0,0 +1,58 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::video::BackgroundMode,
+    png::{BitDepth, ColorType, Encoder},
+    std::{fs::File, rc::Rc},
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let path = format!("{}/background.png", run.out_dir);
+    {
+        let mut encoder = Encoder::new(File::create(&path)?, 2, 2);
+        encoder.set_color(ColorType::Rgb);
+        encoder.set_depth(BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&[255; 12])?;
+    }
+
+    let background = || ds.output.global.persistent.wallpaper.get();
+
+    let res = run
+        .cfg
+        .set_output_background(&ds.output, &path, BackgroundMode::Tile)?;
+    tassert!(res.is_ok());
+    run.state.idle().await;
+    let Some(wallpaper) = background() else {
+        bail!("Background was not set");
+    };
+    tassert_eq!(wallpaper.mode, BackgroundMode::Tile);
+
+    let missing = format!("{}/missing.png", run.out_dir);
+    let res = run
+        .cfg
+        .set_output_background(&ds.output, &missing, BackgroundMode::Fill)?;
+    tassert!(res.is_ok());
+    run.state.idle().await;
+    tassert!(background().is_some_and(|b| Rc::ptr_eq(&b, &wallpaper)));
+
+    let large = format!("{}/large.png", run.out_dir);
+    {
+        let mut encoder = Encoder::new(File::create(&large)?, 20000, 1);
+        encoder.set_color(ColorType::Grayscale);
+        encoder.set_depth(BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&[0; 20000])?;
+    }
+    let res = run
+        .cfg
+        .set_output_background(&ds.output, &large, BackgroundMode::Fill)?;
+    tassert!(res.is_ok());
+    run.state.idle().await;
+    tassert!(background().is_some_and(|b| Rc::ptr_eq(&b, &wallpaper)));
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::{Node, ToplevelNodeBase},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    run.cfg.set_animations_enabled(true)?;
+    run.cfg.show_workspace(ds.seat.id(), "1")?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+    let Some(open) = win.tl.server.tl_data().open_animation.get() else {
+        bail!("Window did not start an open animation");
+    };
+    let pos = win.tl.server.node_absolute_position();
+
+    run.cfg.show_workspace(ds.seat.id(), "2")?;
+    let Some(slide) = ds.output.workspace_slide.get() else {
+        bail!("Workspace switch did not start a slide");
+    };
+    tassert!(slide.forward);
+    tassert!(slide.old.is_some());
+
+    run.cfg.show_workspace(ds.seat.id(), "1")?;
+    tassert!(ds
+        .output
+        .workspace_slide
+        .get()
+        .is_some_and(|s| !s.forward));
+    tassert_eq!(win.tl.server.node_absolute_position(), pos);
+
+    run.state.wheel.timeout(200).await?;
+    let now = run.state.now_nsec();
+    tassert!(open.progress(now).is_none());
+    tassert!(slide.animation.progress(now).is_none());
+
+    run.cfg.set_animations_enabled(false)?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+    tassert!(win2.tl.server.tl_data().open_animation.get().is_none());
+
+    Ok(())
+}
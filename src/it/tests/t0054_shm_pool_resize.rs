@@ -0,0 +1,47 @@
+use {
+    crate::{
+        format::ARGB8888,
+        it::{test_error::TestResult, testrun::TestRun},
+        rect::Rect,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+const SIZE: i32 = 4;
+
+/// Test that buffers can be created in the grown part of a resized shm pool
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let _ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+
+    let len = (SIZE * SIZE * 4) as usize;
+    let pool = client.shm.create_pool(len)?;
+    pool.resize(2 * len)?;
+    let buf = pool.create_buffer(len as i32, SIZE, SIZE, SIZE * 4, ARGB8888)?;
+
+    let ss = client.comp.create_surface().await?;
+    let sub = client
+        .sub
+        .get_subsurface(ss.id, win.surface.id)
+        .await?;
+    sub.set_desync()?;
+    ss.attach(buf.buffer.id)?;
+    ss.commit()?;
+    win.surface.commit()?;
+    client.sync().await;
+
+    let Some(buffer) = ss.server.buffer.get() else {
+        bail!("Surface has no buffer attached");
+    };
+    tassert_eq!(
+        buffer.buffer.rect,
+        Rect::new_sized(0, 0, SIZE, SIZE).unwrap()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::ToplevelNodeBase,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+
+    let tl1 = win1.tl.server.tl_data();
+    let tl2 = win2.tl.server.tl_data();
+    tassert!(tl2.active());
+    tassert_eq!(tl1.render_alpha(), None);
+    tassert_eq!(tl2.render_alpha(), None);
+
+    run.cfg.set_window_opacity(ds.seat.id(), 0.5)?;
+    tassert_eq!(tl1.render_alpha(), None);
+    tassert_eq!(tl2.render_alpha(), Some(0.5));
+
+    run.cfg.set_dim_unfocused(0.5)?;
+    tassert_eq!(tl1.render_alpha(), Some(0.5));
+    tassert_eq!(tl2.render_alpha(), Some(0.5));
+
+    run.cfg.set_fullscreen(ds.seat.id(), true)?;
+    tassert_eq!(tl2.render_alpha(), None);
+
+    run.cfg.set_fullscreen(ds.seat.id(), false)?;
+    run.cfg.set_window_opacity(ds.seat.id(), 2.0)?;
+    run.cfg.set_dim_unfocused(0.0)?;
+    tassert_eq!(tl1.render_alpha(), None);
+    tassert_eq!(tl2.render_alpha(), None);
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        rect::Rect,
+        tree::Node,
+    },
+    jay_config::theme::sized::{INNER_GAP, OUTER_GAP},
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    run.backend.install_default()?;
+
+    let client = run.create_client().await?;
+    let window = client.create_window().await?;
+    window.map().await?;
+    let window2 = client.create_window().await?;
+    window2.map().await?;
+
+    run.cfg.set_size(INNER_GAP, 6)?;
+    run.cfg.set_size(OUTER_GAP, 10)?;
+    client.sync().await;
+
+    let otop = 2 * (run.state.theme.sizes.title_height.get() + 1);
+    let bw = run.state.theme.sizes.border_width.get();
+    let pos1 = window.tl.server.node_absolute_position();
+    let pos2 = window2.tl.server.node_absolute_position();
+    tassert_eq!((pos1.x1(), pos1.y1(), pos1.y2()), (10, otop + 10, 590));
+    tassert_eq!((pos2.y1(), pos2.x2(), pos2.y2()), (otop + 10, 790, 590));
+    tassert_eq!(pos2.x1() - pos1.x2(), bw + 6);
+
+    run.cfg.set_size(OUTER_GAP, 1000)?;
+    client.sync().await;
+    // The gaps shrink so that a 100x100 container remains in the middle of the
+    // workspace below the bar.
+    let container = window.tl.container_parent()?.node_absolute_position();
+    tassert_eq!(Some(container), Rect::new_sized(350, 18 + 241, 100, 100));
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use {
+    crate::{
+        ifs::wl_seat::{BTN_LEFT, BTN_RIGHT},
+        it::{test_error::TestResult, testrun::TestRun},
+    },
+    jay_config::{
+        input::{PointerContext, PointerInput},
+        keyboard::mods::Modifiers,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let seat_id = ds.seat.id();
+    let left = PointerInput::Button(BTN_LEFT);
+    let right = PointerInput::Button(BTN_RIGHT);
+    let down = PointerInput::ScrollDown;
+    let none = Modifiers::NONE;
+    run.cfg
+        .add_pointer_binding(seat_id, none, left, PointerContext::Window)?;
+    run.cfg
+        .add_pointer_binding(seat_id, none, right, PointerContext::Root)?;
+    run.cfg
+        .add_pointer_binding(seat_id, none, down, PointerContext::Window)?;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let button = seat.pointer.button.expect()?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+
+    let (x, y) = win.tl.server.node_absolute_position().center();
+    ds.move_to(x, y);
+    ds.mouse.click(BTN_LEFT);
+    client.sync().await;
+    button.none()?;
+    let invoked = &run.cfg.invoked_pointer_bindings;
+    tassert!(invoked.contains(&(seat_id, none, left, PointerContext::Window)));
+    tassert_eq!(run.cfg.pointer_binding_releases.get(), 1);
+
+    ds.mouse.click(BTN_RIGHT);
+    client.sync().await;
+    tassert_eq!(button.next()?.button, BTN_RIGHT);
+    tassert!(!invoked.contains(&(seat_id, none, right, PointerContext::Window)));
+
+    ds.mouse.scroll(1);
+    client.sync().await;
+    tassert!(invoked.contains(&(seat_id, none, down, PointerContext::Window)));
+
+    let enter = seat.pointer.enter.expect()?;
+    let leave = seat.pointer.leave.expect()?;
+    let motion = seat.pointer.motion.expect()?;
+    let click = ds.mouse.click(BTN_LEFT);
+    client.sync().await;
+    leave.next()?;
+    ds.move_to(x + 5, y + 5);
+    client.sync().await;
+    enter.none()?;
+    motion.none()?;
+    tassert_eq!(run.cfg.pointer_binding_motion.get(), Some((x + 5, y + 5)));
+    drop(click);
+    client.sync().await;
+    enter.next()?;
+    button.none()?;
+    tassert_eq!(run.cfg.pointer_binding_releases.get(), 2);
+
+    run.cfg.set_floating(seat_id, true)?;
+    run.sync().await;
+    let pos = win.tl.server.node_absolute_position();
+    let (x, y) = pos.center();
+    ds.move_to(x, y);
+    let click = ds.mouse.click(BTN_LEFT);
+    run.cfg.start_pointer_move(seat_id)?;
+    ds.move_to(x + 20, y + 10);
+    run.sync().await;
+    let moved = win.tl.server.node_absolute_position();
+    tassert_eq!(moved.position(), (pos.x1() + 20, pos.y1() + 10));
+    drop(click);
+    ds.move_to(x, y);
+    run.sync().await;
+    tassert_eq!(win.tl.server.node_absolute_position(), moved);
+    tassert_eq!(run.cfg.pointer_binding_releases.get(), 3);
+
+    ds.mouse.abs(&ds.connector, 0.0, 0.0);
+    ds.mouse.click(BTN_RIGHT);
+    client.sync().await;
+    tassert!(invoked.contains(&(seat_id, none, right, PointerContext::Root)));
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::{Node, ToplevelNodeBase},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+
+    let seat = ds.seat.id();
+    let scratch = run.state.scratch_workspace.get().unwrap();
+    let focus = || ds.seat.keyboard_node().node_id();
+    tassert_eq!(focus(), win2.surface.server.node_id());
+
+    run.cfg.send_to_scratch(seat)?;
+    client.sync().await;
+    let data = win2.tl.server.tl_data();
+    tassert_eq!(data.workspace.get().map(|ws| ws.id), Some(scratch.id));
+    tassert!(!win2.tl.server.node_visible());
+    tassert_eq!(focus(), win1.surface.server.node_id());
+
+    run.cfg.fetch_from_scratch(seat, "does-not-exist")?;
+    tassert_eq!(data.workspace.get().map(|ws| ws.id), Some(scratch.id));
+
+    run.cfg.fetch_from_scratch(seat, "")?;
+    client.sync().await;
+    let ws = ds.output.workspace.get().map(|ws| ws.id);
+    tassert_eq!(data.workspace.get().map(|ws| ws.id), ws);
+    tassert!(data.is_floating.get());
+    tassert!(win2.tl.server.node_visible());
+    tassert_eq!(focus(), win2.surface.server.node_id());
+
+    Ok(())
+}
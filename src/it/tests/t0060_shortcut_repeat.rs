@@ -0,0 +1,55 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::keyboard::syms::SYM_F13,
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let keymap = r#"
+xkb_keymap {
+    xkb_keycodes {
+          <1> = 9; # ESC
+    };
+    xkb_types {
+    };
+    xkb_compatibility {
+    };
+    xkb_symbols {
+        key <1> { [ F13 ] };
+    };
+};
+    "#;
+
+    let keymap = run.cfg.parse_keymap(keymap)?;
+    run.cfg.set_keymap(ds.seat.id(), keymap)?;
+    run.cfg.add_shortcut(ds.seat.id(), SYM_F13)?;
+    run.cfg
+        .set_shortcut_repeat(ds.seat.id(), SYM_F13, true)?;
+    ds.seat.set_rate(100, 10);
+    run.sync().await;
+
+    let invoked = &run.cfg.invoked_shortcuts;
+    let shortcut = (ds.seat.id(), SYM_F13.into());
+
+    let pressed = ds.kb.press(1);
+    run.sync().await;
+    tassert!(invoked.contains(&shortcut));
+
+    invoked.clear();
+    run.state.wheel.advance(50);
+    run.sync().await;
+    tassert!(invoked.contains(&shortcut));
+
+    drop(pressed);
+    run.sync().await;
+    invoked.clear();
+    run.state.wheel.advance(50);
+    run.sync().await;
+    tassert!(invoked.is_empty());
+
+    Ok(())
+}
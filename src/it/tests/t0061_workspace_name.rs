@@ -0,0 +1,20 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    run.cfg.show_workspace(ds.seat.id(), "browser")?;
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+
+    let names = run.cfg.workspace_names()?;
+    tassert!(names.iter().any(|n| n == "browser"));
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use {
+    crate::{
+        backend::{BackendEvent, ConnectorEvent, ConnectorKernelId},
+        ifs::wl_output::OutputId,
+        it::{test_backend::TestConnector, test_error::TestResult, testrun::TestRun},
+        video::drm::ConnectorType,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+    let surface = &win.surface.server;
+
+    let new_connector = Rc::new(TestConnector {
+        id: run.state.connector_ids.next(),
+        kernel_id: ConnectorKernelId {
+            ty: ConnectorType::VGA,
+            idx: 2,
+        },
+        events: Default::default(),
+        feedback: Default::default(),
+        content_type: Default::default(),
+        drm_dev: None,
+    });
+    let mut new_monitor_info = run.backend.default_monitor_info.clone();
+    new_monitor_info.output_id = Rc::new(OutputId {
+        connector: None,
+        manufacturer: "jay".to_string(),
+        model: "jay second connector".to_string(),
+        serial_number: "".to_string(),
+    });
+    run.backend
+        .state
+        .backend_events
+        .push(BackendEvent::NewConnector(new_connector.clone()));
+    new_connector
+        .events
+        .send_event(ConnectorEvent::Connected(new_monitor_info));
+    run.state.eng.yield_now().await;
+
+    ds.connector
+        .events
+        .send_event(ConnectorEvent::Disconnected);
+    run.state.eng.yield_now().await;
+    tassert_eq!(
+        surface.get_output().global.connector.connector.id(),
+        new_connector.id
+    );
+
+    run.cfg.adopt_orphaned_workspaces(new_connector.id)?;
+
+    ds.connector
+        .events
+        .send_event(ConnectorEvent::Connected(
+            run.backend.default_monitor_info.clone(),
+        ));
+    run.state.eng.yield_now().await;
+    tassert_eq!(
+        surface.get_output().global.connector.connector.id(),
+        new_connector.id
+    );
+
+    Ok(())
+}
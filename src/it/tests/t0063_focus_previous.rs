@@ -0,0 +1,44 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::Node,
+    },
+    jay_config::Direction,
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+    let win3 = client.create_window().await?;
+    win3.map2().await?;
+
+    let seat = ds.seat.id();
+    let focus = || ds.seat.keyboard_node().node_id();
+    tassert_eq!(focus(), win3.surface.server.node_id());
+
+    run.cfg.focus_previous(seat)?;
+    tassert_eq!(focus(), win2.surface.server.node_id());
+
+    run.cfg.focus_previous(seat)?;
+    tassert_eq!(focus(), win3.surface.server.node_id());
+
+    run.cfg.focus(seat, Direction::Left)?;
+    run.cfg.focus(seat, Direction::Left)?;
+    tassert_eq!(focus(), win1.surface.server.node_id());
+
+    run.cfg.focus_previous(seat)?;
+    tassert_eq!(focus(), win2.surface.server.node_id());
+
+    run.cfg.focus_previous(seat)?;
+    tassert_eq!(focus(), win1.surface.server.node_id());
+
+    Ok(())
+}
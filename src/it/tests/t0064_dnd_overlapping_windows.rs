@@ -0,0 +1,103 @@
+use {
+    crate::{
+        fixed::Fixed,
+        ifs::wl_seat::BTN_LEFT,
+        it::{test_error::TestResult, testrun::TestRun},
+        rect::Rect,
+        tree::Node,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    run.cfg.set_floating(ds.seat.id(), true)?;
+
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+    run.cfg.set_floating(ds.seat.id(), true)?;
+
+    // Move win1 so that its right half is covered by win2.
+    let pos2 = win2.tl.float_parent()?.position.get();
+    {
+        let parent = win1.tl.float_parent()?;
+        parent
+            .position
+            .set(pos2.at_point(pos2.x1() - pos2.width() / 2, pos2.y1()));
+        parent.schedule_layout();
+    }
+    client.sync().await;
+
+    let seat = client.get_default_seat().await?;
+    let button = seat.pointer.button.expect()?;
+
+    let (x, y) = win2.tl.server.node_absolute_position().center();
+    ds.move_to(x, y);
+    let click = ds.mouse.click(BTN_LEFT);
+
+    client.sync().await;
+    let dev = client
+        .data_device_manager
+        .get_data_device(&seat.seat)?;
+    let src = client.data_device_manager.create_data_source()?;
+    src.set_actions(1)?;
+
+    let pointer_enter = seat.pointer.enter.expect()?;
+    let enter = dev.enter.expect()?;
+    let leave = dev.leave.expect()?;
+    let motion = dev.motion.expect()?;
+
+    let assert_local = |x: Fixed, y: Fixed, px: i32, py: i32, surface: Rect| -> TestResult {
+        tassert_eq!(x, Fixed::from_int(px - surface.x1()));
+        tassert_eq!(y, Fixed::from_int(py - surface.y1()));
+        Ok(())
+    };
+
+    dev.start_drag(&src, &win2.surface, None, button.next()?.serial)?;
+    client.sync().await;
+
+    // The drag origin is entered immediately.
+    let ev = enter.next()?;
+    tassert_eq!(ev.surface, win2.surface.id);
+    assert_local(ev.x, ev.y, x, y, win2.surface.server.buffer_abs_pos.get())?;
+
+    // Motion within the drag origin.
+    let (x, y) = (x + 5, y + 5);
+    ds.move_to(x, y);
+    client.sync().await;
+    let ev = motion.last()?;
+    assert_local(ev.x, ev.y, x, y, win2.surface.server.buffer_abs_pos.get())?;
+
+    // The uncovered half of win1.
+    let (x, y) = (pos2.x1() - 10, y);
+    ds.move_to(x, y);
+    client.sync().await;
+    tassert!(leave.next().is_ok());
+    let ev = enter.next()?;
+    tassert_eq!(ev.surface, win1.surface.id);
+    assert_local(ev.x, ev.y, x, y, win1.surface.server.buffer_abs_pos.get())?;
+
+    // The part of win1 that is covered by win2.
+    let (x, y) = (pos2.x1() + 10, y);
+    ds.move_to(x, y);
+    client.sync().await;
+    tassert!(leave.next().is_ok());
+    let ev = enter.next()?;
+    tassert_eq!(ev.surface, win2.surface.id);
+    assert_local(ev.x, ev.y, x, y, win2.surface.server.buffer_abs_pos.get())?;
+
+    // Normal pointer focus is suppressed for the whole drag.
+    tassert!(pointer_enter.next().is_err());
+
+    drop(click);
+    client.sync().await;
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+
+    let output = client.get_output(&ds.output).await?;
+    tassert_eq!(output.done.get(), 1);
+    tassert_eq!(output.scale.get(), 1);
+
+    let global = &ds.output.global;
+    let name = global.connector.name.clone();
+    let description = global.description();
+    tassert_eq!(output.name.take(), Some(name.clone()));
+    tassert_eq!(output.description.take(), Some(description.clone()));
+
+    let manager = client.registry.get_xdg_output_manager().await?;
+    let xdg = manager.get_xdg_output(&output)?;
+    client.sync().await;
+
+    let pos = global.pos.get();
+    let mode = global.mode.get();
+    tassert_eq!(output.done.get(), 2);
+    tassert_eq!(xdg.logical_position.get(), (pos.x1(), pos.y1()));
+    tassert_eq!(xdg.logical_size.get(), (mode.width, mode.height));
+    tassert_eq!(xdg.name.take(), Some(name));
+    tassert_eq!(xdg.description.take(), Some(description));
+    tassert_eq!(xdg.num_names.get(), 1);
+
+    run.cfg.set_scale(&ds.output, 2.0)?;
+    client.sync().await;
+
+    tassert!(output.done.get() > 2);
+    tassert_eq!(output.scale.get(), 2);
+    tassert_eq!(xdg.logical_size.get(), (mode.width / 2, mode.height / 2));
+    tassert_eq!(xdg.num_names.get(), 1);
+    tassert!(xdg.description.take().is_none());
+
+    Ok(())
+}
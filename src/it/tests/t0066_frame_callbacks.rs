@@ -0,0 +1,28 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+
+    let win = client.create_window().await?;
+    win.map2().await?;
+
+    let cb = win.surface.frame()?;
+    win.surface.commit()?;
+    client.sync().await;
+
+    // Frame callbacks are only dispatched once the output has presented a frame.
+    tassert!(!cb.done.get());
+
+    run.state.vblank(ds.connector.id);
+    client.sync().await;
+    tassert!(cb.done.get());
+
+    Ok(())
+}
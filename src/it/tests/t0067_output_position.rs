@@ -0,0 +1,48 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::Node,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+
+    let output = client.get_output(&ds.output).await?;
+    let manager = client.registry.get_xdg_output_manager().await?;
+    let xdg = manager.get_xdg_output(&output)?;
+
+    let win = client.create_window().await?;
+    win.map2().await?;
+
+    let old_output_pos = ds.output.global.pos.get();
+    let old_win_pos = win.tl.server.node_absolute_position();
+    let done = output.done.get();
+
+    let (dx, dy) = (1000, 500);
+    run.cfg.set_output_position(
+        &ds.output,
+        old_output_pos.x1() + dx,
+        old_output_pos.y1() + dy,
+    )?;
+    client.sync().await;
+
+    let output_pos = ds.output.global.pos.get();
+    tassert_eq!(output_pos, old_output_pos.move_(dx, dy));
+    tassert_eq!(
+        xdg.logical_position.get(),
+        (output_pos.x1(), output_pos.y1())
+    );
+    tassert!(output.done.get() > done);
+    tassert_eq!(
+        win.tl.server.node_absolute_position(),
+        old_win_pos.move_(dx, dy)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use {
+    crate::{
+        backend::{BackendEvent, ConnectorEvent, ConnectorKernelId},
+        gfx_api::{create_output_render_pass, GfxApiOpt},
+        ifs::wl_output::OutputId,
+        it::{test_backend::TestConnector, test_error::TestResult, testrun::TestRun},
+        video::drm::ConnectorType,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let new_connector = Rc::new(TestConnector {
+        id: run.state.connector_ids.next(),
+        kernel_id: ConnectorKernelId {
+            ty: ConnectorType::VGA,
+            idx: 2,
+        },
+        events: Default::default(),
+        feedback: Default::default(),
+        content_type: Default::default(),
+        drm_dev: None,
+    });
+    let mut new_monitor_info = run.backend.default_monitor_info.clone();
+    new_monitor_info.output_id = Rc::new(OutputId {
+        connector: None,
+        manufacturer: "jay".to_string(),
+        model: "jay second connector".to_string(),
+        serial_number: "".to_string(),
+    });
+    run.backend
+        .state
+        .backend_events
+        .push(BackendEvent::NewConnector(new_connector.clone()));
+    new_connector
+        .events
+        .send_event(ConnectorEvent::Connected(new_monitor_info));
+    run.state.eng.yield_now().await;
+
+    let Some(output) = run.state.root.outputs.get(&new_connector.id) else {
+        bail!("the second output was not created");
+    };
+
+    run.cfg.mirror_output(&output, &ds.output)?;
+    let Some(mirror) = output.mirror.get() else {
+        bail!("output is not mirrored");
+    };
+    tassert_eq!(mirror.source.id, ds.output.id);
+
+    // The mirror is not part of the layout and cannot be entered by the pointer.
+    let source_pos = ds.output.global.pos.get();
+    tassert_eq!(run.state.root.extents.get(), source_pos);
+    let mirror_pos = output.global.pos.get();
+    ds.move_to(mirror_pos.x1() + 10, mirror_pos.y1() + 10);
+    run.sync().await;
+    let (x, y) = ds.seat.pointer_cursor().position_int();
+    tassert!(source_pos.contains(x, y));
+
+    // The 800x600 source is rendered at half size, centered vertically in a 400x400
+    // framebuffer: the top-left corner of the source ends up at pixel (0, 50).
+    let pass = create_output_render_pass(vec![], (400, 400), &output, &run.state, false, None);
+    let bar = pass.ops.iter().find_map(|op| match op {
+        GfxApiOpt::FillRect(f) => Some(&f.rect),
+        _ => None,
+    });
+    let Some(bar) = bar else {
+        bail!("the bar of the source was not rendered");
+    };
+    tassert_eq!((bar.x1, bar.x2, bar.y1), (-1.0, 1.0, -0.75));
+
+    // An output that is mirrored cannot mirror another output.
+    run.cfg.mirror_output(&ds.output, &output)?;
+    tassert!(ds.output.mirror.is_none());
+
+    run.cfg.unmirror_output(&output)?;
+    tassert!(output.mirror.is_none());
+
+    run.cfg.mirror_output(&output, &ds.output)?;
+    tassert!(output.mirror.is_some());
+    ds.connector
+        .events
+        .send_event(ConnectorEvent::Disconnected);
+    run.state.eng.yield_now().await;
+    tassert!(output.mirror.is_none());
+
+    Ok(())
+}
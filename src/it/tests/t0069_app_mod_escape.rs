@@ -0,0 +1,66 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::keyboard::{
+        syms::{SYM_Escape, SYM_F1},
+        AppMod,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+const KEY_ESC: u32 = 1;
+const KEY_A: u32 = 30;
+const KEY_F1: u32 = 59;
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+    client.sync().await;
+
+    let resize = AppMod {
+        app_name: AppMod::APP_NAME_JAY.to_string(),
+        mod_name: "Resize".to_string(),
+    };
+    let id = ds.seat.id();
+    let cfg = &run.cfg;
+    cfg.add_modal_shortcut(id, SYM_F1, resize.clone())?;
+    cfg.set_app_mod_escape(id, SYM_Escape)?;
+    cfg.set_app_mod_forward_keys(id, resize.clone(), true)?;
+    cfg.set_app_mod(id, resize.clone())?;
+    tassert_eq!(ds.seat.app_mod(), resize);
+
+    let keys = &seat.kb.key.expect()?;
+    let (run, kb, client) = (&run, &ds.kb, &client);
+    let press = move |key: u32| {
+        drop(kb.press(key));
+        async move {
+            run.sync().await;
+            client.sync().await;
+            let mut res = vec![];
+            while let Ok((_, ev)) = keys.next() {
+                res.push((ev.key, ev.state));
+            }
+            res
+        }
+    };
+
+    tassert_eq!(press(KEY_A).await, [(KEY_A, 1), (KEY_A, 0)]);
+
+    let received = press(KEY_F1).await;
+    tassert!(!received.contains(&(KEY_F1, 1)));
+    tassert!(cfg.invoked_shortcuts.contains(&(id, SYM_F1.into())));
+    tassert_eq!(*cfg.last_invoked_app_mod.borrow(), Some(resize.clone()));
+
+    let received = press(KEY_ESC).await;
+    tassert!(received.iter().all(|(key, _)| *key != KEY_ESC));
+    tassert_eq!(ds.seat.app_mod(), AppMod::default());
+
+    tassert_eq!(press(KEY_A).await, []);
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use {
+    crate::{
+        backend::{BackendEvent, ConnectorEvent, ConnectorKernelId, DrmEvent},
+        ifs::wl_output::OutputId,
+        it::{
+            test_backend::{TestConnector, TestDrmDevice},
+            test_error::TestResult,
+            testrun::TestRun,
+        },
+        video::drm::ConnectorType,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let dev = Rc::new(TestDrmDevice {
+        id: run.state.drm_dev_ids.next(),
+        events: Default::default(),
+    });
+    let connector = Rc::new(TestConnector {
+        id: run.state.connector_ids.next(),
+        kernel_id: ConnectorKernelId {
+            ty: ConnectorType::VGA,
+            idx: 2,
+        },
+        events: Default::default(),
+        feedback: Default::default(),
+        content_type: Default::default(),
+        drm_dev: Some(dev.id),
+    });
+    let mut monitor_info = run.backend.default_monitor_info.clone();
+    monitor_info.output_id = Rc::new(OutputId {
+        connector: None,
+        manufacturer: "jay".to_string(),
+        model: "jay second connector".to_string(),
+        serial_number: "".to_string(),
+    });
+    let events = &run.backend.state.backend_events;
+    events.push(BackendEvent::NewDrmDevice(dev.clone()));
+    events.push(BackendEvent::NewConnector(connector.clone()));
+    connector
+        .events
+        .send_event(ConnectorEvent::Connected(monitor_info));
+    run.sync().await;
+    tassert!(run.state.drm_devs.contains(&dev.id));
+    tassert!(run.state.root.outputs.contains(&connector.id));
+
+    // Remove the device before its connector handlers have run. The device handler
+    // must wait for them.
+    dev.events.send_event(DrmEvent::Removed);
+    connector
+        .events
+        .send_event(ConnectorEvent::Disconnected);
+    connector.events.send_event(ConnectorEvent::Removed);
+    run.sync().await;
+    tassert!(!run.state.root.outputs.contains(&connector.id));
+    tassert!(!run.state.connectors.contains(&connector.id));
+    tassert!(!run.state.drm_devs.contains(&dev.id));
+    tassert!(run.state.root.outputs.contains(&ds.connector.id));
+
+    Ok(())
+}
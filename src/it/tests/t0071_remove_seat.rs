@@ -0,0 +1,16 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    // Seats cannot be removed. The config must be told so.
+    tassert!(!run.cfg.remove_seat(ds.seat.id())?);
+    tassert_eq!(run.cfg.get_seat(ds.seat.seat_name())?, ds.seat.id());
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use {
+    crate::{
+        gfx_api::{create_output_render_pass, GfxApiOpt},
+        it::{test_error::TestResult, testrun::TestRun},
+        rect::Rect,
+        utils::rc_eq::rc_eq,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.set_color(255, 0, 0, 255);
+    win.map2().await?;
+
+    // A subsurface that covers the window exactly.
+    let (width, height) = win.surface.server.buffer_abs_pos.get().size();
+    let buf = client.shm.create_buffer(width, height)?;
+    let ss = client.comp.create_surface().await?;
+    let sub = client
+        .sub
+        .get_subsurface(ss.id, win.surface.id)
+        .await?;
+    sub.set_desync()?;
+    ss.attach(buf.buffer.id)?;
+    ss.commit()?;
+    win.surface.commit()?;
+    client.sync().await;
+    let buffer = ss.server.buffer.get();
+    let tex = buffer.and_then(|b| b.buffer.get_texture(&ss.server));
+    let Some(tex) = tex else {
+        bail!("the subsurface has no texture");
+    };
+
+    // Returns the number of fills that are covered by the subsurface.
+    let covered_fills = || {
+        let pass =
+            create_output_render_pass(vec![], (800, 600), &ds.output, &run.state, false, None);
+        let target = pass.ops.iter().find_map(|op| match op {
+            GfxApiOpt::CopyTexture(c) if rc_eq(&c.tex, &tex) => Some(&c.target),
+            _ => None,
+        })?;
+        let fills = pass.ops.iter().filter(|op| match op {
+            GfxApiOpt::FillRect(f) => target.contains(&f.rect),
+            _ => false,
+        });
+        Some(fills.count())
+    };
+
+    // The buffer has an alpha channel, so the window remains visible.
+    tassert_eq!(covered_fills(), Some(1));
+
+    let region = client.comp.create_region().await?;
+    region.add(Rect::new_sized(0, 0, width, height).unwrap())?;
+    ss.set_opaque_region(&region)?;
+    ss.commit()?;
+    client.sync().await;
+    tassert!(ss.server.opaque.get());
+    tassert_eq!(covered_fills(), Some(0));
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use {
+    crate::{
+        gfx_api::{create_output_render_pass, GfxApiOpt},
+        ifs::wl_seat::BTN_LEFT,
+        it::{test_error::TestResult, testrun::TestRun},
+        rect::Rect,
+        theme::Color,
+        tree::Node,
+    },
+    jay_config::theme::{
+        self,
+        colors::{FOCUSED_BORDER_COLOR, UNFOCUSED_BORDER_COLOR},
+        sized::{BORDER_WIDTH, TITLE_HEIGHT},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    run.cfg.set_size(BORDER_WIDTH, 10)?;
+    run.cfg.set_size(TITLE_HEIGHT, 20)?;
+    let (green, blue) = (theme::Color::new(0, 255, 0), theme::Color::new(0, 0, 255));
+    run.cfg.set_color(FOCUSED_BORDER_COLOR, green)?;
+    run.cfg.set_color(UNFOCUSED_BORDER_COLOR, blue)?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+    run.cfg.set_floating(ds.seat.id(), true)?;
+    client.sync().await;
+
+    let float = win.tl.float_parent()?;
+    let pos = float.position.get();
+    ds.move_to(pos.x1() + pos.width() / 2, pos.y1() + pos.height() / 2);
+    client.sync().await;
+    tassert!(float.active.get());
+
+    // The border and the title bar surround the window.
+    let expected = Rect::new(pos.x1() + 10, pos.y1() + 31, pos.x2() - 10, pos.y2() - 10);
+    tassert_eq!(Some(win.tl.server.node_absolute_position()), expected);
+
+    // Returns the number of fills with the given color.
+    let fills = |color: Color| {
+        let pass =
+            create_output_render_pass(vec![], (800, 600), &ds.output, &run.state, false, None);
+        let fills = pass.ops.iter().filter(|op| match op {
+            GfxApiOpt::FillRect(f) => f.color == color,
+            _ => false,
+        });
+        fills.count()
+    };
+    let focused = Color::from_rgb(0, 255, 0);
+    let unfocused = Color::from_rgb(0, 0, 255);
+    tassert_eq!(fills(focused), 4);
+    tassert_eq!(fills(unfocused), 0);
+
+    // Dragging the right border resizes the window.
+    ds.move_to(pos.x2() - 5, pos.y1() + pos.height() / 2);
+    let click = ds.mouse.click(BTN_LEFT);
+    ds.mouse.rel(50.0, 0.0);
+    drop(click);
+    client.sync().await;
+    let expected = Rect::new(pos.x1(), pos.y1(), pos.x2() + 50, pos.y2());
+    tassert_eq!(Some(float.position.get()), expected);
+    tassert_eq!(win.tl.core.width.get(), pos.width() + 30);
+
+    Ok(())
+}
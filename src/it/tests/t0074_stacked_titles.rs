@@ -0,0 +1,57 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::{ContainerSplit, Node, ToplevelNodeBase},
+    },
+    jay_config::Direction,
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+    let win3 = client.create_window().await?;
+    win3.map2().await?;
+
+    let seat = ds.seat.id();
+    let container = win1.tl.container_parent()?;
+    let th = run.state.theme.sizes.title_height.get();
+    let title_ys = || {
+        let ys = container.children.iter().map(|c| c.title_rect.get());
+        ys.map(|r| r.y1()).collect::<Vec<_>>()
+    };
+
+    // Mono containers show their titles as tabs, even if they are split vertically.
+    run.cfg.set_mono(seat, true)?;
+    container.set_split(ContainerSplit::Vertical);
+    client.sync().await;
+    tassert_eq!(container.mono_body.get().y1(), th + 1);
+    tassert_eq!(title_ys(), [0, 0, 0]);
+    tassert!(container.tl_data().title.borrow().starts_with("T["));
+
+    run.cfg.set_stacked(seat, true)?;
+    client.sync().await;
+    tassert_eq!(container.mono_body.get().y1(), 3 * (th + 1));
+    tassert_eq!(title_ys(), [0, th + 1, 2 * (th + 1)]);
+    tassert!(container.tl_data().title.borrow().starts_with("S["));
+
+    // Stacked titles are navigated vertically.
+    let focus = || ds.seat.keyboard_node().node_id();
+    tassert_eq!(focus(), win3.surface.server.node_id());
+    run.cfg.focus(seat, Direction::Up)?;
+    tassert_eq!(focus(), win2.surface.server.node_id());
+
+    run.cfg.set_stacked(seat, false)?;
+    client.sync().await;
+    tassert_eq!(container.mono_body.get().y1(), th + 1);
+    tassert_eq!(title_ys(), [0, 0, 0]);
+
+    Ok(())
+}
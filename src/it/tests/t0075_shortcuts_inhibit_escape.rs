@@ -0,0 +1,59 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::keyboard::syms::{SYM_Escape, SYM_F1},
+    std::rc::Rc,
+};
+
+testcase!();
+
+const KEY_ESC: u32 = 1;
+const KEY_F1: u32 = 59;
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+    client.sync().await;
+
+    let id = ds.seat.id();
+    let cfg = &run.cfg;
+    cfg.add_shortcut(id, SYM_F1)?;
+    cfg.set_shortcuts_inhibit_escape(id, SYM_Escape)?;
+
+    let manager = client.registry.get_shortcuts_inhibit_manager().await?;
+    let inhibitor = manager.inhibit_shortcuts(&win.surface, &seat.seat)?;
+    client.sync().await;
+    tassert!(inhibitor.active.get());
+
+    let keys = &seat.kb.key.expect()?;
+    let (run, kb, client) = (&run, &ds.kb, &client);
+    let press = move |key: u32| {
+        drop(kb.press(key));
+        async move {
+            run.sync().await;
+            client.sync().await;
+            let mut res = vec![];
+            while let Ok((_, ev)) = keys.next() {
+                res.push((ev.key, ev.state));
+            }
+            res
+        }
+    };
+
+    // Shortcuts are sent to the window while they are inhibited.
+    tassert_eq!(press(KEY_F1).await, [(KEY_F1, 1), (KEY_F1, 0)]);
+    tassert!(!cfg.invoked_shortcuts.contains(&(id, SYM_F1.into())));
+
+    // The escape key is handled by the compositor even while shortcuts are inhibited.
+    tassert_eq!(press(KEY_ESC).await, []);
+    tassert!(!inhibitor.active.get());
+
+    let received = press(KEY_F1).await;
+    tassert!(!received.contains(&(KEY_F1, 1)));
+    tassert!(cfg.invoked_shortcuts.contains(&(id, SYM_F1.into())));
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::{ContainerSplit, Node},
+    },
+    jay_config::Axis,
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let _ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+
+    let cfg = &run.cfg;
+    let container = win.tl.container_parent()?;
+    let id = u64::from(container.node_id().0);
+    tassert_eq!(container.split.get(), ContainerSplit::Horizontal);
+
+    // Ids that do not fit into a node id must not be truncated to the id of another node.
+    let truncated = (1 << 32) | id;
+    cfg.set_container_split(truncated, Axis::Vertical)?;
+    tassert_eq!(container.split.get(), ContainerSplit::Horizontal);
+
+    cfg.set_container_split(id, Axis::Vertical)?;
+    tassert_eq!(container.split.get(), ContainerSplit::Vertical);
+
+    Ok(())
+}
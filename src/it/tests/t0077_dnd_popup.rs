@@ -0,0 +1,86 @@
+use {
+    crate::{
+        fixed::Fixed,
+        ifs::wl_seat::BTN_LEFT,
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::Node,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+
+    // A popup near the top-left corner of the window.
+    let popup = client.create_surface_ext().await?;
+    let xdg = client.xdg.create_xdg_surface(popup.surface.id).await?;
+    let positioner = client.xdg.create_positioner()?;
+    positioner.set_size(50, 50)?;
+    positioner.set_anchor_rect(100, 100, 1, 1)?;
+    let _popup = xdg.create_popup(&win.xdg, &positioner)?;
+    popup.surface.commit()?;
+    client.sync().await;
+    xdg.ack_configure(xdg.last_serial.get())?;
+    popup.map(50, 50).await?;
+    client.sync().await;
+    let popup_pos = popup.surface.server.buffer_abs_pos.get();
+    tassert_eq!((popup_pos.width(), popup_pos.height()), (50, 50));
+
+    let seat = client.get_default_seat().await?;
+    let button = seat.pointer.button.expect()?;
+
+    let (x, y) = win.tl.server.node_absolute_position().center();
+    ds.move_to(x, y);
+    let click = ds.mouse.click(BTN_LEFT);
+
+    client.sync().await;
+    let dev = client
+        .data_device_manager
+        .get_data_device(&seat.seat)?;
+    let src = client.data_device_manager.create_data_source()?;
+    src.set_actions(1)?;
+
+    let enter = dev.enter.expect()?;
+    let leave = dev.leave.expect()?;
+    let motion = dev.motion.expect()?;
+
+    dev.start_drag(&src, &win.surface, None, button.next()?.serial)?;
+    client.sync().await;
+    tassert_eq!(enter.next()?.surface, win.surface.id);
+
+    // Moving onto the popup leaves the window and enters the popup in popup-local
+    // coordinates.
+    let (x, y) = popup_pos.center();
+    ds.move_to(x, y);
+    client.sync().await;
+    tassert!(leave.next().is_ok());
+    let ev = enter.next()?;
+    tassert_eq!(ev.surface, popup.surface.id);
+    tassert_eq!(ev.x, Fixed::from_int(x - popup_pos.x1()));
+    tassert_eq!(ev.y, Fixed::from_int(y - popup_pos.y1()));
+
+    let (x, y) = (x + 5, y + 5);
+    ds.move_to(x, y);
+    client.sync().await;
+    let ev = motion.last()?;
+    tassert_eq!(ev.x, Fixed::from_int(x - popup_pos.x1()));
+    tassert_eq!(ev.y, Fixed::from_int(y - popup_pos.y1()));
+
+    // Moving back onto the window leaves the popup.
+    let (x, y) = win.tl.server.node_absolute_position().center();
+    ds.move_to(x, y);
+    client.sync().await;
+    tassert!(leave.next().is_ok());
+    tassert_eq!(enter.next()?.surface, win.surface.id);
+
+    drop(click);
+    client.sync().await;
+
+    Ok(())
+}
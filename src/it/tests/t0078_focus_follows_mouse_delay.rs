@@ -0,0 +1,56 @@
+use {
+    crate::{
+        ifs::wl_seat::BTN_LEFT,
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::Node,
+    },
+    jay_config::{
+        input::{PointerContext, PointerInput},
+        keyboard::mods::Modifiers,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let seat = ds.seat.id();
+    run.cfg.set_focus_follows_mouse_delay(seat, 100)?;
+    let left = PointerInput::Button(BTN_LEFT);
+    let none = Modifiers::NONE;
+    run.cfg
+        .add_pointer_binding(seat, none, left, PointerContext::Window)?;
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+
+    let focus = || ds.seat.keyboard_node().node_id();
+    tassert_eq!(focus(), win2.surface.server.node_id());
+
+    // The focus follows the mouse once the delay has passed.
+    let (x, y) = win1.tl.server.node_absolute_position().center();
+    ds.move_to(x, y);
+    run.sync().await;
+    tassert_eq!(focus(), win2.surface.server.node_id());
+    run.state.wheel.advance(100);
+    run.sync().await;
+    tassert_eq!(focus(), win1.surface.server.node_id());
+
+    // The focus does not change while a button is held. The pointer binding keeps
+    // the click itself from changing the focus.
+    let (x, y) = win2.tl.server.node_absolute_position().center();
+    ds.move_to(x, y);
+    run.sync().await;
+    let click = ds.mouse.click(BTN_LEFT);
+    run.sync().await;
+    run.state.wheel.advance(100);
+    run.sync().await;
+    tassert_eq!(focus(), win1.surface.server.node_id());
+    drop(click);
+
+    Ok(())
+}
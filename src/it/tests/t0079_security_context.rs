@@ -0,0 +1,104 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestErrorExt, TestResult},
+            test_ifs::{
+                test_gamma_control_manager::TestGammaControlManager, test_registry::TestRegistry,
+                test_screencopy_manager::TestScreencopyManager,
+                test_virtual_keyboard_manager::TestVirtualKeyboardManager,
+            },
+            test_object::TestObject,
+            test_transport::TestTransport,
+            testrun::TestRun,
+        },
+        utils::oserror::OsErrorExt,
+    },
+    std::rc::Rc,
+    uapi::c,
+};
+
+testcase!();
+
+const PRIVILEGED: [&str; 3] = [
+    "zwlr_gamma_control_manager_v1",
+    "zwlr_screencopy_manager_v1",
+    "zwp_virtual_keyboard_manager_v1",
+];
+
+/// Tests that clients connected through a security context can neither see nor bind
+/// privileged globals.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let client = run.create_client().await?;
+    let singletons = client.registry.get_singletons().await?;
+    for name in PRIVILEGED {
+        tassert!(has_global(&client.registry, name));
+    }
+
+    let listen_fd = uapi::socket(c::AF_UNIX, c::SOCK_STREAM | c::SOCK_CLOEXEC, 0)
+        .to_os_error()
+        .with_context(|| "Could not create a unix socket")?;
+    let mut addr: c::sockaddr_un = uapi::pod_zeroed();
+    addr.sun_family = c::AF_UNIX as _;
+    let path = format!("\0jay-test-security-context-{}", uapi::getpid());
+    let sun_path = uapi::as_bytes_mut(&mut addr.sun_path[..]);
+    sun_path[..path.len()].copy_from_slice(path.as_bytes());
+    uapi::bind(listen_fd.raw(), &addr)
+        .to_os_error()
+        .with_context(|| "Could not bind the unix socket")?;
+    uapi::listen(listen_fd.raw(), 4096)
+        .to_os_error()
+        .with_context(|| "Could not listen on the unix socket")?;
+    let (close_fd, _close_fd_write) = uapi::pipe2(c::O_CLOEXEC)
+        .to_os_error()
+        .with_context(|| "Could not create a pipe")?;
+
+    let manager = client.registry.get_security_context_manager().await?;
+    let context = manager.create_listener(&Rc::new(listen_fd), &Rc::new(close_fd))?;
+    context.set_app_id("sandboxed")?;
+    context.commit()?;
+    client.sync().await;
+
+    let tran = run.connect(&addr).await?;
+    let registry = tran.get_registry();
+    tran.sync().await;
+    tassert!(has_global(&registry, "xdg_wm_base"));
+    for name in PRIVILEGED {
+        tassert!(!has_global(&registry, name));
+    }
+    tassert!(run.errors.take().is_empty());
+
+    let gamma = singletons.zwlr_gamma_control_manager_v1;
+    bind_denied(&run, &addr, gamma, TestGammaControlManager::new).await?;
+    let screencopy = singletons.zwlr_screencopy_manager_v1;
+    bind_denied(&run, &addr, screencopy, TestScreencopyManager::new).await?;
+    let vkm = singletons.zwp_virtual_keyboard_manager_v1;
+    bind_denied(&run, &addr, vkm, TestVirtualKeyboardManager::new).await?;
+
+    Ok(())
+}
+
+fn has_global(registry: &TestRegistry, interface: &str) -> bool {
+    registry
+        .globals
+        .lock()
+        .values()
+        .any(|g| g.interface == interface)
+}
+
+async fn bind_denied<T, F>(
+    run: &Rc<TestRun>,
+    addr: &c::sockaddr_un,
+    name: u32,
+    new: F,
+) -> TestResult
+where
+    T: TestObject,
+    F: FnOnce(&Rc<TestTransport>) -> T,
+{
+    let tran = run.connect(addr).await?;
+    let registry = tran.get_registry();
+    registry.bind(&Rc::new(new(&tran)), name, 1)?;
+    tran.sync().await;
+    tassert_eq!(run.errors.take().len(), 1);
+    Ok(())
+}
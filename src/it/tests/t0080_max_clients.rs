@@ -0,0 +1,25 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Tests that connections beyond the client limit are rejected.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let connected = run.state.clients.clients.borrow().len();
+    run.cfg.set_max_clients(connected + 1)?;
+
+    let client = run.create_client().await?;
+    tassert!(run.errors.take().is_empty());
+
+    let tran = run.connect(&run.server_addr).await?;
+    tran.sync().await;
+    tassert_eq!(run.errors.take().len(), 1);
+    tassert_eq!(run.state.clients.clients.borrow().len(), connected + 1);
+
+    client.sync().await;
+    tassert!(run.errors.take().is_empty());
+
+    Ok(())
+}
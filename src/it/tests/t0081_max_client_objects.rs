@@ -0,0 +1,26 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Tests that clients that exceed the object limit are disconnected.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    run.cfg.set_max_client_objects(4)?;
+
+    // The display, two registries, and the sync callback.
+    let tran = run.connect(&run.server_addr).await?;
+    let _registry1 = tran.get_registry();
+    let _registry2 = tran.get_registry();
+    tran.sync().await;
+    tassert!(run.errors.take().is_empty());
+
+    // The display and four registries.
+    let _registry3 = tran.get_registry();
+    let _registry4 = tran.get_registry();
+    tran.sync().await;
+    tassert_eq!(run.errors.take().len(), 1);
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use {
+    crate::{
+        gfx_api::{create_output_render_pass, GfxApiOpt},
+        it::{test_alloc_counter::allocations, test_error::TestResult, testrun::TestRun},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Tests that rendering a static scene does not allocate once the buffers have been
+/// reused for a frame.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+    client.sync().await;
+
+    let mode = ds.output.global.mode.get();
+    let render = |ops: Vec<GfxApiOpt>| {
+        let size = (mode.width, mode.height);
+        create_output_render_pass(ops, size, &ds.output, &run.state, false, None).ops
+    };
+
+    let ops = render(vec![]);
+    let ops = render(ops);
+    let before = allocations();
+    let ops = render(ops);
+    let after = allocations();
+    tassert!(!ops.is_empty());
+    tassert_eq!(after - before, 0);
+
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use {
+    crate::{
+        backend::{
+            BackendEvent, Connector, ConnectorEvent, ConnectorId, ConnectorKernelId, DrmDeviceId,
+            Mode,
+        },
+        ifs::wl_output::OutputId,
+        it::{test_backend::TestConnector, test_error::TestResult, testrun::TestRun},
+        video::drm::ConnectorType,
+    },
+    std::{cell::RefCell, rc::Rc},
+};
+
+testcase!();
+
+const SIZE: u32 = 4;
+
+struct GammaConnector {
+    connector: TestConnector,
+    lut: RefCell<Option<Vec<[u16; 3]>>>,
+}
+
+impl Connector for GammaConnector {
+    fn id(&self) -> ConnectorId {
+        self.connector.id()
+    }
+
+    fn kernel_id(&self) -> ConnectorKernelId {
+        self.connector.kernel_id()
+    }
+
+    fn event(&self) -> Option<ConnectorEvent> {
+        self.connector.event()
+    }
+
+    fn on_change(&self, cb: Rc<dyn Fn()>) {
+        self.connector.on_change(cb)
+    }
+
+    fn damage(&self) {
+        self.connector.damage()
+    }
+
+    fn drm_dev(&self) -> Option<DrmDeviceId> {
+        self.connector.drm_dev()
+    }
+
+    fn set_mode(&self, mode: Mode) {
+        self.connector.set_mode(mode)
+    }
+
+    fn gamma_lut_size(&self) -> Option<u32> {
+        Some(SIZE)
+    }
+
+    fn set_gamma_lut(&self, lut: Option<&[[u16; 3]]>) -> bool {
+        *self.lut.borrow_mut() = lut.map(|l| l.to_vec());
+        true
+    }
+}
+
+/// Tests that gamma ramps are forwarded to the connector and reset when the gamma
+/// control fails or is destroyed.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let _ds = run.create_default_setup().await?;
+
+    let connector = Rc::new(GammaConnector {
+        connector: TestConnector {
+            id: run.state.connector_ids.next(),
+            kernel_id: ConnectorKernelId {
+                ty: ConnectorType::VGA,
+                idx: 2,
+            },
+            events: Default::default(),
+            feedback: Default::default(),
+            content_type: Default::default(),
+            drm_dev: None,
+        },
+        lut: Default::default(),
+    });
+    let mut monitor_info = run.backend.default_monitor_info.clone();
+    monitor_info.output_id = Rc::new(OutputId {
+        connector: None,
+        manufacturer: "jay".to_string(),
+        model: "jay gamma connector".to_string(),
+        serial_number: "".to_string(),
+    });
+    run.backend
+        .state
+        .backend_events
+        .push(BackendEvent::NewConnector(connector.clone()));
+    connector
+        .connector
+        .events
+        .send_event(ConnectorEvent::Connected(monitor_info));
+    run.sync().await;
+    let Some(output) = run.state.root.outputs.get(&connector.id()) else {
+        bail!("the gamma output was not created");
+    };
+
+    let client = run.create_client().await?;
+    let wl_output = client.get_output(&output).await?;
+    let manager = client.registry.get_gamma_control_manager().await?;
+
+    let ramps: Vec<u16> = (0..3 * SIZE as u16).map(|i| i * 1000).collect();
+    let size = SIZE as usize;
+    let expected: Vec<_> = (0..size)
+        .map(|i| [ramps[i], ramps[size + i], ramps[2 * size + i]])
+        .collect();
+
+    let control = manager.get_gamma_control(&wl_output)?;
+    client.sync().await;
+    tassert_eq!(control.gamma_size.get(), Some(SIZE));
+    control.set_gamma(&ramps)?;
+    client.sync().await;
+    tassert!(!control.failed.get());
+    tassert_eq!(connector.lut.borrow().as_deref(), Some(&expected[..]));
+
+    control.set_gamma(&ramps[..size])?;
+    client.sync().await;
+    tassert!(control.failed.get());
+    tassert!(connector.lut.borrow().is_none());
+    tassert!(run.errors.take().is_empty());
+    control.destroy()?;
+
+    let control = manager.get_gamma_control(&wl_output)?;
+    client.sync().await;
+    tassert!(!control.failed.get());
+    control.set_gamma(&ramps)?;
+    client.sync().await;
+    tassert_eq!(connector.lut.borrow().as_deref(), Some(&expected[..]));
+    control.destroy()?;
+    client.sync().await;
+    tassert!(connector.lut.borrow().is_none());
+
+    Ok(())
+}
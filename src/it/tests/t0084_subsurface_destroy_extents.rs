@@ -0,0 +1,47 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        theme::Color,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that destroying a subsurface shrinks the extents of its parent
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let _ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+
+    let parent = client.create_window().await?;
+    parent.map2().await?;
+    let initial = parent.surface.server.extents.get();
+
+    let child = client.comp.create_surface().await?;
+    let child_viewport = client.viewporter.get_viewport(&child)?;
+    let sub = client
+        .sub
+        .get_subsurface(child.id, parent.surface.id)
+        .await?;
+    sub.set_position(-50, -50)?;
+
+    let buffer = client
+        .spbm
+        .create_buffer(Color::from_rgba_straight(255, 255, 255, 255))?;
+    child.attach(buffer.id)?;
+    child_viewport.set_source(0, 0, 1, 1)?;
+    child_viewport.set_destination(100, 100)?;
+    child.commit()?;
+    parent.map().await?;
+
+    let extents = parent.surface.server.extents.get();
+    tassert_eq!((extents.x1(), extents.y1()), (-50, -50));
+    tassert!(extents.contains_rect(&initial));
+
+    sub.destroy()?;
+    client.sync().await;
+    tassert_eq!(parent.surface.server.extents.get(), initial);
+
+    Ok(())
+}
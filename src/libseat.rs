@@ -0,0 +1,233 @@
+#![allow(non_camel_case_types)]
+
+use {
+    crate::utils::{errorfmt::ErrorFmt, oserror::OsError, ptr_ext::PtrExt, syncqueue::SyncQueue},
+    libloading::Library,
+    once_cell::sync::Lazy,
+    std::{ffi::CStr, rc::Rc},
+    thiserror::Error,
+    uapi::{c, Errno, OwnedFd},
+};
+
+#[repr(transparent)]
+struct libseat(u8);
+
+#[repr(C)]
+struct libseat_seat_listener {
+    enable_seat: unsafe extern "C" fn(seat: *mut libseat, userdata: *mut c::c_void),
+    disable_seat: unsafe extern "C" fn(seat: *mut libseat, userdata: *mut c::c_void),
+}
+
+struct LibSeatFunctions {
+    _lib: Library,
+    libseat_open_seat: unsafe extern "C" fn(
+        listener: *const libseat_seat_listener,
+        userdata: *mut c::c_void,
+    ) -> *mut libseat,
+    libseat_disable_seat: unsafe extern "C" fn(seat: *mut libseat) -> c::c_int,
+    libseat_close_seat: unsafe extern "C" fn(seat: *mut libseat) -> c::c_int,
+    libseat_open_device: unsafe extern "C" fn(
+        seat: *mut libseat,
+        path: *const c::c_char,
+        fd: *mut c::c_int,
+    ) -> c::c_int,
+    libseat_close_device: unsafe extern "C" fn(seat: *mut libseat, device_id: c::c_int) -> c::c_int,
+    libseat_seat_name: unsafe extern "C" fn(seat: *mut libseat) -> *const c::c_char,
+    libseat_switch_session: unsafe extern "C" fn(seat: *mut libseat, session: c::c_int) -> c::c_int,
+    libseat_get_fd: unsafe extern "C" fn(seat: *mut libseat) -> c::c_int,
+    libseat_dispatch: unsafe extern "C" fn(seat: *mut libseat, timeout: c::c_int) -> c::c_int,
+}
+
+macro_rules! load {
+    ($lib:ident, $($name:ident,)*) => {{
+        $(
+            let $name = match $lib.get(stringify!($name).as_bytes()) {
+                Ok(s) => *s,
+                Err(e) => {
+                    log::warn!("Could not load {} from libseat: {}", stringify!($name), ErrorFmt(e));
+                    return None;
+                }
+            };
+        )*
+        LibSeatFunctions {
+            _lib: $lib,
+            $(
+                $name,
+            )*
+        }
+    }};
+}
+
+static LIBSEAT: Lazy<Option<LibSeatFunctions>> = Lazy::new(|| unsafe {
+    let lib = match Library::new("libseat.so.1") {
+        Ok(l) => l,
+        Err(e) => {
+            log::info!("Could not load libseat: {}", ErrorFmt(e));
+            return None;
+        }
+    };
+    Some(load!(
+        lib,
+        libseat_open_seat,
+        libseat_disable_seat,
+        libseat_close_seat,
+        libseat_open_device,
+        libseat_close_device,
+        libseat_seat_name,
+        libseat_switch_session,
+        libseat_get_fd,
+        libseat_dispatch,
+    ))
+});
+
+static LISTENER: libseat_seat_listener = libseat_seat_listener {
+    enable_seat,
+    disable_seat,
+};
+
+unsafe extern "C" fn enable_seat(_seat: *mut libseat, userdata: *mut c::c_void) {
+    unsafe {
+        let events = (userdata as *const SyncQueue<LibSeatEvent>).deref();
+        events.push(LibSeatEvent::Enable);
+    }
+}
+
+unsafe extern "C" fn disable_seat(_seat: *mut libseat, userdata: *mut c::c_void) {
+    unsafe {
+        let events = (userdata as *const SyncQueue<LibSeatEvent>).deref();
+        events.push(LibSeatEvent::Disable);
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum LibSeatError {
+    #[error("libseat is not available")]
+    NotAvailable,
+    #[error("Could not open the seat")]
+    OpenSeat(#[source] OsError),
+    #[error("Could not disable the seat")]
+    DisableSeat(#[source] OsError),
+    #[error("Could not open the device")]
+    OpenDevice(#[source] OsError),
+    #[error("Could not close the device")]
+    CloseDevice(#[source] OsError),
+    #[error("Could not switch the session")]
+    SwitchSession(#[source] OsError),
+    #[error("Could not retrieve the libseat file descriptor")]
+    GetFd(#[source] OsError),
+    #[error("Could not dispatch libseat events")]
+    Dispatch(#[source] OsError),
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum LibSeatEvent {
+    Enable,
+    Disable,
+}
+
+pub struct LibSeat {
+    funcs: &'static LibSeatFunctions,
+    seat: *mut libseat,
+    events: Box<SyncQueue<LibSeatEvent>>,
+}
+
+impl LibSeat {
+    pub fn is_available() -> bool {
+        LIBSEAT.is_some()
+    }
+
+    pub fn open() -> Result<Self, LibSeatError> {
+        let Some(funcs) = &*LIBSEAT else {
+            return Err(LibSeatError::NotAvailable);
+        };
+        let events = Box::<SyncQueue<LibSeatEvent>>::default();
+        let seat = unsafe { (funcs.libseat_open_seat)(&LISTENER, &*events as *const _ as _) };
+        if seat.is_null() {
+            return Err(LibSeatError::OpenSeat(Errno::default().into()));
+        }
+        Ok(Self {
+            funcs,
+            seat,
+            events,
+        })
+    }
+
+    pub fn name(&self) -> String {
+        unsafe {
+            let name = (self.funcs.libseat_seat_name)(self.seat);
+            if name.is_null() {
+                return String::new();
+            }
+            CStr::from_ptr(name).to_string_lossy().into_owned()
+        }
+    }
+
+    pub fn fd(&self) -> Result<Rc<OwnedFd>, LibSeatError> {
+        let fd = unsafe { (self.funcs.libseat_get_fd)(self.seat) };
+        if fd < 0 {
+            return Err(LibSeatError::GetFd(Errno::default().into()));
+        }
+        match uapi::fcntl_dupfd_cloexec(fd, 0) {
+            Ok(fd) => Ok(Rc::new(fd)),
+            Err(e) => Err(LibSeatError::GetFd(e.into())),
+        }
+    }
+
+    /// Reads pending messages from the seat without blocking.
+    ///
+    /// Enable and disable requests are queued and can be retrieved with `event`.
+    pub fn dispatch(&self) -> Result<(), LibSeatError> {
+        let res = unsafe { (self.funcs.libseat_dispatch)(self.seat, 0) };
+        if res < 0 {
+            return Err(LibSeatError::Dispatch(Errno::default().into()));
+        }
+        Ok(())
+    }
+
+    pub fn event(&self) -> Option<LibSeatEvent> {
+        self.events.pop()
+    }
+
+    /// Acknowledges a disable request. All devices must have been paused before.
+    pub fn disable(&self) -> Result<(), LibSeatError> {
+        let res = unsafe { (self.funcs.libseat_disable_seat)(self.seat) };
+        if res < 0 {
+            return Err(LibSeatError::DisableSeat(Errno::default().into()));
+        }
+        Ok(())
+    }
+
+    /// Opens a device and returns its libseat id and file descriptor.
+    pub fn open_device(&self, path: &CStr) -> Result<(c::c_int, Rc<OwnedFd>), LibSeatError> {
+        let mut fd = -1;
+        let id = unsafe { (self.funcs.libseat_open_device)(self.seat, path.as_ptr(), &mut fd) };
+        if id < 0 {
+            return Err(LibSeatError::OpenDevice(Errno::default().into()));
+        }
+        Ok((id, Rc::new(OwnedFd::new(fd))))
+    }
+
+    pub fn close_device(&self, id: c::c_int) -> Result<(), LibSeatError> {
+        let res = unsafe { (self.funcs.libseat_close_device)(self.seat, id) };
+        if res < 0 {
+            return Err(LibSeatError::CloseDevice(Errno::default().into()));
+        }
+        Ok(())
+    }
+
+    pub fn switch_session(&self, session: c::c_int) -> Result<(), LibSeatError> {
+        let res = unsafe { (self.funcs.libseat_switch_session)(self.seat, session) };
+        if res < 0 {
+            return Err(LibSeatError::SwitchSession(Errno::default().into()));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for LibSeat {
+    fn drop(&mut self) {
+        unsafe {
+            (self.funcs.libseat_close_seat)(self.seat);
+        }
+    }
+}
@@ -204,6 +204,7 @@ impl Log for LogWrapper {
     }
 
     fn flush(&self) {
-        // nothing
+        // Log messages are written without buffering. Pipes cannot be synced.
+        let _ = uapi::fdatasync(self.logger.file_fd.load(Relaxed));
     }
 }
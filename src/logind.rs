@@ -1,6 +1,6 @@
 use {
     crate::{
-        dbus::{DbusError, DbusSocket, SignalHandler, FALSE},
+        dbus::{DbusError, DbusSocket, SignalHandler, SignalMatch, FALSE},
         utils::errorfmt::ErrorFmt,
         wire_dbus::{
             org,
@@ -114,14 +114,27 @@ impl Session {
         );
     }
 
+    pub fn release_device(&self, dev: c::dev_t) {
+        let major = uapi::major(dev) as _;
+        let minor = uapi::minor(dev) as _;
+        self.socket.call_noreply(
+            LOGIND_NAME,
+            &self.session_path,
+            org::freedesktop::login1::session::ReleaseDevice { major, minor },
+        );
+    }
+
     pub fn on_pause<F>(&self, f: F) -> Result<SignalHandler, DbusError>
     where
         F: for<'b> Fn(PauseDevice<'b>) + 'static,
     {
         self.socket
             .handle_signal::<org::freedesktop::login1::session::PauseDevice, _>(
-                Some(LOGIND_NAME),
-                Some(&self.session_path),
+                SignalMatch {
+                    sender: Some(LOGIND_NAME),
+                    path: Some(&self.session_path),
+                    ..Default::default()
+                },
                 f,
             )
     }
@@ -132,8 +145,11 @@ impl Session {
     {
         self.socket
             .handle_signal::<org::freedesktop::login1::session::ResumeDevice, _>(
-                Some(LOGIND_NAME),
-                Some(&self.session_path),
+                SignalMatch {
+                    sender: Some(LOGIND_NAME),
+                    path: Some(&self.session_path),
+                    ..Default::default()
+                },
                 f,
             )
     }
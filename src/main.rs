@@ -47,6 +47,7 @@ mod leaks;
 mod tracy;
 mod acceptor;
 mod allocator;
+mod animation;
 mod async_engine;
 mod backend;
 mod backends;
@@ -75,6 +76,7 @@ mod io_uring;
 #[cfg(feature = "it")]
 mod it;
 mod libinput;
+mod libseat;
 mod logger;
 mod logind;
 mod object;
@@ -97,10 +99,12 @@ mod tools;
 mod tree;
 mod udev;
 mod udmabuf;
+mod upower;
 mod user_session;
 mod utils;
 mod version;
 mod video;
+mod wallpaper;
 mod wheel;
 mod wire;
 mod wire_dbus;
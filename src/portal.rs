@@ -11,8 +11,7 @@ use {
         async_engine::AsyncEngine,
         cli::GlobalArgs,
         dbus::{
-            Dbus, DbusSocket, BUS_DEST, BUS_PATH, DBUS_NAME_FLAG_DO_NOT_QUEUE,
-            DBUS_REQUEST_NAME_REPLY_PRIMARY_OWNER,
+            Dbus, DbusSocket, DBUS_NAME_FLAG_DO_NOT_QUEUE, DBUS_REQUEST_NAME_REPLY_PRIMARY_OWNER,
         },
         forker::ForkerError,
         io_uring::IoUring,
@@ -39,7 +38,6 @@ use {
         version::VERSION,
         video::dmabuf::DmaBufIds,
         wheel::Wheel,
-        wire_dbus::org,
     },
     log::Level,
     std::{
@@ -235,17 +233,10 @@ async fn init_dbus_session(dbus: &Dbus, logger: Arc<Logger>, freestanding: bool)
         }
     };
     let rv = session
-        .call_async(
-            BUS_DEST,
-            BUS_PATH,
-            org::freedesktop::dbus::RequestName {
-                name: UNIQUE_NAME.into(),
-                flags: DBUS_NAME_FLAG_DO_NOT_QUEUE,
-            },
-        )
+        .request_name(UNIQUE_NAME, DBUS_NAME_FLAG_DO_NOT_QUEUE)
         .await;
     match rv {
-        Ok(r) if r.get().rv == DBUS_REQUEST_NAME_REPLY_PRIMARY_OWNER => {
+        Ok(DBUS_REQUEST_NAME_REPLY_PRIMARY_OWNER) => {
             log::info!("Acquired unique name {}", UNIQUE_NAME);
             let log_file = logger.redirect("portal");
             log::info!("version = {VERSION}");
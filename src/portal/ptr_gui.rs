@@ -216,6 +216,7 @@ impl GuiElement for Button {
             r.render_texture(
                 &tex,
                 None,
+                false,
                 tx.round() as _,
                 ty.round() as _,
                 None,
@@ -316,6 +317,7 @@ impl GuiElement for Label {
             r.render_texture(
                 &tex,
                 None,
+                false,
                 tx.round() as _,
                 ty.round() as _,
                 None,
@@ -13,7 +13,7 @@ use {
         state::State,
         theme::Color,
         tree::{
-            ContainerNode, DisplayNode, FloatNode, OutputNode, PlaceholderNode, ToplevelData,
+            ContainerNode, DisplayNode, FloatNode, Node, OutputNode, PlaceholderNode, ToplevelData,
             ToplevelNodeBase, WorkspaceNode,
         },
     },
@@ -27,6 +27,7 @@ pub struct Renderer<'a> {
     pub state: &'a State,
     pub logical_extents: Rect,
     pub pixel_extents: Rect,
+    pub alpha: Option<f32>,
 }
 
 impl Renderer<'_> {
@@ -80,6 +81,11 @@ impl Renderer<'_> {
         if let Some(fs) = fullscreen {
             fs.tl_as_node().node_render(self, x, y, None);
         } else {
+            if let Some(wallpaper) = output.global.persistent.wallpaper.get() {
+                let rect = Rect::new_sized(x, y, opos.width(), opos.height()).unwrap();
+                let rect = self.base.scale_rect(rect);
+                wallpaper.render(self, rect);
+            }
             render_layer!(output.layers[0]);
             render_layer!(output.layers[1]);
             let non_exclusive_rect = output.non_exclusive_rect_rel.get();
@@ -119,6 +125,7 @@ impl Renderer<'_> {
                     self.base.render_texture(
                         &title.tex,
                         None,
+                        false,
                         x,
                         y,
                         None,
@@ -136,6 +143,7 @@ impl Renderer<'_> {
                         self.base.render_texture(
                             &texture,
                             None,
+                            false,
                             x,
                             y,
                             None,
@@ -158,9 +166,10 @@ impl Renderer<'_> {
                 }
             }
             if let Some(ws) = output.workspace.get() {
-                self.render_workspace(&ws, x, y + th + 1);
+                self.render_output_workspace(output, &ws, x, y + th + 1);
             }
         }
+        self.render_closing_toplevels(opos, x, y);
         macro_rules! render_stacked {
             ($stack:expr) => {
                 for stacked in $stack.iter() {
@@ -188,8 +197,87 @@ impl Renderer<'_> {
         }
     }
 
+    fn render_output_workspace(
+        &mut self,
+        output: &OutputNode,
+        workspace: &WorkspaceNode,
+        x: i32,
+        y: i32,
+    ) {
+        let progress = match output.workspace_slide.get() {
+            None => None,
+            Some(slide) => match slide.animation.progress(self.state.now_nsec()) {
+                Some(progress) => Some((slide, progress)),
+                None => {
+                    output.workspace_slide.take();
+                    None
+                }
+            },
+        };
+        let Some((slide, progress)) = progress else {
+            self.render_workspace(workspace, x, y);
+            return;
+        };
+        let pos = output.global.pos.get();
+        self.state.damage(pos);
+        let width = pos.width();
+        let mut dx = (width as f64 * (1.0 - progress)).round() as i32;
+        let mut old_dx = dx - width;
+        if !slide.forward {
+            dx = -dx;
+            old_dx = -old_dx;
+        }
+        if let Some(old) = &slide.old {
+            self.render_workspace(old, x + old_dx, y);
+        }
+        self.render_workspace(workspace, x + dx, y);
+    }
+
+    fn render_closing_toplevels(&mut self, output: Rect, x: i32, y: i32) {
+        let state = self.state;
+        let now = state.now_nsec();
+        let mut closing = state.animations.closing.borrow_mut();
+        closing.retain(|c| c.animation.progress(now).is_some());
+        for c in closing.iter() {
+            if !c.rect.intersects(&output) {
+                continue;
+            }
+            let Some(progress) = c.animation.progress(now) else {
+                continue;
+            };
+            state.damage(c.rect.intersect(c.bounds));
+            let (bx, by) = output.translate(c.bounds.x1(), c.bounds.y1());
+            let bounds = c.bounds.at_point(x + bx, y + by);
+            let bounds = self.base.scale_rect(bounds);
+            let (x1, y1) = output.translate(c.rect.x1(), c.rect.y1());
+            let (x1, y1) = (x + x1, y + y1);
+            let (x2, y2) = (x1 + c.rect.width(), y1 + c.rect.height());
+            let (x1, y1) = self.base.scale_point(x1, y1);
+            let (x2, y2) = self.base.scale_point(x2, y2);
+            self.base.ops.push(GfxApiOpt::Sync);
+            self.base.render_texture(
+                &c.snapshot.texture,
+                Some(1.0 - progress as f32),
+                false,
+                x1,
+                y1,
+                Some(c.tpoints),
+                Some((x2 - x1, y2 - y1)),
+                self.base.scale,
+                Some(&bounds),
+                None,
+                AcquireSync::None,
+                ReleaseSync::None,
+            );
+        }
+    }
+
     pub fn render_workspace(&mut self, workspace: &WorkspaceNode, x: i32, y: i32) {
         if let Some(node) = workspace.container.get() {
+            let pos = workspace.position.get();
+            let cpos = node.node_absolute_position();
+            let x = x + cpos.x1() - pos.x1();
+            let y = y + cpos.y1() - pos.y1();
             self.render_container(&node, x, y)
         }
     }
@@ -214,6 +302,7 @@ impl Renderer<'_> {
                 self.base.render_texture(
                     &texture,
                     None,
+                    false,
                     x,
                     y,
                     None,
@@ -242,6 +331,9 @@ impl Renderer<'_> {
             self.base.fill_boxes2(&rd.underline_rects, &c, x, y);
             let c = self.state.theme.colors.border.get();
             self.base.fill_boxes2(&rd.border_rects, &c, x, y);
+            let c = self.state.theme.colors.focused_border.get();
+            self.base
+                .fill_boxes2(&rd.active_border_rects, &c, x, y);
             if let Some(lar) = &rd.last_active_rect {
                 let c = self
                     .state
@@ -257,6 +349,7 @@ impl Renderer<'_> {
                     self.base.render_texture(
                         &title.tex,
                         None,
+                        false,
                         x,
                         y,
                         None,
@@ -295,15 +388,30 @@ impl Renderer<'_> {
     }
 
     pub fn render_xwindow(&mut self, tl: &Xwindow, x: i32, y: i32, bounds: Option<&Rect>) {
+        self.alpha = self.toplevel_alpha(tl.tl_data());
         self.render_surface(&tl.x.surface, x, y, bounds);
+        self.alpha = None;
         self.render_tl_aux(tl.tl_data(), bounds, true);
     }
 
     pub fn render_xdg_toplevel(&mut self, tl: &XdgToplevel, x: i32, y: i32, bounds: Option<&Rect>) {
+        self.alpha = self.toplevel_alpha(tl.tl_data());
         self.render_xdg_surface(&tl.xdg, x, y, bounds);
+        self.alpha = None;
         self.render_tl_aux(tl.tl_data(), bounds, true);
     }
 
+    fn toplevel_alpha(&self, tl_data: &ToplevelData) -> Option<f32> {
+        let alpha = tl_data.render_alpha();
+        let animation = tl_data.open_animation.get();
+        let Some(progress) = animation.and_then(|a| a.progress(self.state.now_nsec())) else {
+            tl_data.open_animation.take();
+            return alpha;
+        };
+        self.state.damage(tl_data.pos.get());
+        Some(alpha.unwrap_or(1.0) * progress as f32)
+    }
+
     pub fn render_xdg_surface(
         &mut self,
         xdg: &XdgSurface,
@@ -324,6 +432,14 @@ impl Renderer<'_> {
         bounds: Option<&Rect>,
         render_highlight: bool,
     ) {
+        if tl_data.unresponsive.get() {
+            if let Some(bounds) = bounds {
+                let color = Color::from_rgba_straight(0, 0, 0, 0x7f);
+                self.base.ops.push(GfxApiOpt::Sync);
+                self.base
+                    .fill_scaled_boxes(slice::from_ref(bounds), &color);
+            }
+        }
         if render_highlight {
             self.render_tl_highlight(tl_data, bounds);
         }
@@ -380,7 +496,10 @@ impl Renderer<'_> {
         } else {
             size = self.base.scale_point(size.0, size.1);
         }
-        let alpha = surface.alpha();
+        let alpha = match (surface.alpha(), self.alpha) {
+            (Some(a), Some(b)) => Some(a * b),
+            (a, b) => a.or(b),
+        };
         if let Some(children) = children.deref() {
             macro_rules! render {
                 ($children:expr) => {
@@ -424,6 +543,7 @@ impl Renderer<'_> {
             self.base.render_texture(
                 &tex,
                 alpha,
+                surface.opaque.get(),
                 x,
                 y,
                 Some(tpoints),
@@ -463,7 +583,11 @@ impl Renderer<'_> {
         let theme = &self.state.theme;
         let th = theme.sizes.title_height.get();
         let bw = theme.sizes.border_width.get();
-        let bc = theme.colors.border.get();
+        let bc = if floating.active.get() {
+            theme.colors.focused_border.get()
+        } else {
+            theme.colors.border.get()
+        };
         let tc = if floating.active.get() {
             theme.colors.focused_title_background.get()
         } else if floating.attention_requested.get() {
@@ -490,6 +614,7 @@ impl Renderer<'_> {
                 self.base.render_texture(
                     &texture,
                     None,
+                    false,
                     x,
                     y,
                     None,
@@ -137,6 +137,7 @@ impl RendererBase<'_> {
         &mut self,
         texture: &Rc<dyn GfxTexture>,
         alpha: Option<f32>,
+        opaque: bool,
         x: i32,
         y: i32,
         tpoints: Option<SampleRect>,
@@ -185,6 +186,7 @@ impl RendererBase<'_> {
             source: texcoord,
             target,
             alpha,
+            opaque,
             buffer_resv,
             acquire_sync,
             release_sync,
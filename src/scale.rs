@@ -4,6 +4,11 @@ const BASE: u32 = 120;
 const BASE64: i64 = BASE as i64;
 const BASEF: f64 = BASE as f64;
 
+/// The smallest scale that can be assigned to an output.
+pub const MIN_OUTPUT_SCALE: f64 = 0.25;
+/// The largest scale that can be assigned to an output.
+pub const MAX_OUTPUT_SCALE: f64 = 4.0;
+
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[repr(transparent)]
 pub struct Scale(u32);
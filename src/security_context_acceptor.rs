@@ -1,5 +1,6 @@
 use {
     crate::{
+        acceptor::accept_client,
         async_engine::SpawnedFuture,
         client::ClientCaps,
         state::State,
@@ -10,7 +11,7 @@ use {
         fmt::{Display, Formatter},
         rc::Rc,
     },
-    uapi::{c, OwnedFd},
+    uapi::OwnedFd,
 };
 
 #[derive(Default)]
@@ -92,7 +93,7 @@ impl Acceptor {
     async fn accept(self: Rc<Self>) {
         let s = &self.state;
         loop {
-            let fd = match s.ring.accept(&self.listen_fd, c::SOCK_CLOEXEC).await {
+            let fd = match accept_client(s, &self.listen_fd).await {
                 Ok(fd) => fd,
                 Err(e) => {
                     log::error!("Could not accept a client: {}", ErrorFmt(e));
@@ -100,9 +101,11 @@ impl Acceptor {
                 }
             };
             let id = s.clients.id();
-            if let Err(e) = s.clients.spawn(id, s, fd, self.caps, self.caps) {
-                log::error!("Could not spawn a client: {}", ErrorFmt(e));
-                break;
+            match s.clients.spawn(id, s, fd, self.caps, self.caps) {
+                Ok(()) => {
+                    log::info!("Client {} connected through security acceptor {}", id, self)
+                }
+                Err(e) => log::warn!("Rejected a client: {}", ErrorFmt(e)),
             }
         }
         self.kill();
@@ -1,6 +1,7 @@
 use {
     crate::{
         acceptor::Acceptor,
+        animation::Animations,
         async_engine::{AsyncEngine, SpawnedFuture},
         backend::{
             Backend, BackendDrmDevice, BackendEvent, Connector, ConnectorId, ConnectorIds,
@@ -17,7 +18,7 @@ use {
         cursor::{Cursor, ServerCursors},
         cursor_user::{CursorUserGroup, CursorUserGroupId, CursorUserGroupIds, CursorUserIds},
         damage::DamageVisualizer,
-        dbus::Dbus,
+        dbus::{Dbus, ReconnectHandler},
         drm_feedback::{DrmFeedback, DrmFeedbackIds},
         ei::{
             ei_acceptor::EiAcceptor,
@@ -28,12 +29,13 @@ use {
         format::Format,
         gfx_api::{
             AcquireSync, BufferResv, GfxContext, GfxError, GfxFramebuffer, GfxTexture,
-            PendingShmTransfer, ReleaseSync, SampleRect, SyncFile, STAGING_DOWNLOAD,
+            PendingShmTransfer, ReleaseSync, RenderScratch, SampleRect, SyncFile, STAGING_DOWNLOAD,
         },
         gfx_apis::create_gfx_context,
         globals::{Globals, GlobalsError, RemovableWaylandGlobal, WaylandGlobal},
         ifs::{
             ext_foreign_toplevel_list_v1::ExtForeignToplevelListV1,
+            ext_idle_notification_v1::ExtIdleNotificationV1,
             ext_session_lock_v1::ExtSessionLockV1,
             ipc::{
                 data_control::DataControlDeviceIds, x_data_device::XIpcDeviceIds, DataOfferIds,
@@ -59,6 +61,7 @@ use {
             wp_drm_lease_connector_v1::WpDrmLeaseConnectorV1,
             wp_drm_lease_device_v1::WpDrmLeaseDeviceV1Global,
             wp_linux_drm_syncobj_manager_v1::WpLinuxDrmSyncobjManagerV1Global,
+            xdg_activation_token_v1::ActivationTokenOrigin,
             zwlr_screencopy_frame_v1::ZwlrScreencopyFrameV1,
             zwp_linux_dmabuf_feedback_v1::ZwpLinuxDmabufFeedbackV1,
             zwp_linux_dmabuf_v1::ZwpLinuxDmabufV1Global,
@@ -75,8 +78,9 @@ use {
         tree::{
             ContainerNode, ContainerSplit, Direction, DisplayNode, FloatNode, LatchListener, Node,
             NodeIds, NodeVisitorBase, OutputNode, PlaceholderNode, TearingMode, ToplevelNode,
-            ToplevelNodeBase, VrrMode, WorkspaceNode,
+            ToplevelNodeBase, VblankListener, VrrMode, WorkspaceNode,
         },
+        upower::UPower,
         utils::{
             activation_token::ActivationToken, asyncevent::AsyncEvent, bindings::Bindings,
             clonecell::CloneCell, copyhashmap::CopyHashMap, errorfmt::ErrorFmt,
@@ -94,8 +98,8 @@ use {
         },
         wheel::Wheel,
         wire::{
-            ExtForeignToplevelListV1Id, JayRenderCtxId, JaySeatEventsId, JayWorkspaceWatcherId,
-            ZwpLinuxDmabufFeedbackV1Id,
+            ExtForeignToplevelListV1Id, ExtIdleNotificationV1Id, JayRenderCtxId, JaySeatEventsId,
+            JayWorkspaceWatcherId, ZwpLinuxDmabufFeedbackV1Id,
         },
         xkbcommon::{KeyboardStateIds, XkbContext, XkbKeymap, XkbState},
         xwayland::{self, XWaylandEvent},
@@ -143,6 +147,9 @@ pub struct State {
     pub root: Rc<DisplayNode>,
     pub workspaces: CopyHashMap<String, Rc<WorkspaceNode>>,
     pub dummy_output: CloneCell<Option<Rc<OutputNode>>>,
+    /// A workspace that is not shown on any output and holds the windows in the
+    /// scratchpad.
+    pub scratch_workspace: CloneCell<Option<Rc<WorkspaceNode>>>,
     pub backend_events: AsyncQueue<BackendEvent>,
     pub input_device_handlers: RefCell<AHashMap<InputDeviceId, InputDeviceData>>,
     pub seat_queue: LinkedList<Rc<WlSeatGlobal>>,
@@ -150,6 +157,7 @@ pub struct State {
     pub none_surface_ext: Rc<NoneSurfaceExt>,
     pub tree_changed_sent: Cell<bool>,
     pub config: CloneCell<Option<Rc<ConfigProxy>>>,
+    pub config_reload: Rc<AsyncEvent>,
     pub theme: Theme,
     pub pending_container_layout: AsyncQueue<Rc<ContainerNode>>,
     pub pending_container_render_positions: AsyncQueue<Rc<ContainerNode>>,
@@ -189,7 +197,7 @@ pub struct State {
     pub workspace_watchers: CopyHashMap<(ClientId, JayWorkspaceWatcherId), Rc<JayWorkspaceWatcher>>,
     pub default_workspace_capture: Cell<bool>,
     pub default_gfx_api: Cell<GfxApi>,
-    pub activation_tokens: CopyHashMap<ActivationToken, ()>,
+    pub activation_tokens: CopyHashMap<ActivationToken, Option<ActivationTokenOrigin>>,
     pub toplevel_lists:
         CopyHashMap<(ClientId, ExtForeignToplevelListV1Id), Rc<ExtForeignToplevelListV1>>,
     pub dma_buf_ids: DmaBufIds,
@@ -199,9 +207,15 @@ pub struct State {
     pub double_click_interval_usec: Cell<u64>,
     pub double_click_distance: Cell<i32>,
     pub create_default_seat: Cell<bool>,
+    pub default_seat: CloneCell<Option<Rc<WlSeatGlobal>>>,
     pub subsurface_ids: SubsurfaceIds,
     pub wait_for_sync_obj: Rc<WaitForSyncObj>,
     pub explicit_sync_enabled: Cell<bool>,
+    pub client_send_timeout_msec: Cell<u64>,
+    pub client_ping_timeout_msec: Cell<u64>,
+    pub max_clients: Cell<usize>,
+    pub max_client_objects: Cell<usize>,
+    pub render_scratch: RenderScratch,
     pub keyboard_state_ids: KeyboardStateIds,
     pub security_context_acceptors: SecurityContextAcceptors,
     pub cursor_user_group_ids: CursorUserGroupIds,
@@ -224,10 +238,19 @@ pub struct State {
     pub cpu_worker: Rc<CpuWorker>,
     pub ui_drag_enabled: Cell<bool>,
     pub ui_drag_threshold_squared: Cell<i32>,
+    pub animations: Animations,
+    pub dim_unfocused: Cell<f32>,
+    pub upower: UPower,
     pub toplevels: CopyHashMap<ToplevelIdentifier, Weak<dyn ToplevelNode>>,
     pub const_40hz_latch: EventSource<dyn LatchListener>,
+    pub const_1hz_vblank: EventSource<dyn VblankListener>,
+    pub trace_protocol: Cell<bool>,
     pub tray_item_ids: TrayItemIds,
     pub data_control_device_ids: DataControlDeviceIds,
+    pub imported_environment: CopyHashMap<String, Rc<String>>,
+    pub session_bus_reconnect_handler: Cell<Option<ReconnectHandler>>,
+    pub quit_requested: Rc<AsyncEvent>,
+    pub quitting: Cell<bool>,
 }
 
 // impl Drop for State {
@@ -261,8 +284,11 @@ pub struct IdleState {
     pub change: AsyncEvent,
     pub timeout: Cell<Duration>,
     pub timeout_changed: Cell<bool>,
+    pub force: Cell<bool>,
     pub inhibitors: CopyHashMap<IdleInhibitorId, Rc<ZwpIdleInhibitorV1>>,
     pub inhibitors_changed: Cell<bool>,
+    pub inhibited_notifications:
+        CopyHashMap<(ClientId, ExtIdleNotificationV1Id), Rc<ExtIdleNotificationV1>>,
     pub backend_idle: Cell<bool>,
 }
 
@@ -273,6 +299,11 @@ impl IdleState {
         self.change.trigger();
     }
 
+    pub fn enter_idle(&self) {
+        self.force.set(true);
+        self.change.trigger();
+    }
+
     pub fn add_inhibitor(&self, inhibitor: &Rc<ZwpIdleInhibitorV1>) {
         self.inhibitors.set(inhibitor.inhibit_id, inhibitor.clone());
         self.inhibitors_changed.set(true);
@@ -283,6 +314,23 @@ impl IdleState {
         self.inhibitors.remove(&inhibitor.inhibit_id);
         self.inhibitors_changed.set(true);
         self.change.trigger();
+        if self.inhibitors.is_empty() {
+            for notification in self.inhibited_notifications.lock().drain_values() {
+                notification.resume.trigger();
+            }
+        }
+    }
+
+    pub fn add_inhibited_notification(&self, notification: &Rc<ExtIdleNotificationV1>) {
+        self.inhibited_notifications.set(
+            (notification.client.id, notification.id),
+            notification.clone(),
+        );
+    }
+
+    pub fn remove_inhibited_notification(&self, notification: &ExtIdleNotificationV1) {
+        self.inhibited_notifications
+            .remove(&(notification.client.id, notification.id));
     }
 }
 
@@ -329,6 +377,7 @@ pub struct DrmDevData {
     pub dev: Rc<dyn BackendDrmDevice>,
     pub handler: Cell<Option<SpawnedFuture<()>>>,
     pub connectors: CopyHashMap<ConnectorId, Rc<ConnectorData>>,
+    pub connector_removed: AsyncEvent,
     pub syspath: Option<String>,
     pub devnode: Option<String>,
     pub vendor: Option<String>,
@@ -806,6 +855,19 @@ impl State {
         }
     }
 
+    /// Damages all outputs that mirror the output containing the point.
+    ///
+    /// Mirrors render the cursor in software even if the source uses a hardware cursor.
+    pub fn damage_mirrors(&self, x: i32, y: i32) {
+        for output in self.root.outputs.lock().values() {
+            if let Some(mirror) = output.mirror.get() {
+                if mirror.source.global.pos.get().contains(x, y) {
+                    output.global.connector.damage();
+                }
+            }
+        }
+    }
+
     pub fn do_unlock(&self) {
         self.lock.locked.set(false);
         self.lock.lock.take();
@@ -818,6 +880,45 @@ impl State {
         self.damage(self.root.extents.get());
     }
 
+    pub fn reload_config(self: &Rc<Self>) {
+        log::info!("Reloading config");
+        let config = match ConfigProxy::from_config_dir(self) {
+            Ok(c) => c,
+            Err(e) => {
+                log::error!("Cannot reload config: {}", ErrorFmt(e));
+                return;
+            }
+        };
+        if let Some(config) = self.config.take() {
+            config.destroy();
+            for seat in self.globals.seats.lock().values() {
+                seat.clear_shortcuts();
+            }
+        }
+        let config = config.configure_or_default(self, true);
+        self.config.set(Some(Rc::new(config)));
+    }
+
+    /// Shuts the compositor down and stops the event loop.
+    ///
+    /// Programs spawned by the compositor are sent SIGTERM, the display state that was
+    /// present before the compositor started is restored, and the log is flushed. Once
+    /// the event loop has returned, the compositor is torn down by [`State::clear`]:
+    /// clients are disconnected, which also terminates Xwayland, and all nodes are
+    /// destroyed. Calling this more than once has no further effect.
+    pub fn quit(&self) {
+        if self.quitting.replace(true) {
+            return;
+        }
+        log::info!("Quitting");
+        if let Some(forker) = self.forker.get() {
+            forker.terminate_children();
+        }
+        self.backend.get().restore_display_state();
+        log::logger().flush();
+        self.ring.stop();
+    }
+
     pub fn clear(&self) {
         self.lock.lock.take();
         self.xwayland.handler.borrow_mut().take();
@@ -831,10 +932,13 @@ impl State {
         self.acceptor.set(None);
         self.backend.set(Rc::new(DummyBackend)).clear();
         self.run_toplevel.clear();
+        self.animations.clear();
         self.xwayland.handler.borrow_mut().take();
         self.xwayland.queue.clear();
         self.idle.inhibitors.clear();
+        self.idle.inhibited_notifications.clear();
         self.idle.change.clear();
+        self.default_seat.take();
         for drm_dev in self.drm_devs.lock().drain_values() {
             drm_dev.handler.take();
             drm_dev.connectors.clear();
@@ -847,6 +951,8 @@ impl State {
         for output in self.root.outputs.lock().values() {
             output.clear();
         }
+        self.session_bus_reconnect_handler.take();
+        self.upower.clear();
         self.dbus.clear();
         self.pending_container_layout.clear();
         self.pending_container_render_positions.clear();
@@ -880,6 +986,9 @@ impl State {
         if let Some(output) = self.dummy_output.set(None) {
             output.clear();
         }
+        if let Some(ws) = self.scratch_workspace.set(None) {
+            ws.clear();
+        }
         self.wheel.clear();
         self.eng.clear();
         self.ei_acceptor.take();
@@ -938,15 +1047,8 @@ impl State {
         tex: &Rc<dyn GfxTexture>,
         render_hw_cursor: bool,
     ) -> Result<Option<SyncFile>, GfxError> {
-        let sync_file = fb.render_output(
-            acquire_sync,
-            release_sync,
-            output,
-            self,
-            Some(output.global.pos.get()),
-            output.global.persistent.scale.get(),
-            render_hw_cursor,
-        )?;
+        let sync_file =
+            fb.render_output(acquire_sync, release_sync, output, self, render_hw_cursor)?;
         output.latched(false);
         output.perform_screencopies(
             tex,
@@ -988,12 +1090,14 @@ impl State {
                 let (width, height) = target.logical_size(target_transform);
                 Rect::new_sized(0, 0, width, height).unwrap()
             },
+            alpha: None,
         };
         let mut sample_rect = SampleRect::identity();
         sample_rect.buffer_transform = transform;
         renderer.base.render_texture(
             src,
             None,
+            false,
             x_off,
             y_off,
             Some(sample_rect),
@@ -1090,6 +1194,24 @@ impl State {
         seat
     }
 
+    pub fn client_removed(&self, client: &Client) {
+        for seat in self.globals.seats.lock().values() {
+            seat.client_removed(client.id);
+        }
+        #[cfg(feature = "rc_tracking")]
+        self.root
+            .clone()
+            .node_visit(&mut crate::tree::generic_node_visitor(|node| {
+                if node.node_client_id() == Some(client.id) {
+                    log::error!(
+                        "Node {} of removed client {} is still reachable",
+                        node.node_id(),
+                        client.id,
+                    );
+                }
+            }));
+    }
+
     pub fn signal_point(&self, sync_obj: &SyncObj, point: SyncObjPoint) {
         let Some(ctx) = self.render_ctx.get() else {
             log::error!("Cannot signal sync obj point because there is no render context");
@@ -1119,6 +1241,9 @@ impl State {
         let mut optimal_output = None;
         let outputs = self.root.outputs.lock();
         for output in outputs.values() {
+            if output.mirror.is_some() {
+                continue;
+            }
             let pos = output.global.pos.get();
             let dist = pos.dist_squared(x, y);
             if dist == 0 {
@@ -31,6 +31,18 @@ pub async fn handle_slow_clients(state: Rc<State>) {
     sch.handle_events().await;
 }
 
+pub async fn handle_config_reload(state: Rc<State>) {
+    loop {
+        state.config_reload.triggered().await;
+        state.reload_config();
+    }
+}
+
+pub async fn handle_quit_requests(state: Rc<State>) {
+    state.quit_requested.triggered().await;
+    state.quit();
+}
+
 pub async fn handle_slow_ei_clients(state: Rc<State>) {
     let mut sch = SlowEiClientHandler { state };
     sch.handle_events().await;
@@ -44,3 +56,11 @@ pub async fn handle_const_40hz_latch(state: Rc<State>) {
     })
     .await;
 }
+
+pub async fn handle_const_1hz_vblank(state: Rc<State>) {
+    let duration = Duration::from_secs(1);
+    run_const_clock(duration, &state.ring, &state.const_1hz_vblank, |l| {
+        l.after_vblank()
+    })
+    .await;
+}
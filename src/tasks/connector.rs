@@ -79,6 +79,7 @@ impl ConnectorHandler {
         }
         if let Some(dev) = &self.data.drm_dev {
             dev.connectors.remove(&self.id);
+            dev.connector_removed.trigger();
         }
         if let Some(config) = self.state.config.get() {
             config.del_connector(self.id);
@@ -121,6 +122,8 @@ impl ConnectorHandler {
                     vrr_mode: Cell::new(self.state.default_vrr_mode.get()),
                     vrr_cursor_hz: Cell::new(self.state.default_vrr_cursor_hz.get()),
                     tearing_mode: Cell::new(self.state.default_tearing_mode.get()),
+                    wallpaper: Default::default(),
+                    pending_wallpaper: Default::default(),
                 });
                 self.state
                     .persistent_output_states
@@ -197,6 +200,11 @@ impl ConnectorHandler {
             before_latch_event: Default::default(),
             tray_start_rel: Default::default(),
             tray_items: Default::default(),
+            gamma_control: Default::default(),
+            workspace_slide: Default::default(),
+            mirror: Default::default(),
+            mirrors: Default::default(),
+            content_type: Default::default(),
         });
         on.update_visible();
         on.update_rects();
@@ -300,8 +308,24 @@ impl ConnectorHandler {
         for sc in on.ext_copy_sessions.lock().drain_values() {
             sc.stop();
         }
+        if let Some(gc) = on.gamma_control.take() {
+            gc.output_removed();
+        }
         global.destroyed.set(true);
         self.state.root.outputs.remove(&self.id);
+        on.set_mirror_source(None);
+        let mirrors: Vec<_> = self
+            .state
+            .root
+            .outputs
+            .lock()
+            .values()
+            .filter(|o| o.mirror.get().is_some_and(|m| m.source.id == on.id))
+            .cloned()
+            .collect();
+        for output in mirrors {
+            output.set_mirror_source(None);
+        }
         self.state.output_extents_changed();
         self.state.outputs.remove(&self.id);
         on.lock_surface.take();
@@ -22,6 +22,7 @@ pub fn handle(state: &Rc<State>, dev: Rc<dyn BackendDrmDevice>) {
         dev: dev.clone(),
         handler: Cell::new(None),
         connectors: Default::default(),
+        connector_removed: Default::default(),
         syspath: props.syspath,
         devnode: props.devnode,
         vendor: props.vendor,
@@ -67,8 +68,10 @@ impl DrvDevHandler {
             }
             ae.triggered().await;
         }
-        if !self.data.connectors.is_empty() {
-            panic!("DRM device removed before its connectors");
+        // The backend removes the connectors before the device but their handlers
+        // might not have run yet.
+        while !self.data.connectors.is_empty() {
+            self.data.connector_removed.triggered().await;
         }
         if let Some(config) = self.state.config.get() {
             config.del_drm_dev(self.id);
@@ -30,6 +30,7 @@ pub async fn idle(state: Rc<State>, backend: Rc<dyn Backend>) {
         dead: false,
         is_inhibited: false,
         last_input: now(),
+        forced_at: None,
     };
     idle.run().await;
 }
@@ -42,8 +43,15 @@ struct Idle {
     dead: bool,
     is_inhibited: bool,
     last_input: c::timespec,
+    forced_at: Option<c::timespec>,
 }
 
+/// How long input is ignored after the idle state has been entered explicitly.
+///
+/// This prevents the release of the shortcut that entered the idle state from immediately
+/// leaving it again.
+const FORCED_IDLE_GRACE: Duration = Duration::from_secs(1);
+
 impl Idle {
     async fn run(&mut self) {
         while !self.dead {
@@ -65,11 +73,7 @@ impl Idle {
         let since = duration_since(self.last_input);
         if since >= timeout {
             if !timeout.is_zero() && !self.is_inhibited {
-                if let Some(config) = self.state.config.get() {
-                    config.idle();
-                }
-                self.backend.set_idle(true);
-                self.idle = true;
+                self.enter_idle();
             }
         } else {
             self.program_timer2(timeout - since);
@@ -89,7 +93,17 @@ impl Idle {
         if self.state.idle.timeout_changed.replace(false) {
             self.program_timer();
         }
+        if self.state.idle.force.replace(false) && !self.idle {
+            self.enter_idle();
+            self.forced_at = Some(now());
+        }
         if self.state.idle.input.replace(false) {
+            if let Some(forced_at) = self.forced_at {
+                if self.idle && duration_since(forced_at) < FORCED_IDLE_GRACE {
+                    return;
+                }
+            }
+            self.forced_at = None;
             self.last_input = now();
             if self.idle {
                 self.backend.set_idle(false);
@@ -99,6 +113,14 @@ impl Idle {
         }
     }
 
+    fn enter_idle(&mut self) {
+        if let Some(config) = self.state.config.get() {
+            config.idle();
+        }
+        self.backend.set_idle(true);
+        self.idle = true;
+    }
+
     fn program_timer(&mut self) {
         self.program_timer2(self.state.idle.timeout.get());
     }
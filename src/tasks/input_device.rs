@@ -60,11 +60,16 @@ impl DeviceHandler {
             let ae = self.ae.clone();
             self.dev.on_change(Rc::new(move || ae.trigger()));
         }
-        for seat in self.state.globals.seats.lock().values() {
-            if seat.seat_name() == DEFAULT_SEAT_NAME {
-                self.data.set_seat(Some(seat.clone()));
-                break;
-            }
+        let seat = {
+            let seats = self.state.globals.seats.lock();
+            let claimed = seats.values().find(|s| s.claims_device(&self.data));
+            claimed.cloned()
+        };
+        let seat = seat
+            .or_else(|| self.state.default_seat.get())
+            .or_else(|| self.state.globals.seat_by_name(DEFAULT_SEAT_NAME));
+        if let Some(seat) = seat {
+            self.data.set_seat(Some(seat));
         }
         if let Some(config) = self.state.config.get() {
             config.new_input_device(self.dev.id());
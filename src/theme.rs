@@ -201,6 +201,7 @@ colors! {
     focused_inactive_title_text = (0xff, 0xff, 0xff),
     separator = (0x33, 0x33, 0x33),
     border = (0x3f, 0x47, 0x4a),
+    focused_border = (0x3f, 0x47, 0x4a),
     bar_background = (0x00, 0x00, 0x00),
     bar_text = (0xff, 0xff, 0xff),
     attention_requested_background = (0x23, 0x09, 0x2c),
@@ -282,6 +283,8 @@ macro_rules! sizes {
 sizes! {
     title_height = (1, 1000, 17),
     border_width = (1, 1000, 4),
+    inner_gap = (0, 1000, 0),
+    outer_gap = (0, 1000, 0),
 }
 
 pub const DEFAULT_FONT: &str = "monospace 8";
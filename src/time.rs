@@ -114,6 +114,22 @@ impl Add<Duration> for Time {
     }
 }
 
+impl Sub<Duration> for Time {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Duration) -> Self::Output {
+        let secs = (rhs.as_nanos() / 1_000_000_000) as c::time_t;
+        let nsecs = (rhs.as_nanos() % 1_000_000_000) as c::c_long;
+        self.0.tv_sec -= secs;
+        self.0.tv_nsec -= nsecs;
+        if self.0.tv_nsec < 0 {
+            self.0.tv_sec -= 1;
+            self.0.tv_nsec += 1_000_000_000;
+        }
+        self
+    }
+}
+
 pub fn usec_to_msec(usec: u64) -> u32 {
     (usec / 1000) as u32
 }
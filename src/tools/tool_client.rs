@@ -154,7 +154,10 @@ impl ToolClient {
             Ok(d) => d,
             Err(_) => return Err(ToolClientError::WaylandDisplayNotSet),
         };
-        let path = format_ustr!("{}/{}.jay", xrd, wd);
+        let path = match wd.starts_with('/') {
+            true => format_ustr!("{}.jay", wd),
+            false => format_ustr!("{}/{}.jay", xrd, wd),
+        };
         let socket = match uapi::socket(c::AF_UNIX, c::SOCK_STREAM | c::SOCK_CLOEXEC, 0) {
             Ok(s) => Rc::new(s),
             Err(e) => return Err(ToolClientError::CreateSocket(e.into())),
@@ -332,7 +335,7 @@ impl ToolClient {
             self_id: s.registry,
             name: s.jay_compositor.0,
             interface: JayCompositor.name(),
-            version: s.jay_compositor.1.min(11),
+            version: s.jay_compositor.1.min(13),
             id: id.into(),
         });
         self.jay_compositor.set(Some(id));
@@ -27,13 +27,14 @@ use {
     },
 };
 pub use {
-    container::*, containing::*, display::*, float::*, output::*, placeholder::*, stacked::*,
-    toplevel::*, walker::*, workspace::*,
+    container::*, containing::*, display::*, dump::*, float::*, output::*, placeholder::*,
+    stacked::*, toplevel::*, walker::*, workspace::*,
 };
 
 mod container;
 mod containing;
 mod display;
+mod dump;
 mod float;
 mod output;
 mod placeholder;
@@ -84,7 +85,6 @@ impl NodeIds {
 pub struct NodeId(pub u32);
 
 impl NodeId {
-    #[expect(dead_code)]
     pub fn raw(&self) -> u32 {
         self.0
     }
@@ -104,6 +104,7 @@ pub struct ContainerRenderData {
     pub attention_title_rects: Vec<Rect>,
     pub last_active_rect: Option<Rect>,
     pub border_rects: Vec<Rect>,
+    pub active_border_rects: Vec<Rect>,
     pub underline_rects: Vec<Rect>,
     pub titles: SmallMapMut<Scale, Vec<ContainerTitle>, 2>,
 }
@@ -112,6 +113,7 @@ pub struct ContainerNode {
     pub id: ContainerNodeId,
     pub split: Cell<ContainerSplit>,
     pub mono_child: CloneCell<Option<NodeRef<ContainerChild>>>,
+    pub stacked: Cell<bool>,
     pub mono_body: Cell<Rect>,
     pub mono_content: Cell<Rect>,
     pub abs_x1: Cell<i32>,
@@ -217,6 +219,7 @@ impl ContainerNode {
             id: state.node_ids.next(),
             split: Cell::new(split),
             mono_child: CloneCell::new(None),
+            stacked: Cell::new(false),
             mono_body: Cell::new(Default::default()),
             mono_content: Cell::new(Default::default()),
             abs_x1: Cell::new(0),
@@ -417,6 +420,14 @@ impl ContainerNode {
             .set(child.content.get().at_point(mb.x1(), mb.y1()));
 
         let th = self.state.theme.sizes.title_height.get();
+        if self.stacked.get() {
+            for (i, child) in self.children.iter().enumerate() {
+                let y = i as i32 * (th + 1);
+                let rect = Rect::new_sized(0, y, self.width.get(), th).unwrap();
+                child.title_rect.set(rect);
+            }
+            return;
+        }
         let bw = self.state.theme.sizes.border_width.get();
         let num_children = self.num_children.get() as i32;
         let content_width = self.width.get().sub(bw * (num_children - 1)).max(0);
@@ -438,7 +449,7 @@ impl ContainerNode {
 
     fn perform_split_layout(self: &Rc<Self>) {
         let sum_factors = self.sum_factors.get();
-        let border_width = self.state.theme.sizes.border_width.get();
+        let separator = self.separator_width();
         let title_height = self.state.theme.sizes.title_height.get();
         let split = self.split.get();
         let (content_size, other_content_size) = match split {
@@ -465,7 +476,7 @@ impl ContainerNode {
             };
             let body = Rect::new_sized(x1, y1, width, height).unwrap();
             child.body.set(body);
-            pos += body_size + border_width;
+            pos += body_size + separator;
             if split == ContainerSplit::Vertical {
                 pos += title_height + 1;
             }
@@ -499,7 +510,7 @@ impl ContainerNode {
                 };
                 body = Rect::new_sized(x1, y1, width, height).unwrap();
                 child.body.set(body);
-                pos += size + border_width;
+                pos += size + separator;
                 if split == ContainerSplit::Vertical {
                     pos += title_height + 1;
                 }
@@ -523,13 +534,22 @@ impl ContainerNode {
         }
     }
 
+    fn separator_width(&self) -> i32 {
+        let sizes = &self.state.theme.sizes;
+        sizes.border_width.get() + sizes.inner_gap.get()
+    }
+
     fn update_content_size(&self) {
-        let border_width = self.state.theme.sizes.border_width.get();
+        let separator = self.separator_width();
         let title_height = self.state.theme.sizes.title_height.get();
         let nc = self.num_children.get();
         match self.split.get() {
             ContainerSplit::Horizontal => {
-                let new_content_size = self.width.get().sub((nc - 1) as i32 * border_width).max(0);
+                let new_content_size = self
+                    .width
+                    .get()
+                    .sub((nc - 1) as i32 * separator)
+                    .max(0);
                 self.content_width.set(new_content_size);
                 self.content_height
                     .set(self.height.get().sub(title_height + 1).max(0));
@@ -538,18 +558,22 @@ impl ContainerNode {
                 let new_content_size = self
                     .height
                     .get()
-                    .sub(title_height + 1 + (nc - 1) as i32 * (border_width + title_height + 1))
+                    .sub(title_height + 1 + (nc - 1) as i32 * (separator + title_height + 1))
                     .max(0);
                 self.content_height.set(new_content_size);
                 self.content_width.set(self.width.get());
             }
         }
+        let mono_titles_height = match self.stacked.get() {
+            false => title_height + 1,
+            true => nc as i32 * (title_height + 1),
+        };
         self.mono_body.set(
             Rect::new_sized(
                 0,
-                title_height + 1,
+                mono_titles_height,
                 self.width.get(),
-                self.height.get().sub(title_height + 1).max(0),
+                self.height.get().sub(mono_titles_height).max(0),
             )
             .unwrap(),
         );
@@ -667,6 +691,7 @@ impl ContainerNode {
         let mut title = self.toplevel_data.title.borrow_mut();
         title.clear();
         let split = match (self.mono_child.is_some(), self.split.get()) {
+            (true, _) if self.stacked.get() => "S",
             (true, _) => "T",
             (_, ContainerSplit::Horizontal) => "H",
             (_, ContainerSplit::Vertical) => "V",
@@ -791,6 +816,9 @@ impl ContainerNode {
         let theme = &self.state.theme;
         let th = theme.sizes.title_height.get();
         let bw = theme.sizes.border_width.get();
+        // Borders are centered in the gap between two children.
+        let gap = theme.sizes.inner_gap.get();
+        let gap_after = gap - gap / 2;
         let cwidth = self.width.get();
         let cheight = self.height.get();
         for (_, v) in rd.titles.iter_mut() {
@@ -800,29 +828,41 @@ impl ContainerNode {
         rd.active_title_rects.clear();
         rd.attention_title_rects.clear();
         rd.border_rects.clear();
+        rd.active_border_rects.clear();
         rd.underline_rects.clear();
         rd.last_active_rect.take();
         let last_active = self.focus_history.last().map(|v| v.node.node_id());
         let mono = self.mono_child.is_some();
         let split = self.split.get();
+        let stacked = mono && self.stacked.get();
         let have_active = self.children.iter().any(|c| c.active.get());
         let abs_x = self.abs_x1.get();
         let abs_y = self.abs_y1.get();
+        let mut prev_active = false;
         for (i, child) in self.children.iter().enumerate() {
             let rect = child.title_rect.get();
             if self.toplevel_data.visible.get() {
                 self.state.damage(rect.move_(abs_x, abs_y));
             }
-            if i > 0 {
+            if i > 0 && !stacked {
                 let rect = if mono {
                     Rect::new_sized(rect.x1() - bw, 0, bw, th)
                 } else if split == ContainerSplit::Horizontal {
-                    Rect::new_sized(rect.x1() - bw, 0, bw, cheight)
+                    Rect::new_sized(rect.x1() - bw - gap_after, 0, bw, cheight)
                 } else {
-                    Rect::new_sized(0, rect.y1() - bw, cwidth, bw)
+                    Rect::new_sized(0, rect.y1() - bw - gap_after, cwidth, bw)
                 };
-                rd.border_rects.push(rect.unwrap());
+                let rect = rect.unwrap();
+                if self.toplevel_data.visible.get() {
+                    self.state.damage(rect.move_(abs_x, abs_y));
+                }
+                if prev_active || child.active.get() {
+                    rd.active_border_rects.push(rect);
+                } else {
+                    rd.border_rects.push(rect);
+                }
             }
+            prev_active = child.active.get();
             if child.active.get() {
                 rd.active_title_rects.push(rect);
             } else if child.attention_requested.get() {
@@ -832,7 +872,7 @@ impl ContainerNode {
             } else {
                 rd.title_rects.push(rect);
             }
-            if !mono {
+            if !mono || stacked {
                 let rect = Rect::new_sized(rect.x1(), rect.y2(), rect.width(), 1).unwrap();
                 rd.underline_rects.push(rect);
             }
@@ -848,7 +888,7 @@ impl ContainerNode {
                 }
             }
         }
-        if mono {
+        if mono && !stacked {
             rd.underline_rects
                 .push(Rect::new_sized(0, th, cwidth, 1).unwrap());
         }
@@ -884,6 +924,23 @@ impl ContainerNode {
         }
     }
 
+    pub fn focus_tab(self: &Rc<Self>, seat: &Rc<WlSeatGlobal>, next: bool) {
+        let Some(mc) = self.mono_child.get() else {
+            return;
+        };
+        let new = match next {
+            true => mc.next().or_else(|| self.children.first()),
+            false => mc.prev().or_else(|| self.children.last()),
+        };
+        let Some(new) = new else {
+            return;
+        };
+        self.activate_child(&new);
+        new.node
+            .clone()
+            .node_do_focus(seat, Direction::Unspecified);
+    }
+
     pub fn set_mono(self: &Rc<Self>, child: Option<&dyn ToplevelNode>) {
         if self.mono_child.is_some() == child.is_some() {
             return;
@@ -939,6 +996,22 @@ impl ContainerNode {
         }
     }
 
+    pub fn set_stacked(self: &Rc<Self>, stacked: bool) {
+        if self.stacked.replace(stacked) != stacked {
+            self.update_content_size();
+            self.schedule_layout();
+            self.update_title();
+        }
+    }
+
+    /// Returns the axis along which the titles of a mono container are laid out.
+    fn mono_split(&self) -> ContainerSplit {
+        match self.stacked.get() {
+            true => ContainerSplit::Vertical,
+            false => ContainerSplit::Horizontal,
+        }
+    }
+
     fn parent_container(&self) -> Option<Rc<ContainerNode>> {
         self.toplevel_data
             .parent
@@ -957,15 +1030,13 @@ impl ContainerNode {
             _ => return,
         };
         let mc = self.mono_child.get();
-        let in_line = if mc.is_some() {
-            matches!(direction, Direction::Left | Direction::Right)
-        } else {
-            match self.split.get() {
-                ContainerSplit::Horizontal => {
-                    matches!(direction, Direction::Left | Direction::Right)
-                }
-                ContainerSplit::Vertical => matches!(direction, Direction::Up | Direction::Down),
-            }
+        let split = match mc.is_some() {
+            true => self.mono_split(),
+            false => self.split.get(),
+        };
+        let in_line = match split {
+            ContainerSplit::Horizontal => matches!(direction, Direction::Left | Direction::Right),
+            ContainerSplit::Vertical => matches!(direction, Direction::Up | Direction::Down),
         };
         if !in_line {
             if let Some(c) = self.parent_container() {
@@ -1015,9 +1086,7 @@ impl ContainerNode {
         }
         let (split, prev) = direction_to_split(direction);
         // CASE 2: We're moving the child within the container.
-        if split == self.split.get()
-            || (split == ContainerSplit::Horizontal && self.mono_child.is_some())
-        {
+        if split == self.split.get() || (split == self.mono_split() && self.mono_child.is_some()) {
             let cc = match self.child_nodes.borrow().get(&child.node_id()) {
                 Some(l) => l.to_ref(),
                 None => return,
@@ -1182,7 +1251,11 @@ impl ContainerNode {
             _ => return,
         };
         if button == BTN_RIGHT && pressed {
-            if self.mono_child.is_some() || self.split.get() == ContainerSplit::Horizontal {
+            let titles_in_row = match self.mono_child.is_some() {
+                true => !self.stacked.get(),
+                false => self.split.get() == ContainerSplit::Horizontal,
+            };
+            if titles_in_row {
                 if seat_data.y < self.state.theme.sizes.title_height.get() {
                     self.toggle_mono();
                 }
@@ -1269,6 +1342,12 @@ impl ContainerNode {
         abs_x: i32,
         abs_y: i32,
     ) -> Option<TileDragDestination> {
+        let stacked = self.stacked.get();
+        let titles_height = self.mono_body.get().y1() - 1;
+        let area = |lo: i32, hi: i32| match stacked {
+            true => Rect::new(0, lo, self.width.get(), hi),
+            false => Rect::new(lo, 0, hi, titles_height),
+        };
         let mut prev_is_source = false;
         let mut prev_center = 0;
         for child in self.children.iter() {
@@ -1277,9 +1356,12 @@ impl ContainerNode {
                 continue;
             }
             let rect = child.title_rect.get();
-            let center = (rect.x1() + rect.x2()) / 2;
+            let center = match stacked {
+                true => (rect.y1() + rect.y2()) / 2,
+                false => (rect.x1() + rect.x2()) / 2,
+            };
             if !prev_is_source {
-                let rect = Rect::new(prev_center, 0, center, rect.height())?
+                let rect = area(prev_center, center)?
                     .move_(self.abs_x1.get(), self.abs_y1.get())
                     .intersect(abs_bounds);
                 if rect.contains(abs_x, abs_y) {
@@ -1300,14 +1382,13 @@ impl ContainerNode {
             return None;
         }
         let last = self.children.last()?;
-        let rect = Rect::new(
-            prev_center,
-            0,
-            self.width.get(),
-            self.state.theme.sizes.title_height.get(),
-        )?
-        .move_(self.abs_x1.get(), self.abs_y1.get())
-        .intersect(abs_bounds);
+        let end = match stacked {
+            true => titles_height,
+            false => self.width.get(),
+        };
+        let rect = area(prev_center, end)?
+            .move_(self.abs_x1.get(), self.abs_y1.get())
+            .intersect(abs_bounds);
         if rect.contains(abs_x, abs_y) {
             return Some(TileDragDestination {
                 highlight: rect,
@@ -1329,11 +1410,10 @@ impl ContainerNode {
         abs_x: i32,
         abs_y: i32,
     ) -> Option<TileDragDestination> {
-        let th = self.state.theme.sizes.title_height.get();
-        if abs_y < self.abs_y1.get() + th {
+        let body = self.mono_body.get();
+        if abs_y < self.abs_y1.get() + body.y1() - 1 {
             return self.tile_drag_destination_mono_titles(source, abs_bounds, abs_x, abs_y);
         }
-        let body = self.mono_body.get();
         let bounds = body
             .move_(self.abs_x1.get(), self.abs_y1.get())
             .intersect(abs_bounds);
@@ -1928,7 +2008,6 @@ impl ContainingNode for ContainerNode {
     ) {
         let theme = &self.state.theme;
         let th = theme.sizes.title_height.get();
-        let bw = theme.sizes.border_width.get();
         let mut left_outside = false;
         let mut right_outside = false;
         let mut top_outside = false;
@@ -1967,9 +2046,10 @@ impl ContainingNode for ContainerNode {
             if ci == 0 {
                 ci = 1;
             }
+            let separator = self.separator_width();
             let (new_delta, between) = match split {
-                ContainerSplit::Horizontal => (self.abs_x1.get(), bw),
-                ContainerSplit::Vertical => (self.abs_y1.get(), bw + th + 1),
+                ContainerSplit::Horizontal => (self.abs_x1.get(), separator),
+                ContainerSplit::Vertical => (self.abs_y1.get(), separator + th + 1),
             };
             let new_i1 = new_i1.map(|v| v - new_delta);
             let new_i2 = new_i2.map(|v| v - new_delta);
@@ -50,6 +50,9 @@ impl DisplayNode {
         let mut x2 = i32::MIN;
         let mut y2 = i32::MIN;
         for output in outputs.values() {
+            if output.mirror.is_some() {
+                continue;
+            }
             let pos = output.global.pos.get();
             x1 = x1.min(pos.x1());
             y1 = y1.min(pos.y1());
@@ -156,6 +159,9 @@ impl Node for DisplayNode {
     ) -> FindTreeResult {
         let outputs = self.outputs.lock();
         for output in outputs.values() {
+            if output.mirror.is_some() {
+                continue;
+            }
             let pos = output.global.pos.get();
             if pos.contains(x, y) {
                 let (x, y) = pos.translate(x, y);
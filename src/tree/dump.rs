@@ -0,0 +1,136 @@
+use {
+    crate::{
+        ifs::wl_surface::{
+            ext_session_lock_surface_v1::ExtSessionLockSurfaceV1,
+            tray::jay_tray_item_v1::JayTrayItemV1,
+            x_surface::xwindow::Xwindow,
+            xdg_surface::{xdg_popup::XdgPopup, xdg_toplevel::XdgToplevel},
+            zwlr_layer_surface_v1::ZwlrLayerSurfaceV1,
+            WlSurface,
+        },
+        tree::{
+            ContainerNode, DisplayNode, FloatNode, Node, NodeVisitorBase, OutputNode,
+            PlaceholderNode, ToplevelNode, WorkspaceNode,
+        },
+    },
+    std::{fmt::Write, rc::Rc},
+};
+
+/// Returns a textual representation of the node tree, one node per line.
+pub fn dump_tree(root: &Rc<DisplayNode>) -> Vec<String> {
+    let mut dumper = TreeDumper {
+        depth: 0,
+        lines: vec![],
+    };
+    dumper.visit_display(root);
+    dumper.lines
+}
+
+struct TreeDumper {
+    depth: usize,
+    lines: Vec<String>,
+}
+
+impl TreeDumper {
+    fn node(&mut self, kind: &str, node: &dyn Node, details: &str) {
+        let pos = node.node_absolute_position();
+        let mut line = String::new();
+        let _ = write!(
+            line,
+            "{:indent$}{kind} {}: {}x{}+{}+{}",
+            "",
+            node.node_id().0,
+            pos.width(),
+            pos.height(),
+            pos.x1(),
+            pos.y1(),
+            indent = 2 * self.depth,
+        );
+        if !node.node_visible() {
+            line.push_str(" invisible");
+        }
+        if let Some(client) = node.node_client_id() {
+            let _ = write!(line, " client={}", client);
+        }
+        if !details.is_empty() {
+            let _ = write!(line, " {}", details);
+        }
+        self.lines.push(line);
+        self.depth += 1;
+        node.node_visit_children(self);
+        self.depth -= 1;
+    }
+
+    fn toplevel<T: ToplevelNode>(&mut self, kind: &str, node: &T) {
+        let data = node.tl_data();
+        let details = format!(
+            "title={:?} app_id={:?}",
+            data.title.borrow(),
+            data.app_id.borrow(),
+        );
+        self.node(kind, node, &details);
+    }
+}
+
+impl NodeVisitorBase for TreeDumper {
+    fn visit_surface(&mut self, node: &Rc<WlSurface>) {
+        self.node("surface", &**node, "");
+    }
+
+    fn visit_container(&mut self, node: &Rc<ContainerNode>) {
+        let mut details = format!("split={:?}", node.split.get());
+        if node.mono_child.is_some() {
+            details.push_str(" mono");
+        }
+        if node.stacked.get() {
+            details.push_str(" stacked");
+        }
+        self.node("container", &**node, &details);
+    }
+
+    fn visit_toplevel(&mut self, node: &Rc<XdgToplevel>) {
+        self.toplevel("xdg-toplevel", &**node);
+    }
+
+    fn visit_popup(&mut self, node: &Rc<XdgPopup>) {
+        self.node("xdg-popup", &**node, "");
+    }
+
+    fn visit_display(&mut self, node: &Rc<DisplayNode>) {
+        self.node("display", &**node, "");
+    }
+
+    fn visit_output(&mut self, node: &Rc<OutputNode>) {
+        let details = format!("connector={}", node.global.connector.name);
+        self.node("output", &**node, &details);
+    }
+
+    fn visit_float(&mut self, node: &Rc<FloatNode>) {
+        self.node("float", &**node, "");
+    }
+
+    fn visit_workspace(&mut self, node: &Rc<WorkspaceNode>) {
+        let details = format!("name={:?}", node.name);
+        self.node("workspace", &**node, &details);
+    }
+
+    fn visit_layer_surface(&mut self, node: &Rc<ZwlrLayerSurfaceV1>) {
+        self.node("layer-surface", &**node, "");
+    }
+
+    fn visit_xwindow(&mut self, node: &Rc<Xwindow>) {
+        self.toplevel("xwindow", &**node);
+    }
+
+    fn visit_placeholder(&mut self, node: &Rc<PlaceholderNode>) {
+        self.toplevel("placeholder", &**node);
+    }
+
+    fn visit_lock_surface(&mut self, node: &Rc<ExtSessionLockSurfaceV1>) {
+        self.node("lock-surface", &**node, "");
+    }
+
+    fn visit_tray_item(&mut self, node: &Rc<JayTrayItemV1>) {
+        self.node("tray-item", &**node, "");
+    }
+}
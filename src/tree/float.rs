@@ -419,6 +419,9 @@ impl FloatNode {
 
     fn update_child_active(self: &Rc<Self>, active: bool) {
         if self.active.replace(active) != active {
+            if self.visible.get() {
+                self.state.damage(self.position.get());
+            }
             self.schedule_render_titles();
         }
     }
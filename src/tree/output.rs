@@ -1,5 +1,6 @@
 use {
     crate::{
+        animation::{Animation, WorkspaceSlide},
         backend::{HardwareCursor, KeyState, Mode},
         client::ClientId,
         cursor::KnownCursor,
@@ -22,8 +23,10 @@ use {
                 tray::DynTrayItem,
                 zwlr_layer_surface_v1::{ExclusiveSize, ZwlrLayerSurfaceV1},
                 SurfaceSendPreferredScaleVisitor, SurfaceSendPreferredTransformVisitor,
+                SurfaceUpdateOutputsVisitor,
             },
             wp_content_type_v1::ContentType,
+            zwlr_gamma_control_v1::ZwlrGammaControlV1,
             zwlr_layer_shell_v1::{BACKGROUND, BOTTOM, OVERLAY, TOP},
             zwlr_screencopy_frame_v1::ZwlrScreencopyFrameV1,
         },
@@ -31,7 +34,7 @@ use {
         rect::Rect,
         renderer::Renderer,
         scale::Scale,
-        state::State,
+        state::{ConnectorData, State},
         text::TextTexture,
         tree::{
             walker::NodeVisitor, Direction, FindTreeResult, FindTreeUsecase, FoundNode, Node,
@@ -39,9 +42,15 @@ use {
             WorkspaceNode, WorkspaceNodeId,
         },
         utils::{
-            asyncevent::AsyncEvent, clonecell::CloneCell, copyhashmap::CopyHashMap,
-            errorfmt::ErrorFmt, event_listener::EventSource, hash_map_ext::HashMapExt,
-            linkedlist::LinkedList, on_drop_event::OnDropEvent, scroller::Scroller,
+            asyncevent::AsyncEvent,
+            clonecell::CloneCell,
+            copyhashmap::CopyHashMap,
+            errorfmt::ErrorFmt,
+            event_listener::{EventListener, EventSource},
+            hash_map_ext::HashMapExt,
+            linkedlist::LinkedList,
+            on_drop_event::OnDropEvent,
+            scroller::Scroller,
             transform_ext::TransformExt,
         },
         wire::{
@@ -97,6 +106,24 @@ pub struct OutputNode {
     pub before_latch_event: EventSource<dyn BeforeLatchListener>,
     pub tray_start_rel: Cell<i32>,
     pub tray_items: LinkedList<Rc<dyn DynTrayItem>>,
+    pub gamma_control: CloneCell<Option<Rc<ZwlrGammaControlV1>>>,
+    pub workspace_slide: CloneCell<Option<Rc<WorkspaceSlide>>>,
+    pub mirror: CloneCell<Option<Rc<OutputMirror>>>,
+    pub mirrors: CopyHashMap<OutputNodeId, Rc<OutputNode>>,
+    pub content_type: Cell<Option<ContentType>>,
+}
+
+/// Causes an output to display the contents of another output.
+pub struct OutputMirror {
+    pub source: Rc<OutputNode>,
+    connector: Rc<ConnectorData>,
+    latch_listener: EventListener<dyn LatchListener>,
+}
+
+impl LatchListener for OutputMirror {
+    fn after_latch(self: Rc<Self>, _on: &OutputNode, _tearing: bool) {
+        self.connector.damage();
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -210,7 +237,8 @@ impl OutputNode {
         let mut exclusive = ExclusiveSize::default();
         for layer in &self.layers {
             for surface in layer.iter() {
-                exclusive = exclusive.max(&surface.exclusive_size());
+                surface.set_exclusive_offset(exclusive);
+                exclusive = exclusive.add(&surface.exclusive_size());
             }
         }
         if self.exclusive_zones.replace(exclusive) != exclusive {
@@ -351,7 +379,11 @@ impl OutputNode {
                                 capture.pending.set(p);
                             }
                             Err(e) => {
-                                log::warn!("Could not perform shm screencopy: {}", ErrorFmt(e));
+                                if mem.pool().sigbus_occurred() {
+                                    wl_buffer.client.error(e);
+                                } else {
+                                    log::warn!("Could not perform shm screencopy: {}", ErrorFmt(e));
+                                }
                                 capture.send_failed();
                                 continue;
                             }
@@ -376,7 +408,7 @@ impl OutputNode {
                             ReleaseSync::Implicit,
                             self.global.persistent.transform.get(),
                             self.global.pos.get(),
-                            render_hardware_cursors,
+                            render_hardware_cursors && capture.overlay_cursor,
                             x_off - capture.rect.x1(),
                             y_off - capture.rect.y1(),
                             size,
@@ -414,6 +446,41 @@ impl OutputNode {
         self.screencasts.clear();
         self.screencopies.clear();
         self.ext_copy_sessions.clear();
+        self.gamma_control.take();
+        self.workspace_slide.take();
+        self.mirror.take();
+        self.mirrors.clear();
+    }
+
+    /// Makes this output display the contents of `source` instead of its own workspaces.
+    ///
+    /// While an output mirrors another output, it is not part of the layout and does not
+    /// receive input. Cursors on it are moved to the source.
+    pub fn set_mirror_source(self: &Rc<Self>, source: Option<&Rc<OutputNode>>) {
+        let mirror = source.map(|source| {
+            let mirror = Rc::new_cyclic(|slf| OutputMirror {
+                source: source.clone(),
+                connector: self.global.connector.clone(),
+                latch_listener: EventListener::new(slf.clone()),
+            });
+            mirror.latch_listener.attach(&source.latch_event);
+            mirror
+        });
+        let old = self.mirror.set(mirror);
+        self.global.connector.damage();
+        self.state.output_extents_changed();
+        let mut visitor = SurfaceUpdateOutputsVisitor;
+        if let Some(old) = old {
+            old.source.mirrors.remove(&self.id);
+            old.source.visit_surfaces(&mut visitor);
+        }
+        if let Some(source) = source {
+            source.mirrors.set(self.id, self.clone());
+            source.visit_surfaces(&mut visitor);
+            for seat in self.state.globals.seats.lock().values() {
+                seat.cursor_group().output_disconnected(self, source);
+            }
+        }
     }
 
     pub fn on_spaces_changed(self: &Rc<Self>) {
@@ -439,14 +506,21 @@ impl OutputNode {
         self.state.add_output_scale(scale);
         let rect = self.calculate_extents();
         self.change_extents_(&rect);
-        let mut visitor = SurfaceSendPreferredScaleVisitor;
-        self.node_visit_children(&mut visitor);
+        self.visit_surfaces(&mut SurfaceSendPreferredScaleVisitor);
+        if let Some(mirror) = self.mirror.get() {
+            let mut visitor = SurfaceUpdateOutputsVisitor;
+            mirror.source.visit_surfaces(&mut visitor);
+        }
+        self.schedule_update_render_data();
+    }
+
+    fn visit_surfaces(&self, visitor: &mut dyn NodeVisitor) {
+        self.node_visit_children(visitor);
         for ws in self.workspaces.iter() {
             for stacked in ws.stacked.iter() {
-                stacked.deref().clone().node_visit(&mut visitor);
+                stacked.deref().clone().node_visit(visitor);
             }
         }
-        self.schedule_update_render_data();
     }
 
     pub fn schedule_update_render_data(self: &Rc<Self>) {
@@ -625,10 +699,16 @@ impl OutputNode {
 
     pub fn show_workspace(&self, ws: &Rc<WorkspaceNode>) -> bool {
         let mut seats = SmallVec::new();
+        let mut slide = None;
         if let Some(old) = self.workspace.set(Some(ws.clone())) {
             if old.id == ws.id {
                 return false;
             }
+            let first = self
+                .workspaces
+                .iter()
+                .find(|w| w.id == old.id || w.id == ws.id);
+            let forward = first.map(|w| w.id) != Some(ws.id);
             collect_kb_foci2(old.clone(), &mut seats);
             if old.is_empty() {
                 for jw in old.jay_workspaces.lock().values() {
@@ -637,11 +717,21 @@ impl OutputNode {
                 }
                 old.clear();
                 self.state.workspaces.remove(&old.name);
+                slide = Some((None, forward));
             } else {
                 old.set_visible(false);
                 old.flush_jay_workspaces();
+                slide = Some((Some(old), forward));
             }
         }
+        if let Some((old, forward)) = slide {
+            let slide = Animation::start(&self.state, self).map(|animation| WorkspaceSlide {
+                animation,
+                old,
+                forward,
+            });
+            self.workspace_slide.set(slide.map(Rc::new));
+        }
         self.update_visible();
         if let Some(fs) = ws.fullscreen.get() {
             fs.tl_change_extents(&self.global.pos.get());
@@ -976,6 +1066,23 @@ impl OutputNode {
     pub fn update_presentation_type(&self) {
         self.update_vrr_state();
         self.update_tearing();
+        self.update_content_type();
+    }
+
+    fn update_content_type(&self) {
+        let content_type = self
+            .workspace
+            .get()
+            .and_then(|ws| ws.fullscreen.get())
+            .and_then(|tl| tl.tl_scanout_surface())
+            .and_then(|s| s.content_type.get());
+        let connector = &self.global.connector.connector;
+        connector.set_content_type(content_type);
+        if self.content_type.replace(content_type) != content_type {
+            if let Some(config) = self.state.config.get() {
+                config.content_type_changed(connector.id(), content_type);
+            }
+        }
     }
 
     fn update_vrr_state(&self) {
@@ -1,6 +1,8 @@
 use {
     crate::{
+        animation::{Animation, ClosingToplevel, Snapshot},
         client::{Client, ClientId},
+        gfx_api::BufferResv,
         ifs::{
             ext_foreign_toplevel_handle_v1::ExtForeignToplevelHandleV1,
             ext_foreign_toplevel_list_v1::ExtForeignToplevelListV1,
@@ -20,6 +22,7 @@ use {
             array_to_tuple::ArrayToTuple,
             clonecell::CloneCell,
             copyhashmap::CopyHashMap,
+            errorfmt::ErrorFmt,
             hash_map_ext::HashMapExt,
             numcell::NumCell,
             smallmap::SmallMap,
@@ -203,6 +206,10 @@ pub trait ToplevelNodeBase: Node {
 
     fn tl_close(self: Rc<Self>);
 
+    fn tl_kill(self: Rc<Self>) {
+        log::info!("This window cannot be killed");
+    }
+
     fn tl_set_visible_impl(&self, visible: bool);
     fn tl_destroy_impl(&self);
 
@@ -274,6 +281,7 @@ pub struct ToplevelData {
     pub seat_state: NodeSeatState,
     pub wants_attention: Cell<bool>,
     pub requested_attention: Cell<bool>,
+    pub unresponsive: Cell<bool>,
     pub app_id: RefCell<String>,
     pub identifier: Cell<ToplevelIdentifier>,
     pub handles:
@@ -283,6 +291,8 @@ pub struct ToplevelData {
     pub jay_screencasts: CopyHashMap<(ClientId, JayScreencastId), Rc<JayScreencast>>,
     pub ext_copy_sessions:
         CopyHashMap<(ClientId, ExtImageCopyCaptureSessionV1Id), Rc<ExtImageCopyCaptureSessionV1>>,
+    pub open_animation: Cell<Option<Animation>>,
+    pub opacity: Cell<f32>,
     pub slf: Weak<dyn ToplevelNode>,
 }
 
@@ -315,6 +325,7 @@ impl ToplevelData {
             seat_state: Default::default(),
             wants_attention: Cell::new(false),
             requested_attention: Cell::new(false),
+            unresponsive: Cell::new(false),
             app_id: Default::default(),
             identifier: Cell::new(id),
             handles: Default::default(),
@@ -322,6 +333,8 @@ impl ToplevelData {
             jay_toplevels: Default::default(),
             jay_screencasts: Default::default(),
             ext_copy_sessions: Default::default(),
+            open_animation: Default::default(),
+            opacity: Cell::new(1.0),
             slf: slf.clone(),
         }
     }
@@ -335,6 +348,9 @@ impl ToplevelData {
         f();
         let active_new = self.active();
         if active_old != active_new {
+            if self.state.dim_unfocused.get() > 0.0 {
+                self.state.damage(self.pos.get());
+            }
             tl.tl_set_active(active_new);
             if let Some(parent) = self.parent.get() {
                 parent.node_child_active_changed(tl.tl_as_node(), active_new, 1);
@@ -359,7 +375,81 @@ impl ToplevelData {
         (width, height)
     }
 
+    pub fn set_opacity(&self, opacity: f32) {
+        let opacity = opacity.clamp(0.0, 1.0);
+        if self.opacity.replace(opacity) != opacity {
+            self.state.damage(self.pos.get());
+        }
+    }
+
+    /// Returns the alpha factor that should be applied when rendering this toplevel.
+    pub fn render_alpha(&self) -> Option<f32> {
+        if self.is_fullscreen.get() {
+            return None;
+        }
+        let mut alpha = self.opacity.get();
+        if !self.active() {
+            alpha *= 1.0 - self.state.dim_unfocused.get();
+        }
+        (alpha < 1.0).then_some(alpha)
+    }
+
+    pub fn start_open_animation(&self) {
+        self.open_animation
+            .set(Animation::start(&self.state, &self.output()));
+    }
+
+    /// Keeps the last frame of the toplevel alive so that it can fade out.
+    pub fn start_close_animation(&self) {
+        if !self.visible.get() {
+            return;
+        }
+        let Some(tl) = self.slf.upgrade() else {
+            return;
+        };
+        let Some(surface) = tl.tl_scanout_surface() else {
+            return;
+        };
+        let Some(buffer) = surface.buffer.get() else {
+            return;
+        };
+        let Some(texture) = buffer.buffer.get_texture(&surface) else {
+            return;
+        };
+        let output = self.output();
+        let Some(animation) = Animation::start(&self.state, &output) else {
+            return;
+        };
+        let resv: Rc<dyn BufferResv> = buffer.clone();
+        let snapshot = match Snapshot::new(&self.state, &texture, &resv, buffer.release_sync) {
+            Ok(s) => s,
+            Err(e) => {
+                log::warn!(
+                    "Could not copy the last frame of the toplevel: {}",
+                    ErrorFmt(e)
+                );
+                return;
+            }
+        };
+        let rect = surface.buffer_abs_pos.get();
+        let bounds = match self.workspace.get() {
+            Some(ws) if !self.is_floating.get() => ws.position.get(),
+            _ => output.global.pos.get(),
+        };
+        let closing = ClosingToplevel {
+            animation,
+            snapshot,
+            tpoints: *surface.buffer_points_norm.borrow(),
+            rect,
+            bounds,
+        };
+        self.state.damage(rect);
+        let mut animations = self.state.animations.closing.borrow_mut();
+        animations.push(closing);
+    }
+
     pub fn destroy_node(&self, node: &dyn Node) {
+        self.start_close_animation();
         for jay_tl in self.jay_toplevels.lock().drain_values() {
             jay_tl.destroy();
         }
@@ -579,10 +669,7 @@ impl ToplevelData {
         if !self.requested_attention.replace(false) {
             return;
         }
-        self.wants_attention.set(false);
-        if let Some(parent) = self.parent.get() {
-            parent.cnode_child_attention_request_changed(node, false);
-        }
+        self.update_wants_attention(node);
     }
 
     pub fn request_attention(&self, node: &dyn Node) {
@@ -592,9 +679,26 @@ impl ToplevelData {
         if self.requested_attention.replace(true) {
             return;
         }
-        self.wants_attention.set(true);
+        self.update_wants_attention(node);
+    }
+
+    pub fn set_unresponsive(&self, node: &dyn Node, unresponsive: bool) {
+        if self.unresponsive.replace(unresponsive) == unresponsive {
+            return;
+        }
+        if self.visible.get() {
+            self.state.damage(node.node_absolute_position());
+        }
+        self.update_wants_attention(node);
+    }
+
+    fn update_wants_attention(&self, node: &dyn Node) {
+        let wants_attention = self.requested_attention.get() || self.unresponsive.get();
+        if self.wants_attention.replace(wants_attention) == wants_attention {
+            return;
+        }
         if let Some(parent) = self.parent.get() {
-            parent.cnode_child_attention_request_changed(node, true);
+            parent.cnode_child_attention_request_changed(node, wants_attention);
         }
     }
 
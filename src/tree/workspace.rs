@@ -39,6 +39,9 @@ use {
 
 tree_id!(WorkspaceNodeId);
 
+/// The smallest size that outer gaps leave for the container.
+const MIN_CONTAINER_SIZE: i32 = 100;
+
 pub struct WorkspaceNode {
     pub id: WorkspaceNodeId,
     pub state: Rc<State>,
@@ -137,7 +140,7 @@ impl WorkspaceNode {
             self.discard_child_properties(&*prev);
         }
         self.pull_child_properties(&**container);
-        let pos = self.position.get();
+        let pos = self.container_rect();
         container.clone().tl_change_extents(&pos);
         container.tl_set_parent(self.clone());
         container.tl_set_visible(self.container_visible());
@@ -156,10 +159,25 @@ impl WorkspaceNode {
     pub fn change_extents(&self, rect: &Rect) {
         self.position.set(*rect);
         if let Some(c) = self.container.get() {
-            c.tl_change_extents(rect);
+            c.tl_change_extents(&self.container_rect());
         }
     }
 
+    fn container_rect(&self) -> Rect {
+        let pos = self.position.get();
+        let gap = self.state.theme.sizes.outer_gap.get();
+        // Shrink the gaps instead of the container when they do not fit.
+        let gap_x = gap.min((pos.width() - MIN_CONTAINER_SIZE) / 2).max(0);
+        let gap_y = gap.min((pos.height() - MIN_CONTAINER_SIZE) / 2).max(0);
+        Rect::new(
+            pos.x1() + gap_x,
+            pos.y1() + gap_y,
+            pos.x2() - gap_x,
+            pos.y2() - gap_y,
+        )
+        .unwrap_or(pos)
+    }
+
     pub fn flush_jay_workspaces(&self) {
         for jw in self.jay_workspaces.lock().values() {
             jw.send_done();
@@ -287,6 +305,13 @@ impl Node for WorkspaceNode {
         usecase: FindTreeUsecase,
     ) -> FindTreeResult {
         if let Some(n) = self.container.get() {
+            let pos = self.position.get();
+            let cpos = n.node_absolute_position();
+            let (abs_x, abs_y) = (x + pos.x1(), y + pos.y1());
+            if !cpos.contains(abs_x, abs_y) {
+                return FindTreeResult::Other;
+            }
+            let (x, y) = (abs_x - cpos.x1(), abs_y - cpos.y1());
             tree.push(FoundNode {
                 node: n.clone(),
                 x,
@@ -0,0 +1,130 @@
+use {
+    crate::{
+        async_engine::SpawnedFuture,
+        client::ClientId,
+        dbus::{DbusSocket, DictEntry, ReconnectHandler, SignalHandler, Variant},
+        ifs::jay_battery_watcher::JayBatteryWatcher,
+        state::State,
+        utils::{copyhashmap::CopyHashMap, errorfmt::ErrorFmt},
+        wire::JayBatteryWatcherId,
+    },
+    std::{
+        borrow::Cow,
+        cell::{Cell, RefCell},
+        rc::{Rc, Weak},
+    },
+};
+
+const UPOWER_NAME: &str = "org.freedesktop.UPower";
+const DISPLAY_DEVICE_PATH: &str = "/org/freedesktop/UPower/devices/DisplayDevice";
+const DEVICE_INTERFACE: &str = "org.freedesktop.UPower.Device";
+
+/// The battery status as reported by the UPower display device.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct BatteryStatus {
+    pub percentage: f64,
+    pub state: u32,
+    pub time_to_empty: i64,
+}
+
+#[derive(Default)]
+pub struct UPower {
+    pub status: Cell<Option<BatteryStatus>>,
+    pub watchers: CopyHashMap<(ClientId, JayBatteryWatcherId), Rc<JayBatteryWatcher>>,
+    started: Cell<bool>,
+    task: Cell<Option<SpawnedFuture<()>>>,
+    handlers: RefCell<Option<(SignalHandler, ReconnectHandler)>>,
+}
+
+impl UPower {
+    pub fn clear(&self) {
+        self.watchers.clear();
+        self.started.set(false);
+        self.task.take();
+        self.handlers.take();
+    }
+
+    /// Connects to UPower if this has not already happened.
+    ///
+    /// The connection is only established once the first client is interested in the
+    /// battery status.
+    pub fn start(&self, state: &Rc<State>) {
+        if self.started.replace(true) {
+            return;
+        }
+        let future = state.eng.spawn("upower", connect(state.clone()));
+        self.task.set(Some(future));
+    }
+
+    fn update<'a>(&self, props: &[DictEntry<Cow<'a, str>, Variant<'a>>]) {
+        let old = self.status.get();
+        let mut status = old.unwrap_or_default();
+        for prop in props {
+            match (&*prop.key, &prop.value) {
+                ("Percentage", Variant::F64(v)) => status.percentage = *v,
+                ("State", Variant::U32(v)) => status.state = *v,
+                ("TimeToEmpty", Variant::I64(v)) => status.time_to_empty = *v,
+                _ => {}
+            }
+        }
+        if old == Some(status) {
+            return;
+        }
+        self.status.set(Some(status));
+        for watcher in self.watchers.lock().values() {
+            watcher.send_status(&status);
+        }
+    }
+}
+
+async fn connect(state: Rc<State>) {
+    let socket = match state.dbus.system().await {
+        Ok(s) => s,
+        Err(e) => {
+            log::warn!("Could not connect to the system bus: {}", ErrorFmt(e));
+            return;
+        }
+    };
+    let weak = Rc::downgrade(&state);
+    let signal = socket.handle_properties_changed(
+        UPOWER_NAME,
+        DISPLAY_DEVICE_PATH,
+        DEVICE_INTERFACE,
+        move |changed, _| {
+            if let Some(state) = weak.upgrade() {
+                state.upower.update(changed);
+            }
+        },
+    );
+    let signal = match signal {
+        Ok(s) => s,
+        Err(e) => {
+            log::warn!("Could not subscribe to UPower signals: {}", ErrorFmt(e));
+            return;
+        }
+    };
+    let reconnect = socket.on_reconnect({
+        let state = Rc::downgrade(&state);
+        move |socket| fetch(&state, socket)
+    });
+    *state.upower.handlers.borrow_mut() = Some((signal, reconnect));
+    fetch(&Rc::downgrade(&state), &socket);
+}
+
+fn fetch(state: &Weak<State>, socket: &Rc<DbusSocket>) {
+    let state = state.clone();
+    socket.get_all(
+        UPOWER_NAME,
+        DISPLAY_DEVICE_PATH,
+        DEVICE_INTERFACE,
+        move |res| {
+            let Some(state) = state.upgrade() else {
+                return;
+            };
+            match res {
+                Ok(props) => state.upower.update(props),
+                Err(e) => log::warn!("Could not retrieve the battery status: {}", ErrorFmt(e)),
+            }
+        },
+    );
+}
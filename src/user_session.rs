@@ -1,6 +1,6 @@
 use {
     crate::{
-        dbus::{DbusError, DictEntry, BUS_DEST, BUS_PATH},
+        dbus::{DbusError, DbusSocket, DictEntry, BUS_DEST, BUS_PATH},
         state::State,
         utils::errorfmt::ErrorFmt,
         wire_dbus::org,
@@ -38,6 +38,35 @@ async fn import_environment_(
         Ok(s) => s,
         Err(e) => return Err(UserSessionError::AcquireSessionBus(e)),
     };
+    state
+        .imported_environment
+        .set(key.to_string(), Rc::new(value.to_string()));
+    let handler = match state.session_bus_reconnect_handler.take() {
+        Some(h) => h,
+        None => session.on_reconnect({
+            let state = Rc::downgrade(state);
+            move |session| {
+                let Some(state) = state.upgrade() else {
+                    return;
+                };
+                let env: Vec<_> = state
+                    .imported_environment
+                    .lock()
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect();
+                for (key, value) in env {
+                    send_environment(session, &key, &value);
+                }
+            }
+        }),
+    };
+    state.session_bus_reconnect_handler.set(Some(handler));
+    send_environment(&session, key, value);
+    Ok(())
+}
+
+fn send_environment(session: &Rc<DbusSocket>, key: &str, value: &str) {
     let setting = format!("{}={}", key, value);
     session.call(
         BUS_DEST,
@@ -80,5 +109,4 @@ async fn import_environment_(
             }
         },
     );
-    Ok(())
 }
@@ -67,6 +67,14 @@ impl OutBufferSwapchain {
         self.pending.len() > LIMIT_PENDING
     }
 
+    pub fn queued_bytes(&self) -> usize {
+        self.pending
+            .iter()
+            .chain([&self.cur])
+            .map(|b| b.meta.write_pos - b.meta.read_pos)
+            .sum()
+    }
+
     pub fn commit(&mut self) {
         if self.cur.meta.write_pos > 0 {
             let new = self.free.pop().unwrap_or_default();
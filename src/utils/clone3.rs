@@ -1,5 +1,6 @@
 use {
-    crate::forker::ForkerError,
+    crate::{forker::ForkerError, utils::oserror::OsError},
+    std::ptr,
     uapi::{c, OwnedFd},
 };
 
@@ -58,3 +59,13 @@ pub fn fork_with_pidfd(pidfd_for_child: bool) -> Result<Forked, ForkerError> {
         Ok(res)
     }
 }
+
+/// Sends a signal to the process referred to by `pidfd`.
+///
+/// Unlike a pid, a pidfd never refers to a different process after the process has exited.
+pub fn pidfd_send_signal(pidfd: &OwnedFd, sig: c::c_int) -> Result<(), OsError> {
+    let info = ptr::null::<c::siginfo_t>();
+    let res = unsafe { c::syscall(c::SYS_pidfd_send_signal, pidfd.raw(), sig, info, 0) };
+    uapi::map_err!(res)?;
+    Ok(())
+}
@@ -18,6 +18,8 @@ pub trait TransformExt: Sized {
     fn from_wl(wl: i32) -> Option<Self>;
 
     fn apply_point(self, width: i32, height: i32, point: (i32, i32)) -> (i32, i32);
+
+    fn unapply_normalized_point(self, point: (f64, f64)) -> (f64, f64);
 }
 
 impl TransformExt for Transform {
@@ -68,4 +70,17 @@ impl TransformExt for Transform {
             FlipRotate270 => (width - y, height - x),
         }
     }
+
+    fn unapply_normalized_point(self, (x, y): (f64, f64)) -> (f64, f64) {
+        match self {
+            None => (x, y),
+            Rotate90 => (1.0 - y, x),
+            Rotate180 => (1.0 - x, 1.0 - y),
+            Rotate270 => (y, 1.0 - x),
+            Flip => (1.0 - x, y),
+            FlipRotate90 => (y, x),
+            FlipRotate180 => (x, 1.0 - y),
+            FlipRotate270 => (1.0 - y, 1.0 - x),
+        }
+    }
 }
@@ -0,0 +1,246 @@
+use {
+    crate::{
+        cpu_worker::{AsyncCpuWork, CpuJob, CpuWork, CpuWorker, PendingJob},
+        format::ARGB8888,
+        gfx_api::{AcquireSync, GfxError, GfxTexture, ReleaseSync},
+        rect::Rect,
+        renderer::Renderer,
+        state::State,
+        utils::errorfmt::ErrorFmt,
+    },
+    jay_config::video::BackgroundMode,
+    png::{ColorType, Decoder, DecodingError, Transformations},
+    std::{
+        cell::{Cell, RefCell},
+        fs::File,
+        io::{self, BufReader},
+        rc::Rc,
+    },
+    thiserror::Error,
+};
+
+const MAX_SIZE: u32 = 16384;
+
+/// The maximum number of tiles rendered in tile mode. Small images are scaled up
+/// to stay below this number.
+const MAX_TILES: f64 = 1024.0;
+
+#[derive(Debug, Error)]
+pub enum WallpaperError {
+    #[error("Could not open the file")]
+    Open(#[source] io::Error),
+    #[error("Could not decode the image")]
+    Decode(#[source] DecodingError),
+    #[error("The image is too large: {0}x{1}")]
+    TooLarge(u32, u32),
+    #[error("Could not upload the image")]
+    Upload(#[source] GfxError),
+}
+
+pub struct Wallpaper {
+    pub mode: BackgroundMode,
+    width: i32,
+    height: i32,
+    data: Vec<Cell<u8>>,
+    texture: RefCell<Option<(u32, Rc<dyn GfxTexture>)>>,
+}
+
+impl Wallpaper {
+    /// Loads the image at `path` on the CPU worker and invokes `f` with the result.
+    pub fn load<F>(cpu_worker: &CpuWorker, path: &str, mode: BackgroundMode, f: F) -> PendingJob
+    where
+        F: FnOnce(Result<Wallpaper, WallpaperError>) + 'static,
+    {
+        let job = Box::new(LoadJob {
+            work: LoadWork {
+                path: path.to_string(),
+                result: None,
+            },
+            mode,
+            on_completed: Some(Box::new(f)),
+        });
+        cpu_worker.submit(job)
+    }
+
+    pub fn create_texture(&self, state: &State) -> Result<(), WallpaperError> {
+        if let Some(ctx) = state.render_ctx.get() {
+            let tex = ctx
+                .shmem_texture(
+                    None,
+                    &self.data,
+                    ARGB8888,
+                    self.width,
+                    self.height,
+                    self.width * 4,
+                    None,
+                )
+                .map_err(WallpaperError::Upload)?
+                .into_texture();
+            let version = state.render_ctx_version.get();
+            *self.texture.borrow_mut() = Some((version, tex));
+        }
+        Ok(())
+    }
+
+    fn texture(&self, state: &State) -> Option<Rc<dyn GfxTexture>> {
+        let version = state.render_ctx_version.get();
+        if let Some((v, tex)) = &*self.texture.borrow() {
+            if *v == version {
+                return Some(tex.clone());
+            }
+        }
+        if let Err(e) = self.create_texture(state) {
+            log::error!("Could not create the background texture: {}", ErrorFmt(e));
+            self.texture.take();
+            return None;
+        }
+        let texture = self.texture.borrow();
+        texture.as_ref().map(|(_, tex)| tex.clone())
+    }
+
+    pub fn render(&self, renderer: &mut Renderer, output: Rect) {
+        let Some(tex) = self.texture(renderer.state) else {
+            return;
+        };
+        let (iw, ih) = (self.width as f64, self.height as f64);
+        let (ow, oh) = (output.width() as f64, output.height() as f64);
+        let (width, height) = match self.mode {
+            BackgroundMode::Fill => {
+                let scale = (ow / iw).max(oh / ih);
+                ((iw * scale).round() as i32, (ih * scale).round() as i32)
+            }
+            BackgroundMode::Fit => {
+                let scale = (ow / iw).min(oh / ih);
+                ((iw * scale).round() as i32, (ih * scale).round() as i32)
+            }
+            BackgroundMode::Stretch => (output.width(), output.height()),
+            BackgroundMode::Tile | BackgroundMode::Center => (self.width, self.height),
+        };
+        let (mut width, mut height) = (width.max(1), height.max(1));
+        if self.mode == BackgroundMode::Tile {
+            let tiles = |w: i32, h: i32| (ow / w as f64).ceil() * (oh / h as f64).ceil();
+            while tiles(width, height) > MAX_TILES {
+                width *= 2;
+                height *= 2;
+            }
+        }
+        let scale = renderer.base.scale;
+        let mut render = |x: i32, y: i32| {
+            renderer.base.render_texture(
+                &tex,
+                None,
+                false,
+                x,
+                y,
+                None,
+                Some((width, height)),
+                scale,
+                Some(&output),
+                None,
+                AcquireSync::None,
+                ReleaseSync::None,
+            );
+        };
+        if self.mode == BackgroundMode::Tile {
+            for y in (output.y1()..output.y2()).step_by(height as usize) {
+                for x in (output.x1()..output.x2()).step_by(width as usize) {
+                    render(x, y);
+                }
+            }
+        } else {
+            let x = output.x1() + (output.width() - width) / 2;
+            let y = output.y1() + (output.height() - height) / 2;
+            render(x, y);
+        }
+    }
+}
+
+struct Image {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+struct LoadWork {
+    path: String,
+    result: Option<Result<Image, WallpaperError>>,
+}
+
+struct LoadJob {
+    work: LoadWork,
+    mode: BackgroundMode,
+    on_completed: Option<Box<dyn FnOnce(Result<Wallpaper, WallpaperError>)>>,
+}
+
+impl CpuWork for LoadWork {
+    fn run(&mut self) -> Option<Box<dyn AsyncCpuWork>> {
+        self.result = Some(decode(&self.path));
+        None
+    }
+}
+
+impl CpuJob for LoadJob {
+    fn work(&mut self) -> &mut dyn CpuWork {
+        &mut self.work
+    }
+
+    fn completed(mut self: Box<Self>) {
+        let res = self.work.result.take().unwrap();
+        let res = res.map(|image| Wallpaper {
+            mode: self.mode,
+            width: image.width as _,
+            height: image.height as _,
+            data: image.data.into_iter().map(Cell::new).collect(),
+            texture: Default::default(),
+        });
+        if let Some(f) = self.on_completed.take() {
+            f(res);
+        }
+    }
+}
+
+fn decode(path: &str) -> Result<Image, WallpaperError> {
+    let file = File::open(path).map_err(WallpaperError::Open)?;
+    let mut decoder = Decoder::new(BufReader::new(file));
+    decoder.set_transformations(Transformations::EXPAND | Transformations::STRIP_16);
+    let mut reader = decoder.read_info().map_err(WallpaperError::Decode)?;
+    let (width, height) = {
+        let info = reader.info();
+        (info.width, info.height)
+    };
+    if width > MAX_SIZE || height > MAX_SIZE {
+        return Err(WallpaperError::TooLarge(width, height));
+    }
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader
+        .next_frame(&mut buf)
+        .map_err(WallpaperError::Decode)?;
+    let channels = match info.color_type {
+        ColorType::Grayscale => 1,
+        ColorType::GrayscaleAlpha => 2,
+        ColorType::Rgb => 3,
+        _ => 4,
+    };
+    let mut data = Vec::with_capacity(info.width as usize * info.height as usize * 4);
+    for line in buf.chunks(info.line_size).take(info.height as usize) {
+        for pixel in line.chunks_exact(channels) {
+            let (r, g, b, a) = match *pixel {
+                [l] => (l, l, l, 255),
+                [l, a] => (l, l, l, a),
+                [r, g, b] => (r, g, b, 255),
+                [r, g, b, a, ..] => (r, g, b, a),
+                _ => unreachable!(),
+            };
+            let premultiply = |c: u8| ((c as u32 * a as u32 + 127) / 255) as u8;
+            data.push(premultiply(b));
+            data.push(premultiply(g));
+            data.push(premultiply(r));
+            data.push(a);
+        }
+    }
+    Ok(Image {
+        width: info.width,
+        height: info.height,
+        data,
+    })
+}
@@ -101,6 +101,7 @@ pub struct WheelData {
     fd: Rc<OwnedFd>,
     next_id: NumCell<u64>,
     start: Time,
+    offset: Cell<Duration>,
     current_expiration: Cell<Option<Time>>,
     dispatchers: CopyHashMap<u64, Rc<WheelTimeoutData>>,
     expirations: RefCell<BinaryHeap<Reverse<WheelEntry>>>,
@@ -121,6 +122,7 @@ impl Wheel {
             fd,
             next_id: NumCell::new(1),
             start: eng.now(),
+            offset: Default::default(),
             current_expiration: Default::default(),
             dispatchers: Default::default(),
             expirations: Default::default(),
@@ -150,6 +152,38 @@ impl Wheel {
     }
 
     pub fn timeout(&self, ms: u64) -> WheelTimeoutFuture {
+        let now = self.data.now();
+        self.timeout_at(now + Duration::from_millis(ms))
+    }
+
+    /// Creates a timer that first expires after `delay_ms` and then every `period_ms`.
+    ///
+    /// Expirations are scheduled relative to the creation of the timer so that the
+    /// period does not drift. Expirations that have been missed are coalesced into one.
+    pub fn interval(self: &Rc<Self>, delay_ms: u64, period_ms: u64) -> WheelInterval {
+        WheelInterval {
+            wheel: self.clone(),
+            next: self.data.now() + Duration::from_millis(delay_ms),
+            period: Duration::from_millis(period_ms.max(1)),
+        }
+    }
+
+    /// Advances the clock of the wheel by `ms` milliseconds.
+    ///
+    /// Timeouts that expire in the meantime complete immediately. This allows tests
+    /// to run without waiting for real time to pass.
+    #[cfg(feature = "it")]
+    pub fn advance(&self, ms: u64) {
+        let data = &self.data;
+        let offset = data.offset.get() + Duration::from_millis(ms);
+        data.offset.set(offset);
+        if let Err(e) = data.expire() {
+            log::error!("Could not dispatch wheel expirations: {}", ErrorFmt(e));
+            data.kill();
+        }
+    }
+
+    fn timeout_at(&self, expiration: Time) -> WheelTimeoutFuture {
         if self.data.destroyed.get() {
             return WheelTimeoutFuture {
                 data: Rc::new(WheelTimeoutData {
@@ -161,23 +195,11 @@ impl Wheel {
             };
         }
         let future = self.future();
-        let now = self.data.eng.now();
-        let expiration = (now + Duration::from_millis(ms)).round_to_ms();
+        let expiration = expiration.round_to_ms();
         let current = self.data.current_expiration.get();
         if current.is_none() || expiration - self.data.start < current.unwrap() - self.data.start {
-            let res = uapi::timerfd_settime(
-                self.data.fd.raw(),
-                c::TFD_TIMER_ABSTIME,
-                &c::itimerspec {
-                    it_interval: uapi::pod_zeroed(),
-                    it_value: expiration.0,
-                },
-            );
-            if let Err(e) = res {
-                future
-                    .data
-                    .expired
-                    .set(Some(Err(WheelError::SetFailed(e.into()))));
+            if let Err(e) = self.data.arm(expiration) {
+                future.data.expired.set(Some(Err(e)));
                 return future;
             }
             self.data.current_expiration.set(Some(expiration));
@@ -193,6 +215,28 @@ impl Wheel {
     }
 }
 
+pub struct WheelInterval {
+    wheel: Rc<Wheel>,
+    next: Time,
+    period: Duration,
+}
+
+impl WheelInterval {
+    /// Returns a future that completes at the next expiration of the timer.
+    ///
+    /// Dropping the future cancels the expiration.
+    pub fn tick(&mut self) -> WheelTimeoutFuture {
+        let now = self.wheel.data.now();
+        if self.next < now {
+            let missed = (now - self.next).as_nanos() / self.period.as_nanos();
+            self.next = self.next + self.period * missed as u32;
+        }
+        let future = self.wheel.timeout_at(self.next);
+        self.next = self.next + self.period;
+        future
+    }
+}
+
 impl WheelData {
     fn kill(&self) {
         self.destroyed.set(true);
@@ -214,11 +258,31 @@ impl WheelData {
         }
     }
 
+    fn now(&self) -> Time {
+        self.eng.now() + self.offset.get()
+    }
+
+    fn arm(&self, expiration: Time) -> Result<(), WheelError> {
+        let res = uapi::timerfd_settime(
+            self.fd.raw(),
+            c::TFD_TIMER_ABSTIME,
+            &c::itimerspec {
+                it_interval: uapi::pod_zeroed(),
+                it_value: (expiration - self.offset.get()).0,
+            },
+        );
+        res.map_err(|e| WheelError::SetFailed(e.into()))
+    }
+
     async fn dispatch_once(&self, n: &mut TypedBuf<u64>) -> Result<(), WheelError> {
         if let Err(e) = self.ring.read(&self.fd, n.buf()).await {
             return Err(WheelError::Read(e));
         }
-        let now = self.eng.now();
+        self.expire()
+    }
+
+    fn expire(&self) -> Result<(), WheelError> {
+        let now = self.now();
         let dist = now - self.start;
         {
             let mut expirations = self.expirations.borrow_mut();
@@ -234,17 +298,7 @@ impl WheelData {
             self.current_expiration.set(None);
             while let Some(Reverse(entry)) = expirations.peek() {
                 if self.dispatchers.get(&entry.id).is_some() {
-                    let res = uapi::timerfd_settime(
-                        self.fd.raw(),
-                        c::TFD_TIMER_ABSTIME,
-                        &c::itimerspec {
-                            it_interval: uapi::pod_zeroed(),
-                            it_value: entry.expiration.0,
-                        },
-                    );
-                    if let Err(e) = res {
-                        return Err(WheelError::SetFailed(e.into()));
-                    }
+                    self.arm(entry.expiration)?;
                     self.current_expiration.set(Some(entry.expiration));
                     break;
                 }
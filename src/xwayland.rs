@@ -86,8 +86,22 @@ enum XWaylandError {
     XfixesQueryVersion(#[source] XconError),
 }
 
+/// Xwayland instances that run for at least this long are considered to have started
+/// successfully.
+const STABLE_RUNTIME_USEC: u64 = 10_000_000;
+const MIN_RESTART_DELAY_MS: u64 = 100;
+const MAX_RESTART_DELAY_MS: u64 = 30_000;
+
 pub async fn manage(state: Rc<State>) {
+    let mut restart_delay_ms = 0;
     loop {
+        if restart_delay_ms > 0 {
+            log::info!("Delaying the restart of Xwayland by {restart_delay_ms} ms");
+            if let Err(e) = state.wheel.timeout(restart_delay_ms).await {
+                log::error!("Could not wait for the restart delay: {}", ErrorFmt(e));
+                return;
+            }
+        }
         let forker = match state.forker.get() {
             Some(f) => f,
             None => {
@@ -118,12 +132,18 @@ pub async fn manage(state: Rc<State>) {
             return;
         }
         log::info!("Starting Xwayland");
+        let started = state.now_usec();
         if let Err(e) = run(&state, &forker, socket).await {
             log::error!("Xwayland failed: {}", ErrorFmt(e));
         } else {
             log::warn!("Xwayland exited unexpectedly");
         }
         forker.unsetenv(DISPLAY.as_bytes());
+        restart_delay_ms = if state.now_usec() - started >= STABLE_RUNTIME_USEC {
+            0
+        } else {
+            (restart_delay_ms * 2).clamp(MIN_RESTART_DELAY_MS, MAX_RESTART_DELAY_MS)
+        };
     }
 }
 
@@ -238,6 +258,7 @@ async fn detect_features(state: &State, forker: &ForkerProxy) -> XwaylandFeature
         vec!["-help".to_string()],
         vec![],
         vec![(2, Rc::new(write))],
+        false,
     );
     let read = Rc::new(read);
     let mut help = Vec::new();
@@ -274,6 +295,7 @@ pub enum XWaylandEvent {
     Activate(Rc<XwindowData>),
     ActivateRoot,
     Close(Rc<XwindowData>),
+    Kill(Rc<XwindowData>),
     #[expect(dead_code)]
     SeatChanged,
 
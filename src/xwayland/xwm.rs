@@ -1,5 +1,8 @@
 #![allow(clippy::await_holding_refcell_ref)] // all borrows are to data that is only used by this task
 
+#[cfg(test)]
+mod tests;
+
 use {
     crate::{
         async_engine::SpawnedFuture,
@@ -16,7 +19,7 @@ use {
             },
             wl_seat::{SeatId, WlSeatGlobal},
             wl_surface::{
-                x_surface::xwindow::{XInputModel, Xwindow, XwindowData},
+                x_surface::xwindow::{XInputModel, Xwindow, XwindowData, XwindowInfo},
                 WlSurface,
             },
         },
@@ -47,13 +50,13 @@ use {
                 EVENT_MASK_FOCUS_CHANGE, EVENT_MASK_PROPERTY_CHANGE,
                 EVENT_MASK_SUBSTRUCTURE_NOTIFY, EVENT_MASK_SUBSTRUCTURE_REDIRECT,
                 ICCCM_WM_HINT_INPUT, ICCCM_WM_STATE_ICONIC, ICCCM_WM_STATE_NORMAL,
-                ICCCM_WM_STATE_WITHDRAWN, INPUT_FOCUS_POINTER_ROOT, MWM_HINTS_DECORATIONS_FIELD,
-                MWM_HINTS_FLAGS_FIELD, NOTIFY_DETAIL_POINTER, NOTIFY_MODE_GRAB, NOTIFY_MODE_UNGRAB,
-                PROP_MODE_APPEND, PROP_MODE_REPLACE, RES_CLIENT_ID_MASK_LOCAL_CLIENT_PID,
-                SELECTION_CLIENT_CLOSE_MASK, SELECTION_WINDOW_DESTROY_MASK,
-                SET_SELECTION_OWNER_MASK, STACK_MODE_ABOVE, STACK_MODE_BELOW,
-                WINDOW_CLASS_INPUT_OUTPUT, _NET_WM_STATE_ADD, _NET_WM_STATE_REMOVE,
-                _NET_WM_STATE_TOGGLE,
+                ICCCM_WM_STATE_WITHDRAWN, INPUT_FOCUS_POINTER_ROOT, MWM_HINTS_DECORATIONS,
+                MWM_HINTS_DECORATIONS_FIELD, MWM_HINTS_FLAGS_FIELD, NOTIFY_DETAIL_POINTER,
+                NOTIFY_MODE_GRAB, NOTIFY_MODE_UNGRAB, PROP_MODE_APPEND, PROP_MODE_REPLACE,
+                RES_CLIENT_ID_MASK_LOCAL_CLIENT_PID, SELECTION_CLIENT_CLOSE_MASK,
+                SELECTION_WINDOW_DESTROY_MASK, SET_SELECTION_OWNER_MASK, STACK_MODE_ABOVE,
+                STACK_MODE_BELOW, WINDOW_CLASS_INPUT_OUTPUT, _NET_WM_STATE_ADD,
+                _NET_WM_STATE_REMOVE, _NET_WM_STATE_TOGGLE,
             },
             Event, XEvent, Xcon, XconError,
         },
@@ -221,6 +224,7 @@ pub struct Wm {
     c: Rc<Xcon>,
     atoms: Atoms,
     never_focus: AHashSet<u32>,
+    floating_types: AHashSet<u32>,
     root: u32,
     xwin: u32,
     client: Rc<Client>,
@@ -304,6 +308,21 @@ impl Wm {
             nf.insert(atoms._NET_WM_WINDOW_TYPE_UTILITY);
             nf
         };
+        let floating_types = {
+            let mut ft = AHashSet::new();
+            ft.insert(atoms._NET_WM_WINDOW_TYPE_COMBO);
+            ft.insert(atoms._NET_WM_WINDOW_TYPE_DIALOG);
+            ft.insert(atoms._NET_WM_WINDOW_TYPE_DND);
+            ft.insert(atoms._NET_WM_WINDOW_TYPE_DROPDOWN_MENU);
+            ft.insert(atoms._NET_WM_WINDOW_TYPE_MENU);
+            ft.insert(atoms._NET_WM_WINDOW_TYPE_NOTIFICATION);
+            ft.insert(atoms._NET_WM_WINDOW_TYPE_POPUP_MENU);
+            ft.insert(atoms._NET_WM_WINDOW_TYPE_SPLASH);
+            ft.insert(atoms._NET_WM_WINDOW_TYPE_TOOLBAR);
+            ft.insert(atoms._NET_WM_WINDOW_TYPE_TOOLTIP);
+            ft.insert(atoms._NET_WM_WINDOW_TYPE_UTILITY);
+            ft
+        };
         let root = c.root_window();
         {
             let events = 0
@@ -509,6 +528,7 @@ impl Wm {
             c,
             atoms,
             never_focus,
+            floating_types,
             root,
             xwin,
             client,
@@ -599,6 +619,7 @@ impl Wm {
             }
             XWaylandEvent::ActivateRoot => self.activate_window(None, Initiator::Wayland).await,
             XWaylandEvent::Close(window) => self.close_window(&window).await,
+            XWaylandEvent::Kill(window) => self.kill_window(&window),
             XWaylandEvent::SeatChanged => self.seats_changed(),
             XWaylandEvent::IpcCancelSource {
                 location,
@@ -1013,15 +1034,17 @@ impl Wm {
                 }
             }
         }
-        let accepts_input = window.info.icccm_hints.input.get();
-        let mask = if accepts_input {
-            EVENT_MASK_SUBSTRUCTURE_REDIRECT
-        } else {
-            0
-        };
-        self.send_wm_message(window, mask, &[self.atoms.WM_TAKE_FOCUS, 0])
-            .await;
-        if accepts_input {
+        let (take_focus, set_input_focus) = focus_messages(window.info.input_model.get());
+        if take_focus {
+            let mask = if set_input_focus {
+                EVENT_MASK_SUBSTRUCTURE_REDIRECT
+            } else {
+                0
+            };
+            self.send_wm_message(window, mask, &[self.atoms.WM_TAKE_FOCUS, 0])
+                .await;
+        }
+        if set_input_focus {
             let sif = SetInputFocus {
                 revert_to: INPUT_FOCUS_POINTER_ROOT,
                 focus: window.window_id,
@@ -1069,12 +1092,7 @@ impl Wm {
     fn compute_input_model(&self, data: &Rc<XwindowData>) {
         let has_wm_take_focus = data.info.protocols.contains(&self.atoms.WM_TAKE_FOCUS);
         let accepts_input = data.info.icccm_hints.input.get();
-        let model = match (accepts_input, has_wm_take_focus) {
-            (false, false) => XInputModel::None,
-            (true, false) => XInputModel::Passive,
-            (true, true) => XInputModel::Local,
-            (false, true) => XInputModel::Global,
-        };
+        let model = input_model(accepts_input, has_wm_take_focus);
         data.info.input_model.set(model);
     }
 
@@ -1214,12 +1232,13 @@ impl Wm {
             if let Some(w) = self.windows.get(w) {
                 if data.is_ancestor_of(w.clone()) {
                     log::error!("Cannot set WM_TRANSIENT_FOR because it would create a cycle");
-                    return;
+                } else {
+                    w.children.set(data.window_id, data.clone());
+                    data.parent.set(Some(w.clone()));
                 }
-                w.children.set(data.window_id, data.clone());
-                data.parent.set(Some(w.clone()));
             }
         }
+        self.update_wants_floating(data);
     }
 
     async fn load_window_wm_protocols(&self, data: &Rc<XwindowData>) {
@@ -1350,6 +1369,7 @@ impl Wm {
             .motif_hints
             .decorations
             .set(values[MWM_HINTS_DECORATIONS_FIELD]);
+        self.update_wants_floating(data);
     }
 
     async fn load_window_net_startup_id(&self, data: &Rc<XwindowData>) {
@@ -1883,12 +1903,16 @@ impl Wm {
             self.send_wm_message(window, 0, &[self.atoms.WM_DELETE_WINDOW])
                 .await;
         } else {
-            self.c.call(&KillClient {
-                resource: window.window_id,
-            });
+            self.kill_window(window);
         }
     }
 
+    fn kill_window(&mut self, window: &Rc<XwindowData>) {
+        self.c.call(&KillClient {
+            resource: window.window_id,
+        });
+    }
+
     async fn activate_window(&mut self, window: Option<&Rc<XwindowData>>, initiator: Initiator) {
         // log::info!("xwm activate_window {:?}", window.map(|w| w.window_id));
         if self.focus_window.as_ref().map(|w| w.window_id) == window.map(|w| w.window_id) {
@@ -2498,34 +2522,60 @@ impl Wm {
     }
 
     fn update_wants_floating(&self, data: &Rc<XwindowData>) {
-        let res = data.info.modal.get()
-            || data
-                .info
-                .window_types
-                .contains(&self.atoms._NET_WM_WINDOW_TYPE_DIALOG)
-            || data
-                .info
-                .window_types
-                .contains(&self.atoms._NET_WM_WINDOW_TYPE_UTILITY)
-            || data
-                .info
-                .window_types
-                .contains(&self.atoms._NET_WM_WINDOW_TYPE_TOOLBAR)
-            || data
-                .info
-                .window_types
-                .contains(&self.atoms._NET_WM_WINDOW_TYPE_SPLASH)
-            || {
-                let max_w = data.info.normal_hints.max_width.get();
-                let min_w = data.info.normal_hints.min_width.get();
-                let max_h = data.info.normal_hints.max_height.get();
-                let min_h = data.info.normal_hints.min_height.get();
-                max_w > 0 && max_h > 0 && max_w == min_w && max_h == min_h
-            };
+        let has_parent = data.parent.is_some();
+        let res = wants_floating(&data.info, has_parent, &self.floating_types);
         data.info.wants_floating.set(res);
     }
 }
 
+/// Returns whether a managed window should be mapped as a floating window.
+///
+/// `floating_types` contains the `_NET_WM_WINDOW_TYPE` atoms of windows that float.
+/// Override-redirect windows are not managed and are always mapped as stacked nodes.
+fn wants_floating(info: &XwindowInfo, has_parent: bool, floating_types: &AHashSet<u32>) -> bool {
+    if info.modal.get() || has_parent {
+        return true;
+    }
+    let window_types = info.window_types.lock();
+    if window_types.keys().any(|t| floating_types.contains(t)) {
+        return true;
+    }
+    let hints = &info.normal_hints;
+    let (min_w, max_w) = (hints.min_width.get(), hints.max_width.get());
+    let (min_h, max_h) = (hints.min_height.get(), hints.max_height.get());
+    if max_w > 0 && max_h > 0 && max_w == min_w && max_h == min_h {
+        return true;
+    }
+    // Windows that turn off all decorations without declaring a window type are usually
+    // splash screens.
+    let motif = &info.motif_hints;
+    let sets_decorations = motif.flags.get() & MWM_HINTS_DECORATIONS != 0;
+    sets_decorations && motif.decorations.get() == 0 && window_types.is_empty()
+}
+
+/// Returns the ICCCM input model of a window.
+fn input_model(accepts_input: bool, has_wm_take_focus: bool) -> XInputModel {
+    match (accepts_input, has_wm_take_focus) {
+        (false, false) => XInputModel::None,
+        (true, false) => XInputModel::Passive,
+        (true, true) => XInputModel::Local,
+        (false, true) => XInputModel::Global,
+    }
+}
+
+/// Returns whether focusing a window sends it `WM_TAKE_FOCUS` and whether it sets the
+/// input focus to the window.
+///
+/// Only windows that list `WM_TAKE_FOCUS` in `WM_PROTOCOLS` receive the message.
+fn focus_messages(model: XInputModel) -> (bool, bool) {
+    match model {
+        XInputModel::None => (false, false),
+        XInputModel::Passive => (false, true),
+        XInputModel::Local => (true, true),
+        XInputModel::Global => (true, false),
+    }
+}
+
 struct XToWaylandTransfer {
     id: u64,
     data: Buf,
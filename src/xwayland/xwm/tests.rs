@@ -0,0 +1,118 @@
+use {
+    crate::{
+        ifs::wl_surface::x_surface::xwindow::{XInputModel, XwindowInfo},
+        xcon::consts::{MWM_DECOR_ALL, MWM_HINTS_DECORATIONS},
+        xwayland::xwm::{focus_messages, input_model, wants_floating},
+    },
+    ahash::AHashSet,
+};
+
+const NORMAL: u32 = 1;
+const DIALOG: u32 = 2;
+const POPUP_MENU: u32 = 3;
+
+fn floating_types() -> AHashSet<u32> {
+    [DIALOG, POPUP_MENU].into_iter().collect()
+}
+
+fn floats(info: &XwindowInfo, has_parent: bool) -> bool {
+    wants_floating(info, has_parent, &floating_types())
+}
+
+#[test]
+fn normal_window() {
+    let info = XwindowInfo::default();
+    info.window_types.set(NORMAL, ());
+    assert!(!floats(&info, false));
+}
+
+#[test]
+fn no_properties() {
+    assert!(!floats(&XwindowInfo::default(), false));
+}
+
+#[test]
+fn transient_for() {
+    let info = XwindowInfo::default();
+    info.window_types.set(NORMAL, ());
+    assert!(floats(&info, true));
+}
+
+#[test]
+fn modal() {
+    let info = XwindowInfo::default();
+    info.modal.set(true);
+    assert!(floats(&info, false));
+}
+
+#[test]
+fn window_types() {
+    for ty in [DIALOG, POPUP_MENU] {
+        let info = XwindowInfo::default();
+        info.window_types.set(NORMAL, ());
+        info.window_types.set(ty, ());
+        assert!(floats(&info, false));
+    }
+}
+
+#[test]
+fn fixed_size() {
+    let info = XwindowInfo::default();
+    let hints = &info.normal_hints;
+    hints.min_width.set(300);
+    hints.max_width.set(300);
+    hints.min_height.set(200);
+    hints.max_height.set(200);
+    assert!(floats(&info, false));
+    hints.max_height.set(400);
+    assert!(!floats(&info, false));
+}
+
+#[test]
+fn motif_undecorated() {
+    let info = XwindowInfo::default();
+    info.motif_hints.flags.set(MWM_HINTS_DECORATIONS);
+    info.motif_hints.decorations.set(0);
+    assert!(floats(&info, false));
+}
+
+#[test]
+fn motif_undecorated_normal() {
+    let info = XwindowInfo::default();
+    info.window_types.set(NORMAL, ());
+    info.motif_hints.flags.set(MWM_HINTS_DECORATIONS);
+    info.motif_hints.decorations.set(0);
+    assert!(!floats(&info, false));
+}
+
+#[test]
+fn motif_decorated() {
+    let info = XwindowInfo::default();
+    info.motif_hints.flags.set(MWM_HINTS_DECORATIONS);
+    info.motif_hints.decorations.set(MWM_DECOR_ALL);
+    assert!(!floats(&info, false));
+}
+
+#[test]
+fn motif_without_decorations_flag() {
+    let info = XwindowInfo::default();
+    info.motif_hints.flags.set(0);
+    info.motif_hints.decorations.set(0);
+    assert!(!floats(&info, false));
+}
+
+#[test]
+fn input_models() {
+    assert_eq!(input_model(false, false), XInputModel::None);
+    assert_eq!(input_model(true, false), XInputModel::Passive);
+    assert_eq!(input_model(true, true), XInputModel::Local);
+    assert_eq!(input_model(false, true), XInputModel::Global);
+}
+
+#[test]
+fn take_focus_only_if_supported() {
+    assert_eq!(focus_messages(XInputModel::None), (false, false));
+    assert_eq!(focus_messages(XInputModel::Passive), (false, true));
+    assert_eq!(focus_messages(XInputModel::Local), (true, true));
+    assert_eq!(focus_messages(XInputModel::Global), (true, false));
+}
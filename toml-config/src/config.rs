@@ -38,6 +38,7 @@ use {
 #[derive(Debug, Clone)]
 pub enum SimpleCommand {
     Close,
+    Kill,
     DisablePointerConstraint,
     Focus(Direction),
     FocusParent,
@@ -50,6 +51,7 @@ pub enum SimpleCommand {
     ToggleFloating,
     ToggleFullscreen,
     ToggleMono,
+    ToggleStacked,
     ToggleSplit,
     Forward(bool),
     EnableWindowManagement(bool),
@@ -140,6 +142,7 @@ pub struct Theme {
     pub bar_bg_color: Option<Color>,
     pub bar_status_text_color: Option<Color>,
     pub border_color: Option<Color>,
+    pub focused_border_color: Option<Color>,
     pub captured_focused_title_bg_color: Option<Color>,
     pub captured_unfocused_title_bg_color: Option<Color>,
     pub focused_inactive_title_bg_color: Option<Color>,
@@ -153,6 +156,8 @@ pub struct Theme {
     pub border_width: Option<i32>,
     pub title_height: Option<i32>,
     pub font: Option<String>,
+    pub inner_gap: Option<i32>,
+    pub outer_gap: Option<i32>,
 }
 
 #[derive(Debug, Clone)]
@@ -274,6 +279,7 @@ pub struct Exec {
     pub args: Vec<String>,
     pub envs: Vec<(String, String)>,
     pub privileged: bool,
+    pub log_output: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -357,6 +363,8 @@ pub struct Config {
     pub idle: Option<Duration>,
     pub explicit_sync_enabled: Option<bool>,
     pub focus_follows_mouse: bool,
+    pub focus_follows_mouse_delay: Option<u64>,
+    pub warp_pointer_to_focus: bool,
     pub window_management_key: Option<ModifiedKeySym>,
     pub vrr: Option<Vrr>,
     pub tearing: Option<Tearing>,
@@ -103,9 +103,11 @@ impl ActionParser<'_> {
             "split-vertical" => Split(Vertical),
             "toggle-split" => ToggleSplit,
             "toggle-mono" => ToggleMono,
+            "toggle-stacked" => ToggleStacked,
             "toggle-fullscreen" => ToggleFullscreen,
             "focus-parent" => FocusParent,
             "close" => Close,
+            "kill" => Kill,
             "disable-pointer-constraint" => DisablePointerConstraint,
             "toggle-floating" => ToggleFloating,
             "quit" => Quit,
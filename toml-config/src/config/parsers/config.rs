@@ -2,7 +2,7 @@ use {
     crate::{
         config::{
             context::Context,
-            extractor::{arr, bol, opt, recover, str, val, Extractor, ExtractorError},
+            extractor::{arr, bol, n64, opt, recover, str, val, Extractor, ExtractorError},
             parser::{DataType, ParseResult, Parser, UnexpectedDataType},
             parsers::{
                 action::ActionParser,
@@ -118,6 +118,7 @@ impl Parser for ConfigParser<'_> {
                 ui_drag_val,
                 xwayland_val,
             ),
+            (focus_follows_mouse_delay, warp_pointer_to_focus),
         ) = ext.extract((
             (
                 opt(val("keymap")),
@@ -155,6 +156,10 @@ impl Parser for ConfigParser<'_> {
                 opt(val("ui-drag")),
                 opt(val("xwayland")),
             ),
+            (
+                recover(opt(n64("focus-follows-mouse-delay"))),
+                recover(opt(bol("warp-pointer-to-focus"))),
+            ),
         ))?;
         let mut keymap = None;
         if let Some(value) = keymap_val {
@@ -413,6 +418,8 @@ impl Parser for ConfigParser<'_> {
             inputs,
             idle,
             focus_follows_mouse: focus_follows_mouse.despan().unwrap_or(true),
+            focus_follows_mouse_delay: focus_follows_mouse_delay.despan(),
+            warp_pointer_to_focus: warp_pointer_to_focus.despan().unwrap_or(false),
             window_management_key,
             vrr,
             tearing,
@@ -46,6 +46,7 @@ impl Parser for ExecParser<'_> {
             args: vec![],
             envs: vec![],
             privileged: false,
+            log_output: false,
         })
     }
 
@@ -63,6 +64,7 @@ impl Parser for ExecParser<'_> {
             args,
             envs: vec![],
             privileged: false,
+            log_output: false,
         })
     }
 
@@ -72,11 +74,12 @@ impl Parser for ExecParser<'_> {
         table: &IndexMap<Spanned<String>, Spanned<Value>>,
     ) -> ParseResult<Self> {
         let mut ext = Extractor::new(self.0, span, table);
-        let (prog, args_val, envs_val, privileged) = ext.extract((
+        let (prog, args_val, envs_val, privileged, log_output) = ext.extract((
             str("prog"),
             opt(arr("args")),
             opt(val("env")),
             recover(opt(bol("privileged"))),
+            recover(opt(bol("log-output"))),
         ))?;
         let mut args = vec![];
         if let Some(args_val) = args_val {
@@ -93,6 +96,7 @@ impl Parser for ExecParser<'_> {
             args,
             envs,
             privileged: privileged.despan().unwrap_or(false),
+            log_output: log_output.despan().unwrap_or(false),
         })
     }
 }
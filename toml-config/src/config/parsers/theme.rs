@@ -59,7 +59,10 @@ impl Parser for ThemeParser<'_> {
                 border_width,
                 title_height,
                 font,
+                inner_gap,
+                outer_gap,
             ),
+            focused_border_color,
         ) = ext.extract((
             (
                 opt(val("attention-requested-bg-color")),
@@ -82,7 +85,10 @@ impl Parser for ThemeParser<'_> {
                 recover(opt(s32("border-width"))),
                 recover(opt(s32("title-height"))),
                 recover(opt(str("font"))),
+                recover(opt(s32("inner-gap"))),
+                recover(opt(s32("outer-gap"))),
             ),
+            opt(val("focused-border-color")),
         ))?;
         macro_rules! color {
             ($e:expr) => {
@@ -104,6 +110,7 @@ impl Parser for ThemeParser<'_> {
             bar_bg_color: color!(bar_bg_color),
             bar_status_text_color: color!(bar_status_text_color),
             border_color: color!(border_color),
+            focused_border_color: color!(focused_border_color),
             captured_focused_title_bg_color: color!(captured_focused_title_bg_color),
             captured_unfocused_title_bg_color: color!(captured_unfocused_title_bg_color),
             focused_inactive_title_bg_color: color!(focused_inactive_title_bg_color),
@@ -117,6 +124,8 @@ impl Parser for ThemeParser<'_> {
             border_width: border_width.despan(),
             title_height: title_height.despan(),
             font: font.map(|f| f.value.to_string()),
+            inner_gap: inner_gap.despan(),
+            outer_gap: outer_gap.despan(),
         })
     }
 }
@@ -80,9 +80,11 @@ impl Action {
                 SimpleCommand::Split(axis) => B::new(move |_| s.create_split(axis)),
                 SimpleCommand::ToggleSplit => B::new(move |_| s.toggle_split()),
                 SimpleCommand::ToggleMono => B::new(move |_| s.toggle_mono()),
+                SimpleCommand::ToggleStacked => B::new(move |_| s.toggle_stacked()),
                 SimpleCommand::ToggleFullscreen => B::new(move |_| s.toggle_fullscreen()),
                 SimpleCommand::FocusParent => B::new(move |_| s.focus_parent()),
                 SimpleCommand::Close => B::new(move |_| s.close()),
+                SimpleCommand::Kill => B::new(move |_| s.kill()),
                 SimpleCommand::DisablePointerConstraint => {
                     B::new(move |_| s.disable_pointer_constraint())
                 }
@@ -727,6 +729,7 @@ impl State {
         color!(BAR_BACKGROUND_COLOR, bar_bg_color);
         color!(BAR_STATUS_TEXT_COLOR, bar_status_text_color);
         color!(BORDER_COLOR, border_color);
+        color!(FOCUSED_BORDER_COLOR, focused_border_color);
         color!(
             CAPTURED_FOCUSED_TITLE_BACKGROUND_COLOR,
             captured_focused_title_bg_color
@@ -758,6 +761,8 @@ impl State {
         }
         size!(BORDER_WIDTH, border_width);
         size!(TITLE_HEIGHT, title_height);
+        size!(INNER_GAP, inner_gap);
+        size!(OUTER_GAP, outer_gap);
         if let Some(font) = &theme.font {
             set_font(font);
         }
@@ -1080,6 +1085,14 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
             true => FocusFollowsMouseMode::True,
             false => FocusFollowsMouseMode::False,
         });
+    if let Some(delay) = config.focus_follows_mouse_delay {
+        persistent
+            .seat
+            .set_focus_follows_mouse_delay(Duration::from_millis(delay));
+    }
+    persistent
+        .seat
+        .set_warp_pointer_to_focus(config.warp_pointer_to_focus);
     if let Some(window_management_key) = config.window_management_key {
         persistent
             .seat
@@ -1123,6 +1136,9 @@ fn create_command(exec: &Exec) -> Command {
     if exec.privileged {
         command.privileged();
     }
+    if exec.log_output {
+        command.log_output();
+    }
     command
 }
 